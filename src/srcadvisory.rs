@@ -0,0 +1,424 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+
+use serde::{Deserialize, Serialize};
+use serde_json; // Bildiri veritabanı JSON biçiminde (alloc özellikli no_std backend varsayımıyla)
+
+// Paket meta verisi (kurulum planları için)
+use crate::package::Paket;
+// Etkilenen sürüm aralıkları ve sürüm karşılaştırma
+use crate::srcversion::{self, SurumAraligi};
+// Kurulu paket kayıtları
+use crate::srcinstalled::KuruluPaketVeritabani;
+
+// Sahne64 API modülleri
+use crate::resource;
+use crate::SahneError;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, error, debug};
+
+
+// Yerel güvenlik bildirimi veritabanı (ağ gerekmez; dosya ayrıca eşitlenir).
+//
+// Biçim (JSON):
+// {
+//   "bicim_surumu": 1,
+//   "guncelleme": 1760000000,                 // isteğe bağlı, veritabanının oluşturulma zamanı (Unix saniyesi)
+//   "bildiriler": [
+//     {
+//       "kimlik": "CVE-2022-37434",
+//       "paket": "zlib",
+//       "etkilenen": [">=1.2.0, <1.2.12"],    // sürüm aralıkları; herhangi birine giren sürüm etkilenir
+//       "duzeltilen": "1.2.12",               // isteğe bağlı; açığı kapatan ilk sürüm
+//       "ciddiyet": "kritik",                 // dusuk | orta | yuksek | kritik (low | medium | high | critical da kabul edilir)
+//       "ozet": "inflateGetHeader yığın taşması"
+//     }
+//   ]
+// }
+//
+// Aralıklar srcversion::SurumAraligi sözdizimindedir: virgülle ayrılmış kısıtların kesişimi ("=1.0", ">1.0, <=2.0", "*").
+// "etkilenen" boşsa "duzeltilen" zorunludur ve ondan eski tüm sürümler etkilenmiş sayılır.
+pub const VARSAYILAN_BILDIRI_VERITABANI: &str = "sahne://config/advisories.json";
+
+// Desteklenen bildiri veritabanı biçim sürümü
+const BILDIRI_BICIM_SURUMU: u32 = 1;
+
+
+// Güvenlik açığının ciddiyeti; sıralama düşükten kritiğe doğrudur.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Ciddiyet {
+    #[serde(alias = "low")]
+    Dusuk,
+    #[serde(alias = "medium", alias = "moderate")]
+    Orta,
+    #[serde(alias = "high")]
+    Yuksek,
+    #[serde(alias = "critical")]
+    Kritik,
+}
+
+impl Ciddiyet {
+    pub fn ad(&self) -> &'static str {
+        match self {
+            Ciddiyet::Dusuk => "dusuk",
+            Ciddiyet::Orta => "orta",
+            Ciddiyet::Yuksek => "yuksek",
+            Ciddiyet::Kritik => "kritik",
+        }
+    }
+
+    // Komut satırı ve yapılandırma için ciddiyet adını ayrıştırır (Türkçe veya İngilizce adlar).
+    pub fn ayristir(ad: &str) -> Result<Self, PaketYoneticisiHatasi> {
+        match ad.trim().to_lowercase().as_str() {
+            "dusuk" | "düşük" | "low" => Ok(Ciddiyet::Dusuk),
+            "orta" | "medium" | "moderate" => Ok(Ciddiyet::Orta),
+            "yuksek" | "yüksek" | "high" => Ok(Ciddiyet::Yuksek),
+            "kritik" | "critical" => Ok(Ciddiyet::Kritik),
+            _ => Err(PaketYoneticisiHatasi::InvalidParameter(format!(
+                "Bilinmeyen ciddiyet: '{}' (dusuk, orta, yuksek, kritik)", ad
+            ))),
+        }
+    }
+}
+
+// Veritabanı dosyasının ham biçimi
+#[derive(Deserialize)]
+struct BildiriDosyasi {
+    bicim_surumu: u32,
+    #[serde(default)]
+    guncelleme: Option<u64>,
+    bildiriler: Vec<BildiriGirdisi>,
+}
+
+#[derive(Deserialize)]
+struct BildiriGirdisi {
+    kimlik: String,
+    paket: String,
+    #[serde(default)]
+    etkilenen: Vec<String>,
+    #[serde(default)]
+    duzeltilen: Option<String>,
+    ciddiyet: Ciddiyet,
+    #[serde(default)]
+    ozet: String,
+}
+
+// Aralıkları ayrıştırılmış tek bir güvenlik bildirimi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuvenlikBildirimi {
+    pub kimlik: String, // Örn. "CVE-2022-37434" veya "SAHNE-2026-0004"
+    pub paket: String,
+    pub etkilenen: Vec<SurumAraligi>,
+    pub duzeltilen: Option<String>,
+    pub ciddiyet: Ciddiyet,
+    pub ozet: String,
+}
+
+impl GuvenlikBildirimi {
+    // Paketin bu sürümü bildirimden etkileniyor mu?
+    pub fn etkiler_mi(&self, surum: &str) -> bool {
+        if self.etkilenen.is_empty() {
+            return self.duzeltilen.as_deref()
+                .map_or(false, |duzeltilen| srcversion::surum_karsilastir(surum, duzeltilen) == core::cmp::Ordering::Less);
+        }
+        self.etkilenen.iter().any(|aralik| aralik.icerir(surum))
+    }
+}
+
+// Bir paketin bir sürümünde bulunan güvenlik açığı.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Bulgu {
+    pub paket: String,
+    pub surum: String,
+    pub bildiri: String,
+    pub ciddiyet: Ciddiyet,
+    pub duzeltilen: Option<String>,
+    pub ozet: String,
+}
+
+impl core::fmt::Display for Bulgu {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}: {} ({})", self.paket, self.surum, self.bildiri, self.ciddiyet.ad())?;
+        match &self.duzeltilen {
+            Some(duzeltilen) => write!(f, ", {} sürümünde düzeltildi", duzeltilen),
+            None => write!(f, ", düzeltme yok"),
+        }
+    }
+}
+
+// Yerel güvenlik bildirimi veritabanı.
+#[derive(Debug, Clone, Default)]
+pub struct BildiriVeritabani {
+    pub guncelleme: Option<u64>, // Veritabanının oluşturulma zamanı (Unix saniyesi), biliniyorsa
+    bildiriler: Vec<GuvenlikBildirimi>, // Paket adına, sonra kimliğe göre sıralı
+}
+
+impl BildiriVeritabani {
+    // JSON veritabanını ayrıştırır ve doğrular (biçim sürümü, aralık sözdizimi, boş alanlar).
+    pub fn json_ayristir(veri: &[u8]) -> Result<Self, PaketYoneticisiHatasi> {
+        let dosya: BildiriDosyasi = serde_json::from_slice(veri).map_err(|e| {
+            PaketYoneticisiHatasi::ParsingError(format!("Güvenlik bildirimi veritabanı çözülemedi: {}", e))
+        })?;
+        if dosya.bicim_surumu != BILDIRI_BICIM_SURUMU {
+            return Err(PaketYoneticisiHatasi::ParsingError(format!(
+                "Desteklenmeyen güvenlik bildirimi biçim sürümü: {}", dosya.bicim_surumu
+            )));
+        }
+
+        let mut bildiriler = Vec::with_capacity(dosya.bildiriler.len());
+        for girdi in dosya.bildiriler {
+            if girdi.kimlik.trim().is_empty() || girdi.paket.trim().is_empty() {
+                return Err(PaketYoneticisiHatasi::ParsingError(String::from(
+                    "Güvenlik bildiriminde kimlik veya paket adı eksik"
+                )));
+            }
+            if girdi.etkilenen.is_empty() && girdi.duzeltilen.is_none() {
+                return Err(PaketYoneticisiHatasi::ParsingError(format!(
+                    "{} ({}): 'etkilenen' veya 'duzeltilen' gerekli", girdi.kimlik, girdi.paket
+                )));
+            }
+            let etkilenen = girdi.etkilenen.iter()
+                .map(|aralik| SurumAraligi::ayristir(aralik))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| PaketYoneticisiHatasi::ParsingError(format!("{} ({}): {}", girdi.kimlik, girdi.paket, e)))?;
+            bildiriler.push(GuvenlikBildirimi {
+                kimlik: girdi.kimlik,
+                paket: girdi.paket,
+                etkilenen,
+                duzeltilen: girdi.duzeltilen,
+                ciddiyet: girdi.ciddiyet,
+                ozet: girdi.ozet,
+            });
+        }
+        bildiriler.sort_by(|a, b| a.paket.cmp(&b.paket).then_with(|| a.kimlik.cmp(&b.kimlik)));
+
+        Ok(BildiriVeritabani { guncelleme: dosya.guncelleme, bildiriler })
+    }
+
+    pub fn kaynaktan_yukle(resource_id: &str) -> Result<Self, PaketYoneticisiHatasi> {
+        let veri = read_resource_to_vec(resource_id)?;
+        let veritabani = Self::json_ayristir(&veri)?;
+        info!("Güvenlik bildirimi veritabanı yüklendi: {} bildiri ({})", veritabani.bildiriler.len(), resource_id); // no_std log
+        Ok(veritabani)
+    }
+
+    // Veritabanı dosyası yoksa Ok(None) döner; bozuk dosya hatadır.
+    pub fn varsa_yukle(resource_id: &str) -> Result<Option<Self>, PaketYoneticisiHatasi> {
+        match Self::kaynaktan_yukle(resource_id) {
+            Ok(veritabani) => Ok(Some(veritabani)),
+            Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => {
+                debug!("Güvenlik bildirimi veritabanı yok: {}", resource_id); // no_std log
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn bildiriler(&self) -> &[GuvenlikBildirimi] {
+        &self.bildiriler
+    }
+
+    // Bir paketin bir sürümünü etkileyen bildirimler, ciddiyete göre azalan sırada.
+    pub fn paketi_tara(&self, paket_adi: &str, surum: &str) -> Vec<Bulgu> {
+        let baslangic = self.bildiriler.partition_point(|bildiri| bildiri.paket.as_str() < paket_adi);
+        let mut bulgular: Vec<Bulgu> = self.bildiriler[baslangic..]
+            .iter()
+            .take_while(|bildiri| bildiri.paket == paket_adi)
+            .filter(|bildiri| bildiri.etkiler_mi(surum))
+            .map(|bildiri| Bulgu {
+                paket: paket_adi.to_owned(),
+                surum: surum.to_owned(),
+                bildiri: bildiri.kimlik.clone(),
+                ciddiyet: bildiri.ciddiyet,
+                duzeltilen: bildiri.duzeltilen.clone(),
+                ozet: bildiri.ozet.clone(),
+            })
+            .collect();
+        bulgular.sort_by(|a, b| b.ciddiyet.cmp(&a.ciddiyet).then_with(|| a.bildiri.cmp(&b.bildiri)));
+        bulgular
+    }
+
+    // Kurulum planındaki (veya herhangi bir paket listesindeki) paketleri tarar.
+    pub fn plani_tara(&self, paketler: &[Paket]) -> Vec<Bulgu> {
+        paketler.iter().flat_map(|paket| self.paketi_tara(&paket.ad, &paket.surum)).collect()
+    }
+
+    // Kurulu tüm paketleri tarar.
+    pub fn kurulu_paketleri_tara(&self, kurulu: &KuruluPaketVeritabani) -> Result<Vec<Bulgu>, PaketYoneticisiHatasi> {
+        let kayitlar = kurulu.tum_kayitlar()?;
+        Ok(kayitlar.iter().flat_map(|kayit| self.paketi_tara(&kayit.paket.ad, &kayit.paket.surum)).collect())
+    }
+}
+
+// Kurulum öncesi güvenlik açığı politikası.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcikPolitikasi {
+    // Bildiri veritabanının Kaynak ID'si; dosya yoksa tarama atlanır (uyarı verilir).
+    pub veritabani: String,
+    // Bu ciddiyette veya üstünde açığı olan paketlerin kurulumu engellenir; None: yalnızca raporlanır.
+    pub engelleme_esigi: Option<Ciddiyet>,
+}
+
+impl Default for AcikPolitikasi {
+    fn default() -> Self {
+        AcikPolitikasi {
+            veritabani: VARSAYILAN_BILDIRI_VERITABANI.to_owned(),
+            engelleme_esigi: Some(Ciddiyet::Kritik),
+        }
+    }
+}
+
+impl AcikPolitikasi {
+    // Bulgular engelleme eşiğine ulaşıyorsa GuvenlikAcigiEngeli hatası döner.
+    pub fn denetle(&self, bulgular: &[Bulgu]) -> Result<(), PaketYoneticisiHatasi> {
+        let esik = match self.engelleme_esigi {
+            Some(esik) => esik,
+            None => return Ok(()),
+        };
+        let engellenenler: Vec<String> = bulgular.iter()
+            .filter(|bulgu| bulgu.ciddiyet >= esik)
+            .map(|bulgu| bulgu.to_string())
+            .collect();
+        if engellenenler.is_empty() {
+            Ok(())
+        } else {
+            Err(PaketYoneticisiHatasi::GuvenlikAcigiEngeli { esik: esik.ad().to_owned(), bulgular: engellenenler })
+        }
+    }
+
+    // Kurulum planını veritabanına göre tarar, bulguları loglar ve politikayı uygular.
+    // Dönüş değeri: Engellenmeyen bulgular (uyarı olarak gösterilebilir).
+    pub fn plani_denetle(&self, paketler: &[Paket]) -> Result<Vec<Bulgu>, PaketYoneticisiHatasi> {
+        let veritabani = match BildiriVeritabani::varsa_yukle(&self.veritabani)? {
+            Some(veritabani) => veritabani,
+            None => {
+                warn!("Güvenlik bildirimi veritabanı bulunamadı ({}); güvenlik açığı taraması atlandı.", self.veritabani); // no_std log
+                return Ok(Vec::new());
+            }
+        };
+        let bulgular = veritabani.plani_tara(paketler);
+        for bulgu in &bulgular {
+            warn!("Güvenlik açığı: {}", bulgu); // no_std log
+        }
+        self.denetle(&bulgular).map_err(|e| {
+            error!("{}", e); // no_std log
+            e
+        })?;
+        Ok(bulgular)
+    }
+}
+
+
+// Helper fonksiyon: Sahne64 Kaynağından tüm içeriği Vec<u8> olarak oku.
+fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut buffer = Vec::new();
+    let mut temp_buffer = [0u8; 512]; // Stack buffer
+
+    loop {
+        match resource::read(handle, &mut temp_buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => buffer.extend_from_slice(&temp_buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    if let Err(e) = resource::release(handle) {
+        error!("Helper: Kaynak release hatası ({}): {:?}", resource_id, e);
+    }
+
+    Ok(buffer)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORNEK: &str = r#"{
+        "bicim_surumu": 1,
+        "guncelleme": 1760000000,
+        "bildiriler": [
+            { "kimlik": "CVE-2022-37434", "paket": "zlib", "etkilenen": [">=1.2.0, <1.2.12"],
+              "duzeltilen": "1.2.12", "ciddiyet": "critical", "ozet": "inflateGetHeader taşması" },
+            { "kimlik": "SAHNE-2026-0002", "paket": "zlib", "duzeltilen": "1.3", "ciddiyet": "orta" },
+            { "kimlik": "SAHNE-2026-0003", "paket": "openssl", "etkilenen": ["=3.0.6", ">=1.0, <1.1.1"],
+              "ciddiyet": "yuksek" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_veritabani_ayristir_ve_tara() {
+        let veritabani = BildiriVeritabani::json_ayristir(ORNEK.as_bytes()).unwrap();
+        assert_eq!(veritabani.guncelleme, Some(1760000000));
+        assert_eq!(veritabani.bildiriler().len(), 3);
+
+        // En ciddi bulgu önce gelir
+        let bulgular = veritabani.paketi_tara("zlib", "1.2.11");
+        assert_eq!(bulgular.iter().map(|b| b.bildiri.as_str()).collect::<Vec<_>>(), ["CVE-2022-37434", "SAHNE-2026-0002"]);
+        assert_eq!(bulgular[0].ciddiyet, Ciddiyet::Kritik);
+
+        // Yalnızca "duzeltilen" olan bildiri, ondan eski sürümleri etkiler
+        assert_eq!(veritabani.paketi_tara("zlib", "1.2.12").len(), 1);
+        assert!(veritabani.paketi_tara("zlib", "1.3").is_empty());
+
+        assert_eq!(veritabani.paketi_tara("openssl", "3.0.6").len(), 1);
+        assert!(veritabani.paketi_tara("openssl", "3.0.7").is_empty());
+        assert!(veritabani.paketi_tara("libc", "2.31").is_empty());
+    }
+
+    #[test]
+    fn test_gecersiz_veritabani() {
+        assert!(BildiriVeritabani::json_ayristir(br#"{"bicim_surumu": 2, "bildiriler": []}"#).is_err());
+        assert!(BildiriVeritabani::json_ayristir(
+            br#"{"bicim_surumu": 1, "bildiriler": [{"kimlik": "X", "paket": "a", "ciddiyet": "orta"}]}"#
+        ).is_err());
+        assert!(BildiriVeritabani::json_ayristir(
+            br#"{"bicim_surumu": 1, "bildiriler": [{"kimlik": "X", "paket": "a", "etkilenen": [">=1.0 <2"], "ciddiyet": "orta"}]}"#
+        ).is_err());
+        assert!(BildiriVeritabani::json_ayristir(
+            br#"{"bicim_surumu": 1, "bildiriler": [{"kimlik": "X", "paket": "a", "duzeltilen": "2", "ciddiyet": "acil"}]}"#
+        ).is_err());
+    }
+
+    #[test]
+    fn test_politika_esigi() {
+        let veritabani = BildiriVeritabani::json_ayristir(ORNEK.as_bytes()).unwrap();
+        let bulgular = veritabani.paketi_tara("openssl", "3.0.6"); // yuksek
+
+        let mut politika = AcikPolitikasi::default();
+        assert!(politika.denetle(&bulgular).is_ok()); // varsayılan eşik: kritik
+
+        politika.engelleme_esigi = Some(Ciddiyet::Yuksek);
+        match politika.denetle(&bulgular) {
+            Err(PaketYoneticisiHatasi::GuvenlikAcigiEngeli { esik, bulgular }) => {
+                assert_eq!(esik, "yuksek");
+                assert_eq!(bulgular, ["openssl 3.0.6: SAHNE-2026-0003 (yuksek), düzeltme yok"]);
+            }
+            sonuc => panic!("beklenmeyen sonuç: {:?}", sonuc),
+        }
+
+        politika.engelleme_esigi = None;
+        assert!(politika.denetle(&bulgular).is_ok());
+        assert_eq!(Ciddiyet::ayristir("HIGH").unwrap(), Ciddiyet::Yuksek);
+        assert!(Ciddiyet::ayristir("acil").is_err());
+    }
+
+    // Kaynak okuma (kaynaktan_yukle, varsa_yukle) ve kurulu paket taraması testleri
+    // mock resource::acquire/read/release gerektirir.
+}
@@ -3,57 +3,185 @@ extern crate alloc; // Bellek ayırma için alloc crate'i
 
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use alloc::borrow::ToOwned; // &str -> String için
 use alloc::boxed::Box; // ZipArchive::new için Box gerekebilir
+use alloc::collections::BTreeSet; // Çıkarılmış sembolik bağların izlenmesi için
 
 use zip::{ZipArchive, result::ZipError};
-// Eğer zip crate'i no_std modunda Read/Write trait'leri sağlıyorsa, onları import edin.
-// Aksi halde kendi Read benzeri trait'imizi ve implementasyonumuzu yazmalıyız.
-// Şimdilik zip crate'inin core/alloc uyumlu Read beklediğini varsayalım.
-// Gerçek implementasyon Sahne64'ün resource::read üzerine kurulmalı.
- use zip::read::ZipFile; // Belki ZipFile için de core::io::Read implementasyonu gerekir.
+use serde::{Deserialize, Serialize}; // Girdi türü kurulu dosya kayıtlarında saklanır
+// zip crate'inin no_std derlemesi okuma/konumlama için core2::io trait'lerini kullanır (varsayım).
+use core2::io::{self, Read, Seek, SeekFrom, Write};
 
 // Sahne64 API modüllerini içe aktarın
 use crate::resource;
 use crate::memory; // Global allocator için gerekebilir
 use crate::SahneError; // Sahne64'ün hata türü
 use crate::Handle; // Sahne64'ün Handle türü
+// Kaynak Kontrol Komutları (STAT yanıtı 8 bayt boyut (LE) ile başlar; burada sadece boyut kullanılır)
+use crate::srcresource::{
+    RESOURCE_CONTROL_CMD_CHMOD, RESOURCE_CONTROL_CMD_CHOWN, RESOURCE_CONTROL_CMD_LINK, RESOURCE_CONTROL_CMD_SEEK,
+    RESOURCE_CONTROL_CMD_STAT, RESOURCE_CONTROL_CMD_SYMLINK,
+};
 
-// Özel hata enum'ımızı içe aktar (güncellenmiş haliyle)
-use crate::paket_yoneticisi_hata::PaketYoneticisiHata;
+// Özel hata enum'ımızı içe aktar (srcerror.rs)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
 
-// Sahne64 resource::read üzerine kurulu basit bir Read implementasyonu
-// zip crate'inin tam olarak ne beklediğine göre bu struct ve trait değişebilir.
-// Varsayım: zip crate'i Read trait'inin core::io veya benzeri bir no_std versiyonunu kullanıyor.
-struct SahneResourceReader {
+// Tar tabanlı arşivler ve sıkıştırma algoritmaları (tar arka ucu std::io gerektirir; yalnızca `std` özelliğiyle)
+#[cfg(feature = "std")]
+use crate::srctar;
+use crate::srcbuild; // Ayrılmış paket meta dizini
+use crate::srcfeatures::{CompressionAlgorithm, FeatureSet};
+
+// Girdiler çıkarılırken kullanılan aktarım parçası boyutu.
+// Bellek kullanımı girdi boyutundan bağımsız olarak bu değerle sınırlıdır.
+pub(crate) const AKTARIM_PARCA_BOYUTU: usize = 64 * 1024;
+
+// Sahne64 resource::read/control üzerine kurulu Read + Seek implementasyonu.
+// resource::read offset almadığı için konum burada takip edilir, konum değişiklikleri
+// RESOURCE_CONTROL_CMD_SEEK ile çekirdeğe bildirilir. ZIP merkezi dizini arşivin sonunda
+// olduğundan zip crate'i Seek'e ihtiyaç duyar.
+pub(crate) struct SahneResourceReader {
     handle: Handle,
-    // Okuma pozisyonu takip edilebilir, ancak resource::read offset argümanı almıyorsa
-    // bu Reader her zaman baştan okur veya Kaynak seek edilebilir olmalıdır.
-    // Sahne64'ün resource::read'i offset almıyordu, bu bir sınırlama olabilir.
-    // Belki resource::control ile seek yapmak mümkündür?
-    // Şimdilik basitlik adına, okuma işleminin kaynağın başından başladığını varsayalım.
+    konum: u64, // Mevcut okuma konumu
+    boyut: u64, // Kaynağın toplam boyutu (SeekFrom::End için)
 }
 
 impl SahneResourceReader {
-    fn new(handle: Handle) -> Self {
-        Self { handle }
+    pub(crate) fn new(handle: Handle) -> Result<Self, SahneError> {
+        let yanit = resource::control(handle, RESOURCE_CONTROL_CMD_STAT, &[])?;
+        let mut boyut = [0u8; 8];
+        if yanit.len() >= 8 {
+            boyut.copy_from_slice(&yanit[0..8]);
+        }
+        Ok(Self { handle, konum: 0, boyut: u64::from_le_bytes(boyut) })
+    }
+
+    // Kaynağın toplam boyutu (sıkıştırılmış arşiv boyutu, oran sınırı için).
+    pub(crate) fn boyut(&self) -> u64 {
+        self.boyut
+    }
+}
+
+// core2::io::Error kaynak hatayı taşıyamadığı için SahneError burada loglanır.
+fn sahne_io_hatasi(e: SahneError) -> io::Error {
+    eprintln!("Arşiv Kaynağı G/Ç hatası: {:?}", e);
+    io::Error::new(io::ErrorKind::Other, "Sahne64 Kaynak hatası")
+}
+
+impl Read for SahneResourceReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let okunan = resource::read(self.handle, buf).map_err(sahne_io_hatasi)?;
+        self.konum += okunan as u64;
+        Ok(okunan)
+    }
+}
+
+impl Seek for SahneResourceReader {
+    fn seek(&mut self, konum: SeekFrom) -> io::Result<u64> {
+        let yeni_konum = match konum {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(fark) => self.boyut.checked_add_signed(fark),
+            SeekFrom::Current(fark) => self.konum.checked_add_signed(fark),
+        };
+        let yeni_konum = yeni_konum.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Geçersiz konum (negatif veya taşma)")
+        })?;
+
+        if yeni_konum != self.konum {
+            resource::control(self.handle, RESOURCE_CONTROL_CMD_SEEK, &yeni_konum.to_le_bytes())
+                .map_err(sahne_io_hatasi)?;
+            self.konum = yeni_konum;
+        }
+        Ok(self.konum)
+    }
+}
+
+// Sahne64 resource::write/control üzerine kurulu Write + Seek implementasyonu (arşiv oluşturma için).
+// zip crate'i girdi başlıklarını yazdıktan sonra geri dönüp boyut/CRC alanlarını günceller.
+pub(crate) struct SahneResourceWriter {
+    handle: Handle,
+    konum: u64, // Mevcut yazma konumu
+    boyut: u64, // Şimdiye kadar yazılan en uzak konum (SeekFrom::End için)
+}
+
+impl SahneResourceWriter {
+    // Handle MODE_WRITE | MODE_CREATE | MODE_TRUNCATE ile edinilmiş olmalıdır (boş Kaynak).
+    pub(crate) fn new(handle: Handle) -> Self {
+        Self { handle, konum: 0, boyut: 0 }
     }
 }
 
-// Rust'ın henüz deneysel olan core::io::Read trait'ini kullanmak yerine,
-// zip crate'inin kendi no_std okuma trait'i varsa onu kullanmalıyız.
-// Veya geçici olarak std::io::Read'e benzer bir trait tanımlayıp onu implement etmeliyiz.
-// zip crate'i genellikle std::io::Read bekler. No_std zip crate'leri farklı olabilir.
-// Farzedelim ki zip crate'i şöyle bir trait bekliyor:
-// trait NoStdRead { fn read(&mut self, buf: &mut [u8]) -> Result<usize, YourIoError>; }
-// Veya zip crate'i, ZipArchive::new için std::io::Read yerine doğrudan RawHandle veya benzeri bir şey alabilir.
-// zip crate'inin no_std desteğini ve gereksinimlerini kontrol etmek kritik.
+impl Write for SahneResourceWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let yazilan = resource::write(self.handle, buf).map_err(sahne_io_hatasi)?;
+        self.konum += yazilan as u64;
+        self.boyut = self.boyut.max(self.konum);
+        Ok(yazilan)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(()) // resource::write arabelleğe almaz (varsayım)
+    }
+}
+
+impl Seek for SahneResourceWriter {
+    fn seek(&mut self, konum: SeekFrom) -> io::Result<u64> {
+        let yeni_konum = match konum {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(fark) => self.boyut.checked_add_signed(fark),
+            SeekFrom::Current(fark) => self.konum.checked_add_signed(fark),
+        };
+        let yeni_konum = yeni_konum.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Geçersiz konum (negatif veya taşma)")
+        })?;
+
+        if yeni_konum != self.konum {
+            resource::control(self.handle, RESOURCE_CONTROL_CMD_SEEK, &yeni_konum.to_le_bytes())
+                .map_err(sahne_io_hatasi)?;
+            self.konum = yeni_konum;
+        }
+        Ok(self.konum)
+    }
+}
+
+// Verinin tamamını Kaynağa yazar. resource::write istenenden az bayt yazabilir (kısmi yazma);
+// kalan kısım yazılana kadar tekrar denenir. Hiç ilerleme olmazsa hata döner.
+pub(crate) fn tamamini_yaz(handle: Handle, mut veri: &[u8], hedef_resource_id: &str) -> Result<(), PaketYoneticisiHatasi> {
+    while !veri.is_empty() {
+        match resource::write(handle, veri) {
+            Ok(0) => {
+                return Err(PaketYoneticisiHatasi::EksikYazma(alloc::format!(
+                    "Kaynak yazmayı ilerletmedi ({} bayt kaldı): {}", veri.len(), hedef_resource_id
+                )));
+            }
+            Ok(yazilan) => veri = &veri[yazilan..],
+            Err(e) => return Err(PaketYoneticisiHatasi::from(e)),
+        }
+    }
+    Ok(())
+}
 
-// **Önemli Not:** zip crate'inin no_std desteği genellikle `std::io::Read` ve `std::io::Write`'ın
-// bir `no_std` ortamında yeniden implemente edilmiş hallerine dayanır. Bu oldukça karmaşık olabilir.
-// Alternatif olarak, daha basit bir `no_std` uyumlu deflate/zip dekompresyon kütüphanesi aramak
-// veya yazmak gerekebilir.
-// Bu taslakta, SahneResourceReader'ın bir şekilde zip crate'inin beklediği okuma arayüzünü
-// sağladığını VARSAYIYORUZ.
+// Bir arşiv girdisini sabit boyutlu tampon ile parça parça hedef Kaynağa aktarır.
+// tampon: Çağıran tarafından bir kez ayrılıp tüm girdiler için yeniden kullanılır.
+// Dönüş değeri: Aktarılan toplam bayt.
+pub(crate) fn girdiyi_aktar<R: Read + ?Sized>(
+    girdi: &mut R,
+    hedef_handle: Handle,
+    hedef_resource_id: &str,
+    tampon: &mut [u8],
+) -> Result<u64, PaketYoneticisiHatasi> {
+    let mut toplam = 0u64;
+    loop {
+        let okunan = match girdi.read(tampon) {
+            Ok(0) => return Ok(toplam),
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(PaketYoneticisiHatasi::ZipError(ZipError::from(e))),
+        };
+        tamamini_yaz(hedef_handle, &tampon[..okunan], hedef_resource_id)?;
+        toplam += okunan as u64;
+    }
+}
 
 // Dizin oluşturma işlevi için placeholder. Sahne64'te nasıl yapılır?
 // Varsayım: Belirli bir resource ID formatı ("sahne://install/paket_adi/dosya/")
@@ -63,7 +191,7 @@ impl SahneResourceReader {
 // kernelin CREATE flag'i ile dizin benzeri resource'ları oluşturmasını umalım.
 // Bu varsayım gerçek Sahne64 tasarımına bağlıdır. Daha sağlam bir yaklaşım gerekiyorsa
 // burası çekirdek API'sında yeni bir syscall veya resource::control kullanımı gerektirir.
-fn sahne_create_resource_recursive(resource_id: &str) -> Result<(), PaketYoneticisiHata> {
+fn sahne_create_resource_recursive(resource_id: &str) -> Result<(), PaketYoneticisiHatasi> {
     // resource_id "sahne://install/my_package/path/to/file" ise,
     // "sahne://install/my_package/", "sahne://install/my_package/path/", vb.
     // resource'larını oluşturmayı denemeliyiz.
@@ -92,7 +220,7 @@ fn sahne_create_resource_recursive(resource_id: &str) -> Result<(), PaketYonetic
     // Eğer fonksiyon sadece dizinler için çağrılıyorsa, son yolu acquire et:
      match resource::acquire(resource_id, resource::MODE_CREATE) {
          Ok(handle) => { let _ = resource::release(handle); Ok(()) }, // Handle'ı hemen bırakabiliriz? Veya tutmalı mıyız? Dizin resource'larının ömrü nasıl yönetilir?
-         Err(e) => Err(PaketYoneticisiHata::from_sahne_error(e)),
+         Err(e) => Err(PaketYoneticisiHatasi::from(e)),
      }
 
     // Zip çıkarma bağlamında, dosya yazmadan önce parent dizini sağlamak daha yaygın.
@@ -123,11 +251,11 @@ fn sahne_create_resource_recursive(resource_id: &str) -> Result<(), PaketYonetic
              },
              Err(SahneError::NamingError) => {
                   // Kaynak isimlendirme hatası, belki yol geçersiz veya kısıtlı bir alan?
-                  Err(PaketYoneticisiHata::from_sahne_error(SahneError::NamingError))
+                  Err(PaketYoneticisiHatasi::from(SahneError::NamingError))
              }
              Err(e) => {
                  // Diğer Sahne64 hataları
-                 Err(PaketYoneticisiHata::from_sahne_error(e))
+                 Err(PaketYoneticisiHatasi::from(e))
              }
          }
     } else {
@@ -137,101 +265,574 @@ fn sahne_create_resource_recursive(resource_id: &str) -> Result<(), PaketYonetic
 }
 
 
+// Çıkarılan bir girdinin türü.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GirdiTuru {
+    Dosya,
+    Dizin,
+    SembolikBag,
+    SabitBag, // Arşivde daha önce çıkarılmış bir dosyaya ikinci ad
+}
+
+impl GirdiTuru {
+    // Unix mod değerinin dosya türü bitlerinden girdi türünü belirler.
+    pub fn unix_modundan(mod_: u32) -> Self {
+        match mod_ & S_IFMT {
+            S_IFLNK => GirdiTuru::SembolikBag,
+            S_IFDIR => GirdiTuru::Dizin,
+            _ => GirdiTuru::Dosya,
+        }
+    }
+}
+
+// Arşivden çıkarılan bir girdinin kaydı: çıkarmada uygulanan tür, mod, sahip ve bağ hedefi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CikarilanGirdi {
+    pub yol: String, // Çıkarma köküne göre normalleştirilmiş yol (dizinler '/' ile biter)
+    pub tur: GirdiTuru,
+    pub mod_: u32, // Erişim izinleri (örn. 0o755), tür bitleri hariç
+    pub sahip: u32, // Kullanıcı kimliği (uid)
+    pub grup: u32, // Grup kimliği (gid)
+    pub bag_hedefi: Option<String>, // Sembolik bağ hedefi veya sabit bağın arşiv içindeki hedef yolu
+}
+
+// Erişim izni bitleri (setuid/setgid/sticky dahil).
+pub(crate) const IZIN_MASKESI: u32 = 0o7777;
+
+// Kaynağın ebeveynini (son '/' öncesi) oluşturur.
+pub(crate) fn ebeveyni_olustur(resource_id: &str) -> Result<(), PaketYoneticisiHatasi> {
+    if let Some(ebeveyn) = resource_id.trim_end_matches('/').rfind('/').map(|idx| &resource_id[..idx]) {
+        let handle = resource::acquire(ebeveyn, resource::MODE_CREATE).map_err(PaketYoneticisiHatasi::from)?;
+        let _ = resource::release(handle);
+    }
+    Ok(())
+}
+
+// Çıkarılan bir dosya veya dizine mod ve sahip bilgisini uygular.
+// Sahip değiştirme yetkisi yoksa (ayrıcalıksız kurulum) uyarı verilir ve devam edilir; mod hatası ise kurulumu durdurur.
+pub(crate) fn oznitelikleri_uygula(resource_id: &str, girdi: &CikarilanGirdi) -> Result<(), PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_WRITE).map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut sahip_argumani = [0u8; 8];
+    sahip_argumani[..4].copy_from_slice(&girdi.sahip.to_le_bytes());
+    sahip_argumani[4..].copy_from_slice(&girdi.grup.to_le_bytes());
+    match resource::control(handle, RESOURCE_CONTROL_CMD_CHOWN, &sahip_argumani) {
+        Ok(_) => {}
+        Err(SahneError::PermissionDenied) => {
+            eprintln!("Uyarı: Sahip ayarlanamadı (yetki yok), {}:{} atlandı: {}", girdi.sahip, girdi.grup, resource_id);
+        }
+        Err(e) => {
+            let _ = resource::release(handle);
+            return Err(PaketYoneticisiHatasi::from(e));
+        }
+    }
+
+    // Mod sahip değişikliğinden sonra uygulanır (sahip değişimi setuid/setgid bitlerini temizleyebilir).
+    let sonuc = resource::control(handle, RESOURCE_CONTROL_CMD_CHMOD, &(girdi.mod_ & IZIN_MASKESI).to_le_bytes());
+    let _ = resource::release(handle);
+    sonuc.map(|_| ()).map_err(PaketYoneticisiHatasi::from)
+}
+
+// Verilen Kaynak ID'sinde sembolik bağ oluşturur (varsa önceki Kaynak silinir).
+pub fn sembolik_bag_olustur(bag_resource_id: &str, hedef: &str) -> Result<(), PaketYoneticisiHatasi> {
+    match resource::delete(bag_resource_id) {
+        Ok(_) | Err(SahneError::ResourceNotFound) => {}
+        Err(e) => return Err(PaketYoneticisiHatasi::from(e)),
+    }
+    let handle = resource::acquire(bag_resource_id, resource::MODE_WRITE | resource::MODE_CREATE)
+        .map_err(PaketYoneticisiHatasi::from)?;
+    let sonuc = resource::control(handle, RESOURCE_CONTROL_CMD_SYMLINK, hedef.as_bytes());
+    let _ = resource::release(handle);
+    sonuc.map(|_| ()).map_err(PaketYoneticisiHatasi::from)
+}
+
+// Var olan hedef Kaynağa ikinci bir ad (sabit bağ) verir (varsa önceki Kaynak silinir).
+pub(crate) fn sabit_bag_olustur(hedef_resource_id: &str, bag_resource_id: &str) -> Result<(), PaketYoneticisiHatasi> {
+    match resource::delete(bag_resource_id) {
+        Ok(_) | Err(SahneError::ResourceNotFound) => {}
+        Err(e) => return Err(PaketYoneticisiHatasi::from(e)),
+    }
+    let handle = resource::acquire(hedef_resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+    let sonuc = resource::control(handle, RESOURCE_CONTROL_CMD_LINK, bag_resource_id.as_bytes());
+    let _ = resource::release(handle);
+    sonuc.map(|_| ()).map_err(PaketYoneticisiHatasi::from)
+}
+
+// Unix dosya türü maskesi ve türleri (ZIP "external attributes" üst 16 biti, tar başlığı ve STAT yanıtı).
+pub(crate) const S_IFMT: u32 = 0o170000;
+pub(crate) const S_IFLNK: u32 = 0o120000;
+pub(crate) const S_IFDIR: u32 = 0o040000;
+// Sembolik bağ hedefi için kabul edilen en uzun değer (bayt).
+const SEMBOLIK_BAG_AZAMI_UZUNLUK: usize = 4096;
+
+// Bir yolu '/' ile bileşenlere ayırıp '.' ve boş bileşenleri atarak, '..' bileşenlerini
+// bir önceki bileşeni düşürerek çözer. '..' kökün üstüne çıkarsa None döner.
+fn bilesenleri_coz<'a>(baslangic: Vec<&'a str>, yol: &'a str) -> Option<Vec<&'a str>> {
+    let mut bilesenler = baslangic;
+    for bilesen in yol.split('/') {
+        match bilesen {
+            "" | "." => {}
+            ".." => {
+                bilesenler.pop()?;
+            }
+            _ => bilesenler.push(bilesen),
+        }
+    }
+    Some(bilesenler)
+}
+
+// Arşiv girdisi adını çıkarma köküne göre güvenli, göreli bir yola çevirir.
+// "foo..bar" gibi meşru adlar korunur; sadece tam olarak ".." olan bileşenler üst dizin anlamı taşır.
+// Reddedilenler: NUL bayt, ters eğik çizgi, mutlak yol ('/' veya "C:" ile başlayan), kökten kaçan '..'.
+// Dizin girdilerinin sonundaki '/' korunur.
+pub fn girdi_yolunu_normallestir(girdi_adi: &str) -> Result<String, PaketYoneticisiHatasi> {
+    let reddet = |neden: &str| Err(PaketYoneticisiHatasi::PathTraversalHatasi(
+        alloc::format!("{}: {:?}", neden, girdi_adi)
+    ));
+
+    if girdi_adi.contains('\0') {
+        return reddet("Girdi adı NUL bayt içeriyor");
+    }
+    if girdi_adi.contains('\\') {
+        return reddet("Girdi adı ters eğik çizgi içeriyor");
+    }
+    if girdi_adi.starts_with('/') {
+        return reddet("Girdi adı mutlak yol");
+    }
+    let mut karakterler = girdi_adi.chars();
+    if let (Some(harf), Some(':')) = (karakterler.next(), karakterler.next()) {
+        if harf.is_ascii_alphabetic() {
+            return reddet("Girdi adı sürücü harfi içeriyor");
+        }
+    }
+
+    let bilesenler = match bilesenleri_coz(Vec::new(), girdi_adi) {
+        Some(bilesenler) => bilesenler,
+        None => return reddet("Girdi adı çıkarma kökünün dışına çıkıyor"),
+    };
+
+    let mut yol = bilesenler.join("/");
+    if girdi_adi.ends_with('/') && !yol.is_empty() {
+        yol.push('/');
+    }
+    Ok(yol)
+}
+
+// Sembolik bağ hedefinin, bağın bulunduğu dizine göre çözüldüğünde çıkarma kökü içinde kaldığını doğrular.
+// bag_yolu: Normalleştirilmiş bağ yolu (örn. "lib/libz.so").
+// hedef: Bağın işaret ettiği yol (örn. "libz.so.1" veya "../share/x").
+pub fn sembolik_bag_hedefini_dogrula(bag_yolu: &str, hedef: &str) -> Result<(), PaketYoneticisiHatasi> {
+    let reddet = |neden: &str| Err(PaketYoneticisiHatasi::PathTraversalHatasi(
+        alloc::format!("{}: {} -> {:?}", neden, bag_yolu, hedef)
+    ));
+
+    if hedef.is_empty() || hedef.contains('\0') || hedef.contains('\\') {
+        return reddet("Sembolik bağ hedefi geçersiz");
+    }
+    if hedef.starts_with('/') {
+        return reddet("Sembolik bağ hedefi mutlak yol");
+    }
+
+    let mut ebeveyn: Vec<&str> = bag_yolu.split('/').filter(|b| !b.is_empty()).collect();
+    ebeveyn.pop(); // Bağın kendi adı
+    match bilesenleri_coz(ebeveyn, hedef) {
+        Some(_) => Ok(()),
+        None => reddet("Sembolik bağ hedefi çıkarma kökünün dışına çıkıyor"),
+    }
+}
+
+// Çıkarma sırasında oluşturulan sembolik bağları izler.
+// Tek tek kök içinde kalan bağlar zincirlenerek kökten kaçabilir (örn. "x/b -> ..", ardından "x/b/y -> ../.."
+// ve "x/b/y/" altındaki dosyalar); bu yüzden yolu veya hedefi daha önce çıkarılmış bir bağın içinden geçen
+// girdiler reddedilir. Denetim sözcükseldir, çıkarma kökündeki gerçek dosya sistemine bakılmaz.
+#[derive(Debug, Default)]
+pub struct SembolikBagIzleyici {
+    baglar: BTreeSet<String>, // Çıkarılmış sembolik bağların yolları
+    gecilen_yollar: BTreeSet<String>, // Bağ hedeflerinin çözülürken içinden geçtiği ara yollar
+}
+
+impl SembolikBagIzleyici {
+    pub fn yeni() -> Self {
+        Self::default()
+    }
+
+    // Girdinin ebeveyn yollarından biri veya kendisi çıkarılmış bir sembolik bağsa reddeder
+    // (bağın üzerine yazmak da bağı izler).
+    pub fn girdiyi_denetle(&self, yol: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let mut onek = String::new();
+        for bilesen in yol.split('/').filter(|b| !b.is_empty()) {
+            if !onek.is_empty() {
+                onek.push('/');
+            }
+            onek.push_str(bilesen);
+            if self.baglar.contains(&onek) {
+                return Err(PaketYoneticisiHatasi::PathTraversalHatasi(alloc::format!(
+                    "Girdi çıkarılmış bir sembolik bağın içinden geçiyor: {} ({})", yol, onek
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // Sembolik bağ girdisini denetler ve kaydeder: hedef kök içinde kalmalı (sembolik_bag_hedefini_dogrula),
+    // bağın yolu ve hedefin çözülürken geçtiği ara yollar çıkarılmış bir bağın içinden geçmemeli,
+    // bağ da daha önce doğrulanmış bir hedefin ara yoluna yerleşmemeli (sıra ters çevrilmiş zincirler).
+    // Hedefin kendisinin bir bağ olması (bağdan bağa) serbesttir; o bağ ayrıca doğrulanır.
+    pub fn bagi_ekle(&mut self, bag_yolu: &str, hedef: &str) -> Result<(), PaketYoneticisiHatasi> {
+        sembolik_bag_hedefini_dogrula(bag_yolu, hedef)?;
+        self.girdiyi_denetle(bag_yolu)?;
+        let reddet = |neden: &str| Err(PaketYoneticisiHatasi::PathTraversalHatasi(
+            alloc::format!("{}: {} -> {:?}", neden, bag_yolu, hedef)
+        ));
+
+        let bag_yolu = bag_yolu.trim_end_matches('/');
+        if self.gecilen_yollar.contains(bag_yolu) {
+            return reddet("Sembolik bağ, önceden çıkarılmış bir bağın hedef yolunu değiştiriyor");
+        }
+
+        let mut bilesenler: Vec<&str> = bag_yolu.split('/').filter(|b| !b.is_empty()).collect();
+        bilesenler.pop(); // Bağın kendi adı
+        let parcalar: Vec<&str> = hedef.split('/').filter(|b| !b.is_empty() && *b != ".").collect();
+        let mut gecilenler = Vec::new();
+        for (sira, parca) in parcalar.iter().enumerate() {
+            if *parca == ".." {
+                bilesenler.pop(); // Kök içinde kaldığı sembolik_bag_hedefini_dogrula ile doğrulandı
+                continue;
+            }
+            bilesenler.push(parca);
+            if sira + 1 == parcalar.len() {
+                break; // Son bileşen hedefin kendisidir
+            }
+            let ara_yol = bilesenler.join("/");
+            if self.baglar.contains(&ara_yol) {
+                return reddet("Sembolik bağ hedefi çıkarılmış bir bağın içinden geçiyor");
+            }
+            gecilenler.push(ara_yol);
+        }
+
+        self.gecilen_yollar.extend(gecilenler);
+        self.baglar.insert(String::from(bag_yolu));
+        Ok(())
+    }
+}
+
+
+// Paket arşivinin biçimi (dosya uzantısından değil, içeriğin ilk baytlarından belirlenir).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArsivBicimi {
+    Zip,
+    Tar { sikistirma: Option<CompressionAlgorithm> }, // None: sıkıştırılmamış tar
+}
+
+// Biçim algılamak için okunan bayt sayısı (tar "ustar" imzası 257. bayttan başlar).
+const ALGILAMA_BAYT_SAYISI: usize = 262;
+
+// Arşivin ilk baytlarından biçimini algılar.
+// Sıkıştırılmış akışların içinin tar olduğu varsayılır (paket arşivleri için tek desteklenen düzen).
+// Brotli akışlarının sihirli baytı olmadığı için algılanmaz.
+pub fn bicim_algila(baslik: &[u8]) -> Option<ArsivBicimi> {
+    let tar = |sikistirma| Some(ArsivBicimi::Tar { sikistirma });
+    if baslik.starts_with(b"PK\x03\x04") || baslik.starts_with(b"PK\x05\x06") {
+        Some(ArsivBicimi::Zip)
+    } else if baslik.starts_with(&[0x1F, 0x8B]) {
+        tar(Some(CompressionAlgorithm::Gzip))
+    } else if baslik.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        tar(Some(CompressionAlgorithm::Zstd))
+    } else if baslik.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+        tar(Some(CompressionAlgorithm::Xz))
+    } else if baslik.starts_with(&[0x04, 0x22, 0x4D, 0x18]) {
+        tar(Some(CompressionAlgorithm::Lz4))
+    } else if baslik.starts_with(b"BZh") {
+        tar(Some(CompressionAlgorithm::Bzip2))
+    } else if baslik.len() >= 262 && &baslik[257..262] == b"ustar" {
+        tar(None)
+    } else {
+        None
+    }
+}
+
+// Arşiv Kaynağının ilk baytlarını okuyup biçimini algılar.
+pub fn arsiv_bicimini_algila(arsiv_resource_id: &str) -> Result<ArsivBicimi, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(arsiv_resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut baslik = [0u8; ALGILAMA_BAYT_SAYISI];
+    let mut okunan = 0;
+    while okunan < baslik.len() {
+        match resource::read(handle, &mut baslik[okunan..]) {
+            Ok(0) => break,
+            Ok(n) => okunan += n,
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+    let _ = resource::release(handle);
+
+    bicim_algila(&baslik[..okunan]).ok_or_else(|| {
+        PaketYoneticisiHatasi::DesteklenmeyenArsivBicimi(alloc::format!("Arşiv biçimi tanınmadı: {}", arsiv_resource_id))
+    })
+}
+
+// Güvenilmeyen arşivlerin açılmasında uygulanan kaynak sınırları (sıkıştırma bombası koruması).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArsivSinirlari {
+    pub azami_girdi_sayisi: usize,
+    pub azami_toplam_boyut: u64, // Tüm girdilerin açılmış toplam boyutu (bayt)
+    pub azami_girdi_boyutu: u64, // Tek bir girdinin açılmış boyutu (bayt)
+    pub azami_sikistirma_orani: u64, // Açılmış boyut / sıkıştırılmış boyut
+    pub azami_yol_uzunlugu: usize, // Girdi adının bayt uzunluğu
+}
+
+impl Default for ArsivSinirlari {
+    fn default() -> Self {
+        ArsivSinirlari {
+            azami_girdi_sayisi: 100_000,
+            azami_toplam_boyut: 8 * 1024 * 1024 * 1024,
+            azami_girdi_boyutu: 2 * 1024 * 1024 * 1024,
+            azami_sikistirma_orani: 200,
+            azami_yol_uzunlugu: 4096,
+        }
+    }
+}
+
+// Sıkıştırma oranı bu boyutun altındaki girdilerde denetlenmez (küçük, tekrarlı dosyalar meşru olarak yüksek oran verir).
+const ORAN_DENETIM_ESIGI: u64 = 1024 * 1024;
+
+fn sinir_asildi(girdi: &str, sinir: &str, deger: u64, azami: u64) -> PaketYoneticisiHatasi {
+    PaketYoneticisiHatasi::ArsivSiniriAsildi {
+        girdi: girdi.to_string(),
+        sinir: sinir.to_string(),
+        deger,
+        azami,
+    }
+}
+
+// Arşiv girdilerini sırayla ArsivSinirlari ile karşılaştırır, girdi sayısını ve toplam boyutu biriktirir.
+pub(crate) struct SinirSayaci<'a> {
+    sinirlar: &'a ArsivSinirlari,
+    girdi_sayisi: usize,
+    toplam_boyut: u64,
+}
+
+impl<'a> SinirSayaci<'a> {
+    pub(crate) fn yeni(sinirlar: &'a ArsivSinirlari) -> Self {
+        SinirSayaci { sinirlar, girdi_sayisi: 0, toplam_boyut: 0 }
+    }
+
+    // Bir girdiyi beyan edilen boyutlarıyla denetler.
+    // sikistirilmis: Girdinin sıkıştırılmış boyutu (ZIP); girdi başına bilinmiyorsa (tar) None.
+    pub(crate) fn girdi_ekle(&mut self, ad: &str, boyut: u64, sikistirilmis: Option<u64>) -> Result<(), PaketYoneticisiHatasi> {
+        let s = self.sinirlar;
+        self.girdi_sayisi += 1;
+        if self.girdi_sayisi > s.azami_girdi_sayisi {
+            return Err(sinir_asildi(ad, "girdi sayısı", self.girdi_sayisi as u64, s.azami_girdi_sayisi as u64));
+        }
+        if ad.len() > s.azami_yol_uzunlugu {
+            return Err(sinir_asildi(ad, "yol uzunluğu", ad.len() as u64, s.azami_yol_uzunlugu as u64));
+        }
+        if boyut > s.azami_girdi_boyutu {
+            return Err(sinir_asildi(ad, "girdi boyutu", boyut, s.azami_girdi_boyutu));
+        }
+        if let Some(sikistirilmis) = sikistirilmis {
+            if boyut >= ORAN_DENETIM_ESIGI && boyut > sikistirilmis.saturating_mul(s.azami_sikistirma_orani) {
+                return Err(sinir_asildi(ad, "sıkıştırma oranı", boyut / sikistirilmis.max(1), s.azami_sikistirma_orani));
+            }
+        }
+        self.toplam_boyut = self.toplam_boyut.saturating_add(boyut);
+        if self.toplam_boyut > s.azami_toplam_boyut {
+            return Err(sinir_asildi(ad, "toplam boyut", self.toplam_boyut, s.azami_toplam_boyut));
+        }
+        Ok(())
+    }
+
+    // Şimdiye kadarki toplam açılmış boyutu arşivin sıkıştırılmış boyutuyla karşılaştırır (girdi başına oranın bilinmediği tar için).
+    pub(crate) fn toplam_orani_denetle(&self, ad: &str, arsiv_boyutu: u64) -> Result<(), PaketYoneticisiHatasi> {
+        let oran = self.sinirlar.azami_sikistirma_orani;
+        if self.toplam_boyut >= ORAN_DENETIM_ESIGI && self.toplam_boyut > arsiv_boyutu.saturating_mul(oran) {
+            return Err(sinir_asildi(ad, "sıkıştırma oranı", self.toplam_boyut / arsiv_boyutu.max(1), oran));
+        }
+        Ok(())
+    }
+}
+
+// ZIP merkezi dizinindeki tüm girdileri, hiçbir şey açılmadan ve yazılmadan önce sınırlarla denetler.
+fn zip_sinirlarini_denetle<R: Read + Seek>(arsiv: &mut ZipArchive<R>, sinirlar: &ArsivSinirlari) -> Result<(), PaketYoneticisiHatasi> {
+    let mut sayac = SinirSayaci::yeni(sinirlar);
+    for i in 0..arsiv.len() {
+        let girdi = arsiv.by_index_raw(i).map_err(PaketYoneticisiHatasi::ZipError)?; // Açılmadan (sadece başlık)
+        sayac.girdi_ekle(girdi.name(), girdi.size(), Some(girdi.compressed_size()))?;
+    }
+    Ok(())
+}
+
+// Tar arka ucu (tar, flate2, bzip2, xz2 ...) std::io'ya ve C kütüphanelerine dayanır; no_std derlemede yalnızca zip açılır.
+#[cfg(not(feature = "std"))]
+fn tar_desteklenmiyor() -> PaketYoneticisiHatasi {
+    PaketYoneticisiHatasi::DesteklenmeyenArsivBicimi(String::from(
+        "Tar arşivleri bu derlemede desteklenmiyor ('std' özelliği gerekli)"
+    ))
+}
+
+// Paket arşivini biçimine göre (zip veya sıkıştırılmış tar) belirtilen Kaynak ID'si altına açar.
+// ozellikler: Hangi sıkıştırma algoritmalarına izin verildiği.
+// sinirlar: Girdi sayısı, boyut, sıkıştırma oranı ve yol uzunluğu sınırları.
+// Dönüş değeri: Çıkarılan girdiler (kurulu dosya kayıtları için).
+pub fn arsiv_ac(
+    arsiv_resource_id: &str,
+    cikartma_base_resource_id: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<Vec<CikarilanGirdi>, PaketYoneticisiHatasi> {
+    match arsiv_bicimini_algila(arsiv_resource_id)? {
+        ArsivBicimi::Zip => zip_ac(arsiv_resource_id, cikartma_base_resource_id, sinirlar),
+        #[cfg(feature = "std")]
+        ArsivBicimi::Tar { sikistirma } => srctar::tar_ac(arsiv_resource_id, sikistirma, cikartma_base_resource_id, ozellikler, sinirlar),
+        #[cfg(not(feature = "std"))]
+        ArsivBicimi::Tar { .. } => {
+            let _ = ozellikler; // Yalnızca tar çözücüleri kullanır
+            Err(tar_desteklenmiyor())
+        }
+    }
+}
+
+// Paket arşivinin girdilerini biçimine göre ayrıntılarıyla listeler.
+pub fn arsiv_icerik_listele(arsiv_resource_id: &str, ozellikler: &FeatureSet, sinirlar: &ArsivSinirlari) -> Result<Vec<ArsivGirdisi>, PaketYoneticisiHatasi> {
+    match arsiv_bicimini_algila(arsiv_resource_id)? {
+        ArsivBicimi::Zip => zip_icerik_listele(arsiv_resource_id, sinirlar),
+        #[cfg(feature = "std")]
+        ArsivBicimi::Tar { sikistirma } => srctar::tar_icerik_listele(arsiv_resource_id, sikistirma, ozellikler, sinirlar),
+        #[cfg(not(feature = "std"))]
+        ArsivBicimi::Tar { .. } => {
+            let _ = ozellikler; // Yalnızca tar çözücüleri kullanır
+            Err(tar_desteklenmiyor())
+        }
+    }
+}
+
+
 // Verilen ZIP arşivini belirtilen Kaynak ID'si altına açar.
 // arsiv_resource_id: Açılacak ZIP arşivinin Sahne64 Kaynak ID'si (örn. "sahne://downloads/paket.zip")
 // cikartma_base_resource_id: Paket içeriğinin çıkarılacağı ana dizin gibi davranan Sahne64 Kaynak ID'si (örn. "sahne://installed_packages/my_package/")
-pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str) -> Result<(), PaketYoneticisiHata> {
+// sinirlar: Kaynak sınırları; merkezi dizin hiçbir girdi yazılmadan önce bunlarla denetlenir.
+// Dönüş değeri: Çıkarılan girdiler (tür, mod, sahip ve bağ hedefi ile), arşivdeki sırayla.
+pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str, sinirlar: &ArsivSinirlari) -> Result<Vec<CikarilanGirdi>, PaketYoneticisiHatasi> {
     // 1. ZIP Arşiv Kaynağını Aç
     let arsiv_handle = resource::acquire(arsiv_resource_id, resource::MODE_READ)
-        .map_err(|e| PaketYoneticisiHata::from_sahne_error(e))?; // SahneError'ı kendi hatamıza çevir
-
-    // 2. ZIP Arşivini Okumak için SahneResourceReader kullanma (Varsayımsal)
-    // zip crate'inin ZipArchive::new fonksiyonu std::io::Read bekler.
-    // No_std ortamında bu ya zip crate'inin no_std Read trait'i ile,
-    // ya da kendi Read adaptörümüzle çözülmeli.
-    // Geçici olarak zip crate'inin Raw handle alabildiğini veya bizim Reader'ımızı kabul ettiğini varsayalım.
-    // Gerçek zip crate kullanımı muhtemelen bir SahneResourceReader instance'ı yaratıp bunu zip'e vermeyi içerir.
-
-    // ZipArchive::new std::io::Read beklediği için, SahneResourceReader'ın std::io::Read implement etmesi lazım.
-    // No_std'de std::io implementasyonu olamaz. Ya zip crate'inin no_std::io'su var, ya da bu yaklaşım baştan hatalı.
-    // Alternatif: ZIP dosyasının tamamını belleğe oku ve oradan aç (büyük dosyalar için uygun değil).
-    // En olası çözüm: zip crate'inin no_std adaptörünü kullanmak veya farklı kütüphane.
-
-    // VEYA: ZIP dosyasını RAM'e oku (büyük bellek gerektirir!)
-    // let mut arsiv_data = Vec::new();
-    // // Sahne64 resource::read offset almadığı için, tüm dosyayı tek seferde okumak zor olabilir
-    // // veya kaynağın Seek edilebilir olması (resource::control ile?) gerekir.
-    // // En kötü ihtimalle parçalar halinde okuma döngüsü.
-    // // Basitlik adına tüm dosyayı okuduğumuzu varsayalım (Seek mümkünse veya dosya küçükse).
-    // // Bu kısım ciddi bir Sahne64-spesifik IO implementasyonu gerektirir.
-    // // Şimdilik sadece placeholder bırakalım.
-    //
-    // // OKUMA İMPLEMENTASYONU GEREKİYOR: zip crate'inin beklediği arayüze göre resource::read'i kullanma
-    //
-    // // Örnek bir Okuma Döngüsü (resource::read offset almazsa)
-     let mut temp_buffer = [0u8; 4096]; // Küçük okuma tamponu
-     loop {
-         match resource::read(arsiv_handle, &mut temp_buffer) {
-             Ok(0) => break, // EOF
-             Ok(n) => arsiv_data.extend_from_slice(&temp_buffer[..n]),
-             Err(e) => {
-                 let _ = resource::release(arsiv_handle);
-                 return Err(PaketYoneticisiHata::from_sahne_error(e));
-             }
-         }
-     }
-     let arsiv_kullanilabilir_veri = std::io::Cursor::new(arsiv_data); // std::io::Cursor kullanamayız no_std'de
+        .map_err(|e| PaketYoneticisiHatasi::from(e))?; // SahneError'ı kendi hatamıza çevir
 
-    // **GERÇEK ÇÖZÜM:** zip crate'inin no_std desteği varsa, o desteğe uygun bir Reader implementasyonu yazmak.
-    // zip crate'i `by_index` metodu ile doğrudan bir `ZipFile` struct'ı döner, bu struct'ın da
-    // Read implementasyonu olmalıdır. Bu implementasyon da yine alttan Sahne64 resource::read'i kullanmalıdır.
+    // 2-4. Girdileri çıkar; arşiv handle'ı başarıda da her hata yolunda da yalnızca burada bırakılır.
+    let sonuc = zip_girdilerini_cikar(arsiv_handle, arsiv_resource_id, cikartma_base_resource_id, sinirlar);
 
-    // Varsayım: zip crate'inin no_std uyumlu ZipArchive::new fonksiyonu var ve SahneResourceReader gibi bir şeyi alabiliyor.
-    let arsiv_reader = SahneResourceReader::new(arsiv_handle);
-    let mut arsiv = ZipArchive::new(arsiv_reader).map_err(PaketYoneticisiHata::ZipHatasi)?;
+    // 5. ZIP Arşiv Handle'ını Serbest Bırak
+    match resource::release(arsiv_handle) {
+        Ok(_) => sonuc, // Başarılı, tüm işlemler bitti
+        Err(e) => {
+            eprintln!("Arşiv Kaynağı release hatası ({}): {:?}", arsiv_resource_id, e);
+            sonuc.and(Err(PaketYoneticisiHatasi::from(e))) // Çıkarma hatası varsa o öncelikli
+        }
+    }
+}
+
+// zip_ac'ın gövdesi: arşiv handle'ını ödünç alır, bırakmaz (erken dönüşlerde sızıntı olmaması için).
+fn zip_girdilerini_cikar(
+    arsiv_handle: Handle,
+    arsiv_resource_id: &str,
+    cikartma_base_resource_id: &str,
+    sinirlar: &ArsivSinirlari,
+) -> Result<Vec<CikarilanGirdi>, PaketYoneticisiHatasi> {
+    // 2. ZIP Arşivini SahneResourceReader (Read + Seek) üzerinden aç.
+    // Arşiv belleğe okunmaz; zip crate'i merkezi dizini ve girdileri konumlayarak okur.
+    let arsiv_reader = SahneResourceReader::new(arsiv_handle).map_err(PaketYoneticisiHatasi::from)?;
+    let mut arsiv = ZipArchive::new(arsiv_reader).map_err(PaketYoneticisiHatasi::ZipError)?;
+
+    // 3. Sınırları denetle (beyan edilen boyutlarla, tek bayt yazılmadan önce)
+    if let Err(e) = zip_sinirlarini_denetle(&mut arsiv, sinirlar) {
+        eprintln!("Arşiv sınırı aşıldı, hiçbir girdi çıkarılmadı: {}", arsiv_resource_id);
+        return Err(e);
+    }
 
-    // 3. Dosyaları Çıkar
+    // 4. Dosyaları Çıkar
     let cikartma_base_path = String::from(cikartma_base_resource_id); // String olarak tutalım
+    // Tüm girdiler için tek bir aktarım tamponu (bellek kullanımı girdi boyutundan bağımsız)
+    let mut aktarim_tamponu = alloc::vec![0u8; AKTARIM_PARCA_BOYUTU];
+    let mut cikarilanlar = Vec::new();
+    let mut baglar = SembolikBagIzleyici::yeni();
 
     for i in 0..arsiv.len() {
-        let mut arsiv_dosyasi = arsiv.by_index(i)?; // Bu ZipFile struct'ı Read implement etmeli
-
-        let dosya_adi = arsiv_dosyasi.name();
-        // Zip entry isimleri bazen mutlak yol veya '..' içerebilir, temizlemek gerekir.
-        // Bu basit temizlik örneği, daha kapsamlı bir path sanitization gerekebilir.
-        let temizlenmis_dosya_adi = dosya_adi.replace("..", "_").replace("//", "/"); // Basit sanitization
-
-        // Hedef kaynak ID'sini oluştur
-        let cikartma_resource_id = if cikartma_base_path.ends_with('/') || temizlenmis_dosya_adi.starts_with('/') {
-            // Eğer base path '/' ile bitiyorsa veya dosya adı '/' ile başlıyorsa doğrudan birleştir
-            // (Başlangıç '/' zip formatında nadir olsa da ihtimale karşı)
-             let mut path = String::from(&cikartma_base_path);
-             if temizlenmis_dosya_adi.starts_with('/') {
-                 path.push_str(&temizlenmis_dosya_adi[1..]); // Başlangıç '/' karakterini atla
-             } else {
-                 path.push_str(&temizlenmis_dosya_adi);
-             }
-             path
+        let mut arsiv_dosyasi = arsiv.by_index(i).map_err(PaketYoneticisiHatasi::ZipError)?; // Bu ZipFile struct'ı Read implement etmeli
+        // ZIP'te sahip bilgisi yoktur; mod "external attributes" alanının üst 16 bitinden gelir (Unix ile oluşturulmuşsa).
+        let unix_modu = arsiv_dosyasi.unix_mode();
+
+        let dosya_adi = arsiv_dosyasi.name().to_string();
+        // Girdi adı bileşen bileşen normalleştirilir; kökten kaçan, mutlak, ters eğik çizgi veya NUL içeren adlar reddedilir.
+        let temizlenmis_dosya_adi = match girdi_yolunu_normallestir(&dosya_adi) {
+            Ok(yol) => yol,
+            Err(e) => {
+                eprintln!("Güvenlik hatası: Geçersiz girdi adı reddedildi: {:?}", dosya_adi);
+                return Err(e);
+            }
+        };
+        if temizlenmis_dosya_adi.is_empty() {
+            continue; // "./" gibi köke denk gelen girdiler
+        }
+        if temizlenmis_dosya_adi.starts_with(srcbuild::META_DIZINI) {
+            continue; // Paket manifestosu ve özet listesi kurulmaz
+        }
+        // Yolu daha önce çıkarılmış bir sembolik bağın içinden geçen girdi kökün dışına yazabilir.
+        if let Err(e) = baglar.girdiyi_denetle(&temizlenmis_dosya_adi) {
+            eprintln!("Güvenlik hatası: Sembolik bağ zinciri reddedildi: {:?}", dosya_adi);
+            return Err(e);
+        }
+
+        // Hedef kaynak ID'sini oluştur (normalleştirilmiş yol göreli olduğu için kökün altında kalır)
+        let cikartma_resource_id = if cikartma_base_path.ends_with('/') {
+            alloc::format!("{}{}", cikartma_base_path, temizlenmis_dosya_adi)
         } else {
-            // Base path '/' ile bitmiyorsa ve dosya adı '/' ile başlamıyorsa araya '/' ekle
             alloc::format!("{}/{}", cikartma_base_path, temizlenmis_dosya_adi)
         };
 
-
-        // Güvenlik kontrolü (Sahne64 Kaynak ID'leri için):
-        // Çıkarma yolunun, belirtilen temel resource ID'si altında olduğundan emin ol.
-        if !cikartma_resource_id.starts_with(&cikartma_base_path) {
-             // Bu kontrol string manipülasyonu ile yapılıyor,
-             // ancak Sahne64'ün kendi path/naming kurallarına tam uyumlu olmayabilir.
-             // Sahne64 çekirdeği de acquire sırasında yetki/güvenlik kontrolü yapmalıdır.
-             eprintln!("Güvenlik hatası: Geçersiz çıkarma yolu denemesi: {}", cikartma_resource_id);
-             // Mevcut handle'ı bırakıp hata dönebiliriz.
-             let _ = resource::release(arsiv_handle);
-             return Err(PaketYoneticisiHata::ZipHatasi(ZipError::InvalidPath(String::from("Güvenlik sebebiyle geçersiz çıkarma yolu"))));
+        // Sembolik bağlar: hedef, bağın bulunduğu dizine göre çözülür ve çıkarma kökü içinde kalmalıdır.
+        // İçerik (bağ hedefi) dosya olarak yazılmaz, Kaynak sembolik bağ olarak oluşturulur.
+        if unix_modu.map_or(false, |mod_| GirdiTuru::unix_modundan(mod_) == GirdiTuru::SembolikBag) {
+            let mut hedef = Vec::new();
+            let okuma = (&mut arsiv_dosyasi).take(SEMBOLIK_BAG_AZAMI_UZUNLUK as u64 + 1).read_to_end(&mut hedef);
+            let sonuc = match okuma {
+                Ok(_) if hedef.len() > SEMBOLIK_BAG_AZAMI_UZUNLUK => Err(PaketYoneticisiHatasi::PathTraversalHatasi(
+                    alloc::format!("Sembolik bağ hedefi çok uzun: {}", dosya_adi)
+                )),
+                Ok(_) => match core::str::from_utf8(&hedef) {
+                    Ok(hedef) => baglar.bagi_ekle(&temizlenmis_dosya_adi, hedef)
+                        .and_then(|_| ebeveyni_olustur(&cikartma_resource_id))
+                        .and_then(|_| sembolik_bag_olustur(&cikartma_resource_id, hedef))
+                        .map(|_| hedef.to_owned()),
+                    Err(_) => Err(PaketYoneticisiHatasi::PathTraversalHatasi(
+                        alloc::format!("Sembolik bağ hedefi UTF-8 değil: {}", dosya_adi)
+                    )),
+                },
+                Err(e) => Err(PaketYoneticisiHatasi::ZipError(ZipError::from(e))),
+            };
+            match sonuc {
+                Ok(hedef) => cikarilanlar.push(CikarilanGirdi {
+                    yol: temizlenmis_dosya_adi,
+                    tur: GirdiTuru::SembolikBag,
+                    mod_: 0o777,
+                    sahip: 0,
+                    grup: 0,
+                    bag_hedefi: Some(hedef),
+                }),
+                Err(e) => {
+                    eprintln!("Sembolik bağ çıkarılamadı: {}", dosya_adi);
+                    return Err(e);
+                }
+            }
+            continue;
         }
 
-
-        if temizlenmis_dosya_adi.ends_with('/') {
+        let dizin_mi = temizlenmis_dosya_adi.ends_with('/');
+        if dizin_mi {
             // Klasör Kaynağı ise (varsayım) oluşturmayı deneyelim.
             // Sahne64'te 'klasör' resource'u nasıl temsil edilir ve oluşturulur?
             // Varsayım 1: resource::acquire(path_ends_with('/'), MODE_CREATE) işe yarar.
@@ -244,7 +845,7 @@ pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str) -> Resul
             // (Bu da Varsayım 1'e benzer bir etki yaratır).
 
             // `sahne_create_resource_recursive` fonksiyonu artık burada tam yolu alıp parent'ları deneyecek
-             if temizlenmis_dosya_adi.len() > 1 { // Sadece kök '/' değilse işlem yap
+             {
                  let dir_resource_id = &cikartma_resource_id; // Zaten '/' ile bitiyor (muhtemelen zip formatına göre)
                  // Sadece varlığını sağlamak için acquire ve hemen release.
                  // resource::MODE_CREATE yeterli olmalı, klasöre özel flag gerekmeyebilir.
@@ -254,8 +855,7 @@ pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str) -> Resul
                       }
                       Err(e) => {
                            eprintln!("Dizin Kaynağı oluşturma hatası ({}): {:?}", dir_resource_id, e);
-                           let _ = resource::release(arsiv_handle); // Arşiv handle'ını temizle
-                           return Err(PaketYoneticisiHata::from_sahne_error(e));
+                           return Err(PaketYoneticisiHatasi::from(e));
                       }
                  }
              }
@@ -273,8 +873,7 @@ pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str) -> Resul
                           }
                           Err(e) => {
                                eprintln!("Ebeveyn Kaynağı oluşturma hatası ({}): {:?}", ebeveyn_path, e);
-                               let _ = resource::release(arsiv_handle); // Arşiv handle'ını temizle
-                               return Err(PaketYoneticisiHata::from_sahne_error(e));
+                               return Err(PaketYoneticisiHatasi::from(e));
                           }
                      }
                  }
@@ -287,36 +886,27 @@ pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str) -> Resul
                 resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE // Yazma, Oluştur, Varsa içeriği sil
             ).map_err(|e| {
                  eprintln!("Çıkarma Dosya Kaynağı acquire hatası ({}): {:?}", cikartma_resource_id, e);
-                 let _ = resource::release(arsiv_handle); // Arşiv handle'ını temizle
-                 PaketYoneticisiHata::from_sahne_error(e)
+                 PaketYoneticisiHatasi::from(e)
             })?;
 
-            // Arşiv dosyasının içeriğini tampona oku (bu da zip crate'inin Read implementasyonunu kullanır)
-            let mut buffer = Vec::new(); // alloc::vec::Vec kullanılıyor
-            // arsiv_dosyasi, zip::read::ZipFile olmalı ve Read implement etmeli.
-            // Bu read çağrısı alttan SahneResourceReader'ı kullanmalı (varsayım).
-            match arsiv_dosyasi.read_to_end(&mut buffer) {
-                 Ok(_) => {
-                      // Tampondaki veriyi Sahne64 Kaynağına yaz
-                      match resource::write(cikartma_dosyasi_handle, &buffer) {
-                           Ok(_) => {
-                                // Yazma başarılı
-                           }
-                           Err(e) => {
-                                eprintln!("Çıkarma Kaynağına yazma hatası ({}): {:?}", cikartma_resource_id, e);
-                                let _ = resource::release(cikartma_dosyasi_handle); // Dosya handle'ını temizle
-                                let _ = resource::release(arsiv_handle); // Arşiv handle'ını temizle
-                                return Err(PaketYoneticisiHatasi::from_sahne_error(e));
-                           }
-                      }
-                 }
-                 Err(e) => {
-                      eprintln!("Zip dosyasından okuma hatası ({}): {:?}", dosya_adi, e);
-                      let _ = resource::release(cikartma_dosyasi_handle); // Dosya handle'ını temizle
-                      let _ = resource::release(arsiv_handle); // Arşiv handle'ını temizle
-                      // ZipError'ı PaketYoneticisiHata'ya map etmeliyiz.
-                      return Err(PaketYoneticisiHatasi::ZipHatasi(e)); // ZipError::IoError SahneError'dan gelebilir
-                 }
+            // Girdiyi parça parça hedef Kaynağa aktar (girdinin tamamı belleğe alınmaz).
+            // Beyan edilen boyuttan fazlası okunmaz; beyanı aşan (yalan söyleyen) girdi reddedilir.
+            let beyan_edilen = arsiv_dosyasi.size();
+            let aktarim = girdiyi_aktar(
+                &mut (&mut arsiv_dosyasi).take(beyan_edilen.saturating_add(1)),
+                cikartma_dosyasi_handle,
+                &cikartma_resource_id,
+                &mut aktarim_tamponu,
+            ).and_then(|aktarilan| if aktarilan > beyan_edilen {
+                Err(sinir_asildi(&dosya_adi, "beyan edilen boyut", aktarilan, beyan_edilen))
+            } else {
+                Ok(aktarilan)
+            });
+            if let Err(e) = aktarim {
+                eprintln!("Girdi çıkarma hatası ({}): {:?}", dosya_adi, e);
+                let _ = resource::release(cikartma_dosyasi_handle); // Dosya handle'ını temizle
+                let _ = resource::delete(&cikartma_resource_id); // Yarım kalan girdiyi bırakma
+                return Err(e);
             }
 
             // Çıkarma Dosya Kaynağı Handle'ını serbest bırak
@@ -326,108 +916,427 @@ pub fn zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str) -> Resul
                       eprintln!("Çıkarma Dosya Kaynağı release hatası ({}): {:?}", cikartma_resource_id, e);
                       // Hata olsa bile devam etmeye çalışabiliriz, ama handle'ı bırakamamak sorun.
                       // Ciddi bir hata olarak dönebiliriz.
-                      let _ = resource::release(arsiv_handle); // Arşiv handle'ını temizle
-                      return Err(PaketYoneticisiHatasi::from_sahne_error(e));
+                      return Err(PaketYoneticisiHatasi::from(e));
                  }
             }
         }
-    }
 
-    // 4. ZIP Arşiv Handle'ını Serbest Bırak
-    match resource::release(arsiv_handle) {
-        Ok(_) => Ok(()), // Başarılı, tüm işlemler bitti
-        Err(e) => {
-            eprintln!("Arşiv Kaynağı release hatası ({}): {:?}", arsiv_resource_id, e);
-            Err(PaketYoneticisiHatasi::from_sahne_error(e)) // Hata döndür
+        // Erişim izinlerini uygula (mod yoksa dizinler 0o755, dosyalar 0o644).
+        let girdi = CikarilanGirdi {
+            yol: temizlenmis_dosya_adi,
+            tur: if dizin_mi { GirdiTuru::Dizin } else { GirdiTuru::Dosya },
+            mod_: unix_modu.map(|mod_| mod_ & IZIN_MASKESI).unwrap_or(if dizin_mi { 0o755 } else { 0o644 }),
+            sahip: 0,
+            grup: 0,
+            bag_hedefi: None,
+        };
+        if let Err(e) = oznitelikleri_uygula(&cikartma_resource_id, &girdi) {
+            return Err(e);
         }
+        cikarilanlar.push(girdi);
     }
+
+    Ok(cikarilanlar)
 }
 
+// Arşiv listelemesinde bir girdinin ayrıntıları.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArsivGirdisi {
+    pub ad: String, // Normalleştirilmiş yol (dizinler '/' ile biter)
+    pub tur: GirdiTuru,
+    pub boyut: u64, // Açılmış boyut
+    pub sikistirilmis_boyut: Option<u64>, // ZIP'te girdi başına; tar'da yok
+    pub mod_: u32, // Erişim izinleri, tür bitleri hariç
+    pub crc32: Option<u32>, // ZIP'te var; tar'da yok
+    pub degistirilme_zamani: u64, // Unix zamanı (saniye)
+}
 
-// Verilen ZIP arşivinin içeriğini listeleyen fonksiyon.
+// ZIP'in MS-DOS tarih/saatini Unix zamanına çevirir. ZIP saat dilimi taşımaz; UTC varsayılır.
+// Gün sayısı proleptik Gregoryen takvimle hesaplanır (1970-01-01'den itibaren).
+pub(crate) fn dos_zamanini_unix(yil: u16, ay: u8, gun: u8, saat: u8, dakika: u8, saniye: u8) -> u64 {
+    let (y, m) = if ay <= 2 { (yil as i64 - 1, ay as i64 + 9) } else { (yil as i64, ay as i64 - 3) };
+    let devir = y.div_euclid(400);
+    let devir_yili = y - devir * 400;
+    let yil_gunu = (153 * m + 2) / 5 + gun as i64 - 1;
+    let devir_gunu = devir_yili * 365 + devir_yili / 4 - devir_yili / 100 + yil_gunu;
+    let gunler = devir * 146_097 + devir_gunu - 719_468;
+    (gunler * 86_400 + saat as i64 * 3_600 + dakika as i64 * 60 + saniye as i64).max(0) as u64
+}
+
+// Verilen ZIP arşivinin girdilerini ayrıntılarıyla listeler (hiçbir girdi açılmaz).
 // arsiv_resource_id: Listelenecek ZIP arşivinin Sahne64 Kaynak ID'si.
-pub fn zip_icerik_listele(arsiv_resource_id: &str) -> Result<Vec<String>, PaketYoneticisiHatasi> {
-    // 1. ZIP Arşiv Kaynağını Aç
+// sinirlar: Kaynak sınırları; aşılırsa liste döndürülmez.
+pub fn zip_icerik_listele(arsiv_resource_id: &str, sinirlar: &ArsivSinirlari) -> Result<Vec<ArsivGirdisi>, PaketYoneticisiHatasi> {
     let arsiv_handle = resource::acquire(arsiv_resource_id, resource::MODE_READ)
-        .map_err(|e| PaketYoneticisiHatasi::from_sahne_error(e))?; // SahneError'ı kendi hatamıza çevir
+        .map_err(PaketYoneticisiHatasi::from)?;
 
-    // 2. ZIP Arşivini Okumak için SahneResourceReader kullanma (Varsayımsal)
-    let arsiv_reader = SahneResourceReader::new(arsiv_handle);
-    let mut arsiv = ZipArchive::new(arsiv_reader).map_err(PaketYoneticisiHatasi::ZipHatasi)?;
+    let sonuc = (|| -> Result<Vec<ArsivGirdisi>, PaketYoneticisiHatasi> {
+        let arsiv_reader = SahneResourceReader::new(arsiv_handle).map_err(PaketYoneticisiHatasi::from)?;
+        let mut arsiv = ZipArchive::new(arsiv_reader).map_err(PaketYoneticisiHatasi::ZipError)?;
+        zip_sinirlarini_denetle(&mut arsiv, sinirlar)?;
 
-    // 3. İçerikleri Listele
-    let mut icerikler = Vec::new(); // alloc::vec::Vec kullanılıyor
+        let mut icerikler = Vec::with_capacity(arsiv.len());
+        for i in 0..arsiv.len() {
+            let girdi = arsiv.by_index_raw(i).map_err(PaketYoneticisiHatasi::ZipError)?;
+            let ad = girdi_yolunu_normallestir(girdi.name())?;
+            if ad.is_empty() {
+                continue;
+            }
+            let tur = match girdi.unix_mode() {
+                Some(mod_) => GirdiTuru::unix_modundan(mod_),
+                None if ad.ends_with('/') => GirdiTuru::Dizin,
+                None => GirdiTuru::Dosya,
+            };
+            let zaman = girdi.last_modified();
+            icerikler.push(ArsivGirdisi {
+                mod_: girdi.unix_mode().map(|mod_| mod_ & IZIN_MASKESI)
+                    .unwrap_or(if tur == GirdiTuru::Dizin { 0o755 } else { 0o644 }),
+                ad,
+                tur,
+                boyut: girdi.size(),
+                sikistirilmis_boyut: Some(girdi.compressed_size()),
+                crc32: Some(girdi.crc32()),
+                degistirilme_zamani: dos_zamanini_unix(
+                    zaman.year(), zaman.month(), zaman.day(), zaman.hour(), zaman.minute(), zaman.second()
+                ),
+            });
+        }
+        Ok(icerikler)
+    })();
 
-    for i in 0..arsiv.len() {
-        // zip::by_index metodu başarısız olursa ZipError döner, bu zaten ? ile PaketYoneticisiHatasi::ZipHatasi'na döner.
-        let arsiv_dosyasi = arsiv.by_index(i)?;
-        // name() metodu Result<&str, PathBuf> döner, PathBuf kısmı no_std'de sorun olabilir.
-        // Zip formatında entry isimleri raw bytes'tır, UTF-8 olmayabilir.
-        // zip crate'inin no_std modunda name() davranışı kontrol edilmeli.
-        // Geçici olarak name()'in &str döndürdüğünü varsayalım veya to_string() hatasını yakalayalım.
+    let _ = resource::release(arsiv_handle);
+    sonuc
+}
+
+// ZIP arşivindeki adı verilen tek bir dosya girdisini bulur, sınırlarla denetler ve okuyucusunu işleve verir.
+// Girdi adı normalleştirilmiş yol ile karşılaştırılır ("./bin/ls" ile "bin/ls" aynı girdidir).
+// Okuyucu beyan edilen boyuttan en fazla bir bayt fazla verir; fazlası çağıranın denetimindedir.
+fn zip_girdisiyle<T, F>(
+    arsiv_resource_id: &str,
+    girdi_adi: &str,
+    sinirlar: &ArsivSinirlari,
+    islem: F,
+) -> Result<T, PaketYoneticisiHatasi>
+where
+    F: FnOnce(&mut dyn Read, u64) -> Result<T, PaketYoneticisiHatasi>,
+{
+    let aranan = girdi_yolunu_normallestir(girdi_adi)?;
+    let arsiv_handle = resource::acquire(arsiv_resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let sonuc = (|| -> Result<T, PaketYoneticisiHatasi> {
+        let arsiv_reader = SahneResourceReader::new(arsiv_handle).map_err(PaketYoneticisiHatasi::from)?;
+        let mut arsiv = ZipArchive::new(arsiv_reader).map_err(PaketYoneticisiHatasi::ZipError)?;
+
+        let mut bulunan = None;
+        for i in 0..arsiv.len() {
+            let girdi = arsiv.by_index_raw(i).map_err(PaketYoneticisiHatasi::ZipError)?;
+            if girdi_yolunu_normallestir(girdi.name()).map_or(false, |ad| ad == aranan) {
+                bulunan = Some(i);
+                break;
+            }
+        }
+        let indeks = bulunan.ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(alloc::format!("Arşivde girdi bulunamadı: {}", girdi_adi))
+        })?;
+
+        let mut arsiv_dosyasi = arsiv.by_index(indeks)?;
+        let tur = arsiv_dosyasi.unix_mode().map(GirdiTuru::unix_modundan).unwrap_or(GirdiTuru::Dosya);
+        if arsiv_dosyasi.is_dir() || tur != GirdiTuru::Dosya {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(alloc::format!("Girdi bir dosya değil: {}", girdi_adi)));
+        }
+        let beyan_edilen = arsiv_dosyasi.size();
+        SinirSayaci::yeni(sinirlar).girdi_ekle(arsiv_dosyasi.name(), beyan_edilen, Some(arsiv_dosyasi.compressed_size()))?;
 
-        let entry_name = arsiv_dosyasi.name(); // Varsayım: &str dönüyor
+        let mut sinirli = (&mut arsiv_dosyasi).take(beyan_edilen.saturating_add(1));
+        islem(&mut sinirli, beyan_edilen)
+    })();
 
-        // Eğer zip crate'inin no_std'deki name() metodu PathBuf veya Result<_, PathBuf> dönüyorsa
-        // PathBuf kısmı no_std'de çalışmaz. Bu durumda zip formatının raw bytes ismini alıp
-        // kendimiz String'e çevirmemiz (UTF-8 varsayımıyla veya farklı bir kodlama ile) gerekir.
-        // Ya da zip crate'inin no_std'ye özel name() metodunu kullanmalıyız.
+    let _ = resource::release(arsiv_handle);
+    sonuc
+}
+
+// ZIP arşivindeki tek bir dosya girdisini belleğe okur (örn. ".PAKET/manifest.json" incelemesi için).
+// Girdinin boyutu sinirlar.azami_girdi_boyutu ile sınırlıdır; büyük girdiler için zip_dosya_cikar kullanılmalıdır.
+pub fn zip_girdi_oku(arsiv_resource_id: &str, girdi_adi: &str, sinirlar: &ArsivSinirlari) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    zip_girdisiyle(arsiv_resource_id, girdi_adi, sinirlar, |okuyucu, beyan_edilen| {
+        let mut icerik = Vec::new();
+        okuyucu.read_to_end(&mut icerik).map_err(|e| PaketYoneticisiHatasi::ZipError(ZipError::from(e)))?;
+        if icerik.len() as u64 > beyan_edilen {
+            return Err(sinir_asildi(girdi_adi, "beyan edilen boyut", icerik.len() as u64, beyan_edilen));
+        }
+        Ok(icerik)
+    })
+}
+
+// Arşivdeki tek bir girdiyi belirtilen hedef Kaynağa çıkarır (paketin geri kalanına dokunmadan).
+// Doğrulama sonrası onarımda (srcverify.rs) hasarlı dosyaları yeniden yazmak için kullanılır.
+// arsiv_resource_id: ZIP arşivinin Sahne64 Kaynak ID'si.
+// girdi_adi: Arşivdeki girdinin yolu (örn. "bin/ls").
+// hedef_resource_id: Girdinin yazılacağı Kaynak ID'si.
+pub fn zip_dosya_cikar(
+    arsiv_resource_id: &str,
+    girdi_adi: &str,
+    hedef_resource_id: &str,
+    sinirlar: &ArsivSinirlari,
+) -> Result<(), PaketYoneticisiHatasi> {
+    zip_girdisiyle(arsiv_resource_id, girdi_adi, sinirlar, |okuyucu, beyan_edilen| {
+        let hedef_handle = resource::acquire(
+            hedef_resource_id,
+            resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+        ).map_err(PaketYoneticisiHatasi::from)?;
 
-        icerikler.push(entry_name.to_string()); // String'e çevirip listeye ekle
+        let mut aktarim_tamponu = alloc::vec![0u8; AKTARIM_PARCA_BOYUTU];
+        let sonuc = girdiyi_aktar(okuyucu, hedef_handle, hedef_resource_id, &mut aktarim_tamponu)
+            .and_then(|aktarilan| if aktarilan > beyan_edilen {
+                Err(sinir_asildi(girdi_adi, "beyan edilen boyut", aktarilan, beyan_edilen))
+            } else {
+                Ok(())
+            });
+        let _ = resource::release(hedef_handle);
+        if sonuc.is_err() {
+            let _ = resource::delete(hedef_resource_id); // Yarım kalan girdiyi bırakma
+        }
+        sonuc
+    })
+}
+
+// Paket arşivindeki (zip veya tar) tek bir dosya girdisini belleğe okur.
+pub fn arsiv_girdi_oku(
+    arsiv_resource_id: &str,
+    girdi_adi: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    match arsiv_bicimini_algila(arsiv_resource_id)? {
+        ArsivBicimi::Zip => zip_girdi_oku(arsiv_resource_id, girdi_adi, sinirlar),
+        #[cfg(feature = "std")]
+        ArsivBicimi::Tar { sikistirma } => srctar::tar_girdi_oku(arsiv_resource_id, sikistirma, girdi_adi, ozellikler, sinirlar),
+        #[cfg(not(feature = "std"))]
+        ArsivBicimi::Tar { .. } => {
+            let _ = ozellikler; // Yalnızca tar çözücüleri kullanır
+            Err(tar_desteklenmiyor())
+        }
     }
+}
 
-    // 4. ZIP Arşiv Handle'ını Serbest Bırak
-    match resource::release(arsiv_handle) {
-        Ok(_) => Ok(icerikler), // Başarılı, listeyi döndür
-        Err(e) => {
-            eprintln!("Arşiv Kaynağı release hatası ({}): {:?}", arsiv_resource_id, e);
-            Err(PaketYoneticisiHatasi::from_sahne_error(e)) // Hata döndür
+// Paket arşivindeki (zip veya tar) tek bir dosya girdisini hedef Kaynağa çıkarır.
+pub fn arsiv_dosya_cikar(
+    arsiv_resource_id: &str,
+    girdi_adi: &str,
+    hedef_resource_id: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<(), PaketYoneticisiHatasi> {
+    match arsiv_bicimini_algila(arsiv_resource_id)? {
+        ArsivBicimi::Zip => zip_dosya_cikar(arsiv_resource_id, girdi_adi, hedef_resource_id, sinirlar),
+        #[cfg(feature = "std")]
+        ArsivBicimi::Tar { sikistirma } => {
+            srctar::tar_dosya_cikar(arsiv_resource_id, sikistirma, girdi_adi, hedef_resource_id, ozellikler, sinirlar)
+        }
+        #[cfg(not(feature = "std"))]
+        ArsivBicimi::Tar { .. } => {
+            let _ = ozellikler; // Yalnızca tar çözücüleri kullanır
+            Err(tar_desteklenmiyor())
         }
     }
 }
 
-// --- PaketYoneticisiHata enum'ının SahneError'ı içerecek şekilde güncellenmesi ---
-// (Bu enum tanımı muhtemelen başka bir dosyadadır, ancak burada nasıl görüneceğine dair bir taslak)
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-// paket_yoneticisi_hata.rs (Örnek)
-#[derive(Debug)]
-pub enum PaketYoneticisiHatasi {
-    ZipHatasi(zip::result::ZipError), // Zip crate hataları
-    DosyaSistemiHatasi(SahneError), // Sahne64 kaynak/FS hataları için
-    SahneApiHatasi(SahneError), // Genel Sahne64 API hataları için
-    GecersizParametre(String), // Fonksiyona geçersiz parametre geçilmesi
-    PathTraversalHatasi(String), // Güvenlik: Path traversal denemesi
-    // ... diğer paket yöneticisi özel hataları ...
-}
-
-impl From<zip::result::ZipError> for PaketYoneticisiHatasi {
-    fn from(err: zip::result::ZipError) -> Self {
-        // ZipError içindeki IoError'ı da ayrıca map etmek gerekebilir
-        match err {
-            zip::result::ZipError::IoError(io_err) => {
-                 // Burada std::io::Error'ı SahneError'a veya PaketYoneticisiHatasi'na maplemek lazım.
-                 // std::io::Error'ın kendisi no_std'de yok.
-                 // Bu durumda zip crate'inin no_std'de ne tür bir IO hatası döndürdüğünü anlamak gerek.
-                 // Varsayım: zip crate'i no_std modunda ZipError::IoError içinde kendi no_std hata tipini veya () döndürür.
-                 // Veya PaketYoneticisiHatasi::from_sahne_error'ı kullanır.
-                 // En basit: ZipError'ı olduğu gibi sakla ve gerektiğinde detaylandır.
-                 PaketYoneticisiHatasi::ZipHatasi(err)
+    #[test]
+    fn test_mesru_adlar_korunur() {
+        assert_eq!(girdi_yolunu_normallestir("usr/bin/foo..bar").unwrap(), "usr/bin/foo..bar");
+        assert_eq!(girdi_yolunu_normallestir("./bin//ls").unwrap(), "bin/ls");
+        assert_eq!(girdi_yolunu_normallestir("share/doc/").unwrap(), "share/doc/");
+        assert_eq!(girdi_yolunu_normallestir("a/b/../c").unwrap(), "a/c");
+        assert_eq!(girdi_yolunu_normallestir("...").unwrap(), "...");
+    }
+
+    #[test]
+    fn test_dusmanca_girdi_adlari_reddedilir() {
+        let dusmanca = [
+            "../etc/passwd",
+            "a/../../etc/passwd",
+            "a/b/../../../x",
+            "/etc/passwd",
+            "//etc/passwd",
+            "..\\..\\windows\\system32",
+            "bin\\ls",
+            "C:/windows/system32",
+            "c:evil",
+            "bin/ls\0.txt",
+            "..",
+            "./../x",
+        ];
+        for ad in dusmanca.iter() {
+            match girdi_yolunu_normallestir(ad) {
+                Err(PaketYoneticisiHatasi::PathTraversalHatasi(_)) => {}
+                sonuc => panic!("{:?} reddedilmeliydi, sonuç: {:?}", ad, sonuc),
             }
-            _ => PaketYoneticisiHatasi::ZipHatasi(err),
         }
     }
-}
 
-// SahneError'dan PaketYoneticisiHatasi'na dönüşüm
-impl PaketYoneticisiHatasi {
-    pub fn from_sahne_error(err: SahneError) -> Self {
-        match err {
-            // SahneError'daki spesifik hataları daha özel paket yöneticisi hatalarına mapleyebiliriz
-            SahneError::ResourceNotFound => PaketYoneticisiHatasi::DosyaSistemiHatasi(err), // Veya PaketNotFound?
-            SahneError::PermissionDenied => PaketYoneticisiHatasi::DosyaSistemiHatasi(err), // Yetki Hatası
-            SahneError::InvalidHandle => PaketYoneticisiHatasi::SahneApiHatasi(err), // Geçersiz Handle
-            // ... diğer SahneError varyantları ...
-            _ => PaketYoneticisiHatasi::SahneApiHatasi(err), // Kalanları genel API hatası
+    #[test]
+    fn test_sembolik_bag_hedefleri() {
+        assert!(sembolik_bag_hedefini_dogrula("lib/libz.so", "libz.so.1").is_ok());
+        assert!(sembolik_bag_hedefini_dogrula("bin/sh", "../lib/busybox").is_ok());
+        assert!(sembolik_bag_hedefini_dogrula("bin/sh", "../../etc/shadow").is_err());
+        assert!(sembolik_bag_hedefini_dogrula("kok", "../x").is_err());
+        assert!(sembolik_bag_hedefini_dogrula("bin/sh", "/bin/busybox").is_err());
+        assert!(sembolik_bag_hedefini_dogrula("bin/sh", "..\\x").is_err());
+        assert!(sembolik_bag_hedefini_dogrula("bin/sh", "").is_err());
+    }
+
+    // Girdi dizisini (ad, sembolik bağ hedefi) arşivdeki sırayla izleyiciden geçirir.
+    fn girdi_dizisini_denetle(girdiler: &[(&str, Option<&str>)]) -> Result<(), PaketYoneticisiHatasi> {
+        let mut baglar = SembolikBagIzleyici::yeni();
+        for (ad, hedef) in girdiler.iter() {
+            let yol = girdi_yolunu_normallestir(ad)?;
+            match hedef {
+                Some(hedef) => baglar.bagi_ekle(&yol, hedef)?,
+                None => baglar.girdiyi_denetle(&yol)?,
+            }
         }
+        Ok(())
     }
+
+    #[test]
+    fn test_dusmanca_sembolik_bag_zincirleri_reddedilir() {
+        // Her bağ tek başına kök içinde kalır, ama zincirlendiğinde kökten kaçar.
+        let dusmanca: &[&[(&str, Option<&str>)]] = &[
+            &[("x/b", Some("..")), ("x/b/y", Some("../..")), ("x/b/y/etc/passwd", None)],
+            &[("x/b", Some("..")), ("x/b/passwd", None)],
+            &[("x/b", Some("..")), ("x/b/../b/y", None)],
+            &[("x/b", Some("..")), ("x/b", None)], // Bağın üzerine yazmak bağı izler
+            &[("x/b", Some("..")), ("z", Some("x/b/.."))], // Hedef bağın içinden geçiyor
+            &[("a", Some(".")), ("a/b", Some("..")), ("a/b/c", None)],
+            &[("c", Some("d/e/../..")), ("d/e", Some("f"))], // Ters sıra: doğrulanmış hedefin ara yolu bağa dönüşüyor
+            &[("c", Some("d/e/../..")), ("d", Some("k"))],
+        ];
+        for girdiler in dusmanca.iter() {
+            match girdi_dizisini_denetle(girdiler) {
+                Err(PaketYoneticisiHatasi::PathTraversalHatasi(_)) => {}
+                sonuc => panic!("{:?} reddedilmeliydi, sonuç: {:?}", girdiler, sonuc),
+            }
+        }
+
+        let mesru: &[&[(&str, Option<&str>)]] = &[
+            &[("lib/libz.so", Some("libz.so.1")), ("lib/libz.so.1", Some("libz.so.1.3")), ("lib/libz.so.1.3", None)],
+            &[("x/b", Some("..")), ("w", Some("x/b")), ("x/c", None)], // Bağdan bağa
+            &[("bin/sh", Some("../lib/busybox")), ("lib/busybox", None)],
+        ];
+        for girdiler in mesru.iter() {
+            assert!(girdi_dizisini_denetle(girdiler).is_ok(), "{:?} kabul edilmeliydi", girdiler);
+        }
+    }
+
+    #[test]
+    fn test_bicim_algila() {
+        assert_eq!(bicim_algila(b"PK\x03\x04...."), Some(ArsivBicimi::Zip));
+        assert_eq!(bicim_algila(&[0x28, 0xB5, 0x2F, 0xFD, 0x00]), Some(ArsivBicimi::Tar { sikistirma: Some(CompressionAlgorithm::Zstd) }));
+        assert_eq!(bicim_algila(&[0x1F, 0x8B, 0x08]), Some(ArsivBicimi::Tar { sikistirma: Some(CompressionAlgorithm::Gzip) }));
+        assert_eq!(bicim_algila(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]), Some(ArsivBicimi::Tar { sikistirma: Some(CompressionAlgorithm::Xz) }));
+        assert_eq!(bicim_algila(&[0x04, 0x22, 0x4D, 0x18]), Some(ArsivBicimi::Tar { sikistirma: Some(CompressionAlgorithm::Lz4) }));
+        assert_eq!(bicim_algila(b"BZh91AY"), Some(ArsivBicimi::Tar { sikistirma: Some(CompressionAlgorithm::Bzip2) }));
+
+        let mut tar_basligi = [0u8; 512];
+        tar_basligi[257..262].copy_from_slice(b"ustar");
+        assert_eq!(bicim_algila(&tar_basligi), Some(ArsivBicimi::Tar { sikistirma: None }));
+
+        // Uzantı değil içerik belirleyicidir; tanınmayan içerik reddedilir.
+        assert_eq!(bicim_algila(b"paket.tar.zst"), None);
+        assert_eq!(bicim_algila(&[]), None);
+    }
+
+    #[test]
+    fn test_dos_zamanini_unix() {
+        assert_eq!(dos_zamanini_unix(1980, 1, 1, 0, 0, 0), 315_532_800);
+        assert_eq!(dos_zamanini_unix(2000, 3, 1, 0, 0, 0), 951_868_800);
+        assert_eq!(dos_zamanini_unix(2024, 2, 29, 12, 30, 58), 1_709_209_858);
+    }
+
+    #[test]
+    fn test_sinir_sayaci() {
+        let sinirlar = ArsivSinirlari {
+            azami_girdi_sayisi: 3,
+            azami_toplam_boyut: 10 * ORAN_DENETIM_ESIGI,
+            azami_girdi_boyutu: 4 * ORAN_DENETIM_ESIGI,
+            azami_sikistirma_orani: 100,
+            azami_yol_uzunlugu: 16,
+        };
+        let sinir_adi = |sonuc: Result<(), PaketYoneticisiHatasi>| match sonuc {
+            Err(PaketYoneticisiHatasi::ArsivSiniriAsildi { girdi, sinir, .. }) => (girdi, sinir),
+            diger => panic!("sınır hatası bekleniyordu: {:?}", diger),
+        };
+
+        // Küçük girdilerde oran denetlenmez.
+        let mut sayac = SinirSayaci::yeni(&sinirlar);
+        assert!(sayac.girdi_ekle("a", 1000, Some(1)).is_ok());
+        assert_eq!(sinir_adi(SinirSayaci::yeni(&sinirlar).girdi_ekle("bomba", 2 * ORAN_DENETIM_ESIGI, Some(10))),
+            ("bomba".to_string(), "sıkıştırma oranı".to_string()));
+        assert_eq!(sinir_adi(SinirSayaci::yeni(&sinirlar).girdi_ekle("buyuk", 5 * ORAN_DENETIM_ESIGI, None)).1, "girdi boyutu");
+        assert_eq!(sinir_adi(SinirSayaci::yeni(&sinirlar).girdi_ekle("cok/uzun/bir/yol/adi", 1, None)).1, "yol uzunluğu");
+
+        let mut sayac = SinirSayaci::yeni(&sinirlar);
+        assert!(sayac.girdi_ekle("x", 4 * ORAN_DENETIM_ESIGI, None).is_ok());
+        assert!(sayac.girdi_ekle("y", 4 * ORAN_DENETIM_ESIGI, None).is_ok());
+        assert_eq!(sinir_adi(sayac.girdi_ekle("z", 4 * ORAN_DENETIM_ESIGI, None)), ("z".to_string(), "toplam boyut".to_string()));
+
+        let mut sayac = SinirSayaci::yeni(&sinirlar);
+        for ad in ["1", "2", "3"].iter() {
+            assert!(sayac.girdi_ekle(ad, 0, None).is_ok());
+        }
+        assert_eq!(sinir_adi(sayac.girdi_ekle("4", 0, None)), ("4".to_string(), "girdi sayısı".to_string()));
+    }
+
+    // Kaynak tabanlı çıkarma testleri mock resource gerektirir.
 }
+
+// --- PaketYoneticisiHata enum'ının SahneError'ı içerecek şekilde güncellenmesi ---
+// (Bu enum tanımı muhtemelen başka bir dosyadadır, ancak burada nasıl görüneceğine dair bir taslak)
+
+// paket_yoneticisi_hata.rs (Örnek; bu modül srcerror.rs'deki PaketYoneticisiHatasi'nı kullanır)
+// #[derive(Debug)]
+// pub enum PaketYoneticisiHatasi {
+//     ZipHatasi(zip::result::ZipError), // Zip crate hataları
+//     DosyaSistemiHatasi(SahneError), // Sahne64 kaynak/FS hataları için
+//     SahneApiHatasi(SahneError), // Genel Sahne64 API hataları için
+//     GecersizParametre(String), // Fonksiyona geçersiz parametre geçilmesi
+//     PathTraversalHatasi(String), // Güvenlik: Path traversal denemesi
+//     // ... diğer paket yöneticisi özel hataları ...
+// }
+
+// impl From<zip::result::ZipError> for PaketYoneticisiHatasi {
+//     fn from(err: zip::result::ZipError) -> Self {
+//         // ZipError içindeki IoError'ı da ayrıca map etmek gerekebilir
+//         match err {
+//             zip::result::ZipError::IoError(io_err) => {
+//                  // Burada std::io::Error'ı SahneError'a veya PaketYoneticisiHatasi'na maplemek lazım.
+//                  // std::io::Error'ın kendisi no_std'de yok.
+//                  // Bu durumda zip crate'inin no_std'de ne tür bir IO hatası döndürdüğünü anlamak gerek.
+//                  // Varsayım: zip crate'i no_std modunda ZipError::IoError içinde kendi no_std hata tipini veya () döndürür.
+//                  // Veya PaketYoneticisiHatasi::from_sahne_error'ı kullanır.
+//                  // En basit: ZipError'ı olduğu gibi sakla ve gerektiğinde detaylandır.
+//                  PaketYoneticisiHatasi::ZipHatasi(err)
+//             }
+//             _ => PaketYoneticisiHatasi::ZipHatasi(err),
+//         }
+//     }
+// }
+
+// // SahneError'dan PaketYoneticisiHatasi'na dönüşüm
+// impl PaketYoneticisiHatasi {
+//     pub fn from_sahne_error(err: SahneError) -> Self {
+//         match err {
+//             // SahneError'daki spesifik hataları daha özel paket yöneticisi hatalarına mapleyebiliriz
+//             SahneError::ResourceNotFound => PaketYoneticisiHatasi::DosyaSistemiHatasi(err), // Veya PaketNotFound?
+//             SahneError::PermissionDenied => PaketYoneticisiHatasi::DosyaSistemiHatasi(err), // Yetki Hatası
+//             SahneError::InvalidHandle => PaketYoneticisiHatasi::SahneApiHatasi(err), // Geçersiz Handle
+//             // ... diğer SahneError varyantları ...
+//             _ => PaketYoneticisiHatasi::SahneApiHatasi(err), // Kalanları genel API hatası
+//         }
+//     }
+// }
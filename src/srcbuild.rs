@@ -0,0 +1,538 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+
+// serde ve JSON (manifesto arşive JSON olarak gömülür)
+use serde::{Deserialize, Serialize};
+use serde_json;
+
+// SHA-256 (dosya özet listesi ve arşiv sağlama toplamı)
+use sha2::{Sha256, Digest};
+use hex;
+
+// ZIP yazıcı (zip crate'inin no_std derlemesinin core2::io trait'lerini kullandığı varsayılır, srcarchive.rs ile aynı)
+use core2::io::{Read, Seek, Write};
+use zip::{ZipWriter, CompressionMethod, DateTime, write::FileOptions};
+
+// Paket meta verisi
+use crate::package::{BetikIzinleri, Paket, TetikleyiciTanimi};
+// Kaynak yazıcı/okuyucu, yol normalleştirme ve girdi türleri
+use crate::srcarchive::{self, GirdiTuru, SahneResourceWriter, AKTARIM_PARCA_BOYUTU};
+// Kaynak tür/mod sorgusu, bağ okuma ve özet hesaplama
+use crate::srcinstalled;
+
+// Sahne64 API modülleri
+use crate::resource;
+use crate::SahneError;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, debug};
+
+
+// Paket meta verilerinin arşiv içinde tutulduğu ayrılmış dizin. Hazırlık dizininde bu adla girdi olamaz.
+pub const META_DIZINI: &str = ".PAKET/";
+// Gömülü manifesto (JSON).
+pub const MANIFEST_GIRDISI: &str = ".PAKET/manifest.json";
+// Dosya başına SHA-256 özet listesi (`sha256sum` biçimi: "<özet>  <yol>").
+pub const OZET_LISTESI_GIRDISI: &str = ".PAKET/sha256sums";
+// Manifesto biçim sürümü (alan eklenip çıkarıldığında artırılır).
+pub const MANIFEST_BICIM_SURUMU: u32 = 1;
+
+// Sabit Deflate düzeyi. Aynı zip/deflate sürümüyle aynı girdi her zaman aynı baytları üretir.
+const SIKISTIRMA_DUZEYI: i32 = 9;
+
+
+// Manifestodaki bir girdi. Sahip bilgisi normalleştirildiği için (ZIP'te sahip yok) tutulmaz.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestGirdisi {
+    pub yol: String, // Paket köküne göre yol (dizinler '/' ile biter)
+    pub tur: GirdiTuru,
+    pub mod_: u32, // Erişim izinleri, tür bitleri hariç
+    pub boyut: u64, // Dizin ve sembolik bağlarda 0
+    pub bag_hedefi: Option<String>, // Sembolik bağ hedefi
+}
+
+// Arşive gömülen paket manifestosu. Alan ve girdi sırası sabittir (JSON çıktısı deterministik).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PaketManifestosu {
+    pub bicim_surumu: u32,
+    pub ad: String,
+    pub surum: String,
+    pub bagimliliklar: Vec<String>,
+    pub aciklama: Option<String>,
+    pub lisans: Option<String>,
+    pub yazar: Option<String>,
+    pub kurulum_scripti: Option<String>,
+    pub kaldirma_scripti: Option<String>,
+    pub tetikleyiciler: Vec<TetikleyiciTanimi>,
+    #[serde(default)] // Bu alandan önce oluşturulan manifestolarda betikler hiçbir izin almaz
+    pub betik_izinleri: BetikIzinleri,
+    pub kurulu_boyut: u64,
+    pub girdiler: Vec<ManifestGirdisi>,
+}
+
+// Paketleyicinin yazdığı paket tanım dosyası (JSON). Yalnızca ad ve sürüm zorunludur.
+// Dosya listesi, boyutlar ve özetler arşiv oluşturulurken doldurulduğu için tanımda yer almaz.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PaketTanimi {
+    pub ad: String,
+    pub surum: String,
+    #[serde(default)]
+    pub bagimliliklar: Vec<String>,
+    #[serde(default)]
+    pub aciklama: Option<String>,
+    #[serde(default)]
+    pub lisans: Option<String>,
+    #[serde(default)]
+    pub yazar: Option<String>,
+    #[serde(default)]
+    pub kurulum_scripti: Option<String>,
+    #[serde(default)]
+    pub kaldirma_scripti: Option<String>,
+    #[serde(default)]
+    pub tetikleyiciler: Vec<TetikleyiciTanimi>,
+    #[serde(default)]
+    pub betik_izinleri: BetikIzinleri,
+}
+
+impl PaketTanimi {
+    // JSON tanımını ayrıştırır; bilinmeyen alanlar yok sayılır.
+    pub fn ayristir(veri: &[u8]) -> Result<Self, PaketYoneticisiHatasi> {
+        serde_json::from_slice(veri).map_err(|e| {
+            PaketYoneticisiHatasi::ParsingError(format!("Paket tanımı ayrıştırılamadı: {}", e))
+        })
+    }
+
+    pub fn kaynaktan_yukle(resource_id: &str) -> Result<Self, PaketYoneticisiHatasi> {
+        Self::ayristir(&read_resource_to_vec(resource_id)?)
+    }
+
+    // Arşiv oluşturucuya verilecek paket meta verisi.
+    pub fn paket(&self) -> Paket {
+        let mut paket = Paket::yeni(self.ad.clone(), self.surum.clone(), self.bagimliliklar.clone());
+        paket.aciklama = self.aciklama.clone();
+        paket.lisans = self.lisans.clone();
+        paket.yazar = self.yazar.clone();
+        paket.kurulum_scripti = self.kurulum_scripti.clone();
+        paket.kaldirma_scripti = self.kaldirma_scripti.clone();
+        paket.tetikleyiciler = self.tetikleyiciler.clone();
+        paket.betik_izinleri = self.betik_izinleri.clone();
+        paket
+    }
+}
+
+// Hazırlık dizini listesini normalleştirip bayt sırasına dizer.
+// Kökten kaçan veya ayrılmış meta dizinine giren yollar reddedilir; tekrarlar ve kök girdisi atlanır.
+pub fn girdileri_sirala(yollar: Vec<String>) -> Result<Vec<String>, PaketYoneticisiHatasi> {
+    let mut sirali = Vec::with_capacity(yollar.len());
+    for yol in yollar {
+        let normal = srcarchive::girdi_yolunu_normallestir(&yol)?;
+        if normal.is_empty() {
+            continue;
+        }
+        if normal.starts_with(META_DIZINI) || normal == META_DIZINI.trim_end_matches('/') {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!(
+                "Hazırlık dizininde ayrılmış meta dizini kullanılamaz: {}", yol
+            )));
+        }
+        sirali.push(normal);
+    }
+    sirali.sort(); // String sıralaması bayt sırasıdır (yerel ayardan bağımsız)
+    sirali.dedup();
+    Ok(sirali)
+}
+
+// Özet listesini `sha256sum` biçiminde üretir (girdiler verilen sırayla).
+pub fn ozet_listesi_olustur(ozetler: &[(String, String)]) -> String {
+    let mut liste = String::new();
+    for (yol, ozet) in ozetler {
+        liste.push_str(ozet);
+        liste.push_str("  ");
+        liste.push_str(yol);
+        liste.push('\n');
+    }
+    liste
+}
+
+// Sabit zaman damgası (1980-01-01 00:00, ZIP'in temsil edebildiği en erken an) ve sabit sıkıştırma ile girdi seçenekleri.
+fn girdi_secenekleri(mod_: u32) -> FileOptions {
+    FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .compression_level(Some(SIKISTIRMA_DUZEYI))
+        .last_modified_time(DateTime::default())
+        .unix_permissions(mod_ & srcarchive::IZIN_MASKESI)
+}
+
+fn zip_hatasi(e: zip::result::ZipError) -> PaketYoneticisiHatasi {
+    PaketYoneticisiHatasi::ZipError(e)
+}
+
+fn yazma_hatasi(e: core2::io::Error) -> PaketYoneticisiHatasi {
+    PaketYoneticisiHatasi::ZipError(zip::result::ZipError::from(e))
+}
+
+
+// Arşive alınacak ağaç. Kaynak katmanındaki hazırlık dizini (ArsivOlusturucu) ve testlerdeki bellek içi ağaç uygular.
+pub trait HazirlikAgaci {
+    // Ağaçtaki tüm yollar (herhangi bir sırada, dizinler dahil).
+    fn yollar(&self) -> Result<Vec<String>, PaketYoneticisiHatasi>;
+    // Girdinin türü ve erişim izinleri.
+    fn girdi_bilgisi(&self, yol: &str) -> Result<(GirdiTuru, u32), PaketYoneticisiHatasi>;
+    fn bag_hedefi(&self, yol: &str) -> Result<String, PaketYoneticisiHatasi>;
+    // Dosya içeriğini tampon boyutunda parçalar halinde işleve verir.
+    fn dosyayi_oku(
+        &self,
+        yol: &str,
+        tampon: &mut [u8],
+        parca: &mut dyn FnMut(&[u8]) -> Result<(), PaketYoneticisiHatasi>,
+    ) -> Result<(), PaketYoneticisiHatasi>;
+}
+
+// Hazırlık dizininden kanonik paket arşivi oluşturan yapı.
+// Aynı hazırlık dizini ve meta veri her zaman bit bit aynı arşivi üretir:
+// girdiler bayt sırasıyla yazılır, zaman damgaları sabitlenir, sahip bilgisi yazılmaz,
+// manifesto ve özet listesi arşivin sonuna eklenir.
+// Sabit bağlar korunmaz: Kaynak katmanı (STAT) bir dosyanın başka bir adla bağlı olduğunu bildirmediği için
+// her ad ayrı bir dosya olarak paketlenir ve kurulumda ayrı Kaynaklar olarak çıkarılır.
+pub struct ArsivOlusturucu {
+    hazirlik_dizini: String, // Paket içeriğinin kurulum düzeninde hazırlandığı dizin Kaynak ID'si
+}
+
+impl ArsivOlusturucu {
+    pub fn yeni(hazirlik_dizini: &str) -> Self {
+        ArsivOlusturucu {
+            hazirlik_dizini: hazirlik_dizini.trim_end_matches('/').to_owned(),
+        }
+    }
+
+    // Arşivi hedef Kaynağa yazar.
+    // Dönüş değeri: `dosya_adi`, `dosyalar`, `checksums["sha256"]`, `indirme_boyutu` ve `kurulu_boyut`
+    // alanları doldurulmuş paket meta verisi (depo indeksine eklenmeye hazır).
+    // Hata durumunda yarım kalan hedef Kaynak silinir.
+    pub fn olustur(&self, paket: &Paket, hedef_resource_id: &str) -> Result<Paket, PaketYoneticisiHatasi> {
+        let handle = resource::acquire(
+            hedef_resource_id,
+            resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+        ).map_err(PaketYoneticisiHatasi::from)?;
+
+        let sonuc = self.arsivi_yaz(paket, handle);
+        let _ = resource::release(handle);
+
+        let manifesto = match sonuc {
+            Ok(manifesto) => manifesto,
+            Err(e) => {
+                let _ = resource::delete(hedef_resource_id);
+                return Err(e);
+            }
+        };
+
+        let arsiv_sha256 = srcinstalled::kaynak_sha256(hedef_resource_id)?;
+        let arsiv_boyutu = srcinstalled::kaynak_bilgisi_al(hedef_resource_id)?.boyut;
+
+        let mut sonuc_paket = paket.clone();
+        sonuc_paket.dosya_adi = hedef_resource_id.rsplit('/').next().map(|ad| ad.to_owned());
+        sonuc_paket.dosyalar = manifesto.girdiler.iter()
+            .filter(|g| g.tur != GirdiTuru::Dizin)
+            .map(|g| g.yol.clone())
+            .collect();
+        sonuc_paket.checksums.insert(String::from("sha256"), arsiv_sha256.clone());
+        sonuc_paket.indirme_boyutu = Some(arsiv_boyutu);
+        sonuc_paket.kurulu_boyut = Some(manifesto.kurulu_boyut);
+
+        info!("Paket arşivi oluşturuldu: {} {} -> {} (sha256 {})", paket.ad, paket.surum, hedef_resource_id, arsiv_sha256);
+        Ok(sonuc_paket)
+    }
+
+    fn kaynak_id(&self, yol: &str) -> String {
+        format!("{}/{}", self.hazirlik_dizini, yol.trim_end_matches('/'))
+    }
+
+    fn arsivi_yaz(&self, paket: &Paket, handle: crate::Handle) -> Result<PaketManifestosu, PaketYoneticisiHatasi> {
+        arsiv_yaz(self, paket, SahneResourceWriter::new(handle)).map(|(manifesto, _)| manifesto)
+    }
+}
+
+impl HazirlikAgaci for ArsivOlusturucu {
+    fn yollar(&self) -> Result<Vec<String>, PaketYoneticisiHatasi> {
+        match resource::list(&self.hazirlik_dizini) {
+            Ok(yollar) => Ok(yollar),
+            Err(SahneError::NotSupported) => Err(PaketYoneticisiHatasi::InvalidParameter(format!(
+                "Hazırlık dizini listelenemiyor (Kaynak listeleme desteklenmiyor): {}", self.hazirlik_dizini
+            ))),
+            Err(e) => Err(PaketYoneticisiHatasi::from(e)),
+        }
+    }
+
+    fn girdi_bilgisi(&self, yol: &str) -> Result<(GirdiTuru, u32), PaketYoneticisiHatasi> {
+        let bilgi = srcinstalled::kaynak_bilgisi_al(&self.kaynak_id(yol))?;
+        Ok((bilgi.tur, bilgi.mod_))
+    }
+
+    fn bag_hedefi(&self, yol: &str) -> Result<String, PaketYoneticisiHatasi> {
+        srcinstalled::bag_hedefi_oku(&self.kaynak_id(yol))
+    }
+
+    fn dosyayi_oku(
+        &self,
+        yol: &str,
+        tampon: &mut [u8],
+        parca: &mut dyn FnMut(&[u8]) -> Result<(), PaketYoneticisiHatasi>,
+    ) -> Result<(), PaketYoneticisiHatasi> {
+        let kaynak_id = self.kaynak_id(yol);
+        let handle = resource::acquire(&kaynak_id, resource::MODE_READ).map_err(PaketYoneticisiHatasi::from)?;
+        let mut okuyucu = srcarchive::SahneResourceReader::new(handle).map_err(|e| {
+            let _ = resource::release(handle);
+            PaketYoneticisiHatasi::from(e)
+        })?;
+
+        let sonuc = loop {
+            let okunan = match okuyucu.read(tampon) {
+                Ok(0) => break Ok(()),
+                Ok(n) => n,
+                Err(e) if e.kind() == core2::io::ErrorKind::Interrupted => continue,
+                Err(e) => break Err(yazma_hatasi(e)),
+            };
+            if let Err(e) = parca(&tampon[..okunan]) {
+                break Err(e);
+            }
+        };
+
+        let _ = resource::release(handle);
+        sonuc
+    }
+}
+
+// Ağacın kanonik arşivini yazıcıya yazar.
+// Dönüş değeri: Arşive gömülen manifesto ve yazıcı (zip::ZipWriter::finish'ten).
+pub fn arsiv_yaz<A: HazirlikAgaci, W: Write + Seek>(agac: &A, paket: &Paket, yazici: W) -> Result<(PaketManifestosu, W), PaketYoneticisiHatasi> {
+    let yollar = girdileri_sirala(agac.yollar()?)?;
+
+    let mut zip = ZipWriter::new(yazici);
+    let mut tampon = alloc::vec![0u8; AKTARIM_PARCA_BOYUTU];
+    let mut girdiler = Vec::with_capacity(yollar.len());
+    let mut ozetler = Vec::new();
+    let mut kurulu_boyut = 0u64;
+
+    for yol in yollar {
+        let (tur, mod_) = agac.girdi_bilgisi(&yol)?;
+        debug!("Arşive ekleniyor: {} ({:?})", yol, tur);
+
+        let girdi = match tur {
+            GirdiTuru::Dizin => {
+                let mut yol = yol;
+                if !yol.ends_with('/') {
+                    yol.push('/');
+                }
+                zip.add_directory(yol.as_str(), girdi_secenekleri(mod_)).map_err(zip_hatasi)?;
+                ManifestGirdisi { yol, tur: GirdiTuru::Dizin, mod_, boyut: 0, bag_hedefi: None }
+            }
+            GirdiTuru::SembolikBag => {
+                let hedef = agac.bag_hedefi(&yol)?;
+                // Kurulumda reddedilecek bir bağ paketlenmez.
+                srcarchive::sembolik_bag_hedefini_dogrula(&yol, &hedef)?;
+                zip.add_symlink(yol.as_str(), hedef.as_str(), girdi_secenekleri(0o777)).map_err(zip_hatasi)?;
+                ManifestGirdisi { yol, tur: GirdiTuru::SembolikBag, mod_: 0o777, boyut: 0, bag_hedefi: Some(hedef) }
+            }
+            // Sabit bağlar STAT ile ayırt edilemez; her ad ayrı bir dosya olarak paketlenir.
+            GirdiTuru::Dosya | GirdiTuru::SabitBag => {
+                zip.start_file(yol.as_str(), girdi_secenekleri(mod_)).map_err(zip_hatasi)?;
+                let mut hasher = Sha256::new();
+                let mut boyut = 0u64;
+                agac.dosyayi_oku(&yol, &mut tampon, &mut |parca| {
+                    hasher.update(parca);
+                    boyut += parca.len() as u64;
+                    zip.write_all(parca).map_err(yazma_hatasi)
+                })?;
+                kurulu_boyut += boyut;
+                ozetler.push((yol.clone(), hex::encode(hasher.finalize())));
+                ManifestGirdisi { yol, tur: GirdiTuru::Dosya, mod_, boyut, bag_hedefi: None }
+            }
+        };
+        girdiler.push(girdi);
+    }
+
+    let manifesto = PaketManifestosu {
+        bicim_surumu: MANIFEST_BICIM_SURUMU,
+        ad: paket.ad.clone(),
+        surum: paket.surum.clone(),
+        bagimliliklar: paket.bagimliliklar.clone(),
+        aciklama: paket.aciklama.clone(),
+        lisans: paket.lisans.clone(),
+        yazar: paket.yazar.clone(),
+        kurulum_scripti: paket.kurulum_scripti.clone(),
+        kaldirma_scripti: paket.kaldirma_scripti.clone(),
+        tetikleyiciler: paket.tetikleyiciler.clone(),
+        betik_izinleri: paket.betik_izinleri.clone(),
+        kurulu_boyut,
+        girdiler,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifesto).map_err(|e| {
+        PaketYoneticisiHatasi::UnknownError(format!("Paket manifestosu JSON'a çevrilemedi: {:?}", e))
+    })?;
+
+    zip.add_directory(META_DIZINI, girdi_secenekleri(0o755)).map_err(zip_hatasi)?;
+    zip.start_file(MANIFEST_GIRDISI, girdi_secenekleri(0o644)).map_err(zip_hatasi)?;
+    zip.write_all(&manifest_json).map_err(yazma_hatasi)?;
+    zip.start_file(OZET_LISTESI_GIRDISI, girdi_secenekleri(0o644)).map_err(zip_hatasi)?;
+    zip.write_all(ozet_listesi_olustur(&ozetler).as_bytes()).map_err(yazma_hatasi)?;
+
+    let yazici = zip.finish().map_err(zip_hatasi)?;
+    Ok((manifesto, yazici))
+}
+
+// Helper fonksiyon: Sahne64 Kaynağından tüm içeriği Vec<u8> olarak oku.
+fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut buffer = Vec::new();
+    let mut temp_buffer = [0u8; 512]; // Stack buffer
+
+    loop {
+        match resource::read(handle, &mut temp_buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => buffer.extend_from_slice(&temp_buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    let _ = resource::release(handle);
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_girdileri_sirala() {
+        let yollar = alloc::vec![
+            "usr/bin/zz".to_string(),
+            "./usr/".to_string(),
+            "usr/bin/Aa".to_string(),
+            "usr/bin/zz".to_string(),
+            "./".to_string(),
+        ];
+        assert_eq!(
+            girdileri_sirala(yollar).unwrap(),
+            alloc::vec!["usr/".to_string(), "usr/bin/Aa".to_string(), "usr/bin/zz".to_string()]
+        );
+
+        assert!(girdileri_sirala(alloc::vec![".PAKET/manifest.json".to_string()]).is_err());
+        assert!(girdileri_sirala(alloc::vec!["../disari".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_ozet_listesi_ve_manifesto_deterministik() {
+        let ozetler = alloc::vec![("bin/ls".to_string(), "ab12".to_string())];
+        assert_eq!(ozet_listesi_olustur(&ozetler), "ab12  bin/ls\n");
+
+        let manifesto = PaketManifestosu {
+            bicim_surumu: MANIFEST_BICIM_SURUMU,
+            ad: "ls".to_string(),
+            surum: "1.0".to_string(),
+            bagimliliklar: alloc::vec!["libc".to_string()],
+            aciklama: None,
+            lisans: Some("MIT".to_string()),
+            yazar: None,
+            kurulum_scripti: None,
+            kaldirma_scripti: None,
+            tetikleyiciler: Vec::new(),
+            betik_izinleri: BetikIzinleri::default(),
+            kurulu_boyut: 4,
+            girdiler: alloc::vec![ManifestGirdisi { yol: "bin/ls".to_string(), tur: GirdiTuru::Dosya, mod_: 0o755, boyut: 4, bag_hedefi: None }],
+        };
+        let birinci = serde_json::to_vec_pretty(&manifesto).unwrap();
+        let ikinci = serde_json::to_vec_pretty(&manifesto.clone()).unwrap();
+        assert_eq!(birinci, ikinci);
+    }
+
+    // Bellek içi hazırlık ağacı: yol -> (tür, mod, içerik veya bağ hedefi).
+    struct BellekAgaci {
+        girdiler: Vec<(String, GirdiTuru, u32, Vec<u8>)>,
+    }
+
+    impl BellekAgaci {
+        fn girdi(&self, yol: &str) -> Result<&(String, GirdiTuru, u32, Vec<u8>), PaketYoneticisiHatasi> {
+            self.girdiler.iter().find(|g| g.0.trim_end_matches('/') == yol.trim_end_matches('/'))
+                .ok_or_else(|| PaketYoneticisiHatasi::InvalidParameter(yol.to_string()))
+        }
+    }
+
+    impl HazirlikAgaci for BellekAgaci {
+        fn yollar(&self) -> Result<Vec<String>, PaketYoneticisiHatasi> {
+            Ok(self.girdiler.iter().map(|g| g.0.clone()).collect())
+        }
+
+        fn girdi_bilgisi(&self, yol: &str) -> Result<(GirdiTuru, u32), PaketYoneticisiHatasi> {
+            self.girdi(yol).map(|g| (g.1, g.2))
+        }
+
+        fn bag_hedefi(&self, yol: &str) -> Result<String, PaketYoneticisiHatasi> {
+            self.girdi(yol).map(|g| String::from_utf8(g.3.clone()).unwrap())
+        }
+
+        fn dosyayi_oku(
+            &self,
+            yol: &str,
+            tampon: &mut [u8],
+            parca: &mut dyn FnMut(&[u8]) -> Result<(), PaketYoneticisiHatasi>,
+        ) -> Result<(), PaketYoneticisiHatasi> {
+            for dilim in self.girdi(yol)?.3.chunks(tampon.len()) {
+                parca(dilim)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn ornek_agac(ters_sira: bool) -> BellekAgaci {
+        let mut girdiler = alloc::vec![
+            ("usr/".to_string(), GirdiTuru::Dizin, 0o755, Vec::new()),
+            ("usr/bin/ls".to_string(), GirdiTuru::Dosya, 0o755, alloc::vec![0x7fu8; 3 * AKTARIM_PARCA_BOYUTU + 17]),
+            ("usr/bin/dir".to_string(), GirdiTuru::SembolikBag, 0o777, b"ls".to_vec()),
+            ("usr/share/doc/README".to_string(), GirdiTuru::Dosya, 0o644, b"merhaba\n".to_vec()),
+        ];
+        if ters_sira {
+            girdiler.reverse();
+        }
+        BellekAgaci { girdiler }
+    }
+
+    #[test]
+    fn test_ayni_agac_bit_bit_ayni_arsivi_uretir() {
+        let mut paket = Paket::yeni("ls".to_string(), "1.0".to_string(), alloc::vec!["libc".to_string()]);
+        paket.lisans = Some("MIT".to_string());
+
+        let (ilk_manifesto, ilk) = arsiv_yaz(&ornek_agac(false), &paket, core2::io::Cursor::new(Vec::new())).unwrap();
+        // Listeleme sırası çıktıyı değiştirmemeli
+        let (ikinci_manifesto, ikinci) = arsiv_yaz(&ornek_agac(true), &paket, core2::io::Cursor::new(Vec::new())).unwrap();
+
+        assert_eq!(ilk.into_inner(), ikinci.into_inner());
+        assert_eq!(ilk_manifesto, ikinci_manifesto);
+        assert_eq!(ilk_manifesto.kurulu_boyut, (3 * AKTARIM_PARCA_BOYUTU + 17 + 8) as u64);
+        let yollar: Vec<&str> = ilk_manifesto.girdiler.iter().map(|g| g.yol.as_str()).collect();
+        assert_eq!(yollar, ["usr/", "usr/bin/dir", "usr/bin/ls", "usr/share/doc/README"]);
+    }
+
+    #[test]
+    fn test_paket_tanimi() {
+        let tanim = PaketTanimi::ayristir(br#"{"ad": "ls", "surum": "1.0", "lisans": "MIT", "bagimliliklar": ["libc >= 2"]}"#).unwrap();
+        let paket = tanim.paket();
+        assert_eq!((paket.ad.as_str(), paket.surum.as_str()), ("ls", "1.0"));
+        assert_eq!(paket.lisans.as_deref(), Some("MIT"));
+        assert_eq!(paket.bagimliliklar, ["libc >= 2"]);
+        assert!(paket.tetikleyiciler.is_empty());
+
+        assert!(PaketTanimi::ayristir(br#"{"ad": "ls"}"#).is_err()); // Sürüm zorunlu
+    }
+}
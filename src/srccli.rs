@@ -100,98 +100,462 @@ mod pkg_manager {
         // Kurulum scripti Kaynak ID'sini oluştur (varsayımsal)
         let install_script_resource_id = format!("{}{}/install.sh", PACKAGE_SCRIPT_BASE_RESOURCE, package_name);
 
-        // Kurulum scripti Kaynağına erişim Handle'ı edin (çalıştırma izniyle?)
-        // Sahne64 API'sında 'çalıştırma izni' Mode flag'i olmayabilir.
-        // Task::spawn bir kod Kaynağı Handle'ı bekler. Bu Kaynak çalıştırılabilir kodu içermelidir.
-        // Varsayım: install.sh Kaynağı, task::spawn'ın anlayacağı bir formatta (örneğin, bir ara dil veya native kod).
-        // Veya paket yöneticisi, scripti okuyup kendisi yorumlar/çalıştırır (daha karmaşık).
-        // Basitlik adına, install.sh Kaynağının task::spawn tarafından doğrudan çalıştırılabildiğini varsayalım.
-
-        match resource::acquire(&install_script_resource_id, resource::MODE_READ) { // MODE_READ yeterli mi? Çalıştırma için özel flag mi lazım?
-             Ok(script_handle) => {
-                 println!("Kurulum scripti Kaynağı edinildi: {}", install_script_resource_id);
-
-                 // Yeni bir görev (task) olarak scripti başlat
-                 // task::spawn function signature: spawn(code_handle: Handle, args: &[u8])
-                 let task_args: &[u8] = b""; // Kurulum scriptine argüman geçilebilir. Şimdilik boş.
-
-                 match task::spawn(script_handle, task_args) {
-                      Ok(new_tid) => {
-                          println!("Paket kurulum görevi başlatıldı, TaskId: {:?}", new_tid);
-                          // Script Handle'ı görev başlatıldıktan sonra serbest bırakılabilir mi?
-                          // Çekirdeğin code_handle'ın bir kopyasını alıp almadığına bağlı.
-                          // Güvenli olması için görev bitene kadar Handle'ı açık tutmak gerekebilir.
-                          // Veya kernel handle'ı consume ediyordur. Varsayım: Kernel kopyalar, user space bırakabilir.
-                          let _ = resource::release(script_handle); // Handle'ı serbest bırak
-
-                          // Kurulum görevinin tamamlanmasını beklemek isteyebiliriz (process::wait benzeri).
-                          // Sahne64 API'sında task::wait syscall'u tanımlı değildi.
-                          // Bu bir eksiklik. Paket yöneticisi bu görevin bitmesini nasıl bekleyecek?
-                          // Messaging ile görev bitiş mesajı gönderebilir mi?
-                          // Şimdilik beklemeyelim.
-
-                          Ok(())
-                      }
-                      Err(e) => {
-                          let _ = resource::release(script_handle); // Hata durumunda handle'ı temizle
-                          eprintln!("Paket kurulum görevi başlatılamadı (Kaynak: {}): {:?}", install_script_resource_id, e);
-                          Err(PaketYoneticisiHatasi::from(e)) // SahneError'ı PaketYoneticisiHatasi'na çevir
-                      }
-                 }
-             }
-             Err(e) => {
-                 eprintln!("Kurulum scripti Kaynağı acquire hatası ({}): {:?}", install_script_resource_id, e);
-                 Err(PaketYoneticisiHatasi::from(e)) // SahneError'ı PaketYoneticisiHatasi'na çevir
-             }
-        }
+        // Betik, paketin kurulu kaydındaki betik izinleriyle çalıştırılır; kayıt yoksa hiçbir ek izin verilmez.
+        // Not: Sahne64 API'sında task::wait olmadığından görevin tamamlanması beklenmez.
+        let izinler = crate::srcinstalled::KuruluPaketVeritabani::yeni().kaydi_yukle(package_name)?
+            .map(|kayit| kayit.paket.betik_izinleri)
+            .unwrap_or_default();
+        let politika = crate::srcsandbox::SandboxPolitikasi::betik_icin(&izinler, &install_script_resource_id);
+        crate::srcscript::betik_calistir(&install_script_resource_id, b"", &politika).map_err(|e| {
+            eprintln!("Paket kurulum betiği çalıştırılamadı (Kaynak: {}): {}", install_script_resource_id, e);
+            e
+        })
     }
 
-    // Bir paketi kaldırır.
+    // Bir paketi kaldırır: kaldırma betiği çalıştırılır, kurulu dosya kaydındaki dosyalar, bağlar ve dizinler silinir.
     // package_name: Kaldırılacak paketin adı.
     pub fn remove_package(package_name: &str) -> Result<(), PaketYoneticisiHatasi> { // Result türü PaketYoneticisiHatasi olmalı
         println!("{} paketi kaldırılıyor...", package_name);
+        kurulum_yoneticisi().paketi_kaldir(package_name)
+    }
 
-        // Kaldırma scripti Kaynak ID'sini oluştur (varsayımsal)
-        let uninstall_script_resource_id = format!("{}{}/uninstall.sh", PACKAGE_SCRIPT_BASE_RESOURCE, package_name);
-
-        // Kaldırma scripti Kaynağına erişim Handle'ı edin
-        match resource::acquire(&uninstall_script_resource_id, resource::MODE_READ) { // Yine çalıştırma için Handle edinme
-             Ok(script_handle) => {
-                 println!("Kaldırma scripti Kaynağı edinildi: {}", uninstall_script_resource_id);
-
-                 // Yeni bir görev (task) olarak scripti başlat
-                 let task_args: &[u8] = b""; // Kaldırma scriptine argüman geçilebilir.
-
-                 match task::spawn(script_handle, task_args) {
-                      Ok(new_tid) => {
-                          println!("Paket kaldırma görevi başlatıldı, TaskId: {:?}", new_tid);
-                           let _ = resource::release(script_handle); // Handle'ı serbest bırak
-                          // Görevin bitmesini beklemek gerekebilir (task::wait eksikliği).
-                          Ok(())
-                      }
-                      Err(e) => {
-                          let _ = resource::release(script_handle); // Hata durumunda handle'ı temizle
-                          eprintln!("Paket kaldırma görevi başlatılamadı (Kaynak: {}): {:?}", uninstall_script_resource_id, e);
-                          Err(PaketYoneticisiHatasi::from(e)) // SahneError'ı PaketYoneticisiHatasi'na çevir
-                      }
-                 }
-             }
-             Err(SahneError::ResourceNotFound) => {
-                  // Kaldırma scripti yoksa paket belki de düzgün kurulmamıştır veya script gerektirmiyordur.
-                  // Veya bu bir hata olabilir. Şimdilik loglayıp devam edelim veya özel hata dönelim.
-                  eprintln!("Kaldırma scripti bulunamadı ({}). Paket dosyaları manuel silinmeli?", uninstall_script_resource_id);
-                  // Paketin dosyalarının ve veritabanı kaydının silinmesi burada ele alınmalı.
-                  // Bu, resource::delete veya resource::control kullanımı gerektirir (eksik fonksiyonellik).
-                  // Varsayım: Paket yöneticisi, kurulu dosyaların listesini tutar ve silme işlemi bu listeye göre yapılır.
-                  // Bu da resource::delete'in varlığını gerektirir.
-                  // Şimdilik sadece script çalıştırmayı taklit ettiğimiz için, script yoksa hata dönmek yerine uyarı verelim.
-                  Ok(()) // Script yoksa hata değil, uyarı verip devam et
-             }
-             Err(e) => {
-                 eprintln!("Kaldırma scripti Kaynağı acquire hatası ({}): {:?}", uninstall_script_resource_id, e);
-                 Err(PaketYoneticisiHatasi::from(e)) // SahneError'ı PaketYoneticisiHatasi'na çevir
-             }
+    // Kurulu paketleri (veya tek bir paketi) kayıtlı özet, boyut ve modlara göre doğrular.
+    // package_name: Doğrulanacak paket (None ise tüm kurulu paketler).
+    // json: Raporu makine tarafından okunabilir JSON olarak yazdır.
+    // onar: Hasarlı dosyaları önbellekteki arşivden yeniden çıkar.
+    pub fn verify_packages(package_name: Option<&str>, json: bool, onar: bool) -> Result<(), PaketYoneticisiHatasi> {
+        let dogrulayici = crate::srcverify::Dogrulayici::yeni(
+            crate::srcinstalled::KuruluPaketVeritabani::yeni(),
+            String::from("sahne://installed_packages"),
+            String::from("sahne://cache/packages"),
+        );
+
+        let rapor = dogrulayici.dogrula(package_name)?;
+        if json {
+            println!("{}", rapor.json()?);
+        } else {
+            print!("{}", rapor.metin());
+        }
+
+        if onar && !rapor.temiz() {
+            let onarilan = dogrulayici.onar(&rapor)?;
+            println!("{} dosya onarıldı.", onarilan);
+        }
+
+        Ok(())
+    }
+
+    // Planlı kurulum işlemleri için kurulum yöneticisi (uzak depo, kurulum ve önbellek konumları).
+    fn kurulum_yoneticisi() -> crate::srcinstaller::KurulumYoneticisi {
+        crate::srcinstaller::KurulumYoneticisi::yeni(
+            String::from("sahne://remoterepo/packages"),
+            String::from("sahne://installed_packages"),
+            String::from("sahne://cache/packages"),
+        )
+    }
+
+    // Kurulu bir paketi aynı sürümle yeniden kurar (dosyalar yeniden çıkarılır, betikler yeniden çalışır).
+    pub fn reinstall_package(package_name: &str) -> Result<(), PaketYoneticisiHatasi> {
+        kurulum_yoneticisi().yeniden_kur(package_name)?;
+        println!("{} paketi yeniden kuruldu.", package_name);
+        Ok(())
+    }
+
+    // Kurulu bir paketin sürümünü düşürür.
+    // version: Hedef sürüm (None ise depo indeksindeki kurulu sürümden eski en yeni sürüm).
+    // onayla: Sürüm düşürme açıkça onaylanmalı (--onayla).
+    pub fn downgrade_package(package_name: &str, version: Option<&str>, onayla: bool) -> Result<(), PaketYoneticisiHatasi> {
+        let kurulu = crate::srcinstalled::KuruluPaketVeritabani::yeni().kaydi_yukle(package_name)?
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(package_name.to_owned()))?;
+
+        // Sürüm listesi yalnızca imzalı depo bildirimiyle doğrulanmış indeksten okunur
+        let halka = anahtar_halkasini_yukle()?;
+        let guven = crate::srctrust::TrustManager::varsayilan();
+        let bildirim = crate::srcrepositoryrelease::bildirimi_yukle("sahne://remoterepo/packages", &halka, guven.key_scopes())?;
+        let indeks = crate::srcrepositoryindex::load_index("sahne://remoterepo/packages", &bildirim.bildirim)?;
+        let surumler = indeks.get_versions(package_name)
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(package_name.to_owned()))?;
+
+        let hedef_surum = match version {
+            Some(surum) => surumler.iter().find(|s| s.as_str() == surum).ok_or_else(|| {
+                PaketYoneticisiHatasi::PaketBulunamadi(format!("{} {}", package_name, surum))
+            })?,
+            None => crate::srcversion::onceki_surum_sec(surumler, &kurulu.paket.surum).ok_or_else(|| {
+                PaketYoneticisiHatasi::PaketBulunamadi(format!("{} için {} sürümünden eski sürüm yok", package_name, kurulu.paket.surum))
+            })?,
+        };
+
+        let mut depo = crate::srcrepository::DepoYoneticisi::yeni(
+            String::from("sahne://remoterepo/packages"),
+            String::from("sahne://cache/repo"),
+        );
+        let hedef = depo.paket_listesini_al()?
+            .into_iter()
+            .find(|p| p.ad == package_name && &p.surum == hedef_surum)
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(format!("{} {}", package_name, hedef_surum)))?;
+
+        kurulum_yoneticisi().surum_dusur(&hedef, onayla)?;
+        println!("{} paketi {} sürümüne düşürüldü.", package_name, hedef.surum);
+        Ok(())
+    }
+
+    // Hazırlık dizininden kanonik paket arşivi oluşturur ve depo indeksi için sağlama toplamını yazdırır.
+    // staging_dir: Paket içeriğinin kurulum düzeninde hazırlandığı dizin Kaynak ID'si.
+    // metadata: Paket tanım dosyasının (JSON: ad, sürüm, bağımlılıklar, lisans, betikler, tetikleyiciler, betik izinleri) Kaynak ID'si.
+    // output: Oluşturulacak arşivin Kaynak ID'si (örn. "sahne://cache/packages/ls-1.0.zip").
+    // Sabit bağlar korunmaz; her ad ayrı bir dosya olarak paketlenir (srcbuild::ArsivOlusturucu).
+    pub fn build_package(staging_dir: &str, metadata: &str, output: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let paket = crate::srcbuild::PaketTanimi::kaynaktan_yukle(metadata)?.paket();
+        let olusan = crate::srcbuild::ArsivOlusturucu::yeni(staging_dir).olustur(&paket, output)?;
+        println!("{} {} paketlendi: {}", olusan.ad, olusan.surum, output);
+        if let Some(ozet) = olusan.checksums.get("sha256") {
+            println!("sha256: {}", ozet);
+        }
+        println!("{} dosya, kurulu boyut {} bayt.", olusan.dosyalar.len(), olusan.kurulu_boyut.unwrap_or(0));
+        Ok(())
+    }
+
+    // Depo kökündeki indeks dosyaları (paketler.bin, index.bin) için imzalı depo bildirimini oluşturur.
+    // repo_root: Depo kökünün Kaynak ID'si.
+    // signing_key: Depo özel anahtarının Kaynak ID'si (hex kodlu Ed25519 tohumu).
+    // valid_days: Bildirimin geçerli kalacağı gün sayısı (None ise varsayılan süre).
+    pub fn sign_repository(repo_root: &str, repo_name: &str, signing_key: &str, valid_days: Option<u64>) -> Result<(), PaketYoneticisiHatasi> {
+        let anahtar = crate::srcsigning::ImzalamaAnahtari::kaynaktan_yukle(signing_key).map_err(|e| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Özel anahtar yüklenemedi ({}): {}", signing_key, e))
+        })?;
+        let bildirim = crate::srcrepositoryrelease::bildirimi_yayinla(
+            repo_root,
+            repo_name,
+            &[crate::srcrepository::PAKET_LISTESI_DOSYASI, "index.bin"],
+            valid_days.map_or(crate::srcrepositoryrelease::VARSAYILAN_GECERLILIK_SURESI_SN, |gun| gun.saturating_mul(24 * 60 * 60)),
+            &anahtar,
+        )?;
+        for dosya in &bildirim.dosyalar {
+            println!("{} {:>12} {}", dosya.sha256, dosya.boyut, dosya.ad);
+        }
+        println!("Depo bildirimi {} anahtarıyla imzalandı: {}/{}", anahtar.kimlik(), repo_root, crate::srcrepositoryrelease::BILDIRIM_DOSYASI);
+        println!("Sürüm {}, son geçerlilik {} (Unix zamanı).", bildirim.surum, bildirim.son_gecerlilik);
+        Ok(())
+    }
+
+    // Varsayılan anahtar halkasını yükler (anahtar-* komutları için).
+    fn anahtar_halkasini_yukle() -> Result<crate::srcsigning::AnahtarHalkasi, PaketYoneticisiHatasi> {
+        use crate::srcsigning::{AnahtarHalkasi, VARSAYILAN_ANAHTAR_HALKASI};
+        AnahtarHalkasi::kaynaktan_yukle(VARSAYILAN_ANAHTAR_HALKASI).map_err(|e| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar halkası yüklenemedi ({}): {}", VARSAYILAN_ANAHTAR_HALKASI, e))
+        })
+    }
+
+    fn anahtar_halkasini_kaydet(halka: &crate::srcsigning::AnahtarHalkasi) -> Result<(), PaketYoneticisiHatasi> {
+        use crate::srcsigning::VARSAYILAN_ANAHTAR_HALKASI;
+        halka.kaynaga_yaz(VARSAYILAN_ANAHTAR_HALKASI).map_err(|e| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar halkası yazılamadı ({}): {}", VARSAYILAN_ANAHTAR_HALKASI, e))
+        })
+    }
+
+    // Anahtar halkası biçimindeki Kaynaktaki genel anahtarları güvenilen anahtarlara ekler.
+    // valid_days: Son geçerliliği belirtilmemiş anahtarlar için geçerlilik süresi (gün).
+    pub fn import_keys(source: &str, valid_days: Option<u64>) -> Result<(), PaketYoneticisiHatasi> {
+        let gelen = crate::srcsigning::AnahtarHalkasi::kaynaktan_yukle(source).map_err(|e| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Anahtarlar okunamadı ({}): {}", source, e))
+        })?;
+        if gelen.anahtarlar().is_empty() {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!("İçe aktarılacak anahtar yok: {}", source)));
+        }
+
+        let simdi = crate::srcrepositoryrelease::simdiki_zaman()?;
+        let mut halka = anahtar_halkasini_yukle()?;
+        let guven = crate::srctrust::TrustManager::varsayilan();
+        for anahtar in gelen.anahtarlar() {
+            let mut anahtar = anahtar.clone();
+            if anahtar.olusturma == 0 {
+                anahtar.olusturma = simdi;
+            }
+            if anahtar.son_gecerlilik.is_none() {
+                anahtar.son_gecerlilik = valid_days.map(|gun| simdi.saturating_add(gun.saturating_mul(24 * 60 * 60)));
+            }
+            if halka.bul(&anahtar.kimlik()).map_or(false, |mevcut| mevcut.iptal_zamani.is_some()) {
+                eprintln!("Uyarı: {} daha önce iptal edilmiş, iptal kaydı korunuyor.", anahtar.tanim());
+            }
+            println!("İçe aktarıldı: {} {}", anahtar.tanim(), anahtar.parmak_izi());
+            if guven.key_scope(&anahtar.kimlik()).is_none() {
+                println!("    Kapsam verilene kadar bu anahtarın imzaları kabul edilmez: anahtar-kapsam {} <depo,...> <paket_deseni,...>", anahtar.kimlik());
+            }
+            halka.ekle(anahtar);
+        }
+        anahtar_halkasini_kaydet(&halka)
+    }
+
+    // Güvenilen anahtarları durumlarıyla listeler. Zamanlar Unix saniyesidir.
+    pub fn list_keys() -> Result<(), PaketYoneticisiHatasi> {
+        use crate::srcsecurity::SecurityError;
+        let halka = anahtar_halkasini_yukle()?;
+        let guven = crate::srctrust::TrustManager::varsayilan();
+        let simdi = crate::srcrepositoryrelease::simdiki_zaman()?;
+        if halka.anahtarlar().is_empty() {
+            println!("Anahtar halkası boş.");
+            return Ok(());
+        }
+        for anahtar in halka.anahtarlar() {
+            let durum = match anahtar.kullanilabilir_mi(simdi) {
+                Ok(()) => "geçerli",
+                Err(SecurityError::RevokedSigningKey { .. }) => "iptal",
+                Err(_) => "süresi dolmuş",
+            };
+            let bitis = anahtar.son_gecerlilik.map_or(String::from("-"), |zaman| zaman.to_string());
+            println!("{} {:<14} oluşturma {} bitiş {} {}", anahtar.kimlik(), durum, anahtar.olusturma, bitis, anahtar.ad);
+            println!("    parmak izi: {}", anahtar.parmak_izi());
+            match guven.key_scope(&anahtar.kimlik()) {
+                Some(kapsam) => println!("    kapsam: depo {} paket {}", kapsam.depolar.join(","), kapsam.paket_desenleri.join(",")),
+                None => println!("    kapsam: yok (hiçbir paketi veya depo bildirimini imzalayamaz)"),
+            }
+        }
+        Ok(())
+    }
+
+    // Anahtarı anahtar halkası biçiminde dışa aktarır (hedef yoksa standart çıktıya yazar).
+    pub fn export_key(key_id: &str, destination: Option<&str>) -> Result<(), PaketYoneticisiHatasi> {
+        let halka = anahtar_halkasini_yukle()?;
+        let anahtar = halka.bul(key_id).ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar bulunamadı: {}", key_id))
+        })?;
+        let mut tek = crate::srcsigning::AnahtarHalkasi::yeni();
+        tek.ekle(anahtar.clone());
+        match destination {
+            Some(hedef) => {
+                tek.kaynaga_yaz(hedef).map_err(|e| {
+                    PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar yazılamadı ({}): {}", hedef, e))
+                })?;
+                println!("{} dışa aktarıldı: {}", anahtar.tanim(), hedef);
+            }
+            None => print!("{}", tek.metne_donustur()),
+        }
+        Ok(())
+    }
+
+    // Anahtarı iptal eder; anahtar halkada kalır ve imzaları artık kabul edilmez.
+    pub fn revoke_key(key_id: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let mut halka = anahtar_halkasini_yukle()?;
+        if !halka.iptal_et(key_id, crate::srcrepositoryrelease::simdiki_zaman()?) {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar bulunamadı: {}", key_id)));
+        }
+        anahtar_halkasini_kaydet(&halka)?;
+        println!("Anahtar iptal edildi: {}", key_id);
+        Ok(())
+    }
+
+    // Anahtarı anahtar halkasından siler. İptal kaydı da silinir; anahtar yeniden içe aktarılırsa tekrar güvenilir.
+    pub fn delete_key(key_id: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let mut halka = anahtar_halkasini_yukle()?;
+        if !halka.cikar(key_id) {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar bulunamadı: {}", key_id)));
+        }
+        anahtar_halkasini_kaydet(&halka)?;
+        println!("Anahtar silindi: {}", key_id);
+        Ok(())
+    }
+
+    // Güven deposu denetim kaydına yazılan, değişikliği yapan (CLI görevi).
+    fn islemi_yapan() -> String {
+        match task::current_id() {
+            Ok(id) => format!("cli (görev {:?})", id),
+            Err(_) => String::from("cli"),
+        }
+    }
+
+    // Güven deposunun denetim kaydını (eklemeler ve kaldırmalar) yazdırır.
+    pub fn trust_history() -> Result<(), PaketYoneticisiHatasi> {
+        let kayit = crate::srctrust::TrustManager::varsayilan().audit_log()?;
+        if kayit.is_empty() {
+            println!("Güven deposu denetim kaydı boş.");
+        } else {
+            print!("{}", kayit);
+        }
+        Ok(())
+    }
+
+    // Anahtarın yalnızca verilen depolardan gelen ve adı desenlere uyan paketleri imzalayabilmesini sağlar.
+    // repositories / patterns: Virgülle ayrılmış desenler (örn. "vendor" ve "vendor-*").
+    pub fn scope_key(key_id: &str, repositories: &str, patterns: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let halka = anahtar_halkasini_yukle()?;
+        let anahtar = halka.bul(key_id).ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar bulunamadı: {}", key_id))
+        })?;
+        let depolar: Vec<&str> = repositories.split(',').map(str::trim).filter(|desen| !desen.is_empty()).collect();
+        let desenler: Vec<&str> = patterns.split(',').map(str::trim).filter(|desen| !desen.is_empty()).collect();
+        crate::srctrust::TrustManager::varsayilan().set_key_scope(
+            &anahtar.kimlik(),
+            &depolar,
+            &desenler,
+            &format!("anahtar sahibi: {}", anahtar.ad),
+            &islemi_yapan(),
+        )?;
+        println!("{} yalnızca {} deposundan {} paketlerini imzalayabilir.", anahtar.tanim(), depolar.join(","), desenler.join(","));
+        Ok(())
+    }
+
+    // Anahtarın kapsamını kaldırır; anahtar artık hiçbir paketi veya depo bildirimini imzalayamaz.
+    pub fn unscope_key(key_id: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let kimlik = anahtar_halkasini_yukle()?.bul(key_id).map_or_else(|| key_id.to_lowercase(), |anahtar| anahtar.kimlik());
+        if !crate::srctrust::TrustManager::varsayilan().remove_key_scope(&kimlik, &islemi_yapan())? {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!("Anahtarın kapsamı yok: {}", key_id)));
+        }
+        println!("Anahtar kapsamı kaldırıldı: {}", kimlik);
+        Ok(())
+    }
+
+    // Paket desenlerine uyan paketler için M-of-N eşik imza kuralı ekler veya günceller.
+    // keys: Virgülle ayrılmış anahtar kimlikleri veya parmak izleri; hepsi anahtar halkasında olmalıdır.
+    pub fn threshold_rule(name: &str, patterns: &str, threshold: &str, keys: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let esik: u32 = threshold.parse().map_err(|_| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Geçersiz eşik: {}", threshold))
+        })?;
+        let halka = anahtar_halkasini_yukle()?;
+        let mut anahtarlar = Vec::new();
+        for key_id in keys.split(',').map(str::trim).filter(|kimlik| !kimlik.is_empty()) {
+            let anahtar = halka.bul(key_id).ok_or_else(|| {
+                PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar bulunamadı: {}", key_id))
+            })?;
+            anahtarlar.push(anahtar.kimlik());
+        }
+        let anahtarlar: Vec<&str> = anahtarlar.iter().map(String::as_str).collect();
+        let desenler: Vec<&str> = patterns.split(',').map(str::trim).filter(|desen| !desen.is_empty()).collect();
+        let mut guven = crate::srctrust::TrustManager::varsayilan();
+        guven.set_threshold_rule(name, &desenler, esik, &anahtarlar, "", &islemi_yapan())?;
+        if let Some(kural) = guven.threshold_rules().iter().find(|kural| kural.ad == name) {
+            println!("'{}' kuralı: {} paketleri {}/{} imza gerektirir.", name, desenler.join(","), kural.esik, kural.anahtarlar.len());
+        }
+        Ok(())
+    }
+
+    // Eşik imza kuralını kaldırır.
+    pub fn remove_threshold_rule(name: &str) -> Result<(), PaketYoneticisiHatasi> {
+        if !crate::srctrust::TrustManager::varsayilan().remove_threshold_rule(name, &islemi_yapan())? {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!("Eşik kuralı bulunamadı: {}", name)));
+        }
+        println!("Eşik kuralı kaldırıldı: {}", name);
+        Ok(())
+    }
+
+    // Paketin ayrık imzalarını eşik kurallarına göre doğrular ve geçerli, reddedilen ve eksik imzalayanları yazdırır.
+    // signatures: İmza dosyalarının Kaynak ID'leri (her dosya birden çok imza bloğu içerebilir).
+    pub fn verify_signatures(archive: &str, package_name: &str, repository: &str, signatures: &[&str]) -> Result<(), PaketYoneticisiHatasi> {
+        let guvenlik = crate::srcsecurity::SecurityManager::new(anahtar_halkasini_yukle()?);
+        let guven = crate::srctrust::TrustManager::varsayilan();
+        match guvenlik.verify_threshold(archive, signatures, package_name, repository, &guven) {
+            Ok(rapor) => {
+                for kimlik in &rapor.gecerli_imzalayanlar {
+                    println!("geçerli   {}", guvenlik.anahtar_tanimi(kimlik));
+                }
+                for (kimlik, neden) in &rapor.reddedilen_imzalar {
+                    println!("reddedildi {}: {}", kimlik, neden);
+                }
+                for sonuc in &rapor.kurallar {
+                    println!("kural {}: {}/{} geçerli imza", sonuc.kural, sonuc.gecerli.len(), sonuc.gerekli);
+                }
+                println!("'{}' imzaları doğrulandı.", package_name);
+                Ok(())
+            }
+            Err(e) => Err(PaketYoneticisiHatasi::InvalidParameter(format!("İmza doğrulaması başarısız ({}): {}", package_name, e))),
+        }
+    }
+
+    // Kurulu paketleri yerel güvenlik bildirimi veritabanına göre tarar ve bulguları yazdırır.
+    // advisories: Veritabanı Kaynak ID'si (None: srcadvisory::VARSAYILAN_BILDIRI_VERITABANI).
+    pub fn scan_installed(advisories: Option<&str>) -> Result<(), PaketYoneticisiHatasi> {
+        use crate::srcadvisory::{BildiriVeritabani, VARSAYILAN_BILDIRI_VERITABANI};
+        let kaynak = advisories.unwrap_or(VARSAYILAN_BILDIRI_VERITABANI);
+        let veritabani = BildiriVeritabani::kaynaktan_yukle(kaynak)?;
+        let bulgular = veritabani.kurulu_paketleri_tara(&crate::srcinstalled::KuruluPaketVeritabani::yeni())?;
+        for bulgu in &bulgular {
+            println!("{:<7} {}", bulgu.ciddiyet.ad(), bulgu);
+            if !bulgu.ozet.is_empty() {
+                println!("        {}", bulgu.ozet);
+            }
         }
+        match veritabani.guncelleme {
+            Some(zaman) => println!("{} güvenlik açığı bulundu ({} bildiri, veritabanı zamanı {}).", bulgular.len(), veritabani.bildiriler().len(), zaman),
+            None => println!("{} güvenlik açığı bulundu ({} bildiri).", bulgular.len(), veritabani.bildiriler().len()),
+        }
+        Ok(())
+    }
+
+    // Kurulu paketlerin lisanslarını ifadeye göre gruplayarak özetler.
+    // Lisans politikası varsa her ifadenin politika kararı da yazdırılır.
+    pub fn license_summary() -> Result<(), PaketYoneticisiHatasi> {
+        use crate::srclicense::{self, LisansPolitikasi, VARSAYILAN_LISANS_POLITIKASI};
+        let kayitlar = crate::srcinstalled::KuruluPaketVeritabani::yeni().tum_kayitlar()?;
+        let politika = LisansPolitikasi::varsa_yukle(VARSAYILAN_LISANS_POLITIKASI)?;
+        let ozet = srclicense::lisans_ozeti(kayitlar.iter().map(|kayit| &kayit.paket));
+        for (lisans, paketler) in &ozet {
+            // Onaylanan paketler nedeniyle kararlar grup içinde farklı olabilir; en katısı gösterilir.
+            let karar = politika.as_ref().and_then(|politika| {
+                kayitlar.iter()
+                    .filter(|kayit| paketler.contains(&format!("{} {}", kayit.paket.ad, kayit.paket.surum)))
+                    .map(|kayit| politika.paketi_degerlendir(&kayit.paket).karar)
+                    .max()
+                    .map(|karar| karar.ad())
+            });
+            println!("{:<40} {:>4} paket{}", lisans, paketler.len(), karar.map_or(String::new(), |k| format!("  [{}]", k)));
+            println!("    {}", paketler.join(", "));
+        }
+        println!("{} kurulu paket, {} farklı lisans ifadesi.", kayitlar.len(), ozet.len());
+        Ok(())
+    }
+
+    // Kurulu paketler için SBOM (SPDX veya CycloneDX JSON) üretir.
+    // name: Belge adı (örn. imaj adı), time: Oluşturulma zamanı (Unix saniyesi; None ise şimdiki zaman),
+    // output: Yazılacak Kaynak ID'si (None ise standart çıktıya yazdırılır).
+    pub fn export_sbom(format: &str, name: Option<&str>, time: Option<u64>, output: Option<&str>) -> Result<(), PaketYoneticisiHatasi> {
+        use crate::srcsbom::{self, SbomBicimi, SbomSecenekleri};
+        let bicim = SbomBicimi::ayristir(format).ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Bilinmeyen SBOM biçimi: {} (spdx veya cyclonedx)", format))
+        })?;
+        let zaman = match time {
+            Some(zaman) => zaman,
+            None => crate::srcrepositoryrelease::simdiki_zaman()?,
+        };
+        let secenekler = SbomSecenekleri::yeni(name.unwrap_or("sahne64").to_owned(), zaman);
+        let belge = srcsbom::kurulu_paketlerden(bicim, &crate::srcinstalled::KuruluPaketVeritabani::yeni(), &secenekler)?;
+        match output {
+            Some(hedef) => {
+                srcsbom::kaynaga_yaz(hedef, &belge)?;
+                println!("{} SBOM yazıldı: {} ({} bayt)", bicim.ad(), hedef, belge.len());
+            }
+            None => println!("{}", core::str::from_utf8(&belge).unwrap_or("")),
+        }
+        Ok(())
+    }
+
+    // Bir paket arşivinin girdilerini ayrıntılarıyla listeler (açmadan); gömülü manifesto varsa yazdırır.
+    // archive: İncelenecek arşivin Kaynak ID'si (zip veya sıkıştırılmış tar).
+    pub fn inspect_archive(archive: &str) -> Result<(), PaketYoneticisiHatasi> {
+        use crate::srcarchive::{self, ArsivSinirlari, GirdiTuru};
+        let ozellikler = crate::srcfeatures::FeatureSet::with_default_compression();
+        let sinirlar = ArsivSinirlari::default();
+
+        let girdiler = srcarchive::arsiv_icerik_listele(archive, &ozellikler, &sinirlar)?;
+        for girdi in &girdiler {
+            let tur = match girdi.tur {
+                GirdiTuru::Dosya => '-',
+                GirdiTuru::Dizin => 'd',
+                GirdiTuru::SembolikBag => 'l',
+                GirdiTuru::SabitBag => 'h',
+            };
+            let sikistirilmis = girdi.sikistirilmis_boyut.map_or(String::from("-"), |b| b.to_string());
+            let crc = girdi.crc32.map_or(String::from("-"), |c| format!("{:08x}", c));
+            println!(
+                "{}{:04o} {:>12} {:>12} {:>8} {:>10} {}",
+                tur, girdi.mod_, girdi.boyut, sikistirilmis, crc, girdi.degistirilme_zamani, girdi.ad
+            );
+        }
+        println!("{} girdi.", girdiler.len());
+
+        if girdiler.iter().any(|g| g.ad == crate::srcbuild::MANIFEST_GIRDISI) {
+            let manifesto = srcarchive::arsiv_girdi_oku(archive, crate::srcbuild::MANIFEST_GIRDISI, &ozellikler, &sinirlar)?;
+            println!("{}", core::str::from_utf8(&manifesto).unwrap_or("<manifesto UTF-8 değil>"));
+        }
+        Ok(())
     }
 
     // Bir paketi arar.
@@ -211,65 +575,45 @@ mod pkg_manager {
     // package_name: Kurulacak paketin adı.
     // Kurulum genellikle: İndir -> Sağlamasını Kontrol Et -> Çıkar -> Ekle (script çalıştır + DB kaydı) adımlarını içerir.
     pub fn install_package(package_name: &str) -> Result<(), PaketYoneticisiHatasi> { // Result türü PaketYoneticisiHatasi olmalı
-        println!("{} paketi kuruluyor...", package_name);
-        // Burada indirme (ağ kaynağı resource'u?), checksum doğrulama (srcchecksum),
-        // arşivden çıkarma (srcarchive) adımları çağrılmalı.
-        // Ardından add_package fonksiyonu (veya onun içeriği) çalıştırılmalı.
-
-        // Örnek Akış (Placeholder):
-        // 1. Paketi indir (örn. "sahne://remoterepo/packages/my_package.zip" Kaynağından "sahne://cache/packages/my_package.zip" Kaynağına)
-            let download_source = format!("sahne://remoterepo/packages/{}.zip", package_name);
-            let download_dest = format!("sahne://cache/packages/{}.zip", package_name);
-        //    // İndirme fonksiyonu burada çağrılır (ağ kaynakları için özel resource type?).
-        //    // İndirme işlemi resource::read/write ile olabilir, veya özel bir download syscall'u?
-        //    // Geçici olarak indirme başarılı oldu varsayalım.
-            println!("{} paketi indirildi (varsayımsal olarak).", package_name);
-
-
-        // 2. İndirilen dosyanın checksum'ını doğrula
-            let expected_md5 = "..."; // Paket meta verisinden alınmalı
-            match crate::srcchecksum::dogrula_md5(&download_dest, expected_md5) {
-                 Ok(true) => println!("Checksum doğrulama başarılı."),
-                 Ok(false) => {
-                     eprintln!("Checksum doğrulama BAŞARISIZ.");
-                     // İndirilen dosyayı temizle?
-                      resource::delete(&download_dest); // Eksik fonksiyonellik
-                     return Err(PaketYoneticisiHatasi::PaketKurulumHatasi(String::from("Checksum doğrulaması başarısız")));
-                 }
-                 Err(e) => {
-                      eprintln!("Checksum hesaplama/doğrulama hatası: {:?}", e);
-                      return Err(e); // Hata zaten doğru türde
-                 }
-            }
+        install_package_with_policy(package_name, &crate::srcadvisory::AcikPolitikasi::default())
+    }
 
-        // 3. Paketi önbellekten/indirilen yerden kurulu alana çıkar (srcarchive)
-            let archive_resource_id = download_dest;
-            let extract_dest_resource_id = format!("sahne://installed_packages/{}/", package_name);
-            match crate::srcarchive::zip_ac(&archive_resource_id, &extract_dest_resource_id) {
-                 Ok(_) => println!("Paket dosyaları çıkarıldı."),
-                 Err(e) => {
-                     eprintln!("Paket dosyaları çıkarma hatası: {:?}", e);
-        //             // Çıkarılan dosyaları temizle?
-                      resource::delete(&extract_dest_resource_id); // Eksik fonksiyonellik
-                     return Err(e); // Hata zaten doğru türde
-                 }
-            }
+    // install_package ile aynı; kurulum öncesi güvenlik açığı taraması verilen politikayla yapılır.
+    // Bağımlılıklar çözülür ve plan KurulumYoneticisi::plani_uygula ile kurulur: güvenlik açığı, lisans ve
+    // disk alanı denetimleri, eşzamanlı indirme, arşiv biçimine göre çıkarma, kurulu kayıt ve tetikleyiciler.
+    pub fn install_package_with_policy(package_name: &str, policy: &crate::srcadvisory::AcikPolitikasi) -> Result<(), PaketYoneticisiHatasi> {
+        println!("{} paketi kuruluyor...", package_name);
 
-        // 4. Paketi sisteme ekle (script çalıştır, veritabanı kaydı oluştur, vb.)
-        //    Bu genellikle add_package fonksiyonunun içeriği olur.
-            add_package(package_name)?; // Eğer add_package sadece script çalıştırıyorsa
+        // Paket listesi imzalı depo bildirimine göre doğrulanarak alınır.
+        let mut depo = crate::srcrepository::DepoYoneticisi::yeni(
+            String::from("sahne://remoterepo/packages"),
+            String::from("sahne://cache/repo"),
+        );
+        let adaylar = depo.paket_listesini_al()?;
+        let mut yonetici = kurulum_yoneticisi();
+        yonetici.acik_politikasi = policy.clone();
 
-        // Eğer kurulum süreci add_package'in ötesinde adımlar içeriyorsa, onlar burada olur.
-        // Örn: Veritabanına paket bilgilerini kaydetme.
-        // Bu, kurulu paketler listesi Kaynağına (INSTALLED_PACKAGES_LIST_RESOURCE) yazmayı gerektirir.
-        // Bu kaynağın LOCK edilmesi, içeriğinin okunup güncellenmesi ve tekrar yazılması lazım.
-        // Bu işlemler için sync::lock_* ve resource::* kullanılır.
+        // Lisans politikası varsa bağımlılıklar dahil tüm plan denetlenir (kurulu olduğu için atlanacaklar da).
+        let plan = match crate::srclicense::LisansPolitikasi::varsa_yukle(&yonetici.lisans_politikasi_kaynagi)? {
+            Some(politika) => crate::srcdependency::BagimlilikYoneticisi::lisans_denetimiyle_coz(&adaylar, package_name, &politika)?,
+            None => crate::srcdependency::BagimlilikYoneticisi::plani_coz(&adaylar, package_name)?,
+        };
 
-        // Şimdilik sadece add_package çağrısı yapalım (örnekteki gibi script çalıştırmayı taklit eden).
-        add_package(package_name)?; // Script çalıştırma adımı
+        // Aynı sürümü zaten kurulu olan bağımlılıklar plandan çıkarılır; istenen paket her zaman kurulur.
+        let veritabani = crate::srcinstalled::KuruluPaketVeritabani::yeni();
+        let mut kurulacaklar = Vec::new();
+        for paket in plan {
+            let kurulu_surum = veritabani.kaydi_yukle(&paket.ad)?.map(|kayit| kayit.paket.surum);
+            if paket.ad != package_name && kurulu_surum.as_deref() == Some(paket.surum.as_str()) {
+                continue;
+            }
+            kurulacaklar.push(paket);
+        }
 
-        println!("{} paketi başarıyla kuruldu (varsayımsal).", package_name);
+        yonetici.plani_uygula(&kurulacaklar)?;
 
+        let adlar: Vec<String> = kurulacaklar.iter().map(|paket| format!("{} {}", paket.ad, paket.surum)).collect();
+        println!("{} paketi kuruldu ({}).", package_name, adlar.join(", "));
         Ok(())
     }
 }
@@ -388,28 +732,314 @@ pub extern "C" fn main(argc: usize, argv: *const *const u8) -> i32 { // C ABI'si
             }
         }
          Some("kur") => {
-            // kur komutu 1 argüman alır (paket adı)
-             if let Some(package_name) = arg_iter.next() {
+            // kur komutu paket adını ve isteğe bağlı --acik-esigi <dusuk|orta|yuksek|kritik|yok> bayrağını alır
+            let mut package_name = None;
+            let mut politika = crate::srcadvisory::AcikPolitikasi::default();
+            let mut hata = None;
+            while let Some(arg) = arg_iter.next() {
+                match arg {
+                    "--acik-esigi" => match arg_iter.next() {
+                        Some("yok") => politika.engelleme_esigi = None,
+                        Some(esik) => match crate::srcadvisory::Ciddiyet::ayristir(esik) {
+                            Ok(esik) => politika.engelleme_esigi = Some(esik),
+                            Err(e) => hata = Some(e.to_string()),
+                        },
+                        None => hata = Some(String::from("--acik-esigi değer gerektirir")),
+                    },
+                    _ if arg.starts_with("--") => hata = Some(format!("bilinmeyen bayrak: {}", arg)),
+                    _ if package_name.is_none() => package_name = Some(arg),
+                    _ => hata = Some(String::from("fazladan argüman")),
+                }
+            }
+            match (hata, package_name) {
+                (Some(mesaj), _) => {
+                    eprintln!("'kur' komutu: {}", mesaj);
+                    Err(PaketYoneticisiHatasi::GecersizParametre(mesaj))
+                }
+                (None, Some(package_name)) => pkg_manager::install_package_with_policy(package_name, &politika),
+                (None, None) => {
+                    eprintln!("'kur' komutu paket adı gerektirir.");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("paket adı eksik")))
+                }
+            }
+        }
+        Some("dogrula") => {
+            // dogrula komutu isteğe bağlı bir paket adı ve --json / --onar bayraklarını alır
+            let mut package_name = None;
+            let mut json = false;
+            let mut onar = false;
+            let mut hata = None;
+            for arg in arg_iter {
+                match arg {
+                    "--json" => json = true,
+                    "--onar" => onar = true,
+                    _ if arg.starts_with("--") => hata = Some(format!("bilinmeyen bayrak: {}", arg)),
+                    _ if package_name.is_none() => package_name = Some(arg),
+                    _ => hata = Some(String::from("fazladan argüman")),
+                }
+            }
+            match hata {
+                Some(mesaj) => {
+                    eprintln!("'dogrula' komutu: {}", mesaj);
+                    Err(PaketYoneticisiHatasi::GecersizParametre(mesaj))
+                }
+                None => pkg_manager::verify_packages(package_name, json, onar),
+            }
+        }
+        Some("yeniden-kur") => {
+            // yeniden-kur komutu 1 argüman alır (paket adı)
+            if let Some(package_name) = arg_iter.next() {
                 if arg_iter.next().is_none() {
-                    pkg_manager::install_package(package_name)
+                    pkg_manager::reinstall_package(package_name)
                 } else {
-                    eprintln!("'kur' komutu fazladan argüman alamaz.");
+                    eprintln!("'yeniden-kur' komutu fazladan argüman alamaz.");
                     Err(PaketYoneticisiHatasi::GecersizParametre(String::from("fazladan argüman")))
                 }
             } else {
-                eprintln!("'kur' komutu paket adı gerektirir.");
+                eprintln!("'yeniden-kur' komutu paket adı gerektirir.");
                 Err(PaketYoneticisiHatasi::GecersizParametre(String::from("paket adı eksik")))
             }
         }
+        Some("dusur") => {
+            // dusur komutu paket adı, isteğe bağlı hedef sürüm ve --onayla bayrağını alır
+            let mut package_name = None;
+            let mut version = None;
+            let mut onayla = false;
+            let mut hata = None;
+            for arg in arg_iter {
+                match arg {
+                    "--onayla" => onayla = true,
+                    _ if arg.starts_with("--") => hata = Some(format!("bilinmeyen bayrak: {}", arg)),
+                    _ if package_name.is_none() => package_name = Some(arg),
+                    _ if version.is_none() => version = Some(arg),
+                    _ => hata = Some(String::from("fazladan argüman")),
+                }
+            }
+            match (hata, package_name) {
+                (Some(mesaj), _) => {
+                    eprintln!("'dusur' komutu: {}", mesaj);
+                    Err(PaketYoneticisiHatasi::GecersizParametre(mesaj))
+                }
+                (None, None) => {
+                    eprintln!("'dusur' komutu paket adı gerektirir.");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("paket adı eksik")))
+                }
+                (None, Some(package_name)) => pkg_manager::downgrade_package(package_name, version, onayla),
+            }
+        }
+        Some("incele") => {
+            // incele komutu 1 argüman alır (arşiv Kaynak ID'si)
+            if let Some(archive) = arg_iter.next() {
+                if arg_iter.next().is_none() {
+                    pkg_manager::inspect_archive(archive)
+                } else {
+                    eprintln!("'incele' komutu fazladan argüman alamaz.");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("fazladan argüman")))
+                }
+            } else {
+                eprintln!("'incele' komutu arşiv Kaynak ID'si gerektirir.");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("arşiv eksik")))
+            }
+        }
+        Some("paketle") => {
+            // paketle komutu 3 argüman alır (hazırlık dizini, paket tanım dosyası, hedef arşiv)
+            let paketle_args: Vec<&str> = arg_iter.collect();
+            if let [staging_dir, metadata, output] = paketle_args[..] {
+                pkg_manager::build_package(staging_dir, metadata, output)
+            } else {
+                eprintln!("Kullanım: paketle <hazırlık_dizini> <paket_tanımı.json> <hedef_arşiv>");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("paketle: 3 argüman gerekli")))
+            }
+        }
+        Some("depo-imzala") => {
+            // depo-imzala komutu 3 argüman ve isteğe bağlı geçerlilik süresini (gün) alır
+            let depo_args: Vec<&str> = arg_iter.collect();
+            match depo_args[..] {
+                [repo_root, repo_name, signing_key] => pkg_manager::sign_repository(repo_root, repo_name, signing_key, None),
+                [repo_root, repo_name, signing_key, valid_days] => match valid_days.parse::<u64>() {
+                    Ok(gun) if gun > 0 => pkg_manager::sign_repository(repo_root, repo_name, signing_key, Some(gun)),
+                    _ => {
+                        eprintln!("'depo-imzala' komutu: geçersiz geçerlilik süresi: {}", valid_days);
+                        Err(PaketYoneticisiHatasi::GecersizParametre(format!("geçersiz geçerlilik süresi: {}", valid_days)))
+                    }
+                },
+                _ => {
+                    eprintln!("Kullanım: depo-imzala <depo_kökü> <depo_adı> <özel_anahtar> [geçerlilik_günü]");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("depo-imzala: 3 veya 4 argüman gerekli")))
+                }
+            }
+        }
+        Some("anahtar-ice-aktar") => {
+            // anahtar-ice-aktar komutu anahtar Kaynağını ve isteğe bağlı geçerlilik süresini (gün) alır
+            let anahtar_args: Vec<&str> = arg_iter.collect();
+            match anahtar_args[..] {
+                [source] => pkg_manager::import_keys(source, None),
+                [source, valid_days] => match valid_days.parse::<u64>() {
+                    Ok(gun) if gun > 0 => pkg_manager::import_keys(source, Some(gun)),
+                    _ => {
+                        eprintln!("'anahtar-ice-aktar' komutu: geçersiz geçerlilik süresi: {}", valid_days);
+                        Err(PaketYoneticisiHatasi::GecersizParametre(format!("geçersiz geçerlilik süresi: {}", valid_days)))
+                    }
+                },
+                _ => {
+                    eprintln!("Kullanım: anahtar-ice-aktar <anahtar_kaynağı> [geçerlilik_günü]");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("anahtar-ice-aktar: 1 veya 2 argüman gerekli")))
+                }
+            }
+        }
+        Some("anahtar-listele") => {
+            if arg_iter.next().is_none() {
+                pkg_manager::list_keys()
+            } else {
+                eprintln!("'anahtar-listele' komutu argüman almaz.");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("fazladan argüman")))
+            }
+        }
+        Some("anahtar-disa-aktar") => {
+            // anahtar-disa-aktar komutu anahtar kimliğini ve isteğe bağlı hedef Kaynağı alır
+            let anahtar_args: Vec<&str> = arg_iter.collect();
+            match anahtar_args[..] {
+                [key_id] => pkg_manager::export_key(key_id, None),
+                [key_id, destination] => pkg_manager::export_key(key_id, Some(destination)),
+                _ => {
+                    eprintln!("Kullanım: anahtar-disa-aktar <anahtar_kimliği> [hedef]");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("anahtar-disa-aktar: 1 veya 2 argüman gerekli")))
+                }
+            }
+        }
+        Some(komut @ ("anahtar-iptal" | "anahtar-sil")) => {
+            // anahtar-iptal ve anahtar-sil komutları 1 argüman alır (anahtar kimliği veya parmak izi)
+            match (arg_iter.next(), arg_iter.next()) {
+                (Some(key_id), None) if komut == "anahtar-iptal" => pkg_manager::revoke_key(key_id),
+                (Some(key_id), None) => pkg_manager::delete_key(key_id),
+                _ => {
+                    eprintln!("Kullanım: {} <anahtar_kimliği>", komut);
+                    Err(PaketYoneticisiHatasi::GecersizParametre(format!("{}: 1 argüman gerekli", komut)))
+                }
+            }
+        }
+        Some("anahtar-kapsam") => {
+            // anahtar-kapsam komutu 3 argüman alır (anahtar kimliği, depo desenleri, paket desenleri)
+            let kapsam_args: Vec<&str> = arg_iter.collect();
+            if let [key_id, repositories, patterns] = kapsam_args[..] {
+                pkg_manager::scope_key(key_id, repositories, patterns)
+            } else {
+                eprintln!("Kullanım: anahtar-kapsam <anahtar_kimliği> <depo,...> <paket_deseni,...>");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("anahtar-kapsam: 3 argüman gerekli")))
+            }
+        }
+        Some("anahtar-kapsam-kaldir") => {
+            match (arg_iter.next(), arg_iter.next()) {
+                (Some(key_id), None) => pkg_manager::unscope_key(key_id),
+                _ => {
+                    eprintln!("Kullanım: anahtar-kapsam-kaldir <anahtar_kimliği>");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("anahtar-kapsam-kaldir: 1 argüman gerekli")))
+                }
+            }
+        }
+        Some("esik-kurali") => {
+            // esik-kurali komutu 4 argüman alır (kural adı, paket desenleri, eşik, anahtarlar)
+            let kural_args: Vec<&str> = arg_iter.collect();
+            if let [name, patterns, threshold, keys] = kural_args[..] {
+                pkg_manager::threshold_rule(name, patterns, threshold, keys)
+            } else {
+                eprintln!("Kullanım: esik-kurali <ad> <paket_deseni,...> <M> <anahtar_kimliği,...>");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("esik-kurali: 4 argüman gerekli")))
+            }
+        }
+        Some("esik-kurali-kaldir") => {
+            match (arg_iter.next(), arg_iter.next()) {
+                (Some(name), None) => pkg_manager::remove_threshold_rule(name),
+                _ => {
+                    eprintln!("Kullanım: esik-kurali-kaldir <ad>");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("esik-kurali-kaldir: 1 argüman gerekli")))
+                }
+            }
+        }
+        Some("imza-dogrula") => {
+            // imza-dogrula komutu arşiv, paket adı, depo adı ve en az bir imza dosyası alır
+            let imza_args: Vec<&str> = arg_iter.collect();
+            match imza_args[..] {
+                [archive, package_name, repository, ref signatures @ ..] if !signatures.is_empty() => {
+                    pkg_manager::verify_signatures(archive, package_name, repository, signatures)
+                }
+                _ => {
+                    eprintln!("Kullanım: imza-dogrula <arşiv> <paket_adı> <depo> <imza_dosyası>...");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("imza-dogrula: en az 4 argüman gerekli")))
+                }
+            }
+        }
+        Some("acik-tara") => {
+            // acik-tara komutu isteğe bağlı bir bildiri veritabanı Kaynak ID'si alır
+            match (arg_iter.next(), arg_iter.next()) {
+                (advisories, None) => pkg_manager::scan_installed(advisories),
+                _ => {
+                    eprintln!("Kullanım: acik-tara [bildiri_veritabanı]");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("fazladan argüman")))
+                }
+            }
+        }
+        Some("sbom") => {
+            // sbom komutu biçimi, isteğe bağlı --ad <ad> ve --zaman <unix_saniyesi> bayraklarını ve hedef Kaynak ID'sini alır
+            let mut format = None;
+            let mut name = None;
+            let mut time = None;
+            let mut output = None;
+            let mut hata = None;
+            while let Some(arg) = arg_iter.next() {
+                match arg {
+                    "--ad" => match arg_iter.next() {
+                        Some(ad) => name = Some(ad),
+                        None => hata = Some(String::from("--ad değer gerektirir")),
+                    },
+                    "--zaman" => match arg_iter.next().map(str::parse::<u64>) {
+                        Some(Ok(zaman)) => time = Some(zaman),
+                        Some(Err(_)) => hata = Some(String::from("--zaman Unix saniyesi olmalı")),
+                        None => hata = Some(String::from("--zaman değer gerektirir")),
+                    },
+                    _ if arg.starts_with("--") => hata = Some(format!("bilinmeyen bayrak: {}", arg)),
+                    _ if format.is_none() => format = Some(arg),
+                    _ if output.is_none() => output = Some(arg),
+                    _ => hata = Some(String::from("fazladan argüman")),
+                }
+            }
+            match (hata, format) {
+                (Some(mesaj), _) => {
+                    eprintln!("'sbom' komutu: {}", mesaj);
+                    Err(PaketYoneticisiHatasi::GecersizParametre(mesaj))
+                }
+                (None, Some(format)) => pkg_manager::export_sbom(format, name, time, output),
+                (None, None) => {
+                    eprintln!("Kullanım: sbom <spdx|cyclonedx> [--ad <ad>] [--zaman <unix_saniyesi>] [hedef]");
+                    Err(PaketYoneticisiHatasi::GecersizParametre(String::from("SBOM biçimi eksik")))
+                }
+            }
+        }
+        Some("lisans-ozeti") => {
+            if arg_iter.next().is_none() {
+                pkg_manager::license_summary()
+            } else {
+                eprintln!("'lisans-ozeti' komutu argüman almaz.");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("fazladan argüman")))
+            }
+        }
+        Some("guven-gecmisi") => {
+            if arg_iter.next().is_none() {
+                pkg_manager::trust_history()
+            } else {
+                eprintln!("'guven-gecmisi' komutu argüman almaz.");
+                Err(PaketYoneticisiHatasi::GecersizParametre(String::from("fazladan argüman")))
+            }
+        }
         Some(cmd) => {
-            eprintln!("Bilinmeyen komut: '{}'. Bilinen komutlar: listele, ekle, kaldir, ara, kur", cmd);
+            eprintln!("Bilinmeyen komut: '{}'. Bilinen komutlar: listele, ekle, kaldir, ara, kur, dogrula, yeniden-kur, dusur, paketle, incele, depo-imzala, anahtar-ice-aktar, anahtar-listele, anahtar-disa-aktar, anahtar-iptal, anahtar-sil, anahtar-kapsam, anahtar-kapsam-kaldir, esik-kurali, esik-kurali-kaldir, imza-dogrula, acik-tara, lisans-ozeti, sbom, guven-gecmisi", cmd);
              Err(PaketYoneticisiHatasi::GecersizParametre(format!("bilinmeyen komut: {}", cmd)))
         }
         None => {
             // Hiç argüman yoksa (sadece program adı) kullanım bilgisini göster
             println!("Paket Yöneticisi (Sahne64)");
             println!("Kullanım: paket_yoneticisi <komut> [argümanlar]");
-            println!("Komutlar: listele, ekle, kaldir, ara, kur");
+            println!("Komutlar: listele, ekle, kaldir, ara, kur, dogrula, yeniden-kur, dusur, paketle, incele, depo-imzala, anahtar-ice-aktar, anahtar-listele, anahtar-disa-aktar, anahtar-iptal, anahtar-sil, anahtar-kapsam, anahtar-kapsam-kaldir, esik-kurali, esik-kurali-kaldir, imza-dogrula, acik-tara, lisans-ozeti, sbom, guven-gecmisi");
              Ok(()) // Bilgi mesajı başarı sayılır
         }
     };
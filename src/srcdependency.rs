@@ -5,9 +5,15 @@ use alloc::collections::{HashMap, HashSet};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::borrow::ToOwned; // to_string() yerine to_owned() daha genel
+use alloc::format; // format! makrosu için
+use core::cmp::Ordering; // Sürüm karşılaştırması için
 
 // 'Paket' struct tanımını içeren modül
 use crate::package::Paket;
+// Bağımlılık girdilerindeki sürüm kısıtları ("libc >= 2.31")
+use crate::srcversion::BagimlilikKisiti;
+// Çözümlenen planın lisans denetimi
+use crate::srclicense::LisansPolitikasi;
 
 // Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
 use crate::paket_yoneticisi_hata::PaketYoneticisiHatasi;
@@ -36,115 +42,99 @@ impl BagimlilikYoneticisi {
     pub fn bagimliliklari_coz(paketler: &Vec<Paket>, baslangic_paketi: &str) -> Result<Vec<String>, PaketYoneticisiHatasi> { // Result türü PaketYoneticisiHatasi olmalı
 
         // Bağımlılık çözümleme mantığı temel olarak bellek içi veri yapıları (HashMap, HashSet, Vec, String) üzerinde çalışır.
-        // Bu nedenle, `Sahne64` özgü dosya sistemi (`resource`), görev yönetimi (`task`), IPC (`messaging`) veya çekirdek (`kernel`) fonksiyonlarının
-        // bu özel mantıkta doğrudan bir karşılığı bulunmamaktadır.
         // Bu fonksiyonun ana gereksinimi `alloc` crate'i tarafından sağlanan heap ayırma yeteneğidir.
 
-        // Paketleri ada göre hızlı erişim için bir HashMap'e dönüştür
-        // HashMap<Paket Adı: String, Paket Referansı: &Paket>
-        let paket_haritasi: HashMap<String, &Paket> = paketler
-            .iter()
-            // Paketin adını String olarak klonla ve paket referansı ile eşle.
-            // paket.ad.clone() String klonlama (alloc gerektirir).
-            .map(|paket| (paket.ad.clone(), paket))
-            .collect(); // HashMap oluşturma (alloc gerektirir).
-
-        // Çözülen bağımlılıkların listesi (kurulum sırasına göre, sondan başa doğru eklenecek).
-        let mut cozulen_bagimliliklar = Vec::new(); // alloc::vec::Vec (alloc gerektirir).
-
-        // Ziyaret edilen paketleri takip etmek için küme (döngüleri önlemek).
-        // Paket adlarını saklıyoruz: HashSet<Paket Adı: String>.
-        let mut ziyaret_edilenler = HashSet::new(); // alloc::collections::HashSet (alloc gerektirir).
-
-        // Ziyaret edilecek paketlerin listesi (DFS için yığın olarak kullanılır).
-        // Paket adlarını saklıyoruz: Vec<Paket Adı: String>.
-        let mut ziyaret_edilecekler = Vec::new(); // alloc::vec::Vec (alloc gerektirir).
-
-        // Başlangıç paketini ziyaret edilecekler listesine ekle.
-        // baslangic_paketi &str -> String dönüşümü (alloc gerektirir).
-        ziyaret_edilecekler.push(baslangic_paketi.to_owned()); // to_string() yerine to_owned()
-
-
-        // Ziyaret edilecek paketler listesi boşalana kadar döngü.
-        while let Some(paket_adi) = ziyaret_edilecekler.pop() { // Vec pop (alloc gerektirmez, ama içindeki String'i taşır)
-            // Eğer paket adı zaten ziyaret edilenler kümesindeyse (döngü veya tekrar ziyaret durumu), atla.
-            if ziyaret_edilenler.contains(&paket_adi) { // HashSet contains (&String, String için Hash ve Eq kullanır)
+        // Paketleri ada göre hızlı erişim için bir HashMap'e dönüştür (her adın en yeni sürümü).
+        let paket_haritasi = aday_haritasi(paketler);
+
+        // Çözülen paketler (kurulum sırasına göre: bağımlılıklar, onlara bağımlı paketlerden önce gelir).
+        let mut cozulen_bagimliliklar = Vec::new();
+        // Çözümü tamamlanan paketler ve şu an DFS yığınında olan paketler (döngü tespiti için).
+        let mut ziyaret_edilenler: HashSet<String> = HashSet::new();
+        let mut yoldakiler: Vec<String> = Vec::new();
+
+        // Post-order DFS: (paket adı, bağımlılıkları yığına eklendi mi?)
+        let mut ziyaret_edilecekler: Vec<(String, bool)> = Vec::new();
+        ziyaret_edilecekler.push((baslangic_paketi.to_owned(), false));
+
+        while let Some((paket_adi, bagimliliklar_eklendi)) = ziyaret_edilecekler.pop() {
+            if bagimliliklar_eklendi {
+                // Tüm bağımlılıkları çözüldü; paketi sıraya ekle.
+                yoldakiler.pop();
+                ziyaret_edilenler.insert(paket_adi.clone());
+                cozulen_bagimliliklar.push(paket_adi);
+                continue;
+            }
+            if ziyaret_edilenler.contains(&paket_adi) {
                 continue;
             }
+            if yoldakiler.contains(&paket_adi) {
+                return Err(PaketYoneticisiHatasi::ConflictError(format!(
+                    "Döngüsel bağımlılık: {} -> {}", yoldakiler.join(" -> "), paket_adi
+                )));
+            }
 
-            // Paket adını ziyaret edilenler kümesine ekle.
-            // ziyaret_edilenler.insert(paket_adi.clone()); // clone() ile eklemeden önce kullanıldıysa paket_adi'nin bir klonu alınmalıydı.
-            // Ancak pop ile ownership alındığı için doğrudan insert yapabiliriz.
-            // insert(String) String'i kümeye taşır (alloc gerektirmez ama rehash/resize alloc gerektirebilir).
-            ziyaret_edilenler.insert(paket_adi.clone()); // DFS'de visited kümesine paketi *işlemeye başlamadan önce* eklemek yaygındır.
-                                                      // Ancak burada visited kontrolü pop sonrası yapıldığı için, paketi işledikten
-                                                      // sonra visited'a eklemek daha uygun olabilir. Mevcut kod paketi işledikten
-                                                      // sonra ekliyor, bu da döngüleri tespit etmede biraz farklılık yaratabilir
-                                                      // ama temel amacı sağlar. Paketi *ziyaret edilecek* olarak işaretlemek
-                                                      // için buraya eklemek ve döngü kontrolünü başta yapmak daha standart DFS'tir.
-                                                      // Kodunuz pop sonrası visited kontrolü yapıyor, visited insert'i döngü sonuna yakın.
-                                                      // Bu durumda cycle tespit etme biraz farklı işler. Standart yaklaşım:
-                                                       let paket_adi = ziyaret_edilecekler.pop().unwrap(); // pop sonrası visited kontrolü için ownership alınır.
-                                                       if visited.contains(&paket_adi) { continue; }
-                                                       visited.insert(paket_adi.clone()); // Buraya eklenmeliydi
-                                                      // ... paket işleme ...
-                                                       cozulen_bagimliliklar.push(paket_adi); // Buraya ownership taşınır.
-
-            // Paket haritasında paket adını ara.
-            if let Some(paket) = paket_haritasi.get(&paket_adi) { // HashMap get (&String, String için Hash ve Eq kullanır)
-                // Paketin bağımlılıklarını işle (genellikle önce bağımlılıklar ziyaret edilir).
-                for bagimlilik_adi in &paket.bagimliliklar { // &Vec<String> üzerinde iterasyon
-                    // Bağımlılık paket haritasında (yani bilinen paketler arasında) yoksa
-                    if !paket_haritasi.contains_key(bagimlilik_adi) { // HashMap contains_key (&String)
-                         eprintln!("Bağımlılık bulunamadı: {}", bagimlilik_adi);
-                        // Hata: Bağımlılık bulunamadı. Hatayı PaketYoneticisiHatasi türünde döndür.
-                        // bagimlilik_adi.clone() String klonlama (alloc gerektirir).
-                        return Err(PaketYoneticisiHatasi::BagimlilikBulunamadi(bagimlilik_adi.clone()));
+            let paket = paket_haritasi.get(&paket_adi).ok_or_else(|| {
+                eprintln!("Başlangıç paketi bilinmiyor: {}", paket_adi);
+                PaketYoneticisiHatasi::PaketBulunamadi(paket_adi.clone())
+            })?;
+            yoldakiler.push(paket_adi.clone());
+            ziyaret_edilecekler.push((paket_adi.clone(), true));
+
+            // Bağımlılık girdileri sürüm kısıtı içerebilir (örn. "libc >= 2.31").
+            for girdi in paket.bagimliliklar.iter().rev() {
+                let kisit = BagimlilikKisiti::ayristir(girdi)?;
+                match paket_haritasi.get(&kisit.ad) {
+                    Some(bagimlilik) if kisit.saglanir_mi(&bagimlilik.surum) => {
+                        if !ziyaret_edilenler.contains(&kisit.ad) {
+                            ziyaret_edilecekler.push((kisit.ad.clone(), false));
+                        }
+                    }
+                    Some(bagimlilik) => {
+                        eprintln!("Bağımlılık kısıtı sağlanmıyor: {} ({} mevcut)", kisit, bagimlilik.surum);
+                        return Err(PaketYoneticisiHatasi::BagimlilikBulunamadi(format!("{} ({} için)", kisit, paket_adi)));
                     }
-                    // Bağımlılığı ziyaret edilecekler listesine ekle.
-                    // Ziyaret edilecekler yığınına eklenmeden önce visited kontrolü yapılmalı.
-                    if !ziyaret_edilenler.contains(bagimlilik_adi) { // HashSet contains (&String)
-                        ziyaret_edilecekler.push(bagimlilik_adi.clone()); // String klonlama (alloc gerektirir).
+                    None => {
+                        eprintln!("Bağımlılık bulunamadı: {}", kisit.ad);
+                        return Err(PaketYoneticisiHatasi::BagimlilikBulunamadi(kisit.ad));
                     }
                 }
-
-                // Paketi çözülen bağımlılıklar listesine ekle.
-                // Bu liste kurulum sırasını temsil ediyorsa, önce bağımlılıklar gelmeli.
-                // DFS'de genellikle düğüm, bağımlılıkları işlendikten sonra son listeye eklenir (post-order traversal).
-                // Mevcut kodunuz pop sonrası visited kontrolü ve döngü sonunda push yapıyor.
-                // Bu aslında DFS'in biraz farklı bir implementasyonu.
-                // Standart post-order DFS için:
-                 while let Some(paket_adi) = ziyaret_edilecekler.pop() { ... logic ... cozulen_bagimliliklar.push(paket_adi); }
-                // Bu durumda çözülen_bagimliliklar listesi, bağımlılıkları önce gelecek şekilde sondan başa sıralanır.
-                // Listenin tersine çevrilmesi gerekebilir.
-
-                // Mevcut kodun mantığına göre paket_adi'nin kendisi cozulen_bagimliliklar'a ekleniyor.
-                // Bu da bir tür topolojik sıralamaya benzer, ancak tam olarak standart post-order DFS değil.
-                // Let's stick to the original logic's intent.
-                cozulen_bagimliliklar.push(paket_adi.clone()); // String klonlama (alloc gerektirir).
-
-                 // Paket adı ziyaret edilenler kümesine eklenmişti, burada tekrar gerek yok.
-                 // ziyaret_edilenler.insert(paket_adi); // Original code had this here, redundant if added at the start.
-                 // Let's remove the redundant insert.
-            } else {
-                 eprintln!("Başlangıç paketi veya bir bağımlılık bilinmiyor: {}", paket_adi);
-                // Hata: Paket bulunamadı (başlangıç paketi veya geçersiz bir bağımlılık adı).
-                // paket_adi String'i pop ile alınmıştı, ownership kullanılabilir.
-                return Err(PaketYoneticisiHatasi::PaketBulunamadi(paket_adi));
             }
         }
 
-        // Başarılı sonuç: Çözülen bağımlılıkların listesini döndür.
-        // Eğer cozulen_bagimliliklar listesi post-order DFS ile oluşturulduysa, kurulum sırası için tersine çevrilmesi gerekebilir.
-        // cozuldukten sonra reverse() yapilabilir: cozulen_bagimliliklar.reverse();
-        // Mevcut kodun mantığı tam bir post-order gibi durmuyor, paketi işledikten sonra ekliyor.
-        // Test senaryolarına göre listenin kurulum sırasını doğru temsil ettiğini varsayalım veya
-        /// burada reverse() ekleyelim. Tipik kurulum sırası için reverse() gerekir.
+        Ok(cozulen_bagimliliklar) // Başarılı
+    }
 
-        cozulen_bagimliliklar.reverse(); // Genellikle kurulum sırası için listeyi ters çevirmek gerekir.
+    // Bağımlılıkları çözer ve kurulum sırasına göre paket meta verilerini döndürür (her paketin en yeni sürümü).
+    // Kurulum yöneticisinin plani_uygula fonksiyonuna doğrudan verilebilir.
+    pub fn plani_coz(paketler: &Vec<Paket>, baslangic_paketi: &str) -> Result<Vec<Paket>, PaketYoneticisiHatasi> {
+        let sira = Self::bagimliliklari_coz(paketler, baslangic_paketi)?;
+        let paket_haritasi = aday_haritasi(paketler);
+        Ok(sira.iter().filter_map(|ad| paket_haritasi.get(ad).map(|paket| (*paket).clone())).collect())
+    }
 
-        Ok(cozulen_bagimliliklar) // Başarılı
+    // Bağımlılıkları çözer ve ortaya çıkan planı (zaten kurulu bağımlılıklar dahil) lisans politikasına göre denetler.
+    // Plandaki bir paketin lisansı yasaksa veya incelenmemişse, hangi paketlerin neden reddedildiğini
+    // listeleyen LisansPolitikasiIhlali hatası döner. Dönüş değeri plani_coz ile aynıdır.
+    pub fn lisans_denetimiyle_coz(paketler: &Vec<Paket>, baslangic_paketi: &str, politika: &LisansPolitikasi) -> Result<Vec<Paket>, PaketYoneticisiHatasi> {
+        let plan = Self::plani_coz(paketler, baslangic_paketi)?;
+        politika.plani_denetle(&plan)?;
+        Ok(plan)
+    }
+}
+
+// Paket adı -> o adın en yeni sürümü. Aynı adın birden çok sürümü depoda bulunabilir.
+fn aday_haritasi(paketler: &[Paket]) -> HashMap<String, &Paket> {
+    let mut paket_haritasi: HashMap<String, &Paket> = HashMap::new();
+    for paket in paketler {
+        let daha_yeni = paket_haritasi
+            .get(&paket.ad)
+            .map_or(true, |mevcut| paket.surum_karsilastir(&mevcut.surum) == Ordering::Greater);
+        if daha_yeni {
+            paket_haritasi.insert(paket.ad.clone(), paket);
+        }
     }
+    paket_haritasi
 }
 
 #![no_std]
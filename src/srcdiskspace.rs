@@ -0,0 +1,233 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+
+// Paket struct tanımını içeren modül
+use crate::package::Paket;
+
+// Sahne64 API modülleri
+use crate::resource; // Kaynak işlemleri (acquire, control, release)
+use crate::SahneError; // Sahne64 hata türü
+use crate::srcresource::RESOURCE_CONTROL_CMD_STATFS; // Kaynak Kontrol Komutu
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, debug};
+
+// Hata mesajında listelenecek en büyük paket sayısı.
+const RAPORLANACAK_PAKET_SAYISI: usize = 5;
+
+// Çıkarma sırasında dosya sistemi meta verisi ve blok yuvarlaması için eklenen pay (yüzde).
+const KURULUM_PAYI_YUZDE: u64 = 5;
+
+
+// Bir depolama biriminin boş alan bilgisi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlanBilgisi {
+    pub bos_alan: u64, // Bayt cinsinden kullanılabilir alan
+    pub birim_kimligi: u64, // Aynı birim üzerindeki hedefleri birleştirmek için
+}
+
+// Verilen Kaynağın bulunduğu depolama biriminin boş alanını sorgular.
+pub fn alan_bilgisi_al(resource_id: &str) -> Result<AlanBilgisi, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let yanit = resource::control(handle, RESOURCE_CONTROL_CMD_STATFS, &[]);
+    let _ = resource::release(handle);
+    let yanit = yanit.map_err(PaketYoneticisiHatasi::from)?;
+
+    if yanit.len() < 16 {
+        return Err(PaketYoneticisiHatasi::ParsingError(format!(
+            "Boş alan yanıtı beklenenden kısa ({} bayt): {}", yanit.len(), resource_id
+        )));
+    }
+
+    let mut bos = [0u8; 8];
+    bos.copy_from_slice(&yanit[0..8]);
+    let mut birim = [0u8; 8];
+    birim.copy_from_slice(&yanit[8..16]);
+
+    Ok(AlanBilgisi {
+        bos_alan: u64::from_le_bytes(bos),
+        birim_kimligi: u64::from_le_bytes(birim),
+    })
+}
+
+
+// Bir hedef (önbellek veya kurulum dizini) için alan gereksinimi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlanGereksinimi {
+    pub hedef: String, // Hedefin Kaynak ID'si
+    pub gereken: u64, // Toplam bayt
+    pub paketler: Vec<(String, u64)>, // Katkıda bulunan paketler (ad, bayt)
+}
+
+impl AlanGereksinimi {
+    fn yeni(hedef: &str) -> Self {
+        AlanGereksinimi { hedef: hedef.to_owned(), gereken: 0, paketler: Vec::new() }
+    }
+
+    fn ekle(&mut self, paket_adi: &str, boyut: u64) {
+        if boyut == 0 {
+            return;
+        }
+        self.gereken = self.gereken.saturating_add(boyut);
+        self.paketler.push((paket_adi.to_owned(), boyut));
+    }
+
+    fn birlestir(&mut self, diger: AlanGereksinimi) {
+        self.gereken = self.gereken.saturating_add(diger.gereken);
+        self.paketler.extend(diger.paketler);
+    }
+
+    // Katkısı en büyük paketleri büyükten küçüğe döndürür.
+    pub fn en_buyuk_paketler(&self, adet: usize) -> Vec<(String, u64)> {
+        let mut paketler = self.paketler.clone();
+        paketler.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        paketler.truncate(adet);
+        paketler
+    }
+}
+
+// Bir kurulum planının önbellek ve kurulum hedeflerinde ihtiyaç duyduğu alanı hesaplar.
+// paketler: Kurulacak paketler.
+// onbellekte_mi: Arşivi zaten önbellekte olan paketler için true döner (indirme alanı gerekmez).
+// Dönüş değeri: (önbellek gereksinimi, kurulum gereksinimi).
+pub fn gereksinimleri_hesapla<F>(
+    paketler: &[Paket],
+    onbellek_base_resource_id: &str,
+    kurulum_base_resource_id: &str,
+    onbellekte_mi: F,
+) -> Result<(AlanGereksinimi, AlanGereksinimi), PaketYoneticisiHatasi>
+where
+    F: Fn(&Paket) -> bool,
+{
+    let mut onbellek = AlanGereksinimi::yeni(onbellek_base_resource_id);
+    let mut kurulum = AlanGereksinimi::yeni(kurulum_base_resource_id);
+
+    for paket in paketler {
+        // Boyut bilgisi olmayan bir paketle alan garantisi verilemez; tahmin etmek yerine reddediyoruz.
+        let indirme = paket.indirme_boyutu.ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Paket '{}' için indirme boyutu belirtilmemiş.", paket.ad))
+        })?;
+        let kurulu = paket.kurulu_boyut.ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Paket '{}' için kurulu boyut belirtilmemiş.", paket.ad))
+        })?;
+
+        if !onbellekte_mi(paket) {
+            onbellek.ekle(&paket.ad, indirme);
+        }
+        kurulum.ekle(&paket.ad, kurulu.saturating_add(kurulu.saturating_mul(KURULUM_PAYI_YUZDE) / 100));
+    }
+
+    Ok((onbellek, kurulum))
+}
+
+// Gereksinimleri hedeflerin boş alanıyla karşılaştırır.
+// İki hedef aynı depolama birimindeyse gereksinimleri toplanarak tek seferde kontrol edilir.
+pub fn alan_yeterli_mi(
+    onbellek: AlanGereksinimi,
+    onbellek_alani: AlanBilgisi,
+    kurulum: AlanGereksinimi,
+    kurulum_alani: AlanBilgisi,
+) -> Result<(), PaketYoneticisiHatasi> {
+    let mut kontroller = Vec::new();
+    if onbellek_alani.birim_kimligi == kurulum_alani.birim_kimligi {
+        let mut toplam = kurulum;
+        toplam.birlestir(onbellek);
+        kontroller.push((toplam, kurulum_alani));
+    } else {
+        kontroller.push((onbellek, onbellek_alani));
+        kontroller.push((kurulum, kurulum_alani));
+    }
+
+    for (gereksinim, alan) in kontroller {
+        debug!("Alan kontrolü ({}): {} bayt gerekiyor, {} bayt boş.", gereksinim.hedef, gereksinim.gereken, alan.bos_alan);
+        if gereksinim.gereken > alan.bos_alan {
+            return Err(PaketYoneticisiHatasi::YetersizDiskAlani {
+                en_buyuk_paketler: gereksinim.en_buyuk_paketler(RAPORLANACAK_PAKET_SAYISI),
+                hedef: gereksinim.hedef,
+                gereken: gereksinim.gereken,
+                mevcut: alan.bos_alan,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Kurulum planı için alan kontrolünü uçtan uca yapar (sorgu + hesap + karşılaştırma).
+// Hiçbir indirme veya çıkarma başlamadan önce çağrılmalıdır.
+pub fn alan_kontrolu<F>(
+    paketler: &[Paket],
+    onbellek_base_resource_id: &str,
+    kurulum_base_resource_id: &str,
+    onbellekte_mi: F,
+) -> Result<(), PaketYoneticisiHatasi>
+where
+    F: Fn(&Paket) -> bool,
+{
+    let (onbellek, kurulum) = gereksinimleri_hesapla(paketler, onbellek_base_resource_id, kurulum_base_resource_id, onbellekte_mi)?;
+    let onbellek_alani = alan_bilgisi_al(onbellek_base_resource_id)?;
+    let kurulum_alani = alan_bilgisi_al(kurulum_base_resource_id)?;
+
+    alan_yeterli_mi(onbellek, onbellek_alani, kurulum, kurulum_alani)?;
+    info!("Disk alanı kontrolü başarılı ({} paket).", paketler.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paket(ad: &str, indirme: u64, kurulu: u64) -> Paket {
+        let mut p = Paket::yeni(ad.to_string(), "1.0".to_string(), Vec::new());
+        p.indirme_boyutu = Some(indirme);
+        p.kurulu_boyut = Some(kurulu);
+        p
+    }
+
+    #[test]
+    fn test_onbellekteki_paketler_indirme_alani_istemez() {
+        let paketler = [paket("a", 100, 1000), paket("b", 200, 2000)];
+        let (onbellek, kurulum) = gereksinimleri_hesapla(&paketler, "sahne://cache", "sahne://installed", |p| p.ad == "a").unwrap();
+        assert_eq!(onbellek.gereken, 200);
+        assert_eq!(kurulum.gereken, 1050 + 2100);
+    }
+
+    #[test]
+    fn test_ayni_birimde_gereksinimler_toplanir() {
+        let paketler = [paket("kucuk", 10, 100), paket("buyuk", 500, 5000)];
+        let (onbellek, kurulum) = gereksinimleri_hesapla(&paketler, "sahne://cache", "sahne://installed", |_| false).unwrap();
+        let alan = AlanBilgisi { bos_alan: 5500, birim_kimligi: 1 };
+
+        match alan_yeterli_mi(onbellek, alan, kurulum, alan) {
+            Err(PaketYoneticisiHatasi::YetersizDiskAlani { gereken, mevcut, en_buyuk_paketler, .. }) => {
+                assert_eq!(gereken, 105 + 5250 + 510);
+                assert_eq!(mevcut, 5500);
+                assert_eq!(en_buyuk_paketler[0].0, "buyuk");
+            }
+            sonuc => panic!("Beklenmeyen sonuç: {:?}", sonuc),
+        }
+    }
+
+    #[test]
+    fn test_boyutsuz_paket_reddedilir() {
+        let paketler = [Paket::yeni("x".to_string(), "1.0".to_string(), Vec::new())];
+        assert!(gereksinimleri_hesapla(&paketler, "sahne://cache", "sahne://installed", |_| false).is_err());
+    }
+
+    #[test]
+    fn test_asiri_buyuk_boyut_tasmaz() {
+        let paketler = [paket("dev", 1, u64::MAX), paket("kucuk", 1, 100)];
+        let (_, kurulum) = gereksinimleri_hesapla(&paketler, "sahne://cache", "sahne://installed", |_| false).unwrap();
+        assert_eq!(kurulum.gereken, u64::MAX);
+    }
+}
@@ -0,0 +1,293 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, Arc, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+use alloc::boxed::Box; // İş parçacığı argümanı için
+use alloc::sync::Arc; // İş parçacıkları arasında paylaşılan durum
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU8, AtomicU32, AtomicUsize, Ordering};
+
+// Sahne64 API modülleri
+use crate::task; // create_thread, sleep, exit
+
+// Tek bir dosyayı indiren Kaynak tabanlı ağ fonksiyonu
+use crate::srcnetwork;
+// Akış sırasında doğrulanan özet algoritması
+use crate::srcchecksum::OzetAlgoritmasi;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, debug};
+
+// İşçi iş parçacıklarının yığın boyutu (varsayımsal; indirme tamponu yığında tutulur)
+const ISCI_YIGIN_BOYUTU: u64 = 64 * 1024;
+// Bekleme döngülerinde uyunacak süre (mikrosaniye)
+const BEKLEME_SURESI_US: u64 = 10_000;
+
+// Yuva durumları
+const DURUM_BEKLIYOR: u8 = 0;
+const DURUM_SURUYOR: u8 = 1;
+const DURUM_BITTI: u8 = 2;
+
+
+// Tek bir indirme isteği.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndirmeIstegi {
+    pub paket_adi: String, // Raporlama için
+    pub kaynak_url: String, // Uzak Kaynak ID'si (URL)
+    pub hedef_resource_id: String, // Yerel hedef Kaynak ID'si
+    pub beklenen_boyut: Option<u64>, // Biliniyorsa aşıldığı anda aktarım kesilir
+    pub beklenen_ozet: Option<(OzetAlgoritmasi, String)>, // İndirme sırasında hesaplanıp doğrulanır (hex)
+}
+
+// Bir indirme isteğinin sonucu. Sonuçlar isteklerle aynı sırada döner.
+#[derive(Debug)]
+pub struct IndirmeSonucu {
+    pub paket_adi: String,
+    pub deneme_sayisi: u32,
+    pub sonuc: Result<(), PaketYoneticisiHatasi>,
+}
+
+// URL'den ana makine adını çıkarır ("http://depo.sahne/paketler/a.zip" -> "depo.sahne").
+// Şema içermeyen Kaynak ID'leri için ilk bileşen kullanılır.
+pub fn ana_makine(url: &str) -> &str {
+    let sema_sonrasi = match url.find("://") {
+        Some(idx) => &url[idx + 3..],
+        None => url,
+    };
+    let bitis = sema_sonrasi.find('/').unwrap_or(sema_sonrasi.len());
+    &sema_sonrasi[..bitis]
+}
+
+
+// Aynı anda birden fazla paketi indiren zamanlayıcı.
+// Toplam eşzamanlılık ve ana makine başına bağlantı sayısı sınırlıdır; başarısız öğeler bağımsız olarak yeniden denenir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndirmeZamanlayici {
+    pub eszamanli: usize, // Toplam işçi sayısı
+    pub ana_makine_basina: usize, // Aynı ana makineye aynı anda açık bağlantı sınırı
+    pub deneme_sayisi: u32, // Bir öğe için toplam deneme (ilk deneme dahil)
+}
+
+impl IndirmeZamanlayici {
+    pub fn yeni(eszamanli: usize, ana_makine_basina: usize, deneme_sayisi: u32) -> Self {
+        IndirmeZamanlayici {
+            eszamanli: eszamanli.max(1),
+            ana_makine_basina: ana_makine_basina.max(1),
+            deneme_sayisi: deneme_sayisi.max(1),
+        }
+    }
+
+    // Varsayılan: 4 işçi, ana makine başına 2 bağlantı, öğe başına 3 deneme.
+    pub fn varsayilan() -> Self {
+        IndirmeZamanlayici::yeni(4, 2, 3)
+    }
+
+    // İstekleri indirir ve sonuçları isteklerle aynı sırada döndürür.
+    // Bir öğenin başarısızlığı diğerlerini durdurmaz.
+    pub fn indir(&self, istekler: Vec<IndirmeIstegi>) -> Vec<IndirmeSonucu> {
+        if istekler.is_empty() {
+            return Vec::new();
+        }
+
+        let paylasilan = Arc::new(PaylasilanDurum::yeni(istekler, *self));
+        let isci_sayisi = self.eszamanli.min(paylasilan.yuvalar.len());
+        info!("{} paket indiriliyor ({} işçi, ana makine başına {} bağlantı).", paylasilan.yuvalar.len(), isci_sayisi, self.ana_makine_basina);
+
+        let mut baslatilan = 0;
+        for _ in 0..isci_sayisi {
+            let arg = Box::into_raw(Box::new(Arc::clone(&paylasilan))) as u64;
+            match task::create_thread(indirme_iscisi as u64, ISCI_YIGIN_BOYUTU, arg) {
+                Ok(_) => baslatilan += 1,
+                Err(e) => {
+                    // İş parçacığı başlatılamadıysa argümanı geri alıp serbest bırak.
+                    warn!("İndirme işçisi başlatılamadı: {:?}", e);
+                    unsafe { drop(Box::from_raw(arg as *mut Arc<PaylasilanDurum>)); }
+                }
+            }
+        }
+
+        if baslatilan == 0 {
+            // Hiç işçi başlatılamadıysa indirmeleri bu iş parçacığında sırayla yap.
+            paylasilan.calis();
+        }
+
+        // Tüm yuvalar bitene kadar bekle (Sahne64'te thread join olmadığı için durum sorgulanır).
+        while paylasilan.biten.load(Ordering::Acquire) < paylasilan.yuvalar.len() {
+            let _ = task::sleep(BEKLEME_SURESI_US);
+        }
+
+        paylasilan.sonuclari_al()
+    }
+}
+
+
+// Bir isteğin işçiler arasında paylaşılan durumu.
+struct Yuva {
+    istek: IndirmeIstegi,
+    ana_makine: String,
+    durum: AtomicU8,
+    deneme: AtomicU32,
+    // Sadece yuvayı DURUM_SURUYOR'a geçiren işçi yazar; DURUM_BITTI (Release) sonrasında okunur (Acquire).
+    hata: UnsafeCell<Option<PaketYoneticisiHatasi>>,
+}
+
+// Güvenlik: `hata` alanına erişim yukarıdaki durum makinesiyle sıralanır.
+unsafe impl Sync for Yuva {}
+
+struct PaylasilanDurum {
+    yuvalar: Vec<Yuva>,
+    // Ana makine adı -> aktif bağlantı sayısı (yuvalarla aynı ana makine listesi üzerinden indekslenir)
+    ana_makineler: Vec<(String, AtomicUsize)>,
+    biten: AtomicUsize,
+    ayarlar: IndirmeZamanlayici,
+}
+
+impl PaylasilanDurum {
+    fn yeni(istekler: Vec<IndirmeIstegi>, ayarlar: IndirmeZamanlayici) -> Self {
+        let mut ana_makineler: Vec<(String, AtomicUsize)> = Vec::new();
+        let yuvalar = istekler.into_iter().map(|istek| {
+            let makine = ana_makine(&istek.kaynak_url).to_owned();
+            if !ana_makineler.iter().any(|(m, _)| m == &makine) {
+                ana_makineler.push((makine.clone(), AtomicUsize::new(0)));
+            }
+            Yuva {
+                istek,
+                ana_makine: makine,
+                durum: AtomicU8::new(DURUM_BEKLIYOR),
+                deneme: AtomicU32::new(0),
+                hata: UnsafeCell::new(None),
+            }
+        }).collect();
+
+        PaylasilanDurum { yuvalar, ana_makineler, biten: AtomicUsize::new(0), ayarlar }
+    }
+
+    fn baglanti_sayaci(&self, makine: &str) -> &AtomicUsize {
+        // Yuvalar oluşturulurken her ana makine listeye eklendiği için bulunamama durumu yoktur.
+        &self.ana_makineler.iter().find(|(m, _)| m == makine).map(|(_, sayac)| sayac).unwrap()
+    }
+
+    // Ana makine sınırını aşmadan bir bağlantı ayırmayı dener.
+    fn baglanti_al(&self, makine: &str) -> bool {
+        let sayac = self.baglanti_sayaci(makine);
+        let mut mevcut = sayac.load(Ordering::Acquire);
+        while mevcut < self.ayarlar.ana_makine_basina {
+            match sayac.compare_exchange(mevcut, mevcut + 1, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return true,
+                Err(guncel) => mevcut = guncel,
+            }
+        }
+        false
+    }
+
+    // Bekleyen ve ana makinesinde boş bağlantı olan ilk yuvayı sahiplenir.
+    fn yuva_sahiplen(&self) -> Option<usize> {
+        for (indeks, yuva) in self.yuvalar.iter().enumerate() {
+            if yuva.durum.load(Ordering::Acquire) != DURUM_BEKLIYOR {
+                continue;
+            }
+            if !self.baglanti_al(&yuva.ana_makine) {
+                continue;
+            }
+            if yuva.durum.compare_exchange(DURUM_BEKLIYOR, DURUM_SURUYOR, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                return Some(indeks);
+            }
+            // Başka bir işçi önce davrandı, bağlantıyı geri ver.
+            self.baglanti_sayaci(&yuva.ana_makine).fetch_sub(1, Ordering::AcqRel);
+        }
+        None
+    }
+
+    // İşçi döngüsü: bitmemiş yuva kalmayana kadar indir.
+    fn calis(&self) {
+        while self.biten.load(Ordering::Acquire) < self.yuvalar.len() {
+            let indeks = match self.yuva_sahiplen() {
+                Some(indeks) => indeks,
+                None => {
+                    // Tüm bekleyenlerin ana makinesi dolu veya kalan öğeler başka işçilerde.
+                    let _ = task::sleep(BEKLEME_SURESI_US);
+                    continue;
+                }
+            };
+
+            let yuva = &self.yuvalar[indeks];
+            let deneme = yuva.deneme.fetch_add(1, Ordering::AcqRel) + 1;
+            debug!("İndiriliyor ({}. deneme): {}", deneme, yuva.istek.kaynak_url);
+
+            let sonuc = srcnetwork::dosya_indir_dogrulayarak(
+                &yuva.istek.kaynak_url,
+                &yuva.istek.hedef_resource_id,
+                yuva.istek.beklenen_boyut,
+                yuva.istek.beklenen_ozet.as_ref().map(|(alg, ozet)| (*alg, ozet.as_str())),
+            ).map(|_| ());
+            self.baglanti_sayaci(&yuva.ana_makine).fetch_sub(1, Ordering::AcqRel);
+
+            match sonuc {
+                Ok(()) => {
+                    yuva.durum.store(DURUM_BITTI, Ordering::Release);
+                    self.biten.fetch_add(1, Ordering::AcqRel);
+                }
+                Err(e) if deneme < self.ayarlar.deneme_sayisi => {
+                    warn!("İndirme başarısız, yeniden denenecek ({}): {:?}", yuva.istek.paket_adi, e);
+                    yuva.durum.store(DURUM_BEKLIYOR, Ordering::Release);
+                }
+                Err(e) => {
+                    unsafe { *yuva.hata.get() = Some(e); }
+                    yuva.durum.store(DURUM_BITTI, Ordering::Release);
+                    self.biten.fetch_add(1, Ordering::AcqRel);
+                }
+            }
+        }
+    }
+
+    // Tüm yuvalar bittikten sonra sonuçları istek sırasıyla toplar.
+    fn sonuclari_al(&self) -> Vec<IndirmeSonucu> {
+        self.yuvalar.iter().map(|yuva| {
+            let hata = unsafe { (*yuva.hata.get()).take() };
+            IndirmeSonucu {
+                paket_adi: yuva.istek.paket_adi.clone(),
+                deneme_sayisi: yuva.deneme.load(Ordering::Acquire),
+                sonuc: match hata {
+                    Some(e) => Err(e),
+                    None => Ok(()),
+                },
+            }
+        }).collect()
+    }
+}
+
+// İşçi iş parçacığı giriş noktası (task::create_thread argüman imzasına uygun).
+extern "C" fn indirme_iscisi(arg: u64) -> ! {
+    let paylasilan = unsafe { Box::from_raw(arg as *mut Arc<PaylasilanDurum>) };
+    paylasilan.calis();
+    drop(paylasilan);
+    task::exit(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ana_makine() {
+        assert_eq!(ana_makine("http://depo.sahne/paketler/a.zip"), "depo.sahne");
+        assert_eq!(ana_makine("https://ayna.ornek:8443/a.zip"), "ayna.ornek:8443");
+        assert_eq!(ana_makine("sahne://remotepkgrepo/packages/a.zip"), "remotepkgrepo");
+        assert_eq!(ana_makine("http://yalniz-makine"), "yalniz-makine");
+    }
+
+    #[test]
+    fn test_zamanlayici_sinirlari_en_az_bir() {
+        let z = IndirmeZamanlayici::yeni(0, 0, 0);
+        assert_eq!(z, IndirmeZamanlayici { eszamanli: 1, ana_makine_basina: 1, deneme_sayisi: 1 });
+    }
+
+    // indir() testleri mock task::create_thread ve ağ Kaynağı gerektirir.
+}
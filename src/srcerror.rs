@@ -2,6 +2,7 @@
 extern crate alloc; // String, format!, Box için
 
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use alloc::format;
 // zip crate'inin no_std hatasını varsayalım
 use zip::result::ZipError; // zip crate'inin no_std+alloc uyumlu ZipError'ını varsayıyoruz
@@ -11,6 +12,9 @@ use postcard::Error as PostcardError; // postcard crate'inin no_std+alloc uyumlu
 
 // Sahne64 API'sının hata türü
 use crate::SahneError;
+
+// Paket indeksi hata türü
+use crate::srcrepositoryindex::IndexError;
 // Paket Yöneticisi Hata Türleri (no_std uyumlu)
 // thiserror::Error yerine Debug ve Display manuel implementasyonları.
 #[derive(Debug)] // Debug derive'ı no_std'de çalışır
@@ -45,6 +49,24 @@ pub enum PaketYoneticisiHatasi {
     // Checksum doğrulama hatası (Paket bütünlüğü doğrulanamadı)
     ChecksumVerificationError, // Enum varyantı olarak sabit, alloc gerektirmez.
 
+    // Hesaplanan özet paket meta verisindeki değerle eşleşmedi (değerler hex)
+    OzetUyusmazligi { kaynak: String, algoritma: String, beklenen: String, hesaplanan: String },
+
+    // Meta veride doğrulamaya uygun özet yok (hiç özet yok veya yalnızca izin verilmeyen MD5 var)
+    GuvenilirOzetYok(String),
+
+    // Depo bildirimi imzasız, imzası geçersiz veya indeks dosyası bildirimle uyuşmuyor
+    DepoBildirimiGecersiz(String),
+
+    // Depo bildirimi daha önce kabul edilen sürümden eski (geri alma saldırısı)
+    DepoBildirimiGeriAlindi { depo: String, bilinen_surum: u64, alinan_surum: u64 },
+
+    // Depo bildiriminin geçerlilik süresi dolmuş (dondurma saldırısı veya güncellenmeyen ayna); zamanlar Unix saniyesi
+    DepoBildirimiSuresiDoldu { depo: String, son_gecerlilik: u64, simdi: u64 },
+
+    // İndirilen veri meta verideki boyutu aştı (aktarım kesildi) veya eksik kaldı
+    IndirmeBoyutuUyusmadi { kaynak: String, beklenen: u64, alinan: u64 },
+
     // Paket kurulumu veya kaldırma sırasında oluşan genel hatalar
     // Betik çalıştırma hatası gibi daha spesifik hatalar bu varyanta girebilir veya ayrı tutulur.
     // Genel bir hata mesajı veya nedeni tutabilir.
@@ -54,6 +76,43 @@ pub enum PaketYoneticisiHatasi {
     // Önbellek işlemleri sırasında oluşan hatalar (SahneApiError dışındaki cache mantığı hataları)
     CacheError(String), // Detay String (alloc gerektirir)
 
+    // Kurulum hedefinde veya önbellekte yeterli boş alan yok (işleme başlamadan tespit edilir)
+    // hedef: Alanı yetmeyen Kaynak ID'si, gereken/mevcut: bayt, en_buyuk_paketler: (paket adı, bayt) en büyükten küçüğe
+    YetersizDiskAlani { hedef: String, gereken: u64, mevcut: u64, en_buyuk_paketler: Vec<(String, u64)> },
+
+    // Paket betiği başlatılamadı (betik Kaynağı açılamadı, görev oluşturulamadı)
+    BetikCalistirmaHatasi(String),
+
+    // Paket betiği, paketin bildirdiği izinlerin dışında erişim denedi (erişimler reddedildi)
+    // betik: Betiğin Kaynak ID'si, ihlaller: "erişim hedef" satırları
+    SandboxIhlali { betik: String, ihlaller: Vec<String> },
+
+    // Kurulum planında, politikanın engelleme eşiğinde veya üstünde güvenlik açığı olan paketler var
+    // esik: Politikadaki en düşük engellenen ciddiyet, bulgular: "paket sürüm: bildiri (ciddiyet)" satırları
+    GuvenlikAcigiEngeli { esik: String, bulgular: Vec<String> },
+
+    // Paket arşivinin ayrık imzaları doğrulanamadı veya pakete uygulanan eşik kuralını sağlamadı (arşiv açılmadan)
+    PaketImzasiReddedildi { paket: String, neden: String },
+
+    // Kurulum planında lisans politikasının yasakladığı veya incelenmemiş lisanslı paketler var
+    // yasak / inceleme: "paket sürüm: lisans (neden)" satırları
+    LisansPolitikasiIhlali { yasak: Vec<String>, inceleme: Vec<String> },
+
+    // Hedef Kaynak kısmi yazmada ilerlemedi (0 bayt yazıldı)
+    EksikYazma(String), // Kaynak ID'si ve yazılan/beklenen bayt
+
+    // Arşiv biçimi tanınmadı veya sıkıştırma algoritması etkin değil
+    DesteklenmeyenArsivBicimi(String),
+
+    // Güvenilmeyen arşiv bir kaynak sınırını aştı (girdi sayısı, boyut, sıkıştırma oranı veya yol uzunluğu)
+    ArsivSiniriAsildi { girdi: String, sinir: String, deger: u64, azami: u64 },
+
+    // Güvenlik: Arşiv girdisi veya sembolik bağ çıkarma kökünün dışına çıkıyor (path traversal)
+    PathTraversalHatasi(String), // Reddedilen girdi ve neden
+
+    // Paket indeksi yüklenirken/doğrulanırken oluşan hatalar
+    IndexError(IndexError), // IndexError'ı sarmalar
+
     // Fonksiyona geçersiz parametre geçilmesi
     InvalidParameter(String), // Detay String (alloc gerektirir)
 
@@ -78,9 +137,64 @@ impl core::fmt::Display for PaketYoneticisiHatasi {
             PaketYoneticisiHatasi::PaketBulunamadi(s) => write!(f, "Paket bulunamadı: {}", s),
             PaketYoneticisiHatasi::ConflictError(s) => write!(f, "Paket çakışması: {}", s),
             PaketYoneticisiHatasi::ChecksumVerificationError => write!(f, "Checksum doğrulama hatası: Paket bütünlüğü doğrulanamadı."),
+            PaketYoneticisiHatasi::OzetUyusmazligi { kaynak, algoritma, beklenen, hesaplanan } => {
+                write!(f, "{} özeti eşleşmedi ({}): beklenen {}, hesaplanan {}", algoritma, kaynak, beklenen, hesaplanan)
+            }
+            PaketYoneticisiHatasi::GuvenilirOzetYok(s) => write!(f, "Güvenilir özet yok: {}", s),
+            PaketYoneticisiHatasi::DepoBildirimiGecersiz(s) => write!(f, "Depo meta verisi reddedildi: {}", s),
+            PaketYoneticisiHatasi::DepoBildirimiGeriAlindi { depo, bilinen_surum, alinan_surum } => {
+                write!(f, "Depo meta verisi reddedildi ({}): sürüm {} daha önce görülen {} sürümünden eski (geri alma)", depo, alinan_surum, bilinen_surum)
+            }
+            PaketYoneticisiHatasi::DepoBildirimiSuresiDoldu { depo, son_gecerlilik, simdi } => {
+                write!(f, "Depo meta verisi reddedildi ({}): geçerlilik süresi {} tarihinde doldu, şimdi {} (eski veya dondurulmuş depo)", depo, son_gecerlilik, simdi)
+            }
+            PaketYoneticisiHatasi::IndirmeBoyutuUyusmadi { kaynak, beklenen, alinan } => {
+                write!(f, "İndirme boyutu uyuşmadı ({}): beklenen {} bayt, alınan {} bayt", kaynak, beklenen, alinan)
+            }
             PaketYoneticisiHatasi::InstallationError(s) => write!(f, "Kurulum hatası: {}", s),
             PaketYoneticisiHatasi::RemovalError(s) => write!(f, "Kaldırma hatası: {}", s),
             PaketYoneticisiHatasi::CacheError(s) => write!(f, "Önbellek hatası: {}", s),
+            PaketYoneticisiHatasi::YetersizDiskAlani { hedef, gereken, mevcut, en_buyuk_paketler } => {
+                write!(f, "Yetersiz disk alanı ({}): {} bayt gerekiyor, {} bayt boş.", hedef, gereken, mevcut)?;
+                if !en_buyuk_paketler.is_empty() {
+                    write!(f, " En büyük paketler:")?;
+                    for (ad, boyut) in en_buyuk_paketler {
+                        write!(f, " {} ({} bayt)", ad, boyut)?;
+                    }
+                }
+                Ok(())
+            }
+            PaketYoneticisiHatasi::BetikCalistirmaHatasi(s) => write!(f, "Betik çalıştırma hatası: {}", s),
+            PaketYoneticisiHatasi::SandboxIhlali { betik, ihlaller } => {
+                write!(f, "Betik izin verilmeyen erişimler denedi ({}): {}", betik, ihlaller.join("; "))
+            }
+            PaketYoneticisiHatasi::GuvenlikAcigiEngeli { esik, bulgular } => {
+                write!(f, "Kurulum engellendi: {} veya üstü ciddiyette {} güvenlik açığı bulundu:", esik, bulgular.len())?;
+                for bulgu in bulgular {
+                    write!(f, " {};", bulgu)?;
+                }
+                Ok(())
+            }
+            PaketYoneticisiHatasi::PaketImzasiReddedildi { paket, neden } => {
+                write!(f, "Paket imzası reddedildi ({}): {}", paket, neden)
+            }
+            PaketYoneticisiHatasi::LisansPolitikasiIhlali { yasak, inceleme } => {
+                write!(f, "Kurulum engellendi: lisans politikası {} yasak, {} incelenmemiş paket buldu.", yasak.len(), inceleme.len())?;
+                for satir in yasak {
+                    write!(f, " Yasak: {};", satir)?;
+                }
+                for satir in inceleme {
+                    write!(f, " İnceleme gerekli: {};", satir)?;
+                }
+                Ok(())
+            }
+            PaketYoneticisiHatasi::EksikYazma(s) => write!(f, "Eksik yazma: {}", s),
+            PaketYoneticisiHatasi::DesteklenmeyenArsivBicimi(s) => write!(f, "Desteklenmeyen arşiv biçimi: {}", s),
+            PaketYoneticisiHatasi::ArsivSiniriAsildi { girdi, sinir, deger, azami } => {
+                write!(f, "Arşiv sınırı aşıldı ({}): {} {} > {}", girdi, sinir, deger, azami)
+            }
+            PaketYoneticisiHatasi::PathTraversalHatasi(s) => write!(f, "Güvenlik: Çıkarma kökünün dışına çıkan yol reddedildi: {}", s),
+            PaketYoneticisiHatasi::IndexError(e) => write!(f, "İndeks hatası: {}", e),
             PaketYoneticisiHatasi::InvalidParameter(s) => write!(f, "Geçersiz parametre: {}", s),
             PaketYoneticisiHatasi::UnknownError(s) => write!(f, "Beklenmedik hata: {}", s),
         }
@@ -123,5 +237,12 @@ impl From<ZipError> for PaketYoneticisiHatasi {
     }
 }
 
+// IndexError'dan dönüşüm. İndeks hatası olduğu gibi sarmalanır.
+impl From<IndexError> for PaketYoneticisiHatasi {
+    fn from(err: IndexError) -> Self {
+        PaketYoneticisiHatasi::IndexError(err)
+    }
+}
+
 // Eğer ağ Kaynakları için özel bir hata türü varsa, onun için de From implementasyonu eklenir.
 impl From<YourNoStdNetworkError> for PaketYoneticisiHatasi { ... }
@@ -26,6 +26,7 @@ pub enum CompressionAlgorithm {
     Gzip,
     Bzip2,
     Zstd,
+    Xz,
     Lz4,
     Brotli,
 }
@@ -55,6 +56,8 @@ pub enum SecurityFeature {
     Authentication,
     DataMasking,
     RateLimiting,
+    // Yalnızca MD5 özeti olan paket meta verisini kabul et (güvensiz, eski depolar için)
+    InsecureLegacyChecksums,
 }
 
 // Logging frameworklerini temsil eden enum.
@@ -79,6 +82,7 @@ impl FromStr for Feature {
             "gzip" => Ok(Feature::Compression(CompressionAlgorithm::Gzip)),
             "bzip2" => Ok(Feature::Compression(CompressionAlgorithm::Bzip2)),
             "zstd" => Ok(Feature::Compression(CompressionAlgorithm::Zstd)),
+            "xz" => Ok(Feature::Compression(CompressionAlgorithm::Xz)),
             "lz4" => Ok(Feature::Compression(CompressionAlgorithm::Lz4)),
             "brotli" => Ok(Feature::Compression(CompressionAlgorithm::Brotli)),
 
@@ -100,6 +104,7 @@ impl FromStr for Feature {
             "authentication" => Ok(Feature::Security(SecurityFeature::Authentication)),
             "data_masking" => Ok(Feature::Security(SecurityFeature::DataMasking)),
             "rate_limiting" => Ok(Feature::Security(SecurityFeature::RateLimiting)),
+            "insecure_legacy_checksums" => Ok(Feature::Security(SecurityFeature::InsecureLegacyChecksums)),
 
             "file_logging" => Ok(Feature::Logging(LoggingFramework::File)),
             "console_logging" => Ok(Feature::Logging(LoggingFramework::Console)),
@@ -126,6 +131,22 @@ impl FeatureSet {
         }
     }
 
+    // Paket arşivleri için varsayılan sıkıştırma algoritmalarını etkinleştirilmiş bir küme oluşturur.
+    // Brotli dahil değildir (tar akışlarında algılanamaz, bkz. srcarchive::bicim_algila).
+    pub fn with_default_compression() -> Self {
+        let mut feature_set = FeatureSet::new();
+        for algorithm in [
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Bzip2,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Xz,
+            CompressionAlgorithm::Lz4,
+        ] {
+            feature_set.enable(Feature::Compression(algorithm));
+        }
+        feature_set
+    }
+
     // Bir özelliği etkinleştirir (kümeye ekler).
     pub fn enable(&mut self, feature: Feature) {
         self.features.insert(feature); // HashSet::insert (alloc gerektirebilir)
@@ -179,6 +200,7 @@ mod tests {
         assert_eq!(Feature::from_str("gzip").unwrap(), Feature::Compression(CompressionAlgorithm::Gzip));
         assert_eq!(Feature::from_str("Bzip2").unwrap(), Feature::Compression(CompressionAlgorithm::Bzip2));
         assert_eq!(Feature::from_str("lz4").unwrap(), Feature::Compression(CompressionAlgorithm::Lz4));
+        assert_eq!(Feature::from_str("xz").unwrap(), Feature::Compression(CompressionAlgorithm::Xz));
         assert_eq!(Feature::from_str("HTTP").unwrap(), Feature::Network(NetworkProtocol::Http));
         assert_eq!(Feature::from_str("https").unwrap(), Feature::Network(NetworkProtocol::Https));
         assert_eq!(Feature::from_str("websocket").unwrap(), Feature::Network(NetworkProtocol::Websocket));
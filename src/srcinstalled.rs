@@ -0,0 +1,379 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+
+// serde ve no_std uyumlu serileştirme/deserileştirme kütüphanesi
+use serde::{Deserialize, Serialize};
+use postcard; // no_std uyumlu binary serileştirme
+
+// no_std ve alloc uyumlu kripto ve hex crate'leri
+use sha2::{Sha256, Digest};
+use hex;
+
+// Paket struct tanımını içeren modül
+use crate::package::Paket;
+// Arşivden çıkarılan girdilerin tür, mod ve bağ bilgisi
+use crate::srcarchive::{self, CikarilanGirdi, GirdiTuru};
+
+// Sahne64 API modülleri
+use crate::resource; // Kaynak işlemleri
+use crate::SahneError; // Sahne64 hata türü
+use crate::Handle; // Kaynak Handle'ları
+use crate::srcresource::{RESOURCE_CONTROL_CMD_READLINK, RESOURCE_CONTROL_CMD_STAT}; // Kaynak Kontrol Komutları
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+// SahneError ve PostcardError'dan dönüşüm From implementasyonları ile sağlanacak
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, error, debug};
+
+// Kurulu paket adlarının listesi (satır başına bir paket adı). srccli.rs ile aynı Kaynak.
+pub const INSTALLED_PACKAGES_LIST_RESOURCE: &str = "sahne://config/installed_packages.list";
+// Her kurulu paketin ayrıntılı kaydının tutulduğu temel Kaynak Yolu.
+// Kayıtlar `<base>/<paket_adi>.kayit` olarak postcard formatında saklanır.
+pub const INSTALLED_RECORDS_BASE_RESOURCE: &str = "sahne://config/installed";
+
+
+// Kurulu bir dosyanın kurulum anındaki durumunu temsil eder.
+// Doğrulama (srcverify.rs) bu kayıtla diskteki güncel durumu karşılaştırır.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KuruluDosya {
+    pub yol: String, // Paketin kurulum dizinine göre yol (örn. "bin/ls")
+    pub tur: GirdiTuru, // Dosya, dizin, sembolik bağ veya sabit bağ
+    pub boyut: u64, // Bayt cinsinden boyut (dizin ve sembolik bağlarda 0)
+    pub mod_: u32, // Erişim izinleri (örn. 0o755), tür bitleri hariç
+    pub sahip: u32, // Kullanıcı kimliği (uid)
+    pub grup: u32, // Grup kimliği (gid)
+    pub sha256: String, // İçeriğin SHA-256 özeti (hex); dizin ve sembolik bağlarda boş
+    pub bag_hedefi: Option<String>, // Sembolik bağ hedefi veya sabit bağın paket içindeki hedef yolu
+}
+
+// Kurulu bir paketin kaydı: kurulumda kullanılan meta veri ve kurulan dosyaların listesi.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KuruluPaketKaydi {
+    pub paket: Paket, // Kurulumda kullanılan paket meta verisi
+    pub dosyalar: Vec<KuruluDosya>, // Kurulan dosyaların kayıtları
+}
+
+// Sahne64 Kaynağının tür, boyut, mod ve sahip bilgisi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KaynakBilgisi {
+    pub tur: GirdiTuru, // Dosya, dizin veya sembolik bağ (sabit bağlar dosya olarak görünür)
+    pub boyut: u64,
+    pub mod_: u32, // Erişim izinleri, tür bitleri hariç
+    pub sahip: u32,
+    pub grup: u32,
+}
+
+// Verilen Kaynağın tür, boyut, mod ve sahip bilgisini sorgular.
+// Varsayım: resource::control(handle, RESOURCE_CONTROL_CMD_STAT, &[]) 20 baytlık bir yanıt döner.
+pub fn kaynak_bilgisi_al(resource_id: &str) -> Result<KaynakBilgisi, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?; // SahneError -> PaketYoneticisiHatasi
+
+    let yanit = resource::control(handle, RESOURCE_CONTROL_CMD_STAT, &[]);
+    let _ = resource::release(handle);
+    let yanit = yanit.map_err(PaketYoneticisiHatasi::from)?;
+
+    stat_yanitini_coz(&yanit).ok_or_else(|| PaketYoneticisiHatasi::ParsingError(format!(
+        "Kaynak bilgisi yanıtı beklenenden kısa ({} bayt): {}", yanit.len(), resource_id
+    )))
+}
+
+// STAT yanıtını çözer. Yanıt 20 bayttan kısaysa None döner.
+fn stat_yanitini_coz(yanit: &[u8]) -> Option<KaynakBilgisi> {
+    if yanit.len() < 20 {
+        return None;
+    }
+    let u32_oku = |bas: usize| {
+        let mut baytlar = [0u8; 4];
+        baytlar.copy_from_slice(&yanit[bas..bas + 4]);
+        u32::from_le_bytes(baytlar)
+    };
+    let mut boyut_baytlari = [0u8; 8];
+    boyut_baytlari.copy_from_slice(&yanit[0..8]);
+    let tam_mod = u32_oku(8);
+
+    Some(KaynakBilgisi {
+        tur: GirdiTuru::unix_modundan(tam_mod),
+        boyut: u64::from_le_bytes(boyut_baytlari),
+        mod_: tam_mod & srcarchive::IZIN_MASKESI,
+        sahip: u32_oku(12),
+        grup: u32_oku(16),
+    })
+}
+
+// Sembolik bağın hedefini okur (hedef izlenmez).
+pub fn bag_hedefi_oku(resource_id: &str) -> Result<String, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let yanit = resource::control(handle, RESOURCE_CONTROL_CMD_READLINK, &[]);
+    let _ = resource::release(handle);
+    let yanit = yanit.map_err(PaketYoneticisiHatasi::from)?;
+
+    String::from_utf8(yanit).map_err(|_| PaketYoneticisiHatasi::ParsingError(format!(
+        "Sembolik bağ hedefi UTF-8 değil: {}", resource_id
+    )))
+}
+
+// Verilen Kaynağın içeriğinin SHA-256 özetini hex olarak hesaplar.
+// İçerik belleğe alınmaz, 4 KiB'lık parçalar halinde özetlenir.
+pub fn kaynak_sha256(resource_id: &str) -> Result<String, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 4096]; // Okuma tamponu (stack'te)
+
+    loop {
+        match resource::read(handle, &mut buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => hasher.update(&buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    let _ = resource::release(handle);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+// Arşivden çıkarılmış bir girdinin diskteki güncel durumundan KuruluDosya kaydı oluşturur.
+// kurulum_dizini: Paketin kurulum dizini Kaynak ID'si (örn. "sahne://installed_packages/my_package/").
+// girdi: Arşiv çıkarmasının döndürdüğü girdi (yol, tür ve bağ hedefi buradan alınır).
+// Sembolik bağlarda içerik özeti tutulmaz; bağ hedefi kaydedilir.
+pub fn dosya_kaydi_olustur(kurulum_dizini: &str, girdi: &CikarilanGirdi) -> Result<KuruluDosya, PaketYoneticisiHatasi> {
+    let dosya_id = kurulu_dosya_id(kurulum_dizini, girdi.yol.trim_end_matches('/'));
+    let bilgi = kaynak_bilgisi_al(&dosya_id)?;
+
+    let (boyut, sha256) = match girdi.tur {
+        GirdiTuru::Dosya | GirdiTuru::SabitBag => (bilgi.boyut, kaynak_sha256(&dosya_id)?),
+        GirdiTuru::Dizin | GirdiTuru::SembolikBag => (0, String::new()),
+    };
+
+    Ok(KuruluDosya {
+        yol: girdi.yol.clone(),
+        tur: girdi.tur,
+        boyut,
+        mod_: bilgi.mod_,
+        sahip: bilgi.sahip,
+        grup: bilgi.grup,
+        sha256,
+        bag_hedefi: girdi.bag_hedefi.clone(),
+    })
+}
+
+// Kurulum dizini ile göreli yolu tek bir '/' ile birleştirir.
+pub fn kurulu_dosya_id(kurulum_dizini: &str, yol: &str) -> String {
+    format!("{}/{}", kurulum_dizini.trim_end_matches('/'), yol.trim_start_matches('/'))
+}
+
+
+// Kurulu paket kayıtlarını Sahne64 Kaynaklarında saklayan veritabanı.
+pub struct KuruluPaketVeritabani {
+    liste_resource_id: String, // Kurulu paket adları listesi
+    kayit_base_resource_id: String, // Paket kayıtlarının temel Kaynak ID'si
+}
+
+impl KuruluPaketVeritabani {
+    // Varsayılan Kaynak ID'leri ile yeni bir veritabanı örneği oluşturur.
+    pub fn yeni() -> Self {
+        KuruluPaketVeritabani::konumla(INSTALLED_PACKAGES_LIST_RESOURCE, INSTALLED_RECORDS_BASE_RESOURCE)
+    }
+
+    // Belirtilen Kaynak ID'leri ile yeni bir veritabanı örneği oluşturur.
+    pub fn konumla(liste_resource_id: &str, kayit_base_resource_id: &str) -> Self {
+        KuruluPaketVeritabani {
+            liste_resource_id: liste_resource_id.to_owned(),
+            kayit_base_resource_id: kayit_base_resource_id.to_owned(),
+        }
+    }
+
+    fn kayit_resource_id(&self, paket_adi: &str) -> String {
+        format!("{}/{}.kayit", self.kayit_base_resource_id, paket_adi)
+    }
+
+    // Kurulu paket adlarını döndürür. Liste Kaynağı yoksa boş liste döner.
+    pub fn paket_adlari(&self) -> Result<Vec<String>, PaketYoneticisiHatasi> {
+        let buffer = match read_resource_to_vec(&self.liste_resource_id) {
+            Ok(buffer) => buffer,
+            Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let icerik = core::str::from_utf8(&buffer).map_err(|e| {
+            PaketYoneticisiHatasi::ParsingError(format!("Kurulu paket listesi UTF-8 değil: {:?}", e))
+        })?;
+
+        Ok(icerik.lines()
+            .map(|satir| satir.trim())
+            .filter(|satir| !satir.is_empty())
+            .map(|satir| satir.to_owned())
+            .collect())
+    }
+
+    // Bir paketin kaydını yükler. Paket kurulu değilse Ok(None) döner.
+    pub fn kaydi_yukle(&self, paket_adi: &str) -> Result<Option<KuruluPaketKaydi>, PaketYoneticisiHatasi> {
+        let kayit_id = self.kayit_resource_id(paket_adi);
+        match read_resource_to_vec(&kayit_id) {
+            Ok(buffer) => {
+                let kayit = postcard::from_bytes_copy::<KuruluPaketKaydi>(&buffer)
+                    .map_err(PaketYoneticisiHatasi::from)?; // PostcardError -> PaketYoneticisiHatasi
+                Ok(Some(kayit))
+            }
+            Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Tüm kurulu paketlerin kayıtlarını yükler. Kaydı eksik olan paketler uyarı ile atlanır.
+    pub fn tum_kayitlar(&self) -> Result<Vec<KuruluPaketKaydi>, PaketYoneticisiHatasi> {
+        let mut kayitlar = Vec::new();
+        for ad in self.paket_adlari()? {
+            match self.kaydi_yukle(&ad)? {
+                Some(kayit) => kayitlar.push(kayit),
+                None => warn!("Kurulu paket listesinde olan '{}' için kayıt bulunamadı.", ad),
+            }
+        }
+        Ok(kayitlar)
+    }
+
+    // Bir paketin kaydını yazar ve paket adını kurulu listesine ekler (zaten yoksa).
+    pub fn kaydet(&self, kayit: &KuruluPaketKaydi) -> Result<(), PaketYoneticisiHatasi> {
+        let kayit_id = self.kayit_resource_id(&kayit.paket.ad);
+        debug!("Kurulu paket kaydı yazılıyor: {}", kayit_id);
+
+        let serialized_data = postcard::to_postcard(kayit).map_err(PaketYoneticisiHatasi::from)?;
+        write_bytes_to_resource(&kayit_id, &serialized_data)?;
+
+        let mut adlar = self.paket_adlari()?;
+        if !adlar.iter().any(|ad| ad == &kayit.paket.ad) {
+            adlar.push(kayit.paket.ad.clone());
+            self.listeyi_yaz(&adlar)?;
+        }
+
+        info!("Kurulu paket kaydı güncellendi: {} {}", kayit.paket.ad, kayit.paket.surum);
+        Ok(())
+    }
+
+    // Bir paketi kurulu listesinden çıkarır. Kayıt Kaynağının silinmesi resource::delete'e bağlıdır.
+    pub fn kaydi_sil(&self, paket_adi: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let adlar: Vec<String> = self.paket_adlari()?
+            .into_iter()
+            .filter(|ad| ad != paket_adi)
+            .collect();
+        self.listeyi_yaz(&adlar)?;
+
+        let kayit_id = self.kayit_resource_id(paket_adi);
+        if let Err(e) = resource::delete(&kayit_id) { // Varsayımsal delete API'sı
+            if e != SahneError::ResourceNotFound {
+                error!("Kurulu paket kaydı silinemedi ({}): {:?}", kayit_id, e);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+        Ok(())
+    }
+
+    fn listeyi_yaz(&self, adlar: &[String]) -> Result<(), PaketYoneticisiHatasi> {
+        let mut icerik = String::new();
+        for ad in adlar {
+            icerik.push_str(ad);
+            icerik.push('\n');
+        }
+        write_bytes_to_resource(&self.liste_resource_id, icerik.as_bytes())
+    }
+}
+
+
+// Helper fonksiyon: Sahne64 Kaynağından tüm içeriği Vec<u8> olarak oku.
+fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut buffer = Vec::new();
+    let mut temp_buffer = [0u8; 512]; // Stack buffer
+
+    loop {
+        match resource::read(handle, &mut temp_buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => buffer.extend_from_slice(&temp_buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    if let Err(e) = resource::release(handle) {
+        error!("Helper: Kaynak release hatası ({}): {:?}", resource_id, e);
+    }
+
+    Ok(buffer)
+}
+
+// Helper fonksiyon: Bayt içeriğini Sahne64 Kaynağına yazar (truncate ederek).
+fn write_bytes_to_resource(resource_id: &str, buffer: &[u8]) -> Result<(), PaketYoneticisiHatasi> {
+    let handle = resource::acquire(
+        resource_id,
+        resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+    ).map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut written = 0;
+    while written < buffer.len() {
+        match resource::write(handle, &buffer[written..]) {
+            Ok(0) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(SahneError::InvalidOperation));
+            }
+            Ok(bytes_written) => written += bytes_written,
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    if let Err(e) = resource::release(handle) {
+        error!("Helper: Kaynak release hatası ({}): {:?}", resource_id, e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kurulu_dosya_id() {
+        assert_eq!(kurulu_dosya_id("sahne://installed_packages/ls/", "bin/ls"), "sahne://installed_packages/ls/bin/ls");
+        assert_eq!(kurulu_dosya_id("sahne://installed_packages/ls", "/bin/ls"), "sahne://installed_packages/ls/bin/ls");
+    }
+
+    #[test]
+    fn test_stat_yanitini_coz() {
+        let mut yanit = Vec::new();
+        yanit.extend_from_slice(&42u64.to_le_bytes());
+        yanit.extend_from_slice(&0o120777u32.to_le_bytes());
+        yanit.extend_from_slice(&1000u32.to_le_bytes());
+        yanit.extend_from_slice(&100u32.to_le_bytes());
+
+        let bilgi = stat_yanitini_coz(&yanit).unwrap();
+        assert_eq!(bilgi.tur, GirdiTuru::SembolikBag);
+        assert_eq!(bilgi.boyut, 42);
+        assert_eq!(bilgi.mod_, 0o777);
+        assert_eq!((bilgi.sahip, bilgi.grup), (1000, 100));
+
+        assert!(stat_yanitini_coz(&yanit[..12]).is_none());
+    }
+
+    // Kaynak okuma/yazma testleri mock resource veya Sahne64 simülasyonu gerektirir.
+}
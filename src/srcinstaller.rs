@@ -6,6 +6,8 @@ use alloc::vec::Vec;
 use alloc::format; // Hata mesajları için
 use alloc::borrow::ToOwned; // &str -> String için
 
+use core::cmp::Ordering;
+
 // Paket struct tanımını içeren modül
 use crate::package::Paket;
 // Sahne64 resource modülü
@@ -18,7 +20,36 @@ use crate::paket_yoneticisi_hata::PaketYoneticisiHatasi;
 // SahneError'dan dönüşüm From implementasyonu ile sağlanacak
 
 // ZIP arşiv işlemleri modülü
-use crate::srcarchive;
+use crate::srcarchive::{self, ArsivSinirlari, CikarilanGirdi, GirdiTuru};
+// Kurulumdan önce disk alanı kontrolü
+use crate::srcdiskspace;
+// Arşivlerde izin verilen sıkıştırma algoritmaları
+use crate::srcfeatures::{Feature, FeatureSet, SecurityFeature};
+// Arşivin paket meta verisindeki özetle doğrulanması
+use crate::srcchecksum;
+// Akış sırasında özet hesaplayarak indirme
+use crate::srcnetwork;
+// Eşzamanlı paket indirme
+use crate::srcdownload::{IndirmeIstegi, IndirmeZamanlayici};
+// İşlem sonunda bir kez çalışan tetikleyiciler
+use crate::srctrigger::TetikleyiciKuyrugu;
+// Sürüm karşılaştırma ve bağımlılık kısıtları (sürüm düşürme için)
+use crate::srcversion::BagimlilikKisiti;
+// Kurulum betikleri
+use crate::srcscript;
+// Betiklerin paketin bildirdiği izinlerle çalıştırılması
+use crate::srcsandbox::SandboxPolitikasi;
+// Kurulum öncesi güvenlik açığı taraması ve politikası
+use crate::srcadvisory::AcikPolitikasi;
+// Kurulum öncesi lisans politikası denetimi
+use crate::srclicense::{self, LisansPolitikasi};
+// Kurulu paket kayıtları (doğrulama için)
+use crate::srcinstalled::{self, KuruluPaketKaydi, KuruluPaketVeritabani};
+// Arşivler açılmadan önce ayrık imzaların ve eşik kurallarının doğrulanması
+use crate::srcsecurity::SecurityManager;
+use crate::srcsigning::{self, AnahtarHalkasi, VARSAYILAN_ANAHTAR_HALKASI};
+use crate::srctrust::TrustManager;
+use crate::srcrepositoryrelease;
 
 // no_std uyumlu print makroları
 use crate::print_macros::{println, eprintln};
@@ -32,6 +63,16 @@ pub struct KurulumYoneticisi {
     pub kurulum_base_resource_id: String,
     // Önbellek temel Kaynak ID'si (örn. "sahne://cache/packages/") - İndirilen paketler buraya kaydedilecek
     pub onbellek_base_resource_id: String,
+    // Planlı kurulumlarda önbellekte olmayan paketleri eşzamanlı indiren zamanlayıcı
+    pub indirme_zamanlayici: IndirmeZamanlayici,
+    // Paket arşivlerinde izin verilen sıkıştırma algoritmaları
+    pub ozellikler: FeatureSet,
+    // Güvenilmeyen paket arşivleri açılırken uygulanan girdi sayısı, boyut, oran ve yol uzunluğu sınırları
+    pub arsiv_sinirlari: ArsivSinirlari,
+    // Planlar yerel güvenlik bildirimi veritabanına göre taranır; eşiğe ulaşan açıklar kurulumu engeller
+    pub acik_politikasi: AcikPolitikasi,
+    // Lisans politikası dosyasının Kaynak ID'si; dosya yoksa lisans denetimi yapılmaz
+    pub lisans_politikasi_kaynagi: String,
 }
 
 impl KurulumYoneticisi {
@@ -48,6 +89,11 @@ impl KurulumYoneticisi {
             paket_deposu_base_resource_id,
             kurulum_base_resource_id,
             onbellek_base_resource_id,
+            indirme_zamanlayici: IndirmeZamanlayici::varsayilan(),
+            ozellikler: FeatureSet::with_default_compression(),
+            arsiv_sinirlari: ArsivSinirlari::default(),
+            acik_politikasi: AcikPolitikasi::default(),
+            lisans_politikasi_kaynagi: srclicense::VARSAYILAN_LISANS_POLITIKASI.to_owned(),
         }
     }
 
@@ -65,68 +111,14 @@ impl KurulumYoneticisi {
 
             println!("Paket indirme başlatılıyor: {} -> {}", paket_kaynak_id, onbellek_hedef_id);
 
-            // Uzak Kaynağı oku (ağ resource tipi varsayımı)
-            // Sahne64 API'sında ağ iletişimi resource::acquire(URL, MODE_READ) ile mi yapılıyor?
-            // Veya özel bir ağ resource tipi mi var?
-            // Varsayım: paket_kaynak_id bir ağ Kaynağı ID'sidir ve resource::acquire ile Handle alınabilir.
-            let kaynak_handle = resource::acquire(&paket_kaynak_id, resource::MODE_READ)
-                .map_err(|e| {
-                     eprintln!("Paket Kaynağı acquire hatası ({}): {:?}", paket_kaynak_id, e);
-                    PaketYoneticisiHatasi::from(e) // SahneError -> PaketYoneticisiHatasi (Network hatası da olabilir SahneError içinde)
-                })?;
-
-            // Önbellekteki Hedef Kaynağı yaz (dosya sistemi benzeri resource tipi)
-            // MODE_CREATE varsa Kaynak yoksa oluşturur, MODE_TRUNCATE varsa içeriği siler.
             // onbellek_hedef_id'nin parent resource'larının oluşturulması gerekebilir.
             // srcconfig.rs'deki sahne_create_resource_recursive helper'ı burada kullanılabilir.
             // Veya resource::acquire(..., MODE_CREATE) parent'ları otomatik oluşturur varsayılır.
-            // Şimdilik otomatik oluştuğunu varsayalım veya helper'ı kullanmayalım.
               let _ = super::sahne_create_resource_recursive(&onbellek_hedef_id)?; // Eğer helper varsa
 
-            let hedef_handle = resource::acquire(
-                &onbellek_hedef_id,
-                resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
-            ).map_err(|e| {
-                 eprintln!("Önbellek Hedef Kaynağı acquire hatası ({}): {:?}", onbellek_hedef_id, e);
-                PaketYoneticisiHatasi::from(e) // SahneError -> PaketYoneticisiHatasi
-            })?;
-
-            // Kaynaktan oku ve hedefe yaz döngüsü
-            let mut buffer = [0u8; 4096]; // Okuma/yazma buffer'ı (stack'te)
-            loop {
-                // Kaynak Kaynağından oku
-                match resource::read(kaynak_handle, &mut buffer) {
-                    Ok(0) => break, // Kaynak sonu (indirme tamamlandı)
-                    Ok(bytes_read) => {
-                        // Hedef Kaynağa yaz
-                        match resource::write(hedef_handle, &buffer[..bytes_read]) {
-                            Ok(_) => {
-                                // Başarılı yazma
-                            }
-                            Err(e) => {
-                                eprintln!("Önbellek Hedef Kaynağı yazma hatası ({}): {:?}", onbellek_hedef_id, e);
-                                let _ = resource::release(kaynak_handle); // Handle'ları temizle
-                                let _ = resource::release(hedef_handle);
-                                return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Paket Kaynağı okuma hatası ({}): {:?}", paket_kaynak_id, e);
-                        let _ = resource::release(kaynak_handle); // Handle'ları temizle
-                        let _ = resource::release(hedef_handle);
-                        return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
-                    }
-                }
-            }
-
-            // Handle'ları serbest bırak
-            let release_kaynak_result = resource::release(kaynak_handle);
-             if let Err(e) = release_kaynak_result { eprintln!("Paket Kaynağı release hatası ({}): {:?}", paket_kaynak_id, e); } // Logla
-
-            let release_hedef_result = resource::release(hedef_handle);
-             if let Err(e) = release_hedef_result { eprintln!("Önbellek Hedef Kaynağı release hatası ({}): {:?}", onbellek_hedef_id, e); } // Logla
-
+            // Boyut ve en güçlü özet baytlar akarken doğrulanır; doğrulanamayan dosya önbellekte bırakılmaz.
+            let algoritma = srcnetwork::paketi_dogrulayarak_indir(&paket_kaynak_id, &onbellek_hedef_id, paket, self.eski_md5_izinli())?;
+            println!("Paket özeti indirme sırasında doğrulandı ({}).", algoritma.ad());
 
             println!("Paket indirildi ve önbelleğe kaydedildi: {}", onbellek_hedef_id);
             Ok(())
@@ -141,6 +133,14 @@ impl KurulumYoneticisi {
     // paket: Kurulacak paketin meta verisi.
     // Dönüş değeri: Başarı veya PaketYoneticisiHatasi.
     pub fn paketi_kur(&self, paket: &Paket) -> Result<(), PaketYoneticisiHatasi> { // Result türü PaketYoneticisiHatasi olmalı
+        self.imzalari_dogrula(core::slice::from_ref(paket))?;
+        self.arsivi_kur(paket, true).map(|_| ())
+    }
+
+    // Paketi önbellekten kurar.
+    // ozeti_dogrula: Arşiv bu işlemde indirilirken zaten doğrulandıysa false verilir ve ikinci okuma yapılmaz.
+    // Dönüş değeri: Arşivden gerçekten çıkarılan dizin dışı girdilerin kurulum dizinine göre yolları (tetikleyiciler için).
+    fn arsivi_kur(&self, paket: &Paket, ozeti_dogrula: bool) -> Result<Vec<String>, PaketYoneticisiHatasi> {
         // Paketin dosya adını al
         if let Some(dosya_adi) = &paket.dosya_adi { // dosya_adi Option<String>
             // Önbellekteki paket dosyasının kaynak ID'sini oluştur (örn. "sahne://cache/packages/my_package.zip")
@@ -156,12 +156,19 @@ impl KurulumYoneticisi {
             println!("Paket önbellek yolu: {}", onbellek_paket_id);
             println!("Kurulum hedef yolu: {}", kurulum_hedef_base_id);
 
-            // Zip arşivini önbellek konumundan kurulum hedef dizinine çıkar (srcarchive modülü kullanılarak)
-             srcarchive::zip_ac(arsiv_resource_id: &str, cikartma_base_resource_id: &str)
-            match srcarchive::zip_ac(&onbellek_paket_id, &kurulum_hedef_base_id) {
-                Ok(_) => {
+            // Önbellekte önceden bulunan arşivi açmadan önce meta verideki en güçlü özetle doğrula
+            if ozeti_dogrula {
+                let algoritma = srcchecksum::paket_ozetini_dogrula(&onbellek_paket_id, &paket.checksums, self.eski_md5_izinli())?;
+                println!("Paket özeti doğrulandı ({}): {}", algoritma.ad(), onbellek_paket_id);
+            }
+
+            // Arşivi (zip veya sıkıştırılmış tar, biçim içerikten algılanır) kurulum hedef dizinine çıkar
+            match srcarchive::arsiv_ac(&onbellek_paket_id, &kurulum_hedef_base_id, &self.ozellikler, &self.arsiv_sinirlari) {
+                Ok(girdiler) => {
                     println!("Paket içeriği çıkarıldı ve kuruldu: {:?}", paket.ad);
-                    Ok(())
+                    // Doğrulama (srcverify.rs) ve kaldırma için kurulan girdilerin tür, özet, boyut, mod ve bağlarını kaydet.
+                    self.kurulum_kaydini_yaz(paket, &girdiler, &kurulum_hedef_base_id)?;
+                    Ok(girdiler.into_iter().filter(|g| g.tur != GirdiTuru::Dizin).map(|g| g.yol).collect())
                 }
                 Err(e) => {
                     eprintln!("Paket içeriği çıkarma/kurulum hatası (Kaynak: {}): {:?}", onbellek_paket_id, e);
@@ -176,40 +183,368 @@ impl KurulumYoneticisi {
         }
     }
 
-     // Paketi kaldırma fonksiyonu (Eksik fonksiyonellik: resource silme)
-     // package_name: Kaldırılacak paketin adı.
-     // Dönüş değeri: Başarı veya PaketYoneticisiHatasi.
-      pub fn paketi_kaldir(&self, paket_adi: &str) -> Result<(), PaketYoneticisiHatasi> {
-          println!("Paket kaldırma başlatılıyor: {}", paket_adi);
-     //     // Kurulu paketlerin dosyalarının bulunduğu temel Kaynak ID'sini oluştur (örn. "sahne://installed_packages/my_package/")
-          let kurulum_paket_base_id = format!("{}/{}/", self.kurulum_base_resource_id, paket_adi); // format! alloc gerektirir
-     //
-     //     // Kurulu dosyaları silme mantığı burada olacak.
-     //     // Bu, resource::delete veya resource::control kullanımı gerektirir (Sahne64 API'sında eksik).
-     //     // Ayrıca, silinecek dosyaların listesi bilinmelidir (paket veritabanından veya paket meta verilerinden).
-     //
-          eprintln!("UYARI: Sahne64 API'sında resource silme mekanizması bulunmamaktadır. Paket kaldırma tamamlanmamıştır.");
-          eprintln!("Silinmek istenen temel Kaynak ID: {}", kurulum_paket_base_id);
-     //
-     //     // Placeholder: resource::delete(kurulum_paket_base_id)? gibi bir çağrı yapılacak.
-     //
-     //     // Eğer kaldırma scripti varsa, onu çalıştır (task::spawn).
-           const PACKAGE_SCRIPT_BASE_RESOURCE: &str = "sahne://system/package_scripts/"; // srccli.rs'den hatırlatma
-           let uninstall_script_resource_id = format!("{}{}/uninstall.sh", PACKAGE_SCRIPT_BASE_RESOURCE, paket_adi);
-           match resource::acquire(&uninstall_script_resource_id, resource::MODE_READ) {
-                Ok(script_handle) => {
-                    let _ = task::spawn(script_handle, b"");
-                    let _ = resource::release(script_handle);
+    // Bir paket listesini tek bir plan olarak kurar: önce güvenlik açığı taraması, lisans denetimi ve alan kontrolü,
+    // sonra indirme ve imza doğrulaması, sonra kurulum (her paketin kurulum betiği arşivi çıkarıldıktan hemen sonra,
+    // paketin bildirdiği betik izinleriyle çalışır), en son tetikleyiciler.
+    // Kurulum yarıda kalırsa kurulabilen paketlerin tetikleyicileri yine çalıştırılır, sonra kurulum hatası döner.
+    // Politika eşiğine ulaşan açık varsa GuvenlikAcigiEngeli, izin verilmeyen lisans varsa LisansPolitikasiIhlali,
+    // alan yetmiyorsa YetersizDiskAlani hatası; bu durumlarda hiçbir indirme veya çıkarma başlamaz.
+    // paketler: Kurulum sırasına göre dizilmiş paketler (bağımlılık çözümlemesinden gelir).
+    pub fn plani_uygula(&self, paketler: &[Paket]) -> Result<(), PaketYoneticisiHatasi> {
+        for bulgu in self.acik_politikasi.plani_denetle(paketler)? {
+            println!("Uyarı: güvenlik açığı: {}", bulgu);
+        }
+        if let Some(politika) = LisansPolitikasi::varsa_yukle(&self.lisans_politikasi_kaynagi)? {
+            politika.plani_denetle(paketler)?;
+        }
+
+        srcdiskspace::alan_kontrolu(
+            paketler,
+            &self.onbellek_base_resource_id,
+            &self.kurulum_base_resource_id,
+            |paket| self.onbellekte_mi(paket),
+        )?;
+
+        let indirilenler = self.eksikleri_indir(paketler)?;
+        // Hiçbir arşiv, imzaları ve eşik kuralları doğrulanmadan açılmaz.
+        self.imzalari_dogrula(paketler)?;
+
+        // Tetikleyiciler kurulu paketlerden ve bu plandaki paketlerden toplanır,
+        // tüm paketler kurulduktan sonra her biri bir kez çalıştırılır.
+        let mut tetikleyiciler = TetikleyiciKuyrugu::yeni();
+        tetikleyiciler.kurulu_paketlerden_yukle(&KuruluPaketVeritabani::yeni())?;
+        for paket in paketler {
+            tetikleyiciler.tanimlari_ekle(paket);
+        }
+
+        let mut kurulum_hatasi = None;
+        for (sira, paket) in paketler.iter().enumerate() {
+            // Bu planda indirilen arşivler indirme sırasında doğrulandı.
+            let yollar = match self.arsivi_kur(paket, !indirilenler.contains(&paket.ad)) {
+                Ok(yollar) => yollar,
+                Err(e) => {
+                    kurulmayanlarin_tetikleyicilerini_birak(&mut tetikleyiciler, &paketler[sira..]);
+                    kurulum_hatasi = Some(e);
+                    break;
+                }
+            };
+            // Dosyalar kuruldu; betik başarısız olsa da tetikleyiciler bu dosyalar için çalışmalıdır.
+            tetikleyiciler.dosyalar_degisti(&paket.ad, &yollar);
+            if let Err(e) = self.kurulum_betigini_calistir(paket) {
+                // Bu paketin dosyaları kuruldu; kalan paketlere hiç geçilmeyecek.
+                kurulmayanlarin_tetikleyicilerini_birak(&mut tetikleyiciler, &paketler[sira + 1..]);
+                kurulum_hatasi = Some(e);
+                break;
+            }
+        }
+
+        let tetikleyici_sonucu = tetikleyiciler.calistir();
+        match kurulum_hatasi {
+            Some(e) => {
+                if let Err(tetikleyici_hatasi) = tetikleyici_sonucu {
+                    eprintln!("Kurulum yarıda kaldı, tetikleyiciler de başarısız oldu: {}", tetikleyici_hatasi);
+                }
+                Err(e)
+            }
+            None => tetikleyici_sonucu,
+        }
+    }
+
+    // Kurulu bir paketi aynı sürümle yeniden kurar: arşiv yeniden çıkarılır, kayıt yenilenir,
+    // tetikleyiciler ve paketin kurulum betiği yeniden çalıştırılır.
+    pub fn yeniden_kur(&self, paket_adi: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let kayit = KuruluPaketVeritabani::yeni().kaydi_yukle(paket_adi)?
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(paket_adi.to_owned()))?;
+
+        println!("Paket yeniden kuruluyor: {} {}", kayit.paket.ad, kayit.paket.surum);
+        self.plani_uygula(core::slice::from_ref(&kayit.paket))
+    }
+
+    // Kurulu bir paketi daha eski bir sürüme düşürür.
+    // hedef: Depodan alınan eski sürümün meta verisi.
+    // izin_verildi: Sürüm düşürme açıkça istenmeli (CLI'de --onayla bayrağı); aksi halde reddedilir.
+    // Kurulu paketlerden biri daha yeni sürümü gerektiriyorsa (bağımlılık kısıtı hedef sürümü sağlamıyorsa) reddedilir.
+    // Hedefin kendi bağımlılıkları kurulu sürümlerle karşılanmıyorsa (eksik ya da kısıt dışı) da reddedilir.
+    pub fn surum_dusur(&self, hedef: &Paket, izin_verildi: bool) -> Result<(), PaketYoneticisiHatasi> {
+        if !izin_verildi {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!(
+                "'{}' paketinin sürümünü düşürmek için açık onay (--onayla) gerekli.", hedef.ad
+            )));
+        }
+
+        let veritabani = KuruluPaketVeritabani::yeni();
+        let mevcut = veritabani.kaydi_yukle(&hedef.ad)?
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(hedef.ad.clone()))?;
+
+        if mevcut.paket.surum_karsilastir(&hedef.surum) != Ordering::Greater {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!(
+                "'{}' {} kurulu sürümden ({}) eski değil.", hedef.ad, hedef.surum, mevcut.paket.surum
+            )));
+        }
+
+        // İleri bağımlılıklar: yalnızca hedef paket kurulacağından, hedefin bağımlılıkları kurulu sürümlerle karşılanmalı.
+        let mut karsilanmayanlar = Vec::new();
+        for girdi in &hedef.bagimliliklar {
+            let kisit = BagimlilikKisiti::ayristir(girdi)?;
+            match veritabani.kaydi_yukle(&kisit.ad)? {
+                Some(kurulu) if kisit.saglanir_mi(&kurulu.paket.surum) => {}
+                Some(kurulu) => karsilanmayanlar.push(format!("{} (kurulu: {})", kisit, kurulu.paket.surum)),
+                None => karsilanmayanlar.push(format!("{} (kurulu değil)", kisit)),
+            }
+        }
+        if !karsilanmayanlar.is_empty() {
+            return Err(PaketYoneticisiHatasi::ConflictError(format!(
+                "'{}' {} sürümüne düşürülemez, bağımlılıkları kurulu paketlerle karşılanmıyor: {}",
+                hedef.ad, hedef.surum, karsilanmayanlar.join(", ")
+            )));
+        }
+
+        // Ters bağımlılıklar: hedef paketi kısıtla gerektiren kurulu paketler.
+        let mut engelleyenler = Vec::new();
+        for kayit in veritabani.tum_kayitlar()? {
+            if kayit.paket.ad == hedef.ad {
+                continue;
+            }
+            for girdi in &kayit.paket.bagimliliklar {
+                // Ayrıştırılamayan bir bağımlılık başka paketlerin sürüm düşürmesini engellememeli
+                let kisit = match BagimlilikKisiti::ayristir(girdi) {
+                    Ok(kisit) => kisit,
+                    Err(e) => {
+                        eprintln!("{} bağımlılığı ayrıştırılamadı, atlanıyor: {}", kayit.paket.ad, e); // no_std print
+                        continue;
+                    }
+                };
+                if kisit.ad == hedef.ad && !kisit.saglanir_mi(&hedef.surum) {
+                    engelleyenler.push(format!("{} {} ({})", kayit.paket.ad, kayit.paket.surum, kisit));
                 }
-                Err(SahneError::ResourceNotFound) => { /* Script yoksa devam et */ }
-                Err(e) => { /* Hata yönetimi */ }
-           }
-     //
-     //     // Paket veritabanı kaydını silme (paketi kurulu listesinden çıkarma).
-     //     // Bu da paket veritabanını yöneten modül tarafından yapılmalıdır.
-     
-          Err(PaketYoneticisiHatasi::SahneApiHatasi(SahneError::NotSupported)) // Silme desteği yok hatası
-      }
+            }
+        }
+        if !engelleyenler.is_empty() {
+            return Err(PaketYoneticisiHatasi::ConflictError(format!(
+                "'{}' {} sürümüne düşürülemez, kurulu paketler daha yeni sürüm gerektiriyor: {}",
+                hedef.ad, hedef.surum, engelleyenler.join(", ")
+            )));
+        }
+
+        println!("Paket sürümü düşürülüyor: {} {} -> {}", hedef.ad, mevcut.paket.surum, hedef.surum);
+        self.plani_uygula(core::slice::from_ref(hedef))?;
+
+        // Eski sürümde olup yeni sürümde olmayan dosyaları kaldır.
+        let yeni_kayit = veritabani.kaydi_yukle(&hedef.ad)?
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(hedef.ad.clone()))?;
+        let kurulum_dizini = format!("{}/{}/", self.kurulum_base_resource_id, hedef.ad);
+        for dosya in &mevcut.dosyalar {
+            if yeni_kayit.dosyalar.iter().any(|d| d.yol == dosya.yol) {
+                continue;
+            }
+            let dosya_id = srcinstalled::kurulu_dosya_id(&kurulum_dizini, &dosya.yol);
+            if let Err(e) = resource::delete(&dosya_id) {
+                eprintln!("Eski sürüm dosyası silinemedi ({}): {:?}", dosya_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    // Önbellekteki arşivleri depodaki ayrık imzalarıyla ("<dosya adı>.sig") doğrular.
+    // Her paket için imzalayan anahtarların kapsamı paketi bu depodan imzalamaya izin vermeli ve pakete uygulanan
+    // eşik kuralları (M/N farklı anahtar) sağlanmalıdır; kural yoksa tek geçerli imza yeterlidir.
+    // Depo adı, imzalı depo bildiriminden alınır (kapsamlar bu adla eşleşir).
+    fn imzalari_dogrula(&self, paketler: &[Paket]) -> Result<(), PaketYoneticisiHatasi> {
+        if paketler.is_empty() {
+            return Ok(());
+        }
+        let halka = AnahtarHalkasi::kaynaktan_yukle(VARSAYILAN_ANAHTAR_HALKASI).map_err(|e| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Anahtar halkası yüklenemedi ({}): {}", VARSAYILAN_ANAHTAR_HALKASI, e))
+        })?;
+        let guven = TrustManager::varsayilan();
+        let depo = srcrepositoryrelease::bildirimi_yukle(&self.paket_deposu_base_resource_id, &halka, guven.key_scopes())?
+            .bildirim.depo;
+        let guvenlik = SecurityManager::new(halka);
+
+        for paket in paketler {
+            let dosya_adi = paket.dosya_adi.as_ref().ok_or_else(|| {
+                PaketYoneticisiHatasi::InvalidParameter(format!("Paket '{}' için dosya adı belirtilmemiş.", paket.ad))
+            })?;
+            let arsiv_id = format!("{}/{}", self.onbellek_base_resource_id, dosya_adi);
+            let imza_id = format!("{}/{}{}", self.paket_deposu_base_resource_id, dosya_adi, srcsigning::PAKET_IMZA_UZANTISI);
+            let rapor = guvenlik.verify_threshold(&arsiv_id, &[imza_id.as_str()], &paket.ad, &depo, &guven)
+                .map_err(|e| PaketYoneticisiHatasi::PaketImzasiReddedildi { paket: paket.ad.clone(), neden: e.to_string() })?;
+            println!("Paket imzaları doğrulandı: {} ({} geçerli imzalayan, {} eşik kuralı)",
+                     paket.ad, rapor.gecerli_imzalayanlar.len(), rapor.kurallar.len());
+        }
+        Ok(())
+    }
+
+    // Paketin kurulum betiği varsa paketin betik izinlerinden türetilen politikayla çalıştırır.
+    // Betik paketin kendi betik dizininde olmalıdır.
+    fn kurulum_betigini_calistir(&self, paket: &Paket) -> Result<(), PaketYoneticisiHatasi> {
+        match &paket.kurulum_scripti {
+            Some(betik) => {
+                if !srcscript::paketin_betigi_mi(&paket.ad, betik) {
+                    return Err(PaketYoneticisiHatasi::BetikCalistirmaHatasi(format!(
+                        "'{}' paketinin kurulum betiği kendi betik dizininde değil: {}", paket.ad, betik
+                    )));
+                }
+                let politika = SandboxPolitikasi::betik_icin(&paket.betik_izinleri, betik);
+                srcscript::betik_calistir(betik, paket.ad.as_bytes(), &politika)
+            }
+            None => Ok(()),
+        }
+    }
+
+    // Önbellekte olmayan paketleri zamanlayıcı ile eşzamanlı indirir.
+    // Her paketin boyutu ve en güçlü özeti indirme sırasında doğrulanır.
+    // Her paket bağımsız olarak yeniden denenir; denemeleri tükenen paketler tek bir hatada listelenir.
+    // Dönüş değeri: İndirilip doğrulanan paketlerin adları.
+    fn eksikleri_indir(&self, paketler: &[Paket]) -> Result<Vec<String>, PaketYoneticisiHatasi> {
+        let mut istekler = Vec::new();
+        for paket in paketler {
+            if self.onbellekte_mi(paket) {
+                continue;
+            }
+            let dosya_adi = paket.dosya_adi.as_ref().ok_or_else(|| {
+                PaketYoneticisiHatasi::InvalidParameter(format!("Paket '{}' için dosya adı belirtilmemiş.", paket.ad))
+            })?;
+            // Güvenilir özeti olmayan paket için indirme hiç başlatılmaz.
+            let (algoritma, beklenen) = srcchecksum::dogrulama_ozetini_sec(&paket.checksums, self.eski_md5_izinli())?;
+            istekler.push(IndirmeIstegi {
+                paket_adi: paket.ad.clone(),
+                kaynak_url: format!("{}/{}", self.paket_deposu_base_resource_id, dosya_adi),
+                hedef_resource_id: format!("{}/{}", self.onbellek_base_resource_id, dosya_adi),
+                beklenen_boyut: paket.indirme_boyutu,
+                beklenen_ozet: Some((algoritma, beklenen.to_owned())),
+            });
+        }
+
+        let mut indirilenler = Vec::new();
+        let mut basarisizlar = Vec::new();
+        for sonuc in self.indirme_zamanlayici.indir(istekler) {
+            match sonuc.sonuc {
+                Ok(()) => indirilenler.push(sonuc.paket_adi),
+                Err(e) => {
+                    eprintln!("Paket indirilemedi ({}, {} deneme): {:?}", sonuc.paket_adi, sonuc.deneme_sayisi, e);
+                    basarisizlar.push(sonuc.paket_adi);
+                }
+            }
+        }
+
+        if basarisizlar.is_empty() {
+            Ok(indirilenler)
+        } else {
+            Err(PaketYoneticisiHatasi::NetworkError(format!(
+                "Paketler indirilemedi: {}", basarisizlar.join(", ")
+            )))
+        }
+    }
+
+    // Yalnızca MD5 özeti olan paket meta verisi kabul edilsin mi ('insecure_legacy_checksums' özelliği)?
+    fn eski_md5_izinli(&self) -> bool {
+        self.ozellikler.is_enabled(&Feature::Security(SecurityFeature::InsecureLegacyChecksums))
+    }
+
+    // Paketin arşivi önbellekte zaten var mı?
+    fn onbellekte_mi(&self, paket: &Paket) -> bool {
+        let dosya_adi = match &paket.dosya_adi {
+            Some(dosya_adi) => dosya_adi,
+            None => return false,
+        };
+        let onbellek_id = format!("{}/{}", self.onbellek_base_resource_id, dosya_adi);
+        match resource::acquire(&onbellek_id, resource::MODE_READ) {
+            Ok(handle) => {
+                let _ = resource::release(handle);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // Kurulan paketin dosya kayıtlarını oluşturup kurulu paket veritabanına yazar.
+    // Girdiler arşiv çıkarmasının döndürdüğü listedir; dizinler de kaldırma için kaydedilir.
+    fn kurulum_kaydini_yaz(&self, paket: &Paket, girdiler: &[CikarilanGirdi], kurulum_dizini: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let mut dosyalar = Vec::new();
+        for girdi in girdiler {
+            dosyalar.push(srcinstalled::dosya_kaydi_olustur(kurulum_dizini, girdi)?);
+        }
+
+        let kayit = KuruluPaketKaydi { paket: paket.clone(), dosyalar };
+        KuruluPaketVeritabani::yeni().kaydet(&kayit)
+    }
+
+    // Paketi kurulu dosya kaydına göre kaldırır.
+    // Önce kaldırma betiği çalıştırılır, sonra dosyalar ve bağlar, en son (en derinden başlayarak) dizinler silinir.
+    // Sembolik bağların kendisi silinir, hedeflerine dokunulmaz. Boş olmayan dizinler (başka dosya içeren) bırakılır.
+    // Kayıt silindikten sonra kalan kurulu paketlerin tetikleyicileri kaldırılan dosyalar için çalıştırılır.
+    pub fn paketi_kaldir(&self, paket_adi: &str) -> Result<(), PaketYoneticisiHatasi> {
+        println!("Paket kaldırma başlatılıyor: {}", paket_adi);
+        let veritabani = KuruluPaketVeritabani::yeni();
+        let kayit = veritabani.kaydi_yukle(paket_adi)?
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(paket_adi.to_owned()))?;
+        let kurulum_paket_base_id = format!("{}/{}/", self.kurulum_base_resource_id, paket_adi);
+
+        // Paket bir kaldırma betiği bildirdiyse, dosyalar silinmeden önce paketin betik izinleriyle çalıştır.
+        // Betik paketin kendi betik dizininde değilse hiçbir şey silinmeden reddedilir (bkz. kurulum_betigini_calistir).
+        if let Some(betik) = &kayit.paket.kaldirma_scripti {
+            if !srcscript::paketin_betigi_mi(&kayit.paket.ad, betik) {
+                return Err(PaketYoneticisiHatasi::BetikCalistirmaHatasi(format!(
+                    "'{}' paketinin kaldırma betiği kendi betik dizininde değil: {}", kayit.paket.ad, betik
+                )));
+            }
+            let politika = SandboxPolitikasi::betik_icin(&kayit.paket.betik_izinleri, betik);
+            match srcscript::betik_calistir(betik, kayit.paket.ad.as_bytes(), &politika) {
+                Ok(()) => {}
+                Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => { /* Betik yoksa devam et */ }
+                Err(e) => eprintln!("Kaldırma betiği çalıştırılamadı ({}): {}", betik, e),
+            }
+        }
+
+        let (mut dizinler, dosyalar): (Vec<_>, Vec<_>) = kayit.dosyalar.iter()
+            .partition(|dosya| dosya.tur == GirdiTuru::Dizin);
+        // Varsayım: resource::delete sembolik bağı izlemez, bağın kendisini siler.
+        for dosya in dosyalar {
+            let dosya_id = srcinstalled::kurulu_dosya_id(&kurulum_paket_base_id, &dosya.yol);
+            match resource::delete(&dosya_id) {
+                Ok(_) | Err(SahneError::ResourceNotFound) => {}
+                Err(e) => {
+                    eprintln!("Dosya silinemedi ({}): {:?}", dosya_id, e);
+                    return Err(PaketYoneticisiHatasi::from(e));
+                }
+            }
+        }
+
+        dizinler.sort_by(|a, b| b.yol.len().cmp(&a.yol.len()));
+        for dizin in dizinler {
+            let dizin_id = srcinstalled::kurulu_dosya_id(&kurulum_paket_base_id, dizin.yol.trim_end_matches('/'));
+            if let Err(e) = resource::delete(&dizin_id) {
+                if e != SahneError::ResourceNotFound {
+                    eprintln!("Uyarı: Dizin bırakıldı ({}): {:?}", dizin_id, e);
+                }
+            }
+        }
+
+        veritabani.kaydi_sil(paket_adi)?;
+        println!("Paket kaldırıldı: {} {}", kayit.paket.ad, kayit.paket.surum);
+
+        // Kaldırılan paketin kendi tetikleyicileri, kaydı silindiği için yüklenmez.
+        let kaldirilan_yollar: Vec<String> = kayit.dosyalar.iter()
+            .filter(|dosya| dosya.tur != GirdiTuru::Dizin)
+            .map(|dosya| dosya.yol.clone())
+            .collect();
+        let mut tetikleyiciler = TetikleyiciKuyrugu::yeni();
+        tetikleyiciler.kurulu_paketlerden_yukle(&veritabani)?;
+        tetikleyiciler.dosyalar_degisti(paket_adi, &kaldirilan_yollar);
+        tetikleyiciler.calistir()
+    }
+}
+
+// Plan yarıda kaldığında kurulamayan (ve daha önce de kurulu olmayan) paketlerin tetikleyicileri çalışmaz;
+// aksi halde kurulmamış bir paketin betiği önceki paketlerin dosyaları için çalışırdı.
+fn kurulmayanlarin_tetikleyicilerini_birak(tetikleyiciler: &mut TetikleyiciKuyrugu, kalanlar: &[Paket]) {
+    let veritabani = KuruluPaketVeritabani::yeni();
+    for kurulmayan in kalanlar {
+        if matches!(veritabani.kaydi_yukle(&kurulmayan.ad), Ok(None)) {
+            tetikleyiciler.saglayanin_bekleyenlerini_birak(&kurulmayan.ad);
+        }
+    }
 }
 
 // --- Paket struct tanımı (crate::package modülünde olmalı) ---
@@ -0,0 +1,539 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, Box, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+use alloc::collections::BTreeMap; // Lisans özeti (deterministik sıra)
+
+use serde::{Deserialize, Serialize};
+use serde_json; // Politika dosyası JSON biçiminde (alloc özellikli no_std backend varsayımıyla)
+
+// Paket meta verisi (Paket.lisans)
+use crate::package::Paket;
+// Politika listelerinde '*' ve '?' jokerleri
+use crate::srctrust::desen_eslesir;
+
+// Sahne64 API modülleri
+use crate::resource;
+use crate::SahneError;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, error, debug};
+
+
+// Lisans politikası dosyası. Yoksa lisans denetimi yapılmaz.
+//
+// Biçim (JSON):
+// {
+//   "izinli": ["MIT", "Apache-2.0", "BSD-*-Clause", "GPL-2.0-only WITH Classpath-exception-2.0"],
+//   "yasak": ["AGPL-*", "SSPL-1.0"],
+//   "inceleme": ["GPL-*", "LGPL-*"],
+//   "bilinmeyen": "inceleme",                  // hiçbir listede olmayan lisanslar: izinli | inceleme | yasak
+//   "onaylanan_paketler": ["ffmpeg@6.1", "readline"]  // incelemesi yapılmış paketler ("ad" veya "ad@sürüm")
+// }
+//
+// Listeler SPDX kimlik desenleridir ve büyük/küçük harf duyarsızdır. Bir lisans birden çok listeye uyuyorsa
+// en katı karar geçerlidir (yasak > inceleme > izinli). "X WITH Y" ve "X+" önce tam haliyle, sonra yalın "X" olarak aranır.
+pub const VARSAYILAN_LISANS_POLITIKASI: &str = "sahne://config/license_policy.json";
+
+
+// SPDX lisans ifadesi (SPDX 2.3, Ek D). Öncelik: WITH > AND > OR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LisansIfadesi {
+    Lisans {
+        kimlik: String, // Örn. "MIT", "LicenseRef-sahne-ozel"
+        sonrasi: bool, // "+" soneki: bu sürüm veya sonrası
+        istisna: Option<String>, // "WITH" istisnası
+    },
+    Ve(Box<LisansIfadesi>, Box<LisansIfadesi>),
+    Veya(Box<LisansIfadesi>, Box<LisansIfadesi>),
+}
+
+impl LisansIfadesi {
+    pub fn ayristir(girdi: &str) -> Result<Self, PaketYoneticisiHatasi> {
+        let mut ayristirici = Ayristirici { belirtecler: belirteclere_ayir(girdi), konum: 0 };
+        let sonuc = ayristirici.veya().and_then(|ifade| match ayristirici.siradaki() {
+            None => Ok(ifade),
+            Some(fazla) => Err(format!("beklenmeyen '{}'", fazla)),
+        });
+        sonuc.map_err(|neden| {
+            PaketYoneticisiHatasi::ParsingError(format!("Geçersiz SPDX lisans ifadesi '{}': {}", girdi.trim(), neden))
+        })
+    }
+
+    // İfadedeki lisans kimlikleri (tekrarsız, ilk görülme sırasıyla).
+    pub fn kimlikler(&self) -> Vec<&str> {
+        let mut kimlikler = Vec::new();
+        self.kimlikleri_topla(&mut kimlikler);
+        kimlikler
+    }
+
+    fn kimlikleri_topla<'a>(&'a self, kimlikler: &mut Vec<&'a str>) {
+        match self {
+            LisansIfadesi::Lisans { kimlik, .. } => {
+                if !kimlikler.contains(&kimlik.as_str()) {
+                    kimlikler.push(kimlik);
+                }
+            }
+            LisansIfadesi::Ve(sol, sag) | LisansIfadesi::Veya(sol, sag) => {
+                sol.kimlikleri_topla(kimlikler);
+                sag.kimlikleri_topla(kimlikler);
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for LisansIfadesi {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LisansIfadesi::Lisans { kimlik, sonrasi, istisna } => {
+                write!(f, "{}{}", kimlik, if *sonrasi { "+" } else { "" })?;
+                if let Some(istisna) = istisna {
+                    write!(f, " WITH {}", istisna)?;
+                }
+                Ok(())
+            }
+            LisansIfadesi::Ve(sol, sag) => {
+                // OR, AND'den zayıf bağlandığı için AND içinde parantez gerekir
+                for (i, taraf) in [sol, sag].iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " AND ")?;
+                    }
+                    match taraf.as_ref() {
+                        LisansIfadesi::Veya(..) => write!(f, "({})", taraf)?,
+                        _ => write!(f, "{}", taraf)?,
+                    }
+                }
+                Ok(())
+            }
+            LisansIfadesi::Veya(sol, sag) => write!(f, "{} OR {}", sol, sag),
+        }
+    }
+}
+
+// Parantezleri ayrı belirteç yapar, boşluklarda böler.
+fn belirteclere_ayir(girdi: &str) -> Vec<&str> {
+    let mut belirtecler = Vec::new();
+    let mut baslangic = None;
+    for (i, c) in girdi.char_indices() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if let Some(b) = baslangic.take() {
+                belirtecler.push(&girdi[b..i]);
+            }
+            if c != ' ' && !c.is_whitespace() {
+                belirtecler.push(&girdi[i..i + 1]);
+            }
+        } else if baslangic.is_none() {
+            baslangic = Some(i);
+        }
+    }
+    if let Some(b) = baslangic {
+        belirtecler.push(&girdi[b..]);
+    }
+    belirtecler
+}
+
+struct Ayristirici<'a> {
+    belirtecler: Vec<&'a str>,
+    konum: usize,
+}
+
+impl<'a> Ayristirici<'a> {
+    fn siradaki(&self) -> Option<&'a str> {
+        self.belirtecler.get(self.konum).copied()
+    }
+
+    // SPDX işleçleri tamamen büyük veya tamamen küçük harf olabilir.
+    fn islec_mi(&self, islec: &str) -> bool {
+        self.siradaki().map_or(false, |b| b == islec || b == islec.to_lowercase())
+    }
+
+    fn veya(&mut self) -> Result<LisansIfadesi, String> {
+        let mut sol = self.ve()?;
+        while self.islec_mi("OR") {
+            self.konum += 1;
+            sol = LisansIfadesi::Veya(Box::new(sol), Box::new(self.ve()?));
+        }
+        Ok(sol)
+    }
+
+    fn ve(&mut self) -> Result<LisansIfadesi, String> {
+        let mut sol = self.birim()?;
+        while self.islec_mi("AND") {
+            self.konum += 1;
+            sol = LisansIfadesi::Ve(Box::new(sol), Box::new(self.birim()?));
+        }
+        Ok(sol)
+    }
+
+    fn birim(&mut self) -> Result<LisansIfadesi, String> {
+        let belirtec = self.siradaki().ok_or_else(|| String::from("ifade eksik"))?;
+        self.konum += 1;
+        if belirtec == "(" {
+            let ic = self.veya()?;
+            if self.siradaki() != Some(")") {
+                return Err(String::from("kapanmayan parantez"));
+            }
+            self.konum += 1;
+            return Ok(ic);
+        }
+        if belirtec == ")" || ["AND", "OR", "WITH"].iter().any(|islec| belirtec.eq_ignore_ascii_case(islec)) {
+            return Err(format!("lisans kimliği bekleniyordu, '{}' bulundu", belirtec));
+        }
+
+        let (kimlik, sonrasi) = match belirtec.strip_suffix('+') {
+            Some(kimlik) => (kimlik, true),
+            None => (belirtec, false),
+        };
+        kimlik_dogrula(kimlik)?;
+        let istisna = if self.islec_mi("WITH") {
+            self.konum += 1;
+            let istisna = self.siradaki().ok_or_else(|| String::from("WITH sonrası istisna eksik"))?;
+            kimlik_dogrula(istisna)?;
+            self.konum += 1;
+            Some(istisna.to_owned())
+        } else {
+            None
+        };
+        Ok(LisansIfadesi::Lisans { kimlik: kimlik.to_owned(), sonrasi, istisna })
+    }
+}
+
+// SPDX kimlikleri harf, rakam, '.', '-' içerir; "DocumentRef-x:LicenseRef-y" için ':' de kabul edilir.
+fn kimlik_dogrula(kimlik: &str) -> Result<(), String> {
+    if kimlik.is_empty() || !kimlik.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == ':') {
+        return Err(format!("geçersiz lisans kimliği '{}'", kimlik));
+    }
+    Ok(())
+}
+
+
+// Politikanın bir lisans veya paket için kararı; sıralama izinliden yasağa doğrudur.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LisansKarari {
+    Izinli,
+    Inceleme, // İnsan incelemesi gerekli; paket onaylanan_paketler listesine eklenene kadar kurulmaz
+    Yasak,
+}
+
+impl LisansKarari {
+    pub fn ad(&self) -> &'static str {
+        match self {
+            LisansKarari::Izinli => "izinli",
+            LisansKarari::Inceleme => "inceleme",
+            LisansKarari::Yasak => "yasak",
+        }
+    }
+}
+
+fn varsayilan_bilinmeyen() -> LisansKarari {
+    LisansKarari::Inceleme
+}
+
+// Lisans politikası: izin, yasak ve inceleme listeleri.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LisansPolitikasi {
+    #[serde(default)]
+    pub izinli: Vec<String>,
+    #[serde(default)]
+    pub yasak: Vec<String>,
+    #[serde(default)]
+    pub inceleme: Vec<String>,
+    #[serde(default = "varsayilan_bilinmeyen")]
+    pub bilinmeyen: LisansKarari,
+    #[serde(default)]
+    pub onaylanan_paketler: Vec<String>,
+}
+
+impl Default for LisansPolitikasi {
+    fn default() -> Self {
+        LisansPolitikasi {
+            izinli: Vec::new(),
+            yasak: Vec::new(),
+            inceleme: Vec::new(),
+            bilinmeyen: varsayilan_bilinmeyen(),
+            onaylanan_paketler: Vec::new(),
+        }
+    }
+}
+
+// Bir paketin lisans değerlendirmesi.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LisansDegerlendirmesi {
+    pub paket: String,
+    pub surum: String,
+    pub lisans: Option<String>,
+    pub karar: LisansKarari,
+    pub neden: String,
+}
+
+impl core::fmt::Display for LisansDegerlendirmesi {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}: {} ({})", self.paket, self.surum, self.lisans.as_deref().unwrap_or("-"), self.neden)
+    }
+}
+
+impl LisansPolitikasi {
+    pub fn json_ayristir(veri: &[u8]) -> Result<Self, PaketYoneticisiHatasi> {
+        serde_json::from_slice(veri).map_err(|e| {
+            PaketYoneticisiHatasi::ParsingError(format!("Lisans politikası çözülemedi: {}", e))
+        })
+    }
+
+    // Politika dosyası yoksa Ok(None) döner; bozuk dosya hatadır.
+    pub fn varsa_yukle(resource_id: &str) -> Result<Option<Self>, PaketYoneticisiHatasi> {
+        match read_resource_to_vec(resource_id) {
+            Ok(veri) => {
+                let politika = Self::json_ayristir(&veri)?;
+                info!("Lisans politikası yüklendi: {} izinli, {} yasak, {} inceleme deseni ({})",
+                      politika.izinli.len(), politika.yasak.len(), politika.inceleme.len(), resource_id); // no_std log
+                Ok(Some(politika))
+            }
+            Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => {
+                debug!("Lisans politikası yok: {}", resource_id); // no_std log
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Tek bir ada uyan en katı liste kararı.
+    // Desenler sözcük sözcük eşleşir ('*' boşluğu aşmaz): "GPL-*" yalın "GPL-2.0-only" ile eşleşir, ama
+    // izinli listesinde tam biçimiyle yazılmış "GPL-2.0-only WITH Classpath-exception-2.0" ile eşleşmez.
+    fn liste_karari(&self, ad: &str) -> Option<LisansKarari> {
+        let ad = ad.to_lowercase();
+        let uyar = |liste: &[String]| liste.iter().any(|desen| lisans_deseni_eslesir(&desen.to_lowercase(), &ad));
+        if uyar(&self.yasak) {
+            Some(LisansKarari::Yasak)
+        } else if uyar(&self.inceleme) {
+            Some(LisansKarari::Inceleme)
+        } else if uyar(&self.izinli) {
+            Some(LisansKarari::Izinli)
+        } else {
+            None
+        }
+    }
+
+    // İfadenin kararı ve nedeni: AND için en katı taraf, OR için en serbest taraf (kullanıcı seçebilir).
+    pub fn ifade_karari(&self, ifade: &LisansIfadesi) -> (LisansKarari, String) {
+        match ifade {
+            LisansIfadesi::Lisans { kimlik, sonrasi, istisna } => {
+                let tam = match (sonrasi, istisna) {
+                    (_, Some(istisna)) => format!("{}{} WITH {}", kimlik, if *sonrasi { "+" } else { "" }, istisna),
+                    (true, None) => format!("{}+", kimlik),
+                    (false, None) => kimlik.clone(),
+                };
+                match self.liste_karari(&tam).or_else(|| self.liste_karari(kimlik)) {
+                    Some(LisansKarari::Izinli) => (LisansKarari::Izinli, format!("{} izinli", tam)),
+                    Some(LisansKarari::Inceleme) => (LisansKarari::Inceleme, format!("{} inceleme gerektiriyor", tam)),
+                    Some(LisansKarari::Yasak) => (LisansKarari::Yasak, format!("{} yasak", tam)),
+                    None => (self.bilinmeyen, format!("{} politikada yok ({})", tam, self.bilinmeyen.ad())),
+                }
+            }
+            LisansIfadesi::Ve(sol, sag) => {
+                let (sol, sag) = (self.ifade_karari(sol), self.ifade_karari(sag));
+                if sag.0 > sol.0 { sag } else { sol }
+            }
+            LisansIfadesi::Veya(sol, sag) => {
+                let (sol, sag) = (self.ifade_karari(sol), self.ifade_karari(sag));
+                if sag.0 < sol.0 { sag } else { sol }
+            }
+        }
+    }
+
+    fn onaylandi_mi(&self, paket: &Paket) -> bool {
+        self.onaylanan_paketler.iter().any(|girdi| match girdi.split_once('@') {
+            Some((ad, surum)) => ad == paket.ad && surum == paket.surum,
+            None => *girdi == paket.ad,
+        })
+    }
+
+    pub fn paketi_degerlendir(&self, paket: &Paket) -> LisansDegerlendirmesi {
+        // Lisansı belirtilmemiş veya ayrıştırılamayan paketler en az inceleme gerektirir.
+        let (karar, neden) = match paket.lisans.as_deref().map(str::trim).filter(|lisans| !lisans.is_empty()) {
+            None => (self.bilinmeyen.max(LisansKarari::Inceleme), String::from("lisans belirtilmemiş")),
+            Some(lisans) => match LisansIfadesi::ayristir(lisans) {
+                Ok(ifade) => self.ifade_karari(&ifade),
+                Err(_) => (self.bilinmeyen.max(LisansKarari::Inceleme), String::from("SPDX ifadesi ayrıştırılamadı")),
+            },
+        };
+        let (karar, neden) = if karar == LisansKarari::Inceleme && self.onaylandi_mi(paket) {
+            (LisansKarari::Izinli, format!("{}; inceleme onaylandı", neden))
+        } else {
+            (karar, neden)
+        };
+        LisansDegerlendirmesi {
+            paket: paket.ad.clone(),
+            surum: paket.surum.clone(),
+            lisans: paket.lisans.clone(),
+            karar,
+            neden,
+        }
+    }
+
+    // Kurulum planındaki her paketi değerlendirir. Yasak veya incelenmemiş lisanslı paket varsa
+    // hepsini listeleyen LisansPolitikasiIhlali hatası döner; hiçbir indirme veya kurulum başlamadan çağrılmalıdır.
+    pub fn plani_denetle(&self, paketler: &[Paket]) -> Result<Vec<LisansDegerlendirmesi>, PaketYoneticisiHatasi> {
+        let degerlendirmeler: Vec<LisansDegerlendirmesi> = paketler.iter().map(|paket| self.paketi_degerlendir(paket)).collect();
+        let listele = |karar: LisansKarari| -> Vec<String> {
+            degerlendirmeler.iter().filter(|d| d.karar == karar).map(|d| d.to_string()).collect()
+        };
+        let (yasak, inceleme) = (listele(LisansKarari::Yasak), listele(LisansKarari::Inceleme));
+        if yasak.is_empty() && inceleme.is_empty() {
+            return Ok(degerlendirmeler);
+        }
+        for satir in yasak.iter().chain(inceleme.iter()) {
+            warn!("Lisans politikası: {}", satir); // no_std log
+        }
+        let hata = PaketYoneticisiHatasi::LisansPolitikasiIhlali { yasak, inceleme };
+        error!("{}", hata); // no_std log
+        Err(hata)
+    }
+}
+
+// Lisans deseni ile adı boşluklarla ayrılmış sözcükler halinde eşler; sözcük sayıları aynı olmalıdır.
+fn lisans_deseni_eslesir(desen: &str, ad: &str) -> bool {
+    let mut desenler = desen.split_whitespace();
+    let mut sozcukler = ad.split_whitespace();
+    loop {
+        match (desenler.next(), sozcukler.next()) {
+            (Some(desen), Some(sozcuk)) if desen_eslesir(desen, sozcuk) => continue,
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+// Paketleri lisans ifadesine göre gruplar (ayrıştırılabilen ifadeler kanonik biçimde).
+// Dönüş değeri: Lisans -> "ad sürüm" listesi; lisansı olmayanlar "(belirtilmemiş)" altında.
+pub fn lisans_ozeti<'a>(paketler: impl IntoIterator<Item = &'a Paket>) -> BTreeMap<String, Vec<String>> {
+    let mut ozet: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for paket in paketler {
+        let anahtar = match paket.lisans.as_deref().map(str::trim).filter(|lisans| !lisans.is_empty()) {
+            None => String::from("(belirtilmemiş)"),
+            Some(lisans) => LisansIfadesi::ayristir(lisans).map_or_else(|_| lisans.to_owned(), |ifade| ifade.to_string()),
+        };
+        ozet.entry(anahtar).or_default().push(format!("{} {}", paket.ad, paket.surum));
+    }
+    ozet
+}
+
+
+// Helper fonksiyon: Sahne64 Kaynağından tüm içeriği Vec<u8> olarak oku.
+fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut buffer = Vec::new();
+    let mut temp_buffer = [0u8; 512]; // Stack buffer
+
+    loop {
+        match resource::read(handle, &mut temp_buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => buffer.extend_from_slice(&temp_buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    if let Err(e) = resource::release(handle) {
+        error!("Helper: Kaynak release hatası ({}): {:?}", resource_id, e);
+    }
+
+    Ok(buffer)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paket(ad: &str, surum: &str, lisans: Option<&str>) -> Paket {
+        let mut paket = Paket::yeni(ad.to_string(), surum.to_string(), Vec::new());
+        paket.lisans = lisans.map(str::to_string);
+        paket
+    }
+
+    #[test]
+    fn test_spdx_ayristir() {
+        let ifade = LisansIfadesi::ayristir("MIT OR (Apache-2.0 AND GPL-2.0+ WITH Classpath-exception-2.0)").unwrap();
+        assert_eq!(ifade.to_string(), "MIT OR Apache-2.0 AND GPL-2.0+ WITH Classpath-exception-2.0");
+        assert_eq!(ifade.kimlikler(), ["MIT", "Apache-2.0", "GPL-2.0"]);
+
+        // AND, OR'dan sıkı bağlanır; gerekli parantezler korunur
+        let ifade = LisansIfadesi::ayristir("(MIT or BSD-3-Clause) and Zlib").unwrap();
+        assert_eq!(ifade.to_string(), "(MIT OR BSD-3-Clause) AND Zlib");
+        assert!(matches!(LisansIfadesi::ayristir("A AND B OR C").unwrap(), LisansIfadesi::Veya(..)));
+
+        for gecersiz in ["", "MIT AND", "(MIT", "MIT)", "MIT/Apache-2.0", "WITH MIT", "MIT WITH", "MIT Apache-2.0"] {
+            assert!(LisansIfadesi::ayristir(gecersiz).is_err(), "{}", gecersiz);
+        }
+    }
+
+    #[test]
+    fn test_politika_kararlari() {
+        let politika = LisansPolitikasi::json_ayristir(br#"{
+            "izinli": ["MIT", "Apache-2.0", "GPL-2.0-only WITH Classpath-exception-2.0"],
+            "yasak": ["AGPL-*"],
+            "inceleme": ["GPL-*"],
+            "onaylanan_paketler": ["readline@8.2"]
+        }"#).unwrap();
+        assert_eq!(politika.bilinmeyen, LisansKarari::Inceleme);
+
+        let karar = |lisans: Option<&str>| politika.paketi_degerlendir(&paket("p", "1.0", lisans)).karar;
+        assert_eq!(karar(Some("mit")), LisansKarari::Izinli);
+        assert_eq!(karar(Some("MIT OR AGPL-3.0-only")), LisansKarari::Izinli); // OR: serbest seçenek
+        assert_eq!(karar(Some("MIT AND AGPL-3.0-only")), LisansKarari::Yasak); // AND: en katı taraf
+        assert_eq!(karar(Some("GPL-3.0-only")), LisansKarari::Inceleme);
+        assert_eq!(karar(Some("GPL-2.0-only WITH Classpath-exception-2.0")), LisansKarari::Izinli); // tam biçim önce
+        assert_eq!(karar(Some("Zlib")), LisansKarari::Inceleme); // bilinmeyen
+        assert_eq!(karar(None), LisansKarari::Inceleme);
+        assert_eq!(karar(Some("MIT/Apache-2.0")), LisansKarari::Inceleme); // ayrıştırılamayan
+
+        // Onaylanan paket incelemeden geçer, yasak lisans onayla aşılamaz
+        assert_eq!(politika.paketi_degerlendir(&paket("readline", "8.2", Some("GPL-3.0-or-later"))).karar, LisansKarari::Izinli);
+        assert_eq!(politika.paketi_degerlendir(&paket("readline", "8.3", Some("GPL-3.0-or-later"))).karar, LisansKarari::Inceleme);
+        assert_eq!(politika.paketi_degerlendir(&paket("readline", "8.2", Some("AGPL-3.0-only"))).karar, LisansKarari::Yasak);
+        assert_eq!(politika.paketi_degerlendir(&paket("readline", "8.2", Some("MIT AND AGPL-3.0-only"))).karar, LisansKarari::Yasak);
+
+        // Joker sözcük sınırını aşmaz: tam biçimi listelenmemiş istisna yalın kimliğin kararını alır
+        assert_eq!(karar(Some("GPL-3.0-only WITH GCC-exception-3.1")), LisansKarari::Inceleme);
+        assert_eq!(karar(Some("AGPL-3.0-only WITH Autoconf-exception-3.0")), LisansKarari::Yasak);
+        assert!(lisans_deseni_eslesir("gpl-*", "gpl-2.0-only"));
+        assert!(!lisans_deseni_eslesir("gpl-*", "gpl-2.0-only with classpath-exception-2.0"));
+        assert!(lisans_deseni_eslesir("gpl-* with *", "gpl-2.0-only with classpath-exception-2.0"));
+    }
+
+    #[test]
+    fn test_plan_denetimi_ve_ozet() {
+        let politika = LisansPolitikasi {
+            izinli: Vec::from([String::from("MIT")]),
+            yasak: Vec::from([String::from("SSPL-1.0")]),
+            ..LisansPolitikasi::default()
+        };
+        let plan = [paket("a", "1.0", Some("MIT")), paket("b", "2.0", Some("SSPL-1.0")), paket("c", "3.0", None)];
+        match politika.plani_denetle(&plan) {
+            Err(PaketYoneticisiHatasi::LisansPolitikasiIhlali { yasak, inceleme }) => {
+                assert_eq!(yasak, ["b 2.0: SSPL-1.0 (SSPL-1.0 yasak)"]);
+                assert_eq!(inceleme, ["c 3.0: - (lisans belirtilmemiş)"]);
+            }
+            sonuc => panic!("beklenmeyen sonuç: {:?}", sonuc),
+        }
+        assert_eq!(politika.plani_denetle(&plan[..1]).unwrap()[0].karar, LisansKarari::Izinli);
+
+        let ozet = lisans_ozeti(&plan);
+        assert_eq!(ozet["MIT"], ["a 1.0"]);
+        assert_eq!(ozet["(belirtilmemiş)"], ["c 3.0"]);
+    }
+
+    // Politika dosyasını Kaynaktan yükleme testleri mock resource::acquire/read/release gerektirir.
+}
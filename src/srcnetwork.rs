@@ -2,7 +2,9 @@
 extern crate alloc; // String, format! için
 
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned;
 
 // Sahne64 API modülleri
 use crate::resource; // Ağ ve dosya sistemi benzeri işlemler için
@@ -13,6 +15,13 @@ use crate::Handle; // Kaynak Handle'ları
 use crate::paket_yoneticisi_hata::PaketYoneticisiHatasi;
 // SahneError'dan dönüşüm From implementasyonu ile sağlanacak
 
+// Paket meta verisi (beklenen boyut ve özetler)
+use crate::package::Paket;
+// İndirme sırasında artımlı özet hesaplama
+use crate::srcchecksum::{self, OzetAlgoritmasi, OzetHesaplayici};
+// Kısmi yazmaları tamamlayan yazma yardımcısı
+use crate::srcarchive;
+
 // no_std uyumlu print makroları
 use crate::print_macros::{println, eprintln};
 
@@ -22,11 +31,92 @@ use crate::print_macros::{println, eprintln};
 // (örn. "http://example.com/file.zip").
 // resource::acquire(url, MODE_READ) isteği başlatır, resource::read cevabı okur.
 
+// Bir indirmenin sonucu: aktarılan bayt sayısı ve akış sırasında hesaplanan özetler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndirmeOzeti {
+    pub boyut: u64,
+    pub ozetler: Vec<(OzetAlgoritmasi, Vec<u8>)>, // İstenen algoritmalarla aynı sırada
+}
+
+impl IndirmeOzeti {
+    // Verilen algoritmayla hesaplanan özet (istenmediyse None).
+    pub fn ozet(&self, algoritma: OzetAlgoritmasi) -> Option<&[u8]> {
+        self.ozetler.iter().find(|(alg, _)| *alg == algoritma).map(|(_, ozet)| ozet.as_slice())
+    }
+}
+
+// Aktarılan bayt sayısını beklenen boyutla karşılaştırır.
+// Akış sırasında `tamamlandi == false` ile çağrılır ve yalnızca aşımı yakalar;
+// akış bittiğinde `tamamlandi == true` ile eksik veriyi de yakalar.
+fn boyutu_denetle(kaynak: &str, alinan: u64, beklenen: Option<u64>, tamamlandi: bool) -> Result<(), PaketYoneticisiHatasi> {
+    match beklenen {
+        Some(beklenen) if alinan > beklenen || (tamamlandi && alinan != beklenen) => {
+            Err(PaketYoneticisiHatasi::IndirmeBoyutuUyusmadi { kaynak: kaynak.to_owned(), beklenen, alinan })
+        }
+        _ => Ok(()),
+    }
+}
+
+// Kaynak Handle'ından hedef Handle'ına akışı kopyalar; her parçayı yazarken hesaplayıcılara da besler.
+// Beklenen boyut aşıldığı anda aktarım durdurulur. Dönüş değeri: aktarılan bayt sayısı.
+fn akisi_aktar(
+    kaynak_handle: Handle,
+    hedef_handle: Handle,
+    url: &str,
+    hedef_kaynak_id: &str,
+    beklenen_boyut: Option<u64>,
+    hesaplayicilar: &mut [OzetHesaplayici],
+) -> Result<u64, PaketYoneticisiHatasi> {
+    let mut buffer = [0u8; 4096]; // Okuma/yazma buffer'ı (stack'te)
+    let mut aktarilan: u64 = 0;
+    loop {
+        // Kaynak Kaynağından oku (ağ üzerinden)
+        let bytes_read = match resource::read(kaynak_handle, &mut buffer) {
+            Ok(0) => break, // Kaynak sonu (indirme tamamlandı)
+            Ok(bytes_read) => bytes_read,
+            Err(e) => {
+                eprintln!("Uzak Kaynak okuma hatası ({}): {:?}", url, e); // no_std print
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        };
+
+        aktarilan += bytes_read as u64;
+        // Sunucu beklenenden fazlasını gönderiyorsa fazlasını diske yazmadan dur.
+        boyutu_denetle(url, aktarilan, beklenen_boyut, false)?;
+
+        // Parça tamamen yazılmadan özetlenmez; kısmi yazmalar tamamlanana kadar tekrar denenir.
+        let parca = &buffer[..bytes_read];
+        if let Err(e) = srcarchive::tamamini_yaz(hedef_handle, parca, hedef_kaynak_id) {
+            eprintln!("Hedef Kaynak yazma hatası ({}): {:?}", hedef_kaynak_id, e); // no_std print
+            return Err(e);
+        }
+        for hesaplayici in hesaplayicilar.iter_mut() {
+            hesaplayici.guncelle(parca);
+        }
+    }
+    boyutu_denetle(url, aktarilan, beklenen_boyut, true)?;
+    Ok(aktarilan)
+}
+
 // URL'den hedef Kaynağa dosya indirir.
 // url: İndirilecek dosyanın Kaynak ID'si (URL formatında olabilir).
 // hedef_kaynak_id: Dosyanın kaydedileceği yerel Kaynağın ID'si.
 // Dönüş değeri: Başarı veya PaketYoneticisiHatasi.
 pub fn dosya_indir(url: &str, hedef_kaynak_id: &str) -> Result<(), PaketYoneticisiHatasi> { // Result türü PaketYoneticisiHatasi olmalı
+    dosya_indir_ozetleyerek(url, hedef_kaynak_id, None, &[]).map(|_| ())
+}
+
+// URL'den hedef Kaynağa dosya indirir ve istenen özetleri baytlar akarken hesaplar,
+// böylece özet için dosyanın ikinci kez okunması gerekmez.
+// beklenen_boyut: Biliniyorsa (örn. Paket.indirme_boyutu) aşıldığı anda aktarım kesilir, eksik kalırsa hata döner.
+// algoritmalar: Hesaplanacak özet algoritmaları.
+// Hata durumunda yarım kalan hedef Kaynak silinir.
+pub fn dosya_indir_ozetleyerek(
+    url: &str,
+    hedef_kaynak_id: &str,
+    beklenen_boyut: Option<u64>,
+    algoritmalar: &[OzetAlgoritmasi],
+) -> Result<IndirmeOzeti, PaketYoneticisiHatasi> {
     println!("Dosya indirme başlatılıyor: {} -> {}", url, hedef_kaynak_id); // no_std print
 
     // Uzak (ağ) Kaynağı okuma izniyle acquire et. Bu isteği başlatır.
@@ -40,42 +130,20 @@ pub fn dosya_indir(url: &str, hedef_kaynak_id: &str) -> Result<(), PaketYonetici
     // MODE_CREATE varsa Kaynak yoksa oluşturur, MODE_TRUNCATE varsa içeriği siler.
     // hedef_kaynak_id'nin parent resource'larının oluşturulması gerekebilir.
     // srcconfig.rs/srcinstaller.rs'deki resource oluşturma mantığına bakılabilir.
-    let hedef_handle = resource::acquire(
+    let hedef_handle = match resource::acquire(
         hedef_kaynak_id,
         resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
-    ).map_err(|e| {
-         eprintln!("Hedef Kaynak acquire hatası ({}): {:?}", hedef_kaynak_id, e); // no_std print
-        PaketYoneticisiHatasi::from(e) // SahneError -> PaketYoneticisiHatasi
-    })?;
-
-    // Kaynaktan oku ve hedefe yaz döngüsü
-    let mut buffer = [0u8; 4096]; // Okuma/yazma buffer'ı (stack'te)
-    loop {
-        // Kaynak Kaynağından oku (ağ üzerinden)
-        match resource::read(kaynak_handle, &mut buffer) {
-            Ok(0) => break, // Kaynak sonu (indirme tamamlandı)
-            Ok(bytes_read) => {
-                // Hedef Kaynağa yaz (yerel depolama)
-                match resource::write(hedef_handle, &buffer[..bytes_read]) {
-                    Ok(_) => {
-                        // Başarılı yazma
-                    }
-                    Err(e) => {
-                        eprintln!("Hedef Kaynak yazma hatası ({}): {:?}", hedef_kaynak_id, e); // no_std print
-                        let _ = resource::release(kaynak_handle); // Handle'ları temizle
-                        let _ = resource::release(hedef_handle);
-                        return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Uzak Kaynak okuma hatası ({}): {:?}", url, e); // no_std print
-                let _ = resource::release(kaynak_handle); // Handle'ları temizle
-                let _ = resource::release(hedef_handle);
-                return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
-            }
+    ) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("Hedef Kaynak acquire hatası ({}): {:?}", hedef_kaynak_id, e); // no_std print
+            let _ = resource::release(kaynak_handle);
+            return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
         }
-    }
+    };
+
+    let mut hesaplayicilar: Vec<OzetHesaplayici> = algoritmalar.iter().map(|alg| OzetHesaplayici::yeni(*alg)).collect();
+    let sonuc = akisi_aktar(kaynak_handle, hedef_handle, url, hedef_kaynak_id, beklenen_boyut, &mut hesaplayicilar);
 
     // Handle'ları serbest bırak
     let release_kaynak_result = resource::release(kaynak_handle);
@@ -84,9 +152,54 @@ pub fn dosya_indir(url: &str, hedef_kaynak_id: &str) -> Result<(), PaketYonetici
     let release_hedef_result = resource::release(hedef_handle);
      if let Err(e) = release_hedef_result { eprintln!("Hedef Kaynak release hatası ({}): {:?}", hedef_kaynak_id, e); } // Logla
 
+    let boyut = match sonuc {
+        Ok(boyut) => boyut,
+        Err(e) => {
+            // Yarım veya fazla veri içeren dosya önbellekte kalmamalı.
+            let _ = resource::delete(hedef_kaynak_id);
+            return Err(e);
+        }
+    };
 
-    println!("Dosya başarıyla indirildi: {}", hedef_kaynak_id); // no_std print
-    Ok(())
+    println!("Dosya başarıyla indirildi: {} ({} bayt)", hedef_kaynak_id, boyut); // no_std print
+    Ok(IndirmeOzeti {
+        boyut,
+        ozetler: hesaplayicilar.into_iter().map(|h| (h.algoritma(), h.bitir())).collect(),
+    })
+}
+
+// Dosyayı indirir ve verilen özetle (varsa) akış sırasında doğrular.
+// Özet eşleşmezse indirilen dosya silinir ve OzetUyusmazligi döner.
+pub fn dosya_indir_dogrulayarak(
+    url: &str,
+    hedef_kaynak_id: &str,
+    beklenen_boyut: Option<u64>,
+    beklenen_ozet: Option<(OzetAlgoritmasi, &str)>,
+) -> Result<IndirmeOzeti, PaketYoneticisiHatasi> {
+    let algoritmalar: Vec<OzetAlgoritmasi> = beklenen_ozet.iter().map(|(alg, _)| *alg).collect();
+    let ozet = dosya_indir_ozetleyerek(url, hedef_kaynak_id, beklenen_boyut, &algoritmalar)?;
+    if let Some((algoritma, beklenen_hex)) = beklenen_ozet {
+        // Hesaplayıcı istendiği için özet her zaman vardır.
+        let hesaplanan = ozet.ozet(algoritma).unwrap_or(&[]);
+        if let Err(e) = srcchecksum::ozeti_karsilastir(url, algoritma, hesaplanan, beklenen_hex) {
+            let _ = resource::delete(hedef_kaynak_id);
+            return Err(e);
+        }
+    }
+    Ok(ozet)
+}
+
+// Paketi indirir; `Paket.indirme_boyutu` ile boyutu, `Paket.checksums` içindeki en güçlü özetle içeriği
+// indirme sırasında doğrular. Dönüş değeri: Doğrulamada kullanılan algoritma.
+pub fn paketi_dogrulayarak_indir(
+    url: &str,
+    hedef_kaynak_id: &str,
+    paket: &Paket,
+    eski_md5_izinli: bool,
+) -> Result<OzetAlgoritmasi, PaketYoneticisiHatasi> {
+    let (algoritma, beklenen_hex) = srcchecksum::dogrulama_ozetini_sec(&paket.checksums, eski_md5_izinli)?;
+    dosya_indir_dogrulayarak(url, hedef_kaynak_id, paket.indirme_boyutu, Some((algoritma, beklenen_hex)))?;
+    Ok(algoritma)
 }
 
 // URL'den hedef Kaynağa dosyayı indirir ve ilerleme raporlar (Placeholder).
@@ -131,3 +244,30 @@ pub fn dosya_indir_ilerleme(url: &str, hedef_kaynak_id: &str) -> Result<(), Pake
     // Şu anki API eksikliği nedeniyle, sadece temel indirme fonksiyonunu çağırıyoruz.
     dosya_indir(url, hedef_kaynak_id) // Temel indirme işlevini kullan
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boyutu_denetle() {
+        assert!(boyutu_denetle("u", 10, None, true).is_ok());
+        assert!(boyutu_denetle("u", 5, Some(10), false).is_ok());
+        assert!(matches!(
+            boyutu_denetle("u", 11, Some(10), false),
+            Err(PaketYoneticisiHatasi::IndirmeBoyutuUyusmadi { beklenen: 10, alinan: 11, .. })
+        ));
+        // Akış bittiğinde eksik veri de hatadır
+        assert!(boyutu_denetle("u", 5, Some(10), true).is_err());
+        assert!(boyutu_denetle("u", 10, Some(10), true).is_ok());
+    }
+
+    #[test]
+    fn test_indirme_ozeti_algoritma_ile_bulunur() {
+        let ozet = IndirmeOzeti { boyut: 3, ozetler: alloc::vec![(OzetAlgoritmasi::Sha256, alloc::vec![1, 2])] };
+        assert_eq!(ozet.ozet(OzetAlgoritmasi::Sha256), Some(&[1u8, 2][..]));
+        assert_eq!(ozet.ozet(OzetAlgoritmasi::Sha512), None);
+    }
+
+    // dosya_indir_ozetleyerek testleri için ağ ve hedef Kaynak mock'ları gerekir.
+}
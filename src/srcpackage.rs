@@ -41,6 +41,35 @@ pub struct Paket {
     // Lisans bilgisi, yazar vb. diğer meta veriler eklenebilir.
      pub lisans: Option<String>,
      pub yazar: Option<String>,
+
+    // Boyut bilgileri (depo meta verisinden gelir, bayt cinsinden).
+    // Kurulumdan önce önbellek ve kurulum hedefinde yeterli alan olup olmadığını hesaplamak için kullanılır (srcdiskspace.rs).
+    pub indirme_boyutu: Option<u64>, // Arşiv dosyasının boyutu
+    pub kurulu_boyut: Option<u64>, // Arşiv çıkarıldıktan sonra kapladığı toplam boyut
+
+    // Bu paketin sağladığı tetikleyiciler (örn. fontconfig: "share/fonts/**" -> fc-cache betiği).
+    // Bir işlemde herhangi bir paketin dosyası desene uyarsa, betik işlem sonunda bir kez çalıştırılır (srctrigger.rs).
+    pub tetikleyiciler: Vec<TetikleyiciTanimi>,
+
+    // Paketin betiklerinin (kurulum, kaldırma, tetikleyici) ihtiyaç duyduğu erişimler.
+    // Betikler bu politikayla çalıştırılır (srcsandbox.rs); bildirilmeyen erişimler reddedilir.
+    pub betik_izinleri: BetikIzinleri,
+}
+
+// Bir paketin ilgilendiği yol deseni ve o desen değiştiğinde çalıştırılacak betik.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TetikleyiciTanimi {
+    pub desen: String, // Kurulum dizinine göre yol deseni ('*' tek bileşen, '**' birden çok bileşen, '?' tek karakter)
+    pub betik: String, // Çalıştırılacak betiğin Kaynak ID'si
+}
+
+// Betiklerin erişebileceği Kaynaklar. Desenler Kaynak ID'lerine uygulanır ('*' ve '?' tek bileşen içinde kalır,
+// '**' bileşen sınırlarını aşar; örn. "sahne://config/fonts/**"). Betiğin kendi Kaynağı her zaman okunabilir.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BetikIzinleri {
+    pub yazilabilir_yollar: Vec<String>, // Yazılabilir yollar aynı zamanda okunabilir
+    pub okunabilir_yollar: Vec<String>,
+    pub ag: bool, // Betik ağ bağlantısı açabilir mi?
 }
 
 impl Paket {
@@ -57,7 +86,12 @@ impl Paket {
             dosyalar: Vec::new(), // Vec::new() alloc gerektirir.
             kurulum_scripti: None, // Option alloc gerektirmez
             kaldirma_scripti: None, // Option alloc gerektirmez
-            // ... diğer alanlar default/None ...
+            lisans: None,
+            yazar: None,
+            indirme_boyutu: None,
+            kurulu_boyut: None,
+            tetikleyiciler: Vec::new(),
+            betik_izinleri: BetikIzinleri::default(),
         }
     }
 
@@ -68,6 +102,8 @@ impl Paket {
      impl From<Paket> for PaketId { ... }
      impl From<&Paket> for PaketId { ... }
 
-    // Semantik versiyon karşılaştırması için bir method (semver crate'i no_std uyumluysa kullanılabilir)
-     pub fn surum_karsilastir(&self, diger_surum: &str) -> Option<core::cmp::Ordering> { ... }
+    // Bu paketin sürümünü başka bir sürümle karşılaştırır (srcversion.rs'deki kurallarla).
+    pub fn surum_karsilastir(&self, diger_surum: &str) -> core::cmp::Ordering {
+        crate::srcversion::surum_karsilastir(&self.surum, diger_surum)
+    }
 }
@@ -15,6 +15,12 @@ use postcard::Error as PostcardError; // Postcard hata türü
 // Paket struct tanımını içeren modül
 use crate::package::Paket; // Varsayım: Paket struct'ı srcpackage.rs'de tanımlı ve no_std uyumlu
 
+// İmzalı depo bildirimi (paketler.bin özetini listeler) ve anahtar halkası
+use crate::srcrepositoryrelease::{self, DepoDurumu, DogrulanmisBildirim};
+use crate::srcsigning::{AnahtarHalkasi, VARSAYILAN_ANAHTAR_HALKASI};
+// Bildirimi imzalayan anahtarın hangi depolar için yetkili olduğu
+use crate::srctrust::{AnahtarKapsami, TrustManager};
+
 // Sahne64 API modülleri
 use crate::resource; // Ağ ve dosya sistemi benzeri işlemler için
 use crate::SahneError; // Sahne64 hata türü
@@ -60,26 +66,69 @@ fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, PaketYoneticisiHat
     Ok(buffer)
 }
 
+// Helper: Veriyi Kaynağa yazar (truncate ederek).
+fn write_resource(resource_id: &str, veri: &[u8]) -> Result<(), PaketYoneticisiHatasi> {
+    let handle = resource::acquire(
+        resource_id,
+        resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+    ).map_err(|e| {
+         eprintln!("Helper: Kaynak acquire hatası ({}): {:?}", resource_id, e);
+         PaketYoneticisiHatasi::from(e)
+    })?;
+    let write_result = resource::write(handle, veri);
+    let release_result = resource::release(handle);
+    if let Err(e) = release_result { eprintln!("Helper: Kaynak release hatası ({}): {:?}", resource_id, e); }
+    write_result.map(|_| ()).map_err(|e| {
+         eprintln!("Helper: Kaynak yazma hatası ({}): {:?}", resource_id, e);
+         PaketYoneticisiHatasi::from(e)
+    })
+}
+
+// Depo kökündeki paket listesi dosyası (depo bildiriminde listelenir)
+pub const PAKET_LISTESI_DOSYASI: &str = "paketler.bin";
+
+// Depo bildirimiyle doğrulanmış paket listesi ve diske yazılabilmesi için ham halleri.
+struct DogrulanmisListe {
+    paketler: Vec<Paket>,
+    veri: Vec<u8>, // paketler.bin baytları (bildirimdeki özetle eşleşir, yeniden serileştirilmez)
+    bildirim: DogrulanmisBildirim,
+}
+
 // Depo Yöneticisi Yapısı (Paket Deposunu Yönetir)
 pub struct DepoYoneticisi {
     // Paket deposunun temel Kaynak ID'si (örn. "sahne://remotepkgrepo/packages/")
     pub depo_base_resource_id: String,
     // Yerel depolama veya önbellek dizininin Kaynak ID'si (örn. "sahne://cache/repo/")
     pub yerel_depo_base_resource_id: String,
+    // Depo bildirimi imzasını doğrulamak için güvenilen anahtarlar
+    pub anahtar_halkasi: AnahtarHalkasi,
+    // Anahtarların depo kapsamları; bildirimi imzalayan anahtar bildirimdeki depo için yetkili olmalıdır
+    pub anahtar_kapsamlari: Vec<AnahtarKapsami>,
     // Paket listesi önbelleği (bellek içi)
     paket_listesi_cache: Option<Vec<Paket>>,
 }
 
 impl DepoYoneticisi {
+    // Anahtar halkası varsayılan Kaynaktan (srcsigning::VARSAYILAN_ANAHTAR_HALKASI), kapsamlar varsayılan
+    // güven deposundan yüklenir. Yüklenemezlerse boş kullanılır; bu durumda hiçbir depo bildirimi kabul edilmez.
     pub fn yeni(depo_base_resource_id: String, yerel_depo_base_resource_id: String) -> Self {
+        let anahtar_halkasi = AnahtarHalkasi::kaynaktan_yukle(VARSAYILAN_ANAHTAR_HALKASI).unwrap_or_else(|e| {
+            eprintln!("Anahtar halkası yüklenemedi ({}): {}", VARSAYILAN_ANAHTAR_HALKASI, e);
+            AnahtarHalkasi::yeni()
+        });
+        let anahtar_kapsamlari = TrustManager::varsayilan().key_scopes().to_vec();
         DepoYoneticisi {
             depo_base_resource_id,
             yerel_depo_base_resource_id,
+            anahtar_halkasi,
+            anahtar_kapsamlari,
             paket_listesi_cache: None, // Başlangıçta önbellek boş
         }
     }
 
     // Paket Listesini Alır (Bellek içi cache -> Yerel depo Kaynağı -> Uzak depo Kaynağı).
+    // Paket listesi yalnızca imzalı depo bildirimindeki özetle eşleşiyorsa kabul edilir;
+    // imzasız, hatalı imzalı, süresi dolmuş veya daha önce görülenden eski meta veri reddedilir.
     // İlk başarılı kaynaktan veriyi yükler ve önbelleğe alır.
     pub fn paket_listesini_al(&mut self) -> Result<Vec<Paket>, PaketYoneticisiHatasi> {
         // 1. Bellek içi cache kontrolü
@@ -88,108 +137,84 @@ impl DepoYoneticisi {
             return Ok(paketler.clone());
         }
 
-        // 2. Yerel depo Kaynağı kontrolü (önbellekteki paketler.bin gibi dosya)
-        let yerel_paket_listesi_id = format!("{}/paketler.bin", self.yerel_depo_base_resource_id);
-        match read_resource_to_vec(&yerel_paket_listesi_id) {
-             Ok(buffer) => {
-                 match postcard::from_bytes_copy::<Vec<Paket>>(&buffer) {
-                     Ok(paketler) => {
-                         println!("Yerel depo Kaynağından paket listesi yüklendi: {}", yerel_paket_listesi_id);
-                         self.paket_listesi_cache = Some(paketler.clone());
-                         return Ok(paketler);
-                     }
-                     Err(e) => {
-                         eprintln!("Yerel paket listesi deserialize hatası (Kaynak: {}): {:?}", yerel_paket_listesi_id, e);
-                         // Deserialize hatası durumunda uzak depodan indirmeye devam edelim.
-                     }
-                 }
+        // 2. Yerel depo kopyası kontrolü (bildirim, imzası ve paketler.bin). Yerel kopya da her okumada doğrulanır.
+        match self.dogrulanmis_listeyi_oku(&self.yerel_depo_base_resource_id)
+            .and_then(|liste| self.tazeligi_denetle(&liste).map(|_| liste))
+        {
+             Ok(liste) => {
+                 println!("Yerel depo Kaynağından doğrulanmış paket listesi yüklendi: {}", self.yerel_depo_base_resource_id);
+                 self.paket_listesi_cache = Some(liste.paketler.clone());
+                 return Ok(liste.paketler);
              }
              Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => {
-                 // Yerel depo Kaynağı bulunamadı. Uzak depodan indirmeye devam et.
-                 println!("Yerel paket listesi Kaynağı bulunamadı ({}).", yerel_paket_listesi_id);
+                 // Yerel kopya yok. Uzak depodan indirmeye devam et.
+                 println!("Yerel depo kopyası bulunamadı ({}).", self.yerel_depo_base_resource_id);
              }
              Err(e) => {
-                  // Diğer kaynak okuma hataları. Hata logla ve uzak depodan indirmeyi dene.
-                  eprintln!("Yerel paket listesi Kaynağı okuma hatası ({}): {:?}", yerel_paket_listesi_id, e);
+                  // Bozuk, imzasız, eski biçimli veya süresi dolmuş yerel kopya kullanılmaz; uzak depodan yenilenir.
+                  eprintln!("Yerel depo kopyası reddedildi ({}): {}", self.yerel_depo_base_resource_id, e);
              }
         }
 
-        // 3. Yerel önbellekte yoksa, uzak depodan indir
-        println!("Uzak depodan paket listesi indiriliyor: {}", self.depo_base_resource_id);
-        let uzak_paket_listesi_id = format!("{}/paketler.bin", self.depo_base_resource_id);
-
-        let buffer = read_resource_to_vec(&uzak_paket_listesi_id)?;
-
-        match postcard::from_bytes_copy::<Vec<Paket>>(&buffer) {
-            Ok(paketler) => {
-                println!("Paket listesi uzak depodan başarıyla indirildi ve çözümlendi.");
-                self.paket_listesi_cache = Some(paketler.clone());
-
-                // Başarıyla indirildiyse, yerel depo Kaynağına da kaydet (güncelle)
-                let yerel_depo_dosyasi_id = format!("{}/paketler.bin", self.yerel_depo_base_resource_id);
-                 let serialized_data = postcard::to_postcard(&paketler)
-                     .map_err(|e| {
-                          eprintln!("Yerel depo için paket listesi serileştirme hatası: {:?}", e);
-                          PaketYoneticisiHatasi::SerializationError(e)
-                     })?;
-
-                 match resource::acquire(
-                     &yerel_depo_dosyasi_id,
-                     resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
-                 ) {
-                     Ok(handle) => {
-                         let write_result = resource::write(handle, &serialized_data);
-                         let release_result = resource::release(handle);
-                         if let Err(e) = write_result { eprintln!("Yerel depo Kaynağı yazma hatası: {:?}", e); }
-                         if let Err(e) = release_result { eprintln!("Yerel depo Kaynağı release hatası: {:?}", e); }
-                         println!("Paket listesi yerel depo Kaynağına kaydedildi: {}", yerel_depo_dosyasi_id);
-                     }
-                     Err(e) => {
-                         eprintln!("Yerel depo Kaynağı acquire hatası ({}): {:?}", yerel_depo_dosyasi_id, e);
-                         // Hata durumunda da listeyi yine de döndürelim.
-                     }
-                 }
-
-                Ok(paketler)
-            }
-            Err(e) => {
-                eprintln!("Uzak depodan indirilen paket listesi deserialize hatası: {:?}", e);
-                Err(PaketYoneticisiHatasi::DeserializationError(e))
-            }
-        }
+        // 3. Yerel kopya kullanılamıyorsa uzak depodan indir ve doğrula
+        self.uzaktan_guncelle()
     }
 
-    // Yerel Depoyu Güncelleme (Paket listesini indirip yerel depoya kaydeder).
+    // Yerel Depoyu Güncelleme (Paket listesini ve bildirimi uzak depodan indirip doğrular ve yerel depoya kaydeder).
     pub fn yerel_depoyu_guncelle(&mut self) -> Result<(), PaketYoneticisiHatasi> {
         println!("Yerel depo güncelleniyor: {}", self.yerel_depo_base_resource_id);
+        self.paket_listesi_cache = None;
+        self.uzaktan_guncelle().map(|_| ())
+    }
 
-        let paketler = self.paket_listesini_al()?;
+    // Uzak depodan bildirimi ve paket listesini indirir, doğrular, yerel kopyaya yazar ve önbelleğe alır.
+    fn uzaktan_guncelle(&mut self) -> Result<Vec<Paket>, PaketYoneticisiHatasi> {
+        println!("Uzak depodan paket listesi indiriliyor: {}", self.depo_base_resource_id);
+        let liste = self.dogrulanmis_listeyi_oku(&self.depo_base_resource_id)?;
+        self.tazeligi_denetle(&liste)?;
+        println!("Paket listesi uzak depodan indirildi ve depo bildirimiyle doğrulandı.");
+
+        // Doğrulanan baytlar olduğu gibi yazılır, böylece yerel kopya da aynı bildirimle doğrulanabilir.
+        // Bildirim en son yazılır: yarıda kalan güncellemede yeni liste eski bildirimle uyuşmaz ve reddedilir.
+        let yerel_liste_id = format!("{}/{}", self.yerel_depo_base_resource_id, PAKET_LISTESI_DOSYASI);
+        match write_resource(&yerel_liste_id, &liste.veri)
+            .and_then(|_| srcrepositoryrelease::bildirimi_kaydet(&self.yerel_depo_base_resource_id, &liste.bildirim))
+        {
+            Ok(()) => println!("Paket listesi yerel depo Kaynağına kaydedildi: {}", yerel_liste_id),
+            // Hata durumunda da doğrulanmış listeyi yine de döndürelim.
+            Err(e) => eprintln!("Yerel depo kopyası yazılamadı ({}): {:?}", self.yerel_depo_base_resource_id, e),
+        }
 
-        let yerel_depo_dosyasi_id = format!("{}/paketler.bin", self.yerel_depo_base_resource_id);
-
-        let serialized_data = postcard::to_postcard(&paketler)
-             .map_err(|e| {
-                  eprintln!("Yerel depo için paket listesi serileştirme hatası: {:?}", e);
-                  PaketYoneticisiHatasi::SerializationError(e)
-             })?;
-
-         match resource::acquire(
-             &yerel_depo_dosyasi_id,
-             resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
-         ) {
-             Ok(handle) => {
-                 let write_result = resource::write(handle, &serialized_data);
-                 let release_result = resource::release(handle);
-                 if let Err(e) = write_result { eprintln!("Yerel depo Kaynağı yazma hatası: {:?}", e); return Err(PaketYoneticisiHatasi::from(e)); }
-                 if let Err(e) = release_result { eprintln!("Yerel depo Kaynağı release hatası: {:?}", e); }
-                 println!("Yerel depo başarıyla güncellendi: {}", yerel_depo_dosyasi_id);
-                 Ok(())
-             }
-             Err(e) => {
-                 eprintln!("Yerel depo Kaynağı acquire hatası ({}): {:?}", yerel_depo_dosyasi_id, e);
-                 Err(PaketYoneticisiHatasi::from(e))
-             }
-         }
+        // Kabul edilen sürüm ayrıca kaydedilir; sonraki güncellemeler bundan eski bildirimi kabul etmez.
+        let durum = DepoDurumu::bildirimden(&liste.bildirim.bildirim, &liste.bildirim.veri);
+        if let Err(e) = srcrepositoryrelease::durumu_kaydet(&self.yerel_depo_base_resource_id, &durum) {
+            eprintln!("Depo durumu kaydedilemedi ({}), geri alma koruması bu sürümü hatırlamayacak: {:?}", self.yerel_depo_base_resource_id, e);
+        }
+
+        self.paket_listesi_cache = Some(liste.paketler.clone());
+        Ok(liste.paketler)
+    }
+
+    // Bildirimin süresinin dolmadığını ve depo için en son kabul edilen sürümden eski olmadığını denetler.
+    fn tazeligi_denetle(&self, liste: &DogrulanmisListe) -> Result<(), PaketYoneticisiHatasi> {
+        let onceki = srcrepositoryrelease::durumu_yukle(&self.yerel_depo_base_resource_id)?;
+        let simdi = srcrepositoryrelease::simdiki_zaman()?;
+        srcrepositoryrelease::tazeligi_denetle(&liste.bildirim.bildirim, &liste.bildirim.veri, onceki.as_ref(), simdi)
+    }
+
+    // Depo kökünden imzalı bildirimi ve paket listesini okur; listeyi bildirimdeki boyut ve özetle doğrular.
+    fn dogrulanmis_listeyi_oku(&self, depo_kok_id: &str) -> Result<DogrulanmisListe, PaketYoneticisiHatasi> {
+        let bildirim = srcrepositoryrelease::bildirimi_yukle(depo_kok_id, &self.anahtar_halkasi, &self.anahtar_kapsamlari)?;
+
+        let liste_id = format!("{}/{}", depo_kok_id, PAKET_LISTESI_DOSYASI);
+        let veri = read_resource_to_vec(&liste_id)?;
+        bildirim.bildirim.dosyayi_dogrula(PAKET_LISTESI_DOSYASI, &veri)?;
+
+        let paketler = postcard::from_bytes_copy::<Vec<Paket>>(&veri).map_err(|e| {
+            eprintln!("Paket listesi deserialize hatası (Kaynak: {}): {:?}", liste_id, e);
+            PaketYoneticisiHatasi::DeserializationError(e)
+        })?;
+        Ok(DogrulanmisListe { paketler, veri, bildirim })
     }
 
     // Paket Arama (Paket Adına Göre).
@@ -19,6 +19,8 @@ use crate::Handle; // Kaynak Handle'ları
 
 // Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
 use crate::paket_yoneticisi_hata::PaketYoneticisiHatasi;
+// İndeks dosyası kullanılmadan önce imzalı depo bildirimiyle doğrulanır
+use crate::srcrepositoryrelease::DepoBildirimi;
 // SahneError ve PostcardError'dan dönüşüm From implementasyonları ile sağlanacak
 
 // Helper function to read resource content into a Vec<u8> (reused from srcrepository.rs)
@@ -66,6 +68,9 @@ pub enum IndexError {
     // Geçersiz Kaynak ID'si veya yol hatası
     InvalidResourceID(String), // String alloc gerektirir
 
+    // İndeks dosyası imzalı depo bildirimindeki boyut/özetle uyuşmuyor veya bildirimde listelenmiyor
+    ManifestMismatch(String),
+
     // Diğer beklenmedik hatalar
      UnknownError(String), // Daha spesifik hata varyantları tercih edilir.
 }
@@ -78,6 +83,8 @@ impl core::fmt::Display for IndexError {
             IndexError::SerializationError(e) => write!(f, "İndeks serileştirme hatası: {:?}", e),
             IndexError::DeserializationError(e) => write!(f, "İndeks seriden çıkarma hatası: {:?}", e),
             IndexError::InvalidResourceID(s) => write!(f, "Geçersiz Kaynak ID'si: {}", s),
+            IndexError::ManifestMismatch(s) => write!(f, "İndeks depo bildirimiyle doğrulanamadı: {}", s),
+            IndexError::UnknownError(s) => write!(f, "Bilinmeyen indeks hatası: {}", s),
         }
     }
 }
@@ -105,7 +112,7 @@ type IndexResult<T> = Result<T, IndexError>;
 
 // Paket deposu indeksini temsil eden yapı (no_std uyumlu)
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)] // Debug, PartialEq, Eq, Serialize, Deserialize derive'ları no_std'de çalışır
-struct PackageIndex {
+pub struct PackageIndex {
     // Paket adı -> Sürümler listesi. HashMap ve Vec<String> alloc gerektirir.
     packages: HashMap<String, Vec<String>>,
 }
@@ -134,7 +141,7 @@ impl PackageIndex {
 
     // Paketin sürümlerini döndürür.
     // Eğer paket bulunamazsa `None` döndürür.
-    fn get_versions(&self, package_name: &str) -> Option<&Vec<String>> {
+    pub fn get_versions(&self, package_name: &str) -> Option<&Vec<String>> {
         self.packages.get(package_name) // get (&str)
     }
 
@@ -197,8 +204,10 @@ impl PackageIndex {
 
     // Belirtilen Kaynak ID'sinden indeksi yükler.
     // Binary (postcard) formatındaki Kaynaktan okuma ve deserializasyon işlemini yapar.
+    // Okunan baytlar seriden çıkarılmadan önce bildirimdeki boyut ve SHA-256 özetiyle doğrulanır.
     // index_resource_id: İndeksin yükleneceği Kaynak ID'si.
-    fn load_from_resource(index_resource_id: &str) -> IndexResult<Self> { // load_from_file yerine load_from_resource
+    // bildirim: Deponun doğrulanmış bildirimi (srcrepositoryrelease::bildirimi_yukle).
+    fn load_from_resource(index_resource_id: &str, bildirim: &DepoBildirimi) -> IndexResult<Self> { // load_from_file yerine load_from_resource
         // Kaynak içeriğini oku (Vec<u8> olarak)
         let buffer = read_resource_to_vec(index_resource_id) // Helper fonksiyonu kullanır (PaketYoneticisiHatasi döner)
             .map_err(|e| {
@@ -215,6 +224,12 @@ impl PackageIndex {
                 }
             })?; // Hata durumunda ? ile yay
 
+        // Değiştirilmiş veya başka bir yayından kalmış indeks kullanılmaz
+        bildirim.dosyayi_dogrula(INDEX_FILE_NAME, &buffer).map_err(|e| {
+            eprintln!("İndeks depo bildirimiyle doğrulanamadı (Kaynak: {}): {}", index_resource_id, e); // no_std print
+            IndexError::ManifestMismatch(e.to_string())
+        })?;
+
         // Okunan binary veriyi PackageIndex yapısına deserialize et (postcard)
         postcard::from_bytes_copy::<Self>(&buffer) // Deserileştirme (alloc gerektirir)
             .map_err(|e| {
@@ -224,29 +239,51 @@ impl PackageIndex {
     }
 }
 
+// Depo kökündeki indeks dosyasının adı (depo bildiriminde bu adla listelenir)
+pub const INDEX_FILE_NAME: &str = "index.bin";
+
 // İndeks Kaynağının ID'sini oluşturur.
 // Repo Kaynak ID'sini temel alarak `index.bin` dosyasının Kaynak ID'sini birleştirir.
 // repo_resource_id: Paket deposu temel Kaynak ID'si.
 // Dönüş değeri: İndeks Kaynağı ID'si String olarak (alloc gerektirir).
 fn get_index_resource_id(repo_resource_id: &str) -> String { // Path yerine &str Kaynak ID, PathBuf yerine String
     // Kaynak ID'sini birleştirme. format! alloc gerektirir.
-    format!("{}/index.bin", repo_resource_id) // .json yerine .bin (binary format)
+    format!("{}/{}", repo_resource_id, INDEX_FILE_NAME) // .json yerine .bin (binary format)
+}
+
+// Mevcut indeksi bildirimle doğrulayıp yükler; indeks yoksa boş indeks oluşturmaz, hata döndürür.
+// Sürüm düşürme gibi depoda var olan paketlere dayanan işlemler bunu kullanmalıdır.
+// repo_resource_id: Paket deposu temel Kaynak ID'si.
+// bildirim: Deponun doğrulanmış bildirimi.
+// Dönüş değeri: Yüklenen PackageIndex veya hata (indeks yoksa Sahne64ResourceError(ResourceNotFound)).
+pub fn load_index(repo_resource_id: &str, bildirim: &DepoBildirimi) -> IndexResult<PackageIndex> {
+    let index_resource_id = get_index_resource_id(repo_resource_id); // String (alloc)
+    PackageIndex::load_from_resource(&index_resource_id, bildirim).map_err(|e| {
+        eprintln!("İndeks yüklenemedi ({}): {}", index_resource_id, e); // no_std print
+        e
+    })
 }
 
 // İndeksi oluşturur veya yükler.
-// Eğer indeks Kaynağı varsa yükler, yoksa yeni bir indeks oluşturur.
+// Eğer indeks Kaynağı varsa bildirimle doğrulayıp yükler, yoksa yeni bir indeks oluşturur.
+// Bildirimde listelenen bir indeksin kaybolması ise hatadır (boş indeksle sessizce devam edilmez).
 // repo_resource_id: Paket deposu temel Kaynak ID'si.
+// bildirim: Deponun doğrulanmış bildirimi.
 // Dönüş değeri: Yüklenen veya oluşturulan PackageIndex veya hata.
-fn get_or_create_index(repo_resource_id: &str) -> IndexResult<PackageIndex> { // Path yerine &str Kaynak ID
+pub fn get_or_create_index(repo_resource_id: &str, bildirim: &DepoBildirimi) -> IndexResult<PackageIndex> { // Path yerine &str Kaynak ID
     let index_resource_id = get_index_resource_id(repo_resource_id); // String (alloc)
 
     // İndeksi Kaynaktan yüklemeye çalış.
-    match PackageIndex::load_from_resource(&index_resource_id) { // String'e referans geçeriz
+    match PackageIndex::load_from_resource(&index_resource_id, bildirim) { // String'e referans geçeriz
         Ok(index) => {
              // Başarıyla yüklendi
              println!("İndeks Kaynağından yüklendi: {}", index_resource_id); // no_std print
             Ok(index)
         }
+        Err(IndexError::Sahne64ResourceError(SahneError::ResourceNotFound)) if bildirim.dosya(INDEX_FILE_NAME).is_some() => {
+            eprintln!("Depo bildiriminde listelenen indeks Kaynağı bulunamadı: {}", index_resource_id); // no_std print
+            Err(IndexError::ManifestMismatch(format!("'{}' bildirimde listeleniyor ama depoda yok", INDEX_FILE_NAME)))
+        }
         Err(IndexError::Sahne64ResourceError(SahneError::ResourceNotFound)) => {
             // Kaynak bulunamadı, bu normal (indeks ilk kez oluşturuluyor).
              println!("İndeks Kaynağı bulunamadı ({}). Yeni indeks oluşturuluyor.", index_resource_id); // no_std print
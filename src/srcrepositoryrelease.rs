@@ -0,0 +1,438 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned;
+
+// serde ve no_std uyumlu serileştirme/deserileştirme kütüphanesi
+use serde::{Deserialize, Serialize};
+use postcard; // no_std uyumlu binary serileştirme
+
+use sha2::{Digest, Sha256};
+
+// Sahne64 API modülleri
+use crate::resource;
+use crate::task; // Geçerlilik süresi denetimi için zaman bilgisi
+use crate::SahneError; // Sahne64 hata türü
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hata::PaketYoneticisiHatasi;
+
+// Ed25519 ayrık imzalar ve anahtar halkası
+use crate::srcsigning::{AnahtarHalkasi, AyrikImza, ImzalamaAnahtari};
+// Bildirimi imzalayan anahtarın depo kapsamı
+use crate::srctrust::AnahtarKapsami;
+// İndeks dosyası özetlerinin sabit zamanlı karşılaştırılması
+use crate::srcchecksum::{self, OzetAlgoritmasi};
+use crate::srcresource; // Atomik Kaynak yazma
+
+// no_std uyumlu print makroları
+use crate::print_macros::println;
+
+
+// Deponun imzalı üst düzey meta verisi (Debian'daki Release/InRelease karşılığı).
+// Bildirim, depodaki her indeks dosyasının (paketler.bin, index.bin) boyutunu ve SHA-256 özetini listeler
+// ve depo anahtarıyla imzalanır. Bildirim doğrulandıktan sonra indeks dosyaları özetleriyle, paket arşivleri de
+// indeksteki `Paket.checksums` ile doğrulandığından güven zincirleme olarak paketlere geçer.
+//
+// TUF'taki gibi bildirim tazelik bilgisi de taşır: her yayında artan bir sürüm ve bir son geçerlilik zamanı.
+// İstemci depo başına kabul ettiği son sürümü (DepoDurumu) yerel kopyanın yanında saklar; daha eski sürümü
+// (geri alma) ve süresi dolmuş bildirimi (dondurma) reddeder. İndeks dosyaları yalnızca kendilerini listeleyen
+// bildirimle doğrulandığından farklı yayınlardan dosyaların karıştırılması da (anlık görüntü tutarlılığı) reddedilir.
+
+// Depo kökündeki bildirim dosyası ve ayrık imzası
+pub const BILDIRIM_DOSYASI: &str = "bildirim.bin";
+pub const BILDIRIM_IMZA_DOSYASI: &str = "bildirim.bin.sig";
+
+// Bildirim imzalarının alan ayırıcısı (paket imzalarıyla karıştırılamaz)
+pub const DEPO_BILDIRIMI_IMZA_BAGLAMI: &[u8] = b"sahne-depo-bildirimi-v1\0";
+
+// Bildirim biçim sürümü; desteklenmeyen sürümler reddedilir
+pub const BILDIRIM_BICIM_SURUMU: u32 = 2;
+
+// İstemcinin depo başına kabul ettiği son bildirimin kaydı (yerel depo kökünde)
+pub const DEPO_DURUMU_DOSYASI: &str = "depo_durumu.bin";
+
+// Yayınlanan bildirimin varsayılan geçerlilik süresi (7 gün, saniye)
+pub const VARSAYILAN_GECERLILIK_SURESI_SN: u64 = 7 * 24 * 60 * 60;
+
+// Bildirimde listelenen bir indeks dosyası.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IndeksDosyasi {
+    pub ad: String, // Depo köküne göre yol (örn. "paketler.bin")
+    pub boyut: u64,
+    pub sha256: String, // Hex
+}
+
+// İmzalı depo bildirimi.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DepoBildirimi {
+    pub bicim_surumu: u32,
+    pub depo: String, // Deponun adı (örn. "sahne-ana")
+    pub surum: u64, // Her yayında artar; istemci daha eskisini kabul etmez
+    pub son_gecerlilik: u64, // Unix zamanı (saniye); bu andan sonra bildirim kullanılmaz
+    pub dosyalar: Vec<IndeksDosyasi>, // Ada göre sıralı
+}
+
+impl DepoBildirimi {
+    // İndeks dosyalarının içeriğinden bildirim oluşturur (depo yayınlayan tarafta kullanılır).
+    // dosyalar: (depo köküne göre ad, içerik) çiftleri.
+    pub fn olustur(depo: &str, surum: u64, son_gecerlilik: u64, dosyalar: &[(&str, &[u8])]) -> Self {
+        let mut girdiler: Vec<IndeksDosyasi> = dosyalar.iter().map(|(ad, veri)| IndeksDosyasi {
+            ad: (*ad).to_owned(),
+            boyut: veri.len() as u64,
+            sha256: hex::encode(Sha256::digest(veri)),
+        }).collect();
+        girdiler.sort_by(|a, b| a.ad.cmp(&b.ad));
+        DepoBildirimi { bicim_surumu: BILDIRIM_BICIM_SURUMU, depo: depo.to_owned(), surum, son_gecerlilik, dosyalar: girdiler }
+    }
+
+    pub fn dosya(&self, ad: &str) -> Option<&IndeksDosyasi> {
+        self.dosyalar.iter().find(|dosya| dosya.ad == ad)
+    }
+
+    // İndeks dosyasının içeriğini bildirimdeki boyut ve özetle doğrular.
+    // Bildirimde listelenmeyen dosya reddedilir (aynadan eklenmiş veya eski dosya olabilir).
+    pub fn dosyayi_dogrula(&self, ad: &str, veri: &[u8]) -> Result<(), PaketYoneticisiHatasi> {
+        let girdi = self.dosya(ad).ok_or_else(|| {
+            PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!("'{}' depo bildiriminde listelenmiyor", ad))
+        })?;
+        if girdi.boyut != veri.len() as u64 {
+            return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!(
+                "'{}' boyutu bildirimle uyuşmuyor: beklenen {} bayt, alınan {} bayt", ad, girdi.boyut, veri.len()
+            )));
+        }
+        srcchecksum::ozeti_karsilastir(ad, OzetAlgoritmasi::Sha256, &Sha256::digest(veri), &girdi.sha256)
+    }
+
+    // Bildirimi serileştirir ve imzalar. Dönüş değeri: (bildirim baytları, ayrık imza).
+    pub fn imzala(&self, anahtar: &ImzalamaAnahtari) -> Result<(Vec<u8>, AyrikImza), PaketYoneticisiHatasi> {
+        let veri = postcard::to_allocvec(self).map_err(PaketYoneticisiHatasi::SerializationError)?;
+        let imza = anahtar.veri_imzala(DEPO_BILDIRIMI_IMZA_BAGLAMI, &veri);
+        Ok((veri, imza))
+    }
+}
+
+// Bildirim baytlarını imzasıyla doğrular ve çözer.
+// İmzasız, anahtar halkasında olmayan, süresi dolmuş veya iptal edilmiş bir anahtarla ya da geçersiz imzalanmış
+// bildirim reddedilir. İmzalayan anahtarın kapsamı bildirimin adını verdiği depoyu içermelidir; aksi halde
+// halkadaki herhangi bir anahtar (örn. bir üreticinin) başka bir deponun bildirimini imzalayabilirdi.
+// anahtar_kapsamlari: Güven deposundaki kapsamlar (srctrust::TrustManager::key_scopes); kapsamı olmayan anahtar reddedilir.
+// simdi: Unix zamanı (saniye).
+pub fn bildirimi_dogrula(
+    veri: &[u8],
+    imza_metni: &str,
+    anahtar_halkasi: &AnahtarHalkasi,
+    anahtar_kapsamlari: &[AnahtarKapsami],
+    simdi: u64,
+) -> Result<DepoBildirimi, PaketYoneticisiHatasi> {
+    let imza = AyrikImza::coz(imza_metni).map_err(|e| {
+        PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!("bildirim imzası okunamadı: {}", e))
+    })?;
+    let anahtar = anahtar_halkasi.veri_imzasini_dogrula(DEPO_BILDIRIMI_IMZA_BAGLAMI, veri, &imza, simdi).map_err(|e| {
+        PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!("bildirim imzası doğrulanamadı: {}", e))
+    })?;
+
+    // Biçim sürümü ilk alandır; eski biçimli bildirim çözülmeye çalışılmadan açık bir hatayla reddedilir.
+    let (bicim_surumu, _) = postcard::take_from_bytes::<u32>(veri).map_err(PaketYoneticisiHatasi::DeserializationError)?;
+    if bicim_surumu != BILDIRIM_BICIM_SURUMU {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!(
+            "desteklenmeyen bildirim biçim sürümü: {}", bicim_surumu
+        )));
+    }
+    let bildirim: DepoBildirimi = postcard::from_bytes(veri).map_err(PaketYoneticisiHatasi::DeserializationError)?;
+    if bildirim.dosyalar.windows(2).any(|ikili| ikili[0].ad >= ikili[1].ad) {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(String::from("bildirimdeki dosyalar sıralı ve tekil değil")));
+    }
+    let kimlik = anahtar.kimlik();
+    let yetkili = anahtar_kapsamlari.iter()
+        .any(|kapsam| kapsam.anahtar_kimligi == kimlik && kapsam.depoya_izin_veriyor_mu(&bildirim.depo));
+    if !yetkili {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!(
+            "bildirimi imzalayan anahtar {} ({}) '{}' deposu için yetkili değil", kimlik, anahtar.ad, bildirim.depo
+        )));
+    }
+    println!("Depo bildirimi doğrulandı: {} sürüm {} (imzalayan: {} {})", bildirim.depo, bildirim.surum, anahtar.ad, imza.anahtar_kimligi);
+    Ok(bildirim)
+}
+
+// İstemcinin bir depo için en son kabul ettiği bildirim.
+// Yerel kopyadan ayrı bir dosyada tutulur; yerel kopya silinse veya bozulsa bile geri alma denetimi korunur.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DepoDurumu {
+    pub depo: String,
+    pub surum: u64,
+    pub bildirim_sha256: String, // Aynı sürümün farklı içerikle yeniden yayınlanmasını yakalamak için (hex)
+}
+
+impl DepoDurumu {
+    pub fn bildirimden(bildirim: &DepoBildirimi, veri: &[u8]) -> Self {
+        DepoDurumu { depo: bildirim.depo.clone(), surum: bildirim.surum, bildirim_sha256: hex::encode(Sha256::digest(veri)) }
+    }
+}
+
+// İmzası doğrulanmış bildirimin tazeliğini denetler.
+// onceki: Depo için en son kabul edilen bildirim (ilk kullanımda None).
+// simdi: Unix zamanı (saniye).
+pub fn tazeligi_denetle(
+    bildirim: &DepoBildirimi,
+    veri: &[u8],
+    onceki: Option<&DepoDurumu>,
+    simdi: u64,
+) -> Result<(), PaketYoneticisiHatasi> {
+    if simdi >= bildirim.son_gecerlilik {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiSuresiDoldu {
+            depo: bildirim.depo.clone(),
+            son_gecerlilik: bildirim.son_gecerlilik,
+            simdi,
+        });
+    }
+    let onceki = match onceki {
+        Some(onceki) => onceki,
+        None => return Ok(()),
+    };
+    if onceki.depo != bildirim.depo {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!(
+            "bildirim '{}' deposuna ait, beklenen '{}'", bildirim.depo, onceki.depo
+        )));
+    }
+    if bildirim.surum < onceki.surum {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGeriAlindi {
+            depo: bildirim.depo.clone(),
+            bilinen_surum: onceki.surum,
+            alinan_surum: bildirim.surum,
+        });
+    }
+    if bildirim.surum == onceki.surum && DepoDurumu::bildirimden(bildirim, veri).bildirim_sha256 != onceki.bildirim_sha256 {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!(
+            "'{}' deposunun {} sürümü farklı içerikle yeniden yayınlanmış", bildirim.depo, bildirim.surum
+        )));
+    }
+    Ok(())
+}
+
+// Şimdiki zamanı Unix saniyesi olarak döndürür.
+pub fn simdiki_zaman() -> Result<u64, PaketYoneticisiHatasi> {
+    task::current_time_us().map(|us| us / 1_000_000).map_err(PaketYoneticisiHatasi::from)
+}
+
+// Yerel depo kökünden en son kabul edilen bildirim kaydını okur; kayıt yoksa (ilk kullanım) None döner.
+// Bozuk kayıt hata olarak döner: sessizce yok sayılırsa geri alma koruması devre dışı kalır.
+pub fn durumu_yukle(yerel_depo_base_resource_id: &str) -> Result<Option<DepoDurumu>, PaketYoneticisiHatasi> {
+    match kaynagi_oku(&format!("{}/{}", yerel_depo_base_resource_id, DEPO_DURUMU_DOSYASI)) {
+        Ok(veri) => postcard::from_bytes(&veri).map(Some).map_err(PaketYoneticisiHatasi::DeserializationError),
+        Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn durumu_kaydet(yerel_depo_base_resource_id: &str, durum: &DepoDurumu) -> Result<(), PaketYoneticisiHatasi> {
+    let veri = postcard::to_allocvec(durum).map_err(PaketYoneticisiHatasi::SerializationError)?;
+    kaynaga_yaz(&format!("{}/{}", yerel_depo_base_resource_id, DEPO_DURUMU_DOSYASI), &veri)
+}
+
+// Doğrulanmış bildirim ve diske yeniden yazılabilmesi için ham hali.
+#[derive(Debug, Clone)]
+pub struct DogrulanmisBildirim {
+    pub bildirim: DepoBildirimi,
+    pub veri: Vec<u8>, // Bildirim baytları (imzalanan içerik)
+    pub imza_metni: String, // Ayrık imza metni
+}
+
+// Depo kökünden (uzak depo veya yerel kopya) bildirimi ve imzasını okuyup doğrular.
+// depo_base_resource_id: Depo kökünün Kaynak ID'si.
+// anahtar_kapsamlari: İmzalayan anahtarın depo yetkisinin denetlendiği kapsamlar.
+pub fn bildirimi_yukle(
+    depo_base_resource_id: &str,
+    anahtar_halkasi: &AnahtarHalkasi,
+    anahtar_kapsamlari: &[AnahtarKapsami],
+) -> Result<DogrulanmisBildirim, PaketYoneticisiHatasi> {
+    let bildirim_id = format!("{}/{}", depo_base_resource_id, BILDIRIM_DOSYASI);
+    let imza_id = format!("{}/{}", depo_base_resource_id, BILDIRIM_IMZA_DOSYASI);
+
+    let veri = kaynagi_oku(&bildirim_id)?;
+    let imza_verisi = match kaynagi_oku(&imza_id) {
+        Ok(imza_verisi) => imza_verisi,
+        Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => {
+            return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!("depo bildirimi imzasız: {}", imza_id)));
+        }
+        Err(e) => return Err(e),
+    };
+    let imza_metni = String::from_utf8(imza_verisi).map_err(|_| {
+        PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!("bildirim imzası UTF-8 değil: {}", imza_id))
+    })?;
+
+    let bildirim = bildirimi_dogrula(&veri, &imza_metni, anahtar_halkasi, anahtar_kapsamlari, simdiki_zaman()?)?;
+    Ok(DogrulanmisBildirim { bildirim, veri, imza_metni })
+}
+
+// Doğrulanmış bildirimi ve imzasını yerel depo köküne yazar; yerel kopya sonraki okumalarda yeniden doğrulanır.
+pub fn bildirimi_kaydet(yerel_depo_base_resource_id: &str, dogrulanmis: &DogrulanmisBildirim) -> Result<(), PaketYoneticisiHatasi> {
+    kaynaga_yaz(&format!("{}/{}", yerel_depo_base_resource_id, BILDIRIM_DOSYASI), &dogrulanmis.veri)?;
+    kaynaga_yaz(&format!("{}/{}", yerel_depo_base_resource_id, BILDIRIM_IMZA_DOSYASI), dogrulanmis.imza_metni.as_bytes())
+}
+
+// Depo kökündeki indeks dosyalarından bildirim oluşturur, imzalar ve bildirimi imzasıyla birlikte depo köküne yazar
+// (depoyu yayınlayan tarafta kullanılır). Depoda bulunmayan dosyalar bildirime alınmaz.
+// Sürüm, depodaki önceki bildirimin sürümünden bir fazladır (önceki yoksa veya eski biçimliyse 1).
+// dosya_adlari: Depo köküne göre indeks dosyaları (örn. ["paketler.bin", "index.bin"]).
+// gecerlilik_suresi_sn: Bildirimin şimdiden itibaren geçerli kalacağı süre (saniye).
+pub fn bildirimi_yayinla(
+    depo_kok_id: &str,
+    depo_adi: &str,
+    dosya_adlari: &[&str],
+    gecerlilik_suresi_sn: u64,
+    anahtar: &ImzalamaAnahtari,
+) -> Result<DepoBildirimi, PaketYoneticisiHatasi> {
+    let mut icerikler: Vec<(&str, Vec<u8>)> = Vec::new();
+    for ad in dosya_adlari {
+        match kaynagi_oku(&format!("{}/{}", depo_kok_id, ad)) {
+            Ok(veri) => icerikler.push((ad, veri)),
+            Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => {
+                println!("Depoda bulunmayan indeks dosyası bildirime alınmadı: {}", ad);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if icerikler.is_empty() {
+        return Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(format!("{} altında indeks dosyası yok", depo_kok_id)));
+    }
+
+    let onceki_surum = match kaynagi_oku(&format!("{}/{}", depo_kok_id, BILDIRIM_DOSYASI)) {
+        Ok(veri) => match postcard::from_bytes::<DepoBildirimi>(&veri) {
+            Ok(onceki) if onceki.bicim_surumu == BILDIRIM_BICIM_SURUMU => onceki.surum,
+            _ => 0,
+        },
+        Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => 0,
+        Err(e) => return Err(e),
+    };
+    let son_gecerlilik = simdiki_zaman()?.saturating_add(gecerlilik_suresi_sn);
+
+    let dosyalar: Vec<(&str, &[u8])> = icerikler.iter().map(|(ad, veri)| (*ad, veri.as_slice())).collect();
+    let bildirim = DepoBildirimi::olustur(depo_adi, onceki_surum + 1, son_gecerlilik, &dosyalar);
+    let (veri, imza) = bildirim.imzala(anahtar)?;
+    bildirimi_kaydet(depo_kok_id, &DogrulanmisBildirim { bildirim: bildirim.clone(), veri, imza_metni: imza.kodla() })?;
+    Ok(bildirim)
+}
+
+// Helper: Kaynağın tüm içeriğini okur.
+fn kaynagi_oku(resource_id: &str) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let handle = resource::acquire(resource_id, resource::MODE_READ)?;
+    let mut buffer = Vec::new();
+    let mut temp_buffer = [0u8; 512]; // Stack buffer
+    loop {
+        match resource::read(handle, &mut temp_buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => buffer.extend_from_slice(&temp_buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+    let _ = resource::release(handle);
+    Ok(buffer)
+}
+
+// Helper: Kaynağa atomik olarak yazar (geçici Kaynak + taşıma). Yarıda kalan yazma hedefi bozmaz;
+// aksi halde kesik bir depo durumu ayrıştırılamaz ve hem yerel hem uzak bildirim reddedilirdi.
+fn kaynaga_yaz(resource_id: &str, veri: &[u8]) -> Result<(), PaketYoneticisiHatasi> {
+    srcresource::atomik_yaz(resource_id, veri).map_err(PaketYoneticisiHatasi::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anahtar_ve_halka() -> (ImzalamaAnahtari, AnahtarHalkasi) {
+        let anahtar = ImzalamaAnahtari::tohumdan(&[9u8; 32]);
+        let mut halka = AnahtarHalkasi::yeni();
+        halka.ekle(anahtar.genel_anahtar("depo"));
+        (anahtar, halka)
+    }
+
+    fn kapsam(anahtar: &ImzalamaAnahtari, depo: &str) -> AnahtarKapsami {
+        AnahtarKapsami {
+            anahtar_kimligi: anahtar.genel_anahtar("depo").kimlik(),
+            depolar: alloc::vec![depo.to_owned()],
+            paket_desenleri: alloc::vec![String::from("*")],
+            aciklama: String::new(),
+            eklenme: 0,
+            ekleyen: String::from("test"),
+        }
+    }
+
+    #[test]
+    fn test_bildirim_imzala_ve_dogrula() {
+        let (anahtar, halka) = anahtar_ve_halka();
+        let kapsamlar = [kapsam(&anahtar, "sahne-ana")];
+        let bildirim = DepoBildirimi::olustur("sahne-ana", 1, 1000, &[("paketler.bin", b"liste"), ("index.bin", b"indeks")]);
+        assert_eq!(bildirim.dosyalar[0].ad, "index.bin"); // Ada göre sıralı
+
+        let (veri, imza) = bildirim.imzala(&anahtar).unwrap();
+        let dogrulanan = bildirimi_dogrula(&veri, &imza.kodla(), &halka, &kapsamlar, 0).unwrap();
+        assert_eq!(dogrulanan, bildirim);
+
+        // Değiştirilmiş bildirim veya bilinmeyen anahtar reddedilir
+        let mut bozuk = veri.clone();
+        *bozuk.last_mut().unwrap() ^= 1;
+        assert!(matches!(bildirimi_dogrula(&bozuk, &imza.kodla(), &halka, &kapsamlar, 0), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+        assert!(matches!(bildirimi_dogrula(&veri, &imza.kodla(), &AnahtarHalkasi::yeni(), &kapsamlar, 0), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+        assert!(bildirimi_dogrula(&veri, "", &halka, &kapsamlar, 0).is_err());
+    }
+
+    #[test]
+    fn test_bildirim_imzalayan_kapsami() {
+        let (anahtar, halka) = anahtar_ve_halka();
+        let (veri, imza) = DepoBildirimi::olustur("sahne-ana", 1, 1000, &[("paketler.bin", b"liste")]).imzala(&anahtar).unwrap();
+
+        // Halkadaki anahtar kapsamsızsa veya kapsamı başka depoyu içeriyorsa bildirim reddedilir
+        assert!(matches!(bildirimi_dogrula(&veri, &imza.kodla(), &halka, &[], 0), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+        let uretici = [kapsam(&anahtar, "vendor")];
+        assert!(matches!(bildirimi_dogrula(&veri, &imza.kodla(), &halka, &uretici, 0), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+        assert!(bildirimi_dogrula(&veri, &imza.kodla(), &halka, &[kapsam(&anahtar, "sahne-*")], 0).is_ok());
+    }
+
+    #[test]
+    fn test_indeks_dosyasi_dogrulama() {
+        let bildirim = DepoBildirimi::olustur("sahne-ana", 1, 1000, &[("paketler.bin", b"liste")]);
+        assert!(bildirim.dosyayi_dogrula("paketler.bin", b"liste").is_ok());
+        // Kırpılmış veya değiştirilmiş liste
+        assert!(matches!(bildirim.dosyayi_dogrula("paketler.bin", b"list"), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+        assert!(matches!(bildirim.dosyayi_dogrula("paketler.bin", b"LISTE"), Err(PaketYoneticisiHatasi::OzetUyusmazligi { .. })));
+        // Listelenmeyen dosya
+        assert!(bildirim.dosyayi_dogrula("index.bin", b"indeks").is_err());
+    }
+
+    #[test]
+    fn test_tazelik_denetimi() {
+        let bildirim = DepoBildirimi::olustur("sahne-ana", 5, 1000, &[("paketler.bin", b"liste")]);
+        let veri = postcard::to_allocvec(&bildirim).unwrap();
+
+        // İlk kullanım: yalnızca geçerlilik süresi denetlenir
+        assert!(tazeligi_denetle(&bildirim, &veri, None, 999).is_ok());
+        assert!(matches!(
+            tazeligi_denetle(&bildirim, &veri, None, 1000),
+            Err(PaketYoneticisiHatasi::DepoBildirimiSuresiDoldu { son_gecerlilik: 1000, simdi: 1000, .. })
+        ));
+
+        // Aynı bildirim yeniden kabul edilir; daha yeni sürümden sonra reddedilir
+        let ayni = DepoDurumu::bildirimden(&bildirim, &veri);
+        assert!(tazeligi_denetle(&bildirim, &veri, Some(&ayni), 10).is_ok());
+        let yeni = DepoDurumu { surum: 6, ..ayni.clone() };
+        assert!(matches!(
+            tazeligi_denetle(&bildirim, &veri, Some(&yeni), 10),
+            Err(PaketYoneticisiHatasi::DepoBildirimiGeriAlindi { bilinen_surum: 6, alinan_surum: 5, .. })
+        ));
+
+        // Aynı sürüm farklı içerikle veya başka bir depoya ait bildirim
+        let farkli = DepoDurumu { bildirim_sha256: String::from("00"), ..ayni.clone() };
+        assert!(matches!(tazeligi_denetle(&bildirim, &veri, Some(&farkli), 10), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+        let baska_depo = DepoDurumu { depo: String::from("sahne-test"), ..ayni };
+        assert!(matches!(tazeligi_denetle(&bildirim, &veri, Some(&baska_depo), 10), Err(PaketYoneticisiHatasi::DepoBildirimiGecersiz(_))));
+    }
+
+    // bildirimi_yukle/bildirimi_kaydet/durumu_yukle testleri için mock resource::acquire/read/write gerekir.
+}
@@ -15,6 +15,12 @@ use crate::Handle; // Kaynak Handle'ları
 use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
 // SahneError'dan dönüşüm From implementasyonu ile sağlanacak
 
+// Paket meta verisi
+use crate::package::Paket;
+// Tek dosya indirme ve akış sırasında özet hesaplama
+use crate::srcnetwork::{self, IndirmeOzeti};
+use crate::srcchecksum::OzetAlgoritmasi;
+
 // no_std uyumlu print makroları
 use crate::print_macros::{println, eprintln};
 
@@ -54,103 +60,42 @@ impl RemoteRepository {
     // version: İndirilecek paketin sürümü.
     // package_file_name: İndirilecek paketin arşiv dosyasının adı (Paket struct'ından gelir).
     // destination_resource_id: İndirilen dosyanın kaydedileceği yerel Kaynağın ID'si.
-    // Dönüş değeri: Başarı veya PaketYoneticisiHatasi.
+    // expected_size: Biliniyorsa aşıldığı anda aktarım kesilir.
+    // algorithms: İndirme sırasında hesaplanacak özetler (dosya ikinci kez okunmaz).
+    // Dönüş değeri: Aktarılan boyut ve hesaplanan özetler veya PaketYoneticisiHatasi.
     pub fn download_package(
         &self,
         package_name: &str,
         version: &str,
         package_file_name: &str, // Dosya adını da bilmek gerekir
         destination_resource_id: &str, // PathBuf yerine &str Kaynak ID
-    ) -> Result<(), PaketYoneticisiHatasi> { // SahneError yerine PaketYoneticisiHatasi
+        expected_size: Option<u64>,
+        algorithms: &[OzetAlgoritmasi],
+    ) -> Result<IndirmeOzeti, PaketYoneticisiHatasi> { // SahneError yerine PaketYoneticisiHatasi
         // İndirilecek paketin uzak Kaynak ID'sini (URL) oluştur.
         let source_url = self.get_package_resource_id(package_name, version, package_file_name); // String (alloc)
 
         println!("Paket indiriliyor: {} -> {}", source_url, destination_resource_id); // no_std print
 
-        // Uzak (ağ) Kaynağı okuma izniyle acquire et. Bu HTTP GET isteğini başlatır.
-        let source_handle = resource::acquire(&source_url, resource::MODE_READ) // URL Kaynak ID'si olarak kullanılıyor
-            .map_err(|e| {
-                 eprintln!("Uzak Kaynak acquire hatası ({}): {:?}", source_url, e); // no_std print
-                PaketYoneticisiHatasi::from(e) // SahneError -> PaketYoneticisiHatasi (Ağ hatası olabilir)
-            })?;
-
-        // Hedef (yerel) Kaynağı yazma izniyle aç/oluştur/sil.
-        // MODE_CREATE varsa Kaynak yoksa oluşturur, MODE_TRUNCATE varsa içeriği siler.
-        // hedef_kaynak_id'nin parent resource'larının oluşturulması srcinstaller.rs/srcrepositorylocal.rs'deki helper'ı gerektirebilir.
-          let _ = crate::srcrepositorylocal::sahne_create_resource_recursive(parent_of_destination_resource_id)?; // Eğer helper public ve kullanılabilirse
-
-        let destination_handle = resource::acquire(
-            destination_resource_id,
-            resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
-        ).map_err(|e| {
-             eprintln!("Hedef Kaynak acquire hatası ({}): {:?}", destination_resource_id, e); // no_std print
-            PaketYoneticisiHatasi::from(e) // SahneError -> PaketYoneticisiHatasi
-        })?;
+        // Okuma/yazma döngüsü, boyut sınırı ve artımlı özet hesaplama srcnetwork'te.
+        // HTTP durum kodu Sahne64 API'sında sunulmadığından yalnızca resource::read/write hatalarına güvenilir.
+        srcnetwork::dosya_indir_ozetleyerek(&source_url, destination_resource_id, expected_size, algorithms)
+    }
 
-        // HTTP Status Code kontrolü (Sahne64 API desteği gerekiyor)
-        // resource::control ile status code alınabildiği varsayımıyla:
-         const RESOURCE_CONTROL_CMD_GET_HTTP_STATUS: u33 = 3; // Varsayımsal komut
-         match resource::control(source_handle, RESOURCE_CONTROL_CMD_GET_HTTP_STATUS, &[]) {
-              Ok(status_bytes) => {
-        //          // status_bytes'ı u16/u32'ye çevir.
-                  if status_code != 200 {
-                      let _ = resource::release(source_handle); // Hata durumunda handle'ları kapat
-                      let _ = resource::release(destination_handle);
-                      return Err(PaketYoneticisiHatasi::NetworkError(format!("HTTP hatası: {}, URL: {}", status_code, source_url)));
-                  }
-              }
-              Err(e) => {
-                   // Status code alınamadı hatası (kritik olabilir veya olmayabilir)
-                   eprintln!("HTTP status code alınamadı (Kaynak: {}): {:?}", source_url, e);
-                   // Hata durumunda devam mı, dur mu? Şimdilik devam edelim.
-              }
-         }
-        // API desteği yoksa, sadece resource::read/write hatalarına güvenilir.
-
-        // Kaynaktan oku ve hedefe yaz döngüsü (indirme ve kaydetme).
-        let mut buffer = [0u8; 4096]; // Okuma/yazma buffer'ı (stack'te)
-        loop {
-            // Kaynak Kaynağından oku (ağ üzerinden response body'yi alır)
-            match resource::read(source_handle, &mut buffer) {
-                Ok(0) => break, // Kaynak sonu (indirme tamamlandı)
-                Ok(bytes_read) => {
-                    // Hedef Kaynağa yaz (yerel depolama)
-                    match resource::write(destination_handle, &buffer[..bytes_read]) {
-                        Ok(_) => {
-                            // Başarılı yazma
-                        }
-                        Err(e) => {
-                             eprintln!("İndirme yazma hatası ({} -> {}): {:?}", source_url, destination_resource_id, e); // no_std print
-                            let _ = resource::release(source_handle); // Handle'ları temizle
-                            let _ = resource::release(destination_handle);
-                            return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
-                        }
-                    }
-                }
-                Err(e) => {
-                    // Okuma hatası (örn. ağ kesintisi, sunucu hatası)
-                     eprintln!("İndirme okuma hatası ({}): {:?}", source_url, e); // no_std print
-                    let _ = resource::release(source_handle); // Handle'ları temizle
-                    let _ = resource::release(destination_handle);
-                    return Err(PaketYoneticisiHatasi::from(e)); // SahneError -> PaketYoneticisiHatasi
-                }
-            }
-        }
-
-        // Handle'ları serbest bırak
-        let release_source_result = resource::release(source_handle);
-         if let Err(_e) = release_source_result {
-              eprintln!("Uzak Kaynak Handle release hatası ({}): {:?}", source_url, _e); // no_std print
-         }
-
-        let release_destination_result = resource::release(destination_handle);
-         if let Err(_e) = release_destination_result {
-             eprintln!("Hedef Kaynak Handle release hatası ({}): {:?}", destination_resource_id, _e); // no_std print
-         }
-
-
-        println!("Paket başarıyla indirildi ve kaydedildi: {}", destination_resource_id); // no_std print
-        Ok(()) // Başarı
+    // Paketi indirir; boyutu `Paket.indirme_boyutu` ile, içeriği `Paket.checksums` içindeki en güçlü özetle
+    // indirme sırasında doğrular. Doğrulanamayan dosya hedefte bırakılmaz.
+    // Dönüş değeri: Doğrulamada kullanılan algoritma.
+    pub fn download_package_verified(
+        &self,
+        package: &Paket,
+        destination_resource_id: &str,
+        allow_legacy_md5: bool,
+    ) -> Result<OzetAlgoritmasi, PaketYoneticisiHatasi> {
+        let file_name = package.dosya_adi.as_ref().ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("Paket '{}' için dosya adı belirtilmemiş.", package.ad))
+        })?;
+        let source_url = self.get_package_resource_id(&package.ad, &package.surum, file_name);
+        srcnetwork::paketi_dogrulayarak_indir(&source_url, destination_resource_id, package, allow_legacy_md5)
     }
 }
 
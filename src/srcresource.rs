@@ -0,0 +1,72 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // format! için
+
+use alloc::format; // format! makrosu için
+
+// Sahne64 API modülleri
+use crate::resource; // Kaynak işlemleri (acquire, write, control, release, delete)
+use crate::SahneError; // Sahne64 hata türü
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::error;
+
+// Paket yöneticisinin kullandığı Sahne64 Kaynak Kontrol Komutları (varsayımsal).
+// Gerçek Sahne64 API'sında tanımlanmalıdır; numaralar yalnızca bu modülde tutulur.
+
+// Kaynak meta verisini sorgular.
+// Yanıt: 8 bayt boyut + 4 bayt mod (tür bitleri dahil) + 4 bayt uid + 4 bayt gid (hepsi LE).
+// Sembolik bağlarda bağın kendisi sorgulanır (hedefi izlenmez).
+pub const RESOURCE_CONTROL_CMD_STAT: u64 = 3;
+// Kaynağın bulunduğu depolama biriminin boş alanını sorgular.
+// Yanıt: 8 bayt boş alan (LE) + 8 bayt birim kimliği (LE).
+pub const RESOURCE_CONTROL_CMD_STATFS: u64 = 4;
+// Kaynağın okuma konumunu ayarlar.
+// Argüman: 8 bayt mutlak konum (LE). Sonraki resource::read çağrıları bu konumdan okur.
+pub const RESOURCE_CONTROL_CMD_SEEK: u64 = 5;
+// Kaynak erişim izinlerini ayarlar. Argüman: 4 bayt mod (LE).
+pub const RESOURCE_CONTROL_CMD_CHMOD: u64 = 6;
+// Kaynak sahibini ayarlar. Argüman: 4 bayt uid + 4 bayt gid (LE).
+pub const RESOURCE_CONTROL_CMD_CHOWN: u64 = 7;
+// Yeni oluşturulmuş boş bir Kaynağı sembolik bağa çevirir. Argüman: hedef yol baytları.
+pub const RESOURCE_CONTROL_CMD_SYMLINK: u64 = 8;
+// Mevcut bir Kaynağa ikinci bir ad (sabit bağ) verir. Argüman: yeni Kaynak ID'si baytları.
+pub const RESOURCE_CONTROL_CMD_LINK: u64 = 9;
+// Sembolik bağın hedefini okur. Yanıt: hedef yol baytları.
+pub const RESOURCE_CONTROL_CMD_READLINK: u64 = 10;
+// Kaynağın verisini kalıcı depolamaya yazdırır. Argüman yok.
+pub const RESOURCE_CONTROL_CMD_SYNC: u64 = 11;
+// Kaynağı verilen ada atomik olarak taşır. Argüman: hedef Kaynak ID'si baytları; hedef varsa yerine geçer.
+pub const RESOURCE_CONTROL_CMD_RENAME: u64 = 12;
+
+// Veriyi önce "<hedef>.tmp" Kaynağına yazar, kalıcı depolamaya yazdırır ve hedefin yerine atomik olarak taşır.
+// Yarıda kalan yazma yalnızca geçici Kaynağı etkiler; hedef ya eski ya da yeni içeriğin tamamını taşır.
+pub fn atomik_yaz(resource_id: &str, veri: &[u8]) -> Result<(), SahneError> {
+    let gecici_id = format!("{}.tmp", resource_id);
+    let handle = resource::acquire(
+        &gecici_id,
+        resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE,
+    )?;
+
+    let sonuc = tamamini_yaz(handle, veri)
+        .and_then(|_| resource::control(handle, RESOURCE_CONTROL_CMD_SYNC, &[]))
+        .and_then(|_| resource::control(handle, RESOURCE_CONTROL_CMD_RENAME, resource_id.as_bytes()));
+    let _ = resource::release(handle);
+
+    if let Err(e) = sonuc {
+        error!("Geçici Kaynak hedefe taşınamadı ({} -> {}): {:?}", gecici_id, resource_id, e); // no_std log
+        let _ = resource::delete(&gecici_id);
+        return Err(e);
+    }
+    Ok(())
+}
+
+// Verinin tamamını yazar; resource::write kısmi yazma yapabilir. Hiç ilerleme olmazsa hata döner.
+fn tamamini_yaz(handle: crate::Handle, mut veri: &[u8]) -> Result<(), SahneError> {
+    while !veri.is_empty() {
+        match resource::write(handle, veri)? {
+            0 => return Err(SahneError::InvalidOperation),
+            yazilan => veri = &veri[yazilan..],
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,340 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+
+use serde::{Deserialize, Serialize};
+use postcard; // Politika çekirdeğe postcard ile kodlanarak geçirilir
+
+// Paketin bildirdiği betik izinleri
+use crate::package::BetikIzinleri;
+// Yol desenleri tetikleyicilerle aynı sözdizimini kullanır ('*', '?', '**')
+use crate::srctrigger::desen_eslesir;
+// Sahne64 üzerinde betik görevi başlatma
+use crate::srcscript;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, debug};
+
+
+// Betiğin kaynak katmanından istediği erişim türü.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Erisim {
+    Okuma,
+    Yazma, // Oluşturma, kesme ve silme dahil
+    Ag, // Ağ bağlantısı açma; hedef bağlanılan adrestir
+}
+
+impl Erisim {
+    pub fn ad(&self) -> &'static str {
+        match self {
+            Erisim::Okuma => "okuma",
+            Erisim::Yazma => "yazma",
+            Erisim::Ag => "ag",
+        }
+    }
+}
+
+// Bir betik görevinin uyacağı erişim politikası. Listede olmayan her erişim reddedilir.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxPolitikasi {
+    pub okunabilir: Vec<String>, // Kaynak ID desenleri
+    pub yazilabilir: Vec<String>, // Kaynak ID desenleri; yazılabilir yollar okunabilir de sayılır
+    pub ag: bool,
+}
+
+impl SandboxPolitikasi {
+    // Paketin bildirdiği izinlerden betik politikası oluşturur; betiğin kendi Kaynağı okunabilir eklenir.
+    pub fn betik_icin(izinler: &BetikIzinleri, betik_kaynagi_id: &str) -> Self {
+        let mut okunabilir = izinler.okunabilir_yollar.clone();
+        okunabilir.push(betik_kaynagi_id.to_owned());
+        SandboxPolitikasi {
+            okunabilir,
+            yazilabilir: izinler.yazilabilir_yollar.clone(),
+            ag: izinler.ag,
+        }
+    }
+
+    // Erişime izin veriliyor mu? "." veya ".." bileşeni içeren yollar desenlerden kaçabileceği için her zaman reddedilir.
+    pub fn izin_veriyor_mu(&self, erisim: Erisim, hedef: &str) -> bool {
+        match erisim {
+            Erisim::Ag => self.ag,
+            _ if hedef.split('/').any(|bilesen| bilesen == "." || bilesen == "..") => false,
+            Erisim::Okuma => desenlerden_biri(&self.okunabilir, hedef) || desenlerden_biri(&self.yazilabilir, hedef),
+            Erisim::Yazma => desenlerden_biri(&self.yazilabilir, hedef),
+        }
+    }
+
+    // Görev başlatılırken çekirdeğe (task::spawn_sandboxed) geçirilen kodlanmış biçim.
+    pub fn kodla(&self) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+        postcard::to_allocvec(self).map_err(PaketYoneticisiHatasi::SerializationError)
+    }
+}
+
+fn desenlerden_biri(desenler: &[String], hedef: &str) -> bool {
+    desenler.iter().any(|desen| desen_eslesir(desen, hedef))
+}
+
+// Reddedilen tek bir erişim. Sahne64'te çekirdek biten görevin ihlallerini bu biçimde (postcard) döndürür.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Ihlal {
+    pub erisim: Erisim,
+    pub hedef: String,
+}
+
+impl core::fmt::Display for Ihlal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.erisim.ad(), self.hedef)
+    }
+}
+
+// Bir betik çalışması boyunca erişimleri politikaya göre karara bağlar; reddedilenleri günlüğe yazar ve biriktirir.
+// Kaynak katmanının uygulama noktasıdır: her arka uç erişimleri buradan geçirir.
+pub struct PolitikaDenetleyicisi {
+    betik: String,
+    politika: SandboxPolitikasi,
+    ihlaller: Vec<Ihlal>,
+}
+
+impl PolitikaDenetleyicisi {
+    pub fn yeni(betik_kaynagi_id: &str, politika: SandboxPolitikasi) -> Self {
+        PolitikaDenetleyicisi { betik: betik_kaynagi_id.to_owned(), politika, ihlaller: Vec::new() }
+    }
+
+    // Dönüş değeri: Erişime izin verildiyse true. Reddedilen erişim günlüğe yazılır ve kaydedilir.
+    pub fn denetle(&mut self, erisim: Erisim, hedef: &str) -> bool {
+        if self.politika.izin_veriyor_mu(erisim, hedef) {
+            debug!("Sandbox ({}): {} izin verildi: {}", self.betik, erisim.ad(), hedef); // no_std log
+            return true;
+        }
+        warn!("Sandbox ({}): {} reddedildi: {}", self.betik, erisim.ad(), hedef); // no_std log
+        self.ihlaller.push(Ihlal { erisim, hedef: hedef.to_owned() });
+        false
+    }
+
+    // Uygulamayı başka bir katman yaptığında (Sahne64 çekirdeği) orada reddedilen erişimi kaydeder.
+    pub fn ihlali_kaydet(&mut self, ihlal: Ihlal) {
+        warn!("Sandbox ({}): {} reddedildi: {}", self.betik, ihlal.erisim.ad(), ihlal.hedef); // no_std log
+        self.ihlaller.push(ihlal);
+    }
+
+    pub fn ihlaller(&self) -> &[Ihlal] {
+        &self.ihlaller
+    }
+
+    // Çalışma bittiğinde çağrılır; reddedilen erişim olduysa SandboxIhlali hatası döner.
+    pub fn sonuclandir(self) -> Result<(), PaketYoneticisiHatasi> {
+        if self.ihlaller.is_empty() {
+            return Ok(());
+        }
+        Err(PaketYoneticisiHatasi::SandboxIhlali {
+            betik: self.betik,
+            ihlaller: self.ihlaller.iter().map(|ihlal| ihlal.to_string()).collect(),
+        })
+    }
+}
+
+// Betikleri bir politika altında çalıştıran arka uç.
+pub trait SandboxArkaUcu {
+    // Betiği politika altında çalıştırır. Politika dışı erişimler reddedilir ve SandboxIhlali hatası döner.
+    fn betik_calistir(&mut self, betik_kaynagi_id: &str, args: &[u8], politika: &SandboxPolitikasi) -> Result<(), PaketYoneticisiHatasi>;
+}
+
+// Sahne64 arka ucu: politika görev başlatılırken yalnızca o göreve bağlanır ve çekirdeğin kaynak katmanı tarafından
+// uygulanır. Görev bittikten sonra çekirdeğin reddettiği erişimler okunup PolitikaDenetleyicisi'ne kaydedilir.
+pub struct Sahne64ArkaUcu;
+
+impl SandboxArkaUcu for Sahne64ArkaUcu {
+    fn betik_calistir(&mut self, betik_kaynagi_id: &str, args: &[u8], politika: &SandboxPolitikasi) -> Result<(), PaketYoneticisiHatasi> {
+        info!("Betik Sahne64 sandbox'ında başlatılıyor: {} (ağ: {})", betik_kaynagi_id, politika.ag); // no_std log
+        srcscript::betik_calistir(betik_kaynagi_id, args, politika)
+    }
+}
+
+
+// Host (Linux) arka ucu: Sahne64 kaynak katmanını bir dizin üzerinde taklit eder ve aynı politikayı uygular.
+// "sahne://a/b" Kaynak ID'leri `kok/a/b` dosyalarına eşlenir.
+// Kapsam: bu arka uç gerçek betikleri çalıştırmaz ve bir yalıtım sınırı değildir. Betikler Sahne64 kodu olduğu için
+// host'ta çalıştırılamaz; her betik Kaynak ID'si için önceden kaydedilmiş bir Rust kapanışı çağrılır ve kapanış
+// aynı süreçte, tam host yetkileriyle çalışır. Politika yalnızca kapanışın HostKaynakKatmani üzerinden yaptığı
+// erişimlere uygulanır; amacı politika ve ihlal raporlama mantığını host'ta test etmektir.
+// Yalnızca `std` özelliğiyle derlenir.
+#[cfg(feature = "std")]
+pub mod host {
+    extern crate std;
+
+    use super::*;
+    use std::boxed::Box;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io;
+    use std::net::TcpStream;
+    use std::path::PathBuf;
+
+    pub type HostBetigi = Box<dyn Fn(&mut HostKaynakKatmani, &[u8]) -> io::Result<()>>;
+
+    // Betiğin gördüğü kaynak katmanı; her erişim önce politika denetleyicisinden geçer.
+    pub struct HostKaynakKatmani {
+        kok: PathBuf,
+        denetleyici: PolitikaDenetleyicisi,
+    }
+
+    impl HostKaynakKatmani {
+        fn izin_iste(&mut self, erisim: Erisim, hedef: &str) -> io::Result<()> {
+            if self.denetleyici.denetle(erisim, hedef) {
+                Ok(())
+            } else {
+                Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("sandbox: {} reddedildi: {}", erisim.ad(), hedef)))
+            }
+        }
+
+        fn yol(&self, kaynak_id: &str) -> PathBuf {
+            self.kok.join(kaynak_id.trim_start_matches("sahne://").trim_start_matches('/'))
+        }
+
+        pub fn oku(&mut self, kaynak_id: &str) -> io::Result<Vec<u8>> {
+            self.izin_iste(Erisim::Okuma, kaynak_id)?;
+            fs::read(self.yol(kaynak_id))
+        }
+
+        pub fn yaz(&mut self, kaynak_id: &str, veri: &[u8]) -> io::Result<()> {
+            self.izin_iste(Erisim::Yazma, kaynak_id)?;
+            let yol = self.yol(kaynak_id);
+            if let Some(ust) = yol.parent() {
+                fs::create_dir_all(ust)?;
+            }
+            fs::write(yol, veri)
+        }
+
+        pub fn sil(&mut self, kaynak_id: &str) -> io::Result<()> {
+            self.izin_iste(Erisim::Yazma, kaynak_id)?;
+            fs::remove_file(self.yol(kaynak_id))
+        }
+
+        pub fn baglan(&mut self, adres: &str) -> io::Result<TcpStream> {
+            self.izin_iste(Erisim::Ag, adres)?;
+            TcpStream::connect(adres)
+        }
+    }
+
+    pub struct HostArkaUcu {
+        kok: PathBuf,
+        betikler: HashMap<String, HostBetigi>,
+    }
+
+    impl HostArkaUcu {
+        // kok: Sahne64 Kaynak ağacının eşlendiği host dizini.
+        pub fn yeni(kok: PathBuf) -> Self {
+            HostArkaUcu { kok, betikler: HashMap::new() }
+        }
+
+        pub fn betik_ekle(&mut self, betik_kaynagi_id: &str, betik: HostBetigi) {
+            self.betikler.insert(betik_kaynagi_id.to_owned(), betik);
+        }
+    }
+
+    impl SandboxArkaUcu for HostArkaUcu {
+        fn betik_calistir(&mut self, betik_kaynagi_id: &str, args: &[u8], politika: &SandboxPolitikasi) -> Result<(), PaketYoneticisiHatasi> {
+            let betik = self.betikler.get(betik_kaynagi_id).ok_or_else(|| {
+                PaketYoneticisiHatasi::BetikCalistirmaHatasi(format!("Host betiği kayıtlı değil: {}", betik_kaynagi_id))
+            })?;
+            let mut katman = HostKaynakKatmani {
+                kok: self.kok.clone(),
+                denetleyici: PolitikaDenetleyicisi::yeni(betik_kaynagi_id, politika.clone()),
+            };
+            let sonuc = betik(&mut katman, args);
+            // Reddedilen erişimler, betiğin bunları nasıl ele aldığından bağımsız olarak hatadır.
+            katman.denetleyici.sonuclandir()?;
+            sonuc.map_err(|e| PaketYoneticisiHatasi::BetikCalistirmaHatasi(format!("{}: {}", betik_kaynagi_id, e)))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_host_arka_ucu_politikayi_uygular() {
+            let kok = std::env::temp_dir().join(format!("sahne-sandbox-{}", std::process::id()));
+            let betik_id = "sahne://system/package_scripts/fontconfig/fc-cache";
+            let mut arka_uc = HostArkaUcu::yeni(kok.clone());
+            arka_uc.betik_ekle(betik_id, Box::new(|katman: &mut HostKaynakKatmani, _: &[u8]| {
+                katman.yaz("sahne://config/fonts/cache", b"onbellek")?;
+                assert_eq!(katman.oku("sahne://config/fonts/cache")?, b"onbellek");
+                // Bildirilmemiş erişimler reddedilir; betik hatayı yutsa bile çalışma başarısız sayılır
+                assert!(katman.yaz("sahne://config/passwd", b"x").is_err());
+                assert!(katman.baglan("127.0.0.1:9").is_err());
+                Ok(())
+            }));
+
+            let izinler = BetikIzinleri {
+                yazilabilir_yollar: Vec::from([String::from("sahne://config/fonts/**")]),
+                okunabilir_yollar: Vec::new(),
+                ag: false,
+            };
+            let politika = SandboxPolitikasi::betik_icin(&izinler, betik_id);
+            match arka_uc.betik_calistir(betik_id, b"", &politika) {
+                Err(PaketYoneticisiHatasi::SandboxIhlali { betik, ihlaller }) => {
+                    assert_eq!(betik, betik_id);
+                    assert_eq!(ihlaller, ["yazma sahne://config/passwd", "ag 127.0.0.1:9"]);
+                }
+                sonuc => panic!("beklenmeyen sonuç: {:?}", sonuc),
+            }
+            assert_eq!(fs::read(kok.join("config/fonts/cache")).unwrap(), b"onbellek");
+            assert!(!kok.join("config/passwd").exists());
+            let _ = fs::remove_dir_all(kok);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_politika_denetimi() {
+        let izinler = BetikIzinleri {
+            yazilabilir_yollar: Vec::from([String::from("sahne://config/fonts/**")]),
+            okunabilir_yollar: Vec::from([String::from("sahne://installed_packages/fontconfig/**")]),
+            ag: false,
+        };
+        let betik = "sahne://system/package_scripts/fontconfig/fc-cache";
+        let politika = SandboxPolitikasi::betik_icin(&izinler, betik);
+
+        assert!(politika.izin_veriyor_mu(Erisim::Okuma, betik));
+        assert!(politika.izin_veriyor_mu(Erisim::Okuma, "sahne://installed_packages/fontconfig/share/a.conf"));
+        assert!(!politika.izin_veriyor_mu(Erisim::Yazma, "sahne://installed_packages/fontconfig/share/a.conf"));
+        assert!(politika.izin_veriyor_mu(Erisim::Yazma, "sahne://config/fonts/cache/x"));
+        assert!(politika.izin_veriyor_mu(Erisim::Okuma, "sahne://config/fonts/cache/x")); // yazılabilir => okunabilir
+        assert!(!politika.izin_veriyor_mu(Erisim::Yazma, "sahne://config/fonts/../passwd"));
+        assert!(!politika.izin_veriyor_mu(Erisim::Okuma, "sahne://config/passwd"));
+        assert!(!politika.izin_veriyor_mu(Erisim::Ag, "10.0.0.1:80"));
+
+        let mut denetleyici = PolitikaDenetleyicisi::yeni(betik, politika);
+        assert!(denetleyici.denetle(Erisim::Yazma, "sahne://config/fonts/cache/x"));
+        assert!(denetleyici.sonuclandir().is_ok());
+    }
+
+    #[test]
+    fn test_ihlaller_birikir() {
+        let mut denetleyici = PolitikaDenetleyicisi::yeni("betik", SandboxPolitikasi::default());
+        assert!(!denetleyici.denetle(Erisim::Okuma, "sahne://a"));
+        assert!(!denetleyici.denetle(Erisim::Ag, "example.org:443"));
+        assert_eq!(denetleyici.ihlaller().len(), 2);
+        match denetleyici.sonuclandir() {
+            Err(PaketYoneticisiHatasi::SandboxIhlali { ihlaller, .. }) => {
+                assert_eq!(ihlaller, ["okuma sahne://a", "ag example.org:443"]);
+            }
+            sonuc => panic!("beklenmeyen sonuç: {:?}", sonuc),
+        }
+    }
+
+    // Sahne64ArkaUcu testleri mock resource::control ve task::spawn gerektirir.
+}
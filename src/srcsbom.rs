@@ -0,0 +1,591 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, BTreeMap, format! için
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::collections::BTreeMap; // Deterministik sıra için
+
+use serde::Serialize;
+use serde_json; // SBOM belgeleri JSON biçiminde (alloc özellikli no_std backend varsayımıyla)
+
+// no_std ve alloc uyumlu kripto ve hex crate'leri (belge kimliği için)
+use sha2::{Sha256, Digest};
+use hex;
+
+// Kurulu paket kayıtları: paket meta verisi ve kurulan dosyalar
+use crate::srcinstalled::{KuruluPaketKaydi, KuruluPaketVeritabani};
+// Paket arşivi sağlama toplamlarının algoritma adları
+use crate::srcchecksum::OzetAlgoritmasi;
+// Paket.lisans alanının SPDX ifadesi olarak ayrıştırılması
+use crate::srclicense::LisansIfadesi;
+// Paket.bagimliliklar girdilerinden paket adının çıkarılması ("libc >= 2.31" -> "libc")
+use crate::srcversion::BagimlilikKisiti;
+
+// Sahne64 API modülleri
+use crate::resource;
+use crate::SahneError;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, error};
+
+
+// SBOM'u oluşturan aracın adı (SPDX creators ve CycloneDX metadata.tools içinde).
+pub const ARAC_ADI: &str = "sahne64-paket-yoneticisi";
+// SPDX belge ad alanının tabanı. Çözümlenebilir olması gerekmez, yalnızca benzersiz olmalıdır;
+// belge adı ve içerikten türetilen kimlik sona eklenir.
+pub const VARSAYILAN_SPDX_AD_ALANI: &str = "https://sahne64.local/spdx";
+
+// Desteklenen SBOM biçimleri.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomBicimi {
+    Spdx, // SPDX 2.3 JSON
+    CycloneDx, // CycloneDX 1.5 JSON
+}
+
+impl SbomBicimi {
+    pub fn ad(&self) -> &'static str {
+        match self {
+            SbomBicimi::Spdx => "spdx",
+            SbomBicimi::CycloneDx => "cyclonedx",
+        }
+    }
+
+    // Komut satırı adından biçimi çözer (büyük/küçük harf duyarsız). Bilinmeyen adlar için None.
+    pub fn ayristir(ad: &str) -> Option<Self> {
+        match ad.to_lowercase().as_str() {
+            "spdx" => Some(SbomBicimi::Spdx),
+            "cyclonedx" | "cdx" => Some(SbomBicimi::CycloneDx),
+            _ => None,
+        }
+    }
+}
+
+// Belge düzeyindeki bilgiler. Aynı kayıtlar ve aynı seçenekler her zaman bayt bayt aynı belgeyi üretir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbomSecenekleri {
+    pub ad: String, // Belgenin (ör. imajın) adı
+    pub olusturulma: u64, // Oluşturulma zamanı (Unix saniyesi); yeniden üretilebilir imajlar için sabitlenebilir
+    pub ad_alani: String, // SPDX belge ad alanı tabanı
+}
+
+impl SbomSecenekleri {
+    pub fn yeni(ad: String, olusturulma: u64) -> Self {
+        SbomSecenekleri { ad, olusturulma, ad_alani: String::from(VARSAYILAN_SPDX_AD_ALANI) }
+    }
+}
+
+// Kurulu paket veritabanındaki tüm paketler için SBOM üretir.
+pub fn kurulu_paketlerden(
+    bicim: SbomBicimi,
+    veritabani: &KuruluPaketVeritabani,
+    secenekler: &SbomSecenekleri,
+) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let kayitlar = veritabani.tum_kayitlar()?;
+    let belge = sbom_olustur(bicim, &kayitlar, secenekler)?;
+    info!("{} SBOM oluşturuldu: {} paket, {} bayt", bicim.ad(), kayitlar.len(), belge.len()); // no_std log
+    Ok(belge)
+}
+
+// SBOM belgesini Kaynağa yazar (varsa üzerine yazılır).
+pub fn kaynaga_yaz(resource_id: &str, belge: &[u8]) -> Result<(), PaketYoneticisiHatasi> {
+    let handle = resource::acquire(
+        resource_id,
+        resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+    ).map_err(PaketYoneticisiHatasi::from)?;
+
+    let mut written = 0;
+    while written < belge.len() {
+        match resource::write(handle, &belge[written..]) {
+            Ok(0) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(SahneError::InvalidOperation));
+            }
+            Ok(bytes_written) => written += bytes_written,
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(PaketYoneticisiHatasi::from(e));
+            }
+        }
+    }
+
+    if let Err(e) = resource::release(handle) {
+        error!("SBOM: Kaynak release hatası ({}): {:?}", resource_id, e);
+    }
+    Ok(())
+}
+
+// Verilen kurulu paket kayıtlarından SBOM belgesini (JSON) üretir.
+// Paketler ada göre sıralanır; bağımlılıklar yalnızca kurulu paketlere ilişki olarak yazılır.
+pub fn sbom_olustur(
+    bicim: SbomBicimi,
+    kayitlar: &[KuruluPaketKaydi],
+    secenekler: &SbomSecenekleri,
+) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let sonuc = match bicim {
+        SbomBicimi::Spdx => serde_json::to_vec_pretty(&spdx_belgesi(kayitlar, secenekler)),
+        SbomBicimi::CycloneDx => serde_json::to_vec_pretty(&cyclonedx_belgesi(kayitlar, secenekler)),
+    };
+    sonuc.map_err(|e| {
+        PaketYoneticisiHatasi::UnknownError(format!("{} SBOM JSON'a çevrilemedi: {:?}", bicim.ad(), e))
+    })
+}
+
+
+// --- SPDX 2.3 ---
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxBelgesi<'a> {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: &'a str,
+    document_namespace: String,
+    creation_info: SpdxOlusturmaBilgisi,
+    packages: Vec<SpdxPaketi<'a>>,
+    relationships: Vec<SpdxIliskisi>,
+}
+
+#[derive(Serialize)]
+struct SpdxOlusturmaBilgisi {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPaketi<'a> {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: &'a str,
+    version_info: &'a str,
+    download_location: &'static str,
+    // SPDX 2.3 her dosya için SHA-1 ve paket doğrulama kodu ister; kayıtlarda yalnızca SHA-256 olduğu için
+    // dosyalar analiz edilmemiş bildirilir ve belgede listelenmez (dosya düzeyi ayrıntı CycloneDX belgesindedir).
+    files_analyzed: bool,
+    license_concluded: &'static str,
+    license_declared: String,
+    copyright_text: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checksums: Vec<SpdxOzeti<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxOzeti<'a> {
+    algorithm: &'static str,
+    checksum_value: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxIliskisi {
+    spdx_element_id: String,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
+
+fn spdx_belgesi<'a>(kayitlar: &'a [KuruluPaketKaydi], secenekler: &'a SbomSecenekleri) -> SpdxBelgesi<'a> {
+    let kurulu = kurulu_harita(kayitlar);
+    let paket_kimligi = |kayit: &KuruluPaketKaydi| spdx_kimligi("SPDXRef-Package-", &kayit.paket.ad);
+
+    let mut paketler = Vec::new();
+    let mut iliskiler = Vec::new();
+    for kayit in kurulu.values().copied() {
+        let paket = &kayit.paket;
+        let kimlik = paket_kimligi(kayit);
+        paketler.push(SpdxPaketi {
+            spdx_id: kimlik.clone(),
+            name: &paket.ad,
+            version_info: &paket.surum,
+            download_location: "NOASSERTION",
+            files_analyzed: false,
+            license_concluded: "NOASSERTION",
+            license_declared: lisans_ifadesi(kayit).unwrap_or_else(|| String::from("NOASSERTION")),
+            copyright_text: "NOASSERTION",
+            description: paket.aciklama.as_deref(),
+            checksums: arsiv_ozetleri(kayit)
+                .into_iter()
+                .map(|(algoritma, deger)| SpdxOzeti { algorithm: spdx_algoritma_adi(algoritma), checksum_value: deger })
+                .collect(),
+        });
+        iliskiler.push(SpdxIliskisi {
+            spdx_element_id: String::from("SPDXRef-DOCUMENT"),
+            relationship_type: "DESCRIBES",
+            related_spdx_element: kimlik.clone(),
+        });
+        for bagimlilik in kurulu_bagimliliklar(kayit, &kurulu) {
+            iliskiler.push(SpdxIliskisi {
+                spdx_element_id: kimlik.clone(),
+                relationship_type: "DEPENDS_ON",
+                related_spdx_element: paket_kimligi(bagimlilik),
+            });
+        }
+    }
+
+    SpdxBelgesi {
+        spdx_version: "SPDX-2.3",
+        data_license: "CC0-1.0",
+        spdx_id: "SPDXRef-DOCUMENT",
+        name: &secenekler.ad,
+        document_namespace: format!(
+            "{}/{}-{}",
+            secenekler.ad_alani.trim_end_matches('/'),
+            spdx_kimligi("", &secenekler.ad),
+            belge_kimligi(kayitlar, secenekler)
+        ),
+        creation_info: SpdxOlusturmaBilgisi {
+            created: iso8601(secenekler.olusturulma),
+            creators: Vec::from([format!("Tool: {}", ARAC_ADI)]),
+        },
+        packages: paketler,
+        relationships: iliskiler,
+    }
+}
+
+// SPDX algoritma adları (SPDX 2.3, 7.10).
+fn spdx_algoritma_adi(algoritma: OzetAlgoritmasi) -> &'static str {
+    match algoritma {
+        OzetAlgoritmasi::Md5 => "MD5",
+        OzetAlgoritmasi::Sha256 => "SHA256",
+        OzetAlgoritmasi::Blake3 => "BLAKE3",
+        OzetAlgoritmasi::Sha512 => "SHA512",
+    }
+}
+
+// SPDX kimlikleri yalnızca harf, rakam, '.' ve '-' içerebilir; diğer karakterler '-' olur.
+fn spdx_kimligi(onek: &str, ad: &str) -> String {
+    let mut kimlik = String::from(onek);
+    kimlik.extend(ad.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' }));
+    kimlik
+}
+
+
+// --- CycloneDX 1.5 ---
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CdxBelgesi<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    serial_number: String,
+    version: u32,
+    metadata: CdxMetaVerisi<'a>,
+    components: Vec<CdxBileseni<'a>>,
+    dependencies: Vec<CdxBagimliligi>,
+}
+
+#[derive(Serialize)]
+struct CdxMetaVerisi<'a> {
+    timestamp: String,
+    tools: CdxAraclari,
+    component: CdxBileseni<'a>,
+}
+
+#[derive(Serialize)]
+struct CdxAraclari {
+    components: Vec<CdxBileseni<'static>>,
+}
+
+#[derive(Serialize)]
+struct CdxBileseni<'a> {
+    #[serde(rename = "type")]
+    tur: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<CdxLisansi>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<CdxOzeti<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    components: Vec<CdxBileseni<'a>>,
+}
+
+impl<'a> CdxBileseni<'a> {
+    fn yeni(tur: &'static str, bom_ref: String, name: &'a str) -> Self {
+        CdxBileseni {
+            tur,
+            bom_ref,
+            name,
+            version: None,
+            author: None,
+            description: None,
+            licenses: Vec::new(),
+            hashes: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CdxLisansi {
+    expression: String,
+}
+
+#[derive(Serialize)]
+struct CdxOzeti<'a> {
+    alg: &'static str,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CdxBagimliligi {
+    #[serde(rename = "ref")]
+    bom_ref: String,
+    depends_on: Vec<String>,
+}
+
+fn cyclonedx_belgesi<'a>(kayitlar: &'a [KuruluPaketKaydi], secenekler: &'a SbomSecenekleri) -> CdxBelgesi<'a> {
+    let kurulu = kurulu_harita(kayitlar);
+    let bilesen_kimligi = |kayit: &KuruluPaketKaydi| format!("{}@{}", kayit.paket.ad, kayit.paket.surum);
+
+    let mut bilesenler = Vec::new();
+    let mut bagimliliklar = Vec::new();
+    for kayit in kurulu.values().copied() {
+        let paket = &kayit.paket;
+        let kimlik = bilesen_kimligi(kayit);
+        let mut bilesen = CdxBileseni::yeni("library", kimlik.clone(), &paket.ad);
+        bilesen.version = Some(&paket.surum);
+        bilesen.author = paket.yazar.as_deref();
+        bilesen.description = paket.aciklama.as_deref();
+        bilesen.licenses = lisans_ifadesi(kayit).map(|expression| Vec::from([CdxLisansi { expression }])).unwrap_or_default();
+        bilesen.hashes = arsiv_ozetleri(kayit)
+            .into_iter()
+            .map(|(algoritma, deger)| CdxOzeti { alg: cyclonedx_algoritma_adi(algoritma), content: deger })
+            .collect();
+        // İçeriği olan dosyalar paketin alt bileşenleri olarak; dizinler ve sembolik bağlar listelenmez.
+        bilesen.components = kayit.dosyalar
+            .iter()
+            .filter(|dosya| !dosya.sha256.is_empty())
+            .map(|dosya| {
+                let mut alt = CdxBileseni::yeni("file", format!("{}:{}", kimlik, dosya.yol), &dosya.yol);
+                alt.hashes = Vec::from([CdxOzeti { alg: "SHA-256", content: &dosya.sha256 }]);
+                alt
+            })
+            .collect();
+        bilesenler.push(bilesen);
+
+        // Bağımlılığı olmayan paketler de listelenir (CycloneDX'te "bağımlılığı yok" ile "bilinmiyor" ayrımı için).
+        bagimliliklar.push(CdxBagimliligi {
+            bom_ref: kimlik,
+            depends_on: kurulu_bagimliliklar(kayit, &kurulu).into_iter().map(bilesen_kimligi).collect(),
+        });
+    }
+
+    let arac = CdxBileseni::yeni("application", String::from(ARAC_ADI), ARAC_ADI);
+    CdxBelgesi {
+        bom_format: "CycloneDX",
+        spec_version: "1.5",
+        serial_number: format!("urn:uuid:{}", belge_kimligi(kayitlar, secenekler)),
+        version: 1,
+        metadata: CdxMetaVerisi {
+            timestamp: iso8601(secenekler.olusturulma),
+            tools: CdxAraclari { components: Vec::from([arac]) },
+            component: CdxBileseni::yeni("operating-system", secenekler.ad.clone(), &secenekler.ad),
+        },
+        components: bilesenler,
+        dependencies: bagimliliklar,
+    }
+}
+
+// CycloneDX algoritma adları (CycloneDX 1.5, hash-alg).
+fn cyclonedx_algoritma_adi(algoritma: OzetAlgoritmasi) -> &'static str {
+    match algoritma {
+        OzetAlgoritmasi::Md5 => "MD5",
+        OzetAlgoritmasi::Sha256 => "SHA-256",
+        OzetAlgoritmasi::Blake3 => "BLAKE3",
+        OzetAlgoritmasi::Sha512 => "SHA-512",
+    }
+}
+
+
+// --- Ortak yardımcılar ---
+
+// Kayıtları ada göre sıralı bir haritaya koyar (belgelerde deterministik sıra ve bağımlılık araması için).
+fn kurulu_harita(kayitlar: &[KuruluPaketKaydi]) -> BTreeMap<&str, &KuruluPaketKaydi> {
+    kayitlar.iter().map(|kayit| (kayit.paket.ad.as_str(), kayit)).collect()
+}
+
+// Paketin kurulu olan bağımlılıkları (tekrarsız, bildirim sırasıyla).
+// Kurulu olmayan veya ayrıştırılamayan bağımlılıklar belgeye yazılamaz; uyarı verilip atlanır.
+fn kurulu_bagimliliklar<'a>(kayit: &KuruluPaketKaydi, kurulu: &BTreeMap<&str, &'a KuruluPaketKaydi>) -> Vec<&'a KuruluPaketKaydi> {
+    let mut sonuc: Vec<&KuruluPaketKaydi> = Vec::new();
+    for girdi in &kayit.paket.bagimliliklar {
+        let kisit = match BagimlilikKisiti::ayristir(girdi) {
+            Ok(kisit) => kisit,
+            Err(e) => {
+                warn!("SBOM: {} bağımlılığı ayrıştırılamadı, atlanıyor: {}", kayit.paket.ad, e); // no_std log
+                continue;
+            }
+        };
+        match kurulu.get(kisit.ad.as_str()) {
+            Some(bagimlilik) if !sonuc.iter().any(|k| k.paket.ad == bagimlilik.paket.ad) => sonuc.push(bagimlilik),
+            Some(_) => {}
+            None => warn!("SBOM: {} bağımlılığı {} kurulu değil, ilişki yazılmıyor", kayit.paket.ad, kisit.ad), // no_std log
+        }
+    }
+    sonuc
+}
+
+// Paketin lisansı, kanonik SPDX ifadesi olarak. Lisansı olmayan veya ayrıştırılamayan paketler için None.
+fn lisans_ifadesi(kayit: &KuruluPaketKaydi) -> Option<String> {
+    let lisans = kayit.paket.lisans.as_deref().map(str::trim).filter(|lisans| !lisans.is_empty())?;
+    match LisansIfadesi::ayristir(lisans) {
+        Ok(ifade) => Some(format!("{}", ifade)),
+        Err(e) => {
+            warn!("SBOM: {} lisansı yazılmıyor: {}", kayit.paket.ad, e); // no_std log
+            None
+        }
+    }
+}
+
+// Paket arşivinin bilinen algoritmalarla sağlama toplamları, algoritma sırasıyla (checksums bir HashMap).
+fn arsiv_ozetleri(kayit: &KuruluPaketKaydi) -> Vec<(OzetAlgoritmasi, &str)> {
+    let mut ozetler: Vec<(OzetAlgoritmasi, &str)> = kayit.paket.checksums
+        .iter()
+        .filter_map(|(ad, deger)| OzetAlgoritmasi::addan(ad).map(|algoritma| (algoritma, deger.as_str())))
+        .collect();
+    ozetler.sort();
+    ozetler
+}
+
+// Belge adı, zaman ve paket listesinden türetilen UUID (RFC 9562 sürüm 8 biçiminde).
+// Aynı girdiler aynı kimliği verir, böylece SBOM yeniden üretilebilir kalır.
+fn belge_kimligi(kayitlar: &[KuruluPaketKaydi], secenekler: &SbomSecenekleri) -> String {
+    let mut ozet = Sha256::new();
+    ozet.update(secenekler.ad.as_bytes());
+    ozet.update(secenekler.olusturulma.to_le_bytes());
+    for kayit in kurulu_harita(kayitlar).values() {
+        ozet.update([0]);
+        ozet.update(kayit.paket.ad.as_bytes());
+        ozet.update([0]);
+        ozet.update(kayit.paket.surum.as_bytes());
+    }
+    let mut bayt = [0u8; 16];
+    bayt.copy_from_slice(&ozet.finalize()[..16]);
+    bayt[6] = (bayt[6] & 0x0f) | 0x80; // sürüm 8
+    bayt[8] = (bayt[8] & 0x3f) | 0x80; // RFC 4122 varyantı
+    let metin = hex::encode(bayt);
+    format!("{}-{}-{}-{}-{}", &metin[..8], &metin[8..12], &metin[12..16], &metin[16..20], &metin[20..])
+}
+
+// Unix saniyesini ISO 8601 UTC zaman damgasına çevirir (örn. "2024-03-01T12:00:00Z").
+fn iso8601(unix: u64) -> String {
+    // Gün sayısından takvim tarihine (proleptik Gregoryen, Hinnant'ın civil_from_days algoritması)
+    let gun = (unix / 86_400) as i64 + 719_468;
+    let saniye = unix % 86_400;
+    let donem = gun.div_euclid(146_097);
+    let donem_gunu = gun - donem * 146_097;
+    let donem_yili = (donem_gunu - donem_gunu / 1_460 + donem_gunu / 36_524 - donem_gunu / 146_096) / 365;
+    let yil_gunu = donem_gunu - (365 * donem_yili + donem_yili / 4 - donem_yili / 100);
+    let mp = (5 * yil_gunu + 2) / 153;
+    let ay_gunu = yil_gunu - (153 * mp + 2) / 5 + 1;
+    let ay = if mp < 10 { mp + 3 } else { mp - 9 };
+    let yil = donem_yili + donem * 400 + if ay <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", yil, ay, ay_gunu, saniye / 3_600, saniye / 60 % 60, saniye % 60)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use crate::package::Paket;
+    use crate::srcarchive::GirdiTuru;
+    use crate::srcinstalled::KuruluDosya;
+    use serde_json::Value;
+
+    fn kayit(ad: &str, surum: &str, bagimliliklar: &[&str], lisans: Option<&str>) -> KuruluPaketKaydi {
+        let mut paket = Paket::yeni(ad.to_string(), surum.to_string(), bagimliliklar.iter().map(|b| b.to_string()).collect());
+        paket.lisans = lisans.map(str::to_string);
+        paket.checksums.insert(String::from("sha256"), format!("{}-arsiv-ozeti", ad));
+        let dosya = |yol: &str, tur: GirdiTuru, sha256: &str| KuruluDosya {
+            yol: yol.to_string(), tur, boyut: 0, mod_: 0o644, sahip: 0, grup: 0, sha256: sha256.to_string(), bag_hedefi: None,
+        };
+        KuruluPaketKaydi {
+            paket,
+            dosyalar: Vec::from([
+                dosya("bin", GirdiTuru::Dizin, ""),
+                dosya(&format!("bin/{}", ad), GirdiTuru::Dosya, "ab12"),
+            ]),
+        }
+    }
+
+    fn ornek() -> (Vec<KuruluPaketKaydi>, SbomSecenekleri) {
+        let kayitlar = Vec::from([
+            kayit("zlib", "1.3", &[], Some("Zlib")),
+            kayit("curl", "8.5.0", &["zlib >= 1.2", "openssl"], Some("curl AND (MIT or ISC)")),
+        ]);
+        (kayitlar, SbomSecenekleri::yeni(String::from("imaj 1"), 951_782_400))
+    }
+
+    #[test]
+    fn test_iso8601() {
+        assert_eq!(iso8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(iso8601(951_782_400 + 3_723), "2000-02-29T01:02:03Z");
+        assert_eq!(iso8601(1_709_294_400), "2024-03-01T12:00:00Z");
+    }
+
+    #[test]
+    fn test_spdx() {
+        let (kayitlar, secenekler) = ornek();
+        let belge: Value = serde_json::from_slice(&sbom_olustur(SbomBicimi::Spdx, &kayitlar, &secenekler).unwrap()).unwrap();
+        assert_eq!(belge["spdxVersion"], "SPDX-2.3");
+        assert_eq!(belge["creationInfo"]["created"], "2000-02-29T00:00:00Z");
+        assert!(belge["documentNamespace"].as_str().unwrap().starts_with("https://sahne64.local/spdx/imaj-1-"));
+
+        // Paketler ada göre sıralı, lisans kanonik biçimde
+        let paketler = belge["packages"].as_array().unwrap();
+        assert_eq!(paketler[0]["SPDXID"], "SPDXRef-Package-curl");
+        assert_eq!(paketler[0]["licenseDeclared"], "curl AND (MIT OR ISC)");
+        assert_eq!(paketler[0]["checksums"][0]["algorithm"], "SHA256");
+        assert_eq!(paketler[1]["versionInfo"], "1.3");
+
+        // SHA-1 olmadan SPDX dosya girdisi yazılamaz; dosyalar analiz edilmemiş bildirilir
+        assert_eq!(paketler[0]["filesAnalyzed"], false);
+        assert!(belge.get("files").is_none());
+        // Kurulu olmayan openssl için ilişki yazılmaz
+        let iliskiler: Vec<(&str, &str, &str)> = belge["relationships"].as_array().unwrap().iter()
+            .map(|i| (i["spdxElementId"].as_str().unwrap(), i["relationshipType"].as_str().unwrap(), i["relatedSpdxElement"].as_str().unwrap()))
+            .collect();
+        assert!(iliskiler.contains(&("SPDXRef-Package-curl", "DEPENDS_ON", "SPDXRef-Package-zlib")));
+        assert_eq!(iliskiler.iter().filter(|i| i.1 == "CONTAINS").count(), 0);
+        assert_eq!(iliskiler.iter().filter(|i| i.1 == "DEPENDS_ON").count(), 1);
+    }
+
+    #[test]
+    fn test_cyclonedx_ve_yeniden_uretilebilirlik() {
+        let (mut kayitlar, secenekler) = ornek();
+        let ilk = sbom_olustur(SbomBicimi::CycloneDx, &kayitlar, &secenekler).unwrap();
+        let belge: Value = serde_json::from_slice(&ilk).unwrap();
+        assert_eq!(belge["bomFormat"], "CycloneDX");
+        assert_eq!(belge["components"][0]["bom-ref"], "curl@8.5.0");
+        assert_eq!(belge["components"][0]["licenses"][0]["expression"], "curl AND (MIT OR ISC)");
+        assert_eq!(belge["components"][0]["hashes"][0]["alg"], "SHA-256");
+        assert_eq!(belge["components"][0]["components"][0]["name"], "bin/curl");
+        assert_eq!(belge["dependencies"][0]["dependsOn"][0], "zlib@1.3");
+        assert_eq!(belge["dependencies"][1]["dependsOn"].as_array().unwrap().len(), 0);
+
+        // Girdi sırası çıktıyı değiştirmez
+        kayitlar.reverse();
+        assert_eq!(sbom_olustur(SbomBicimi::CycloneDx, &kayitlar, &secenekler).unwrap(), ilk);
+        assert_eq!(SbomBicimi::ayristir("CDX"), Some(SbomBicimi::CycloneDx));
+    }
+
+    // Kurulu paket veritabanından okuma testleri mock resource::acquire/read/release gerektirir.
+}
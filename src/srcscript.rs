@@ -12,57 +12,92 @@ use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
 // log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
 use log::{info, error};
 
+use postcard; // Politika ve ihlal kayıtları çekirdekle postcard ile değiş tokuş edilir
+
+// Betiğin çalışacağı erişim politikası ve kaynak katmanının reddettiği erişimler
+use crate::srcsandbox::{Ihlal, PolitikaDenetleyicisi, SandboxPolitikasi};
+
 // Sahne64 API modülleri
 use crate::task; // Görev yönetimi (spawn, exit)
 use crate::resource; // Betik Kaynağını acquire etmek için
 use crate::SahneError; // Sahne64 hata türü
 use crate::Handle; // Kaynak Handle'ları
 
-// Verilen betik Kaynağını (executable code resource) yeni bir Sahne64 görevi olarak çalıştırır.
-// Not: Sahne64 API'sında task::wait ve task çıktısını (stdout/stderr) yakalama mekanizmaları
-// henüz tanımlanmamıştır. Bu nedenle bu fonksiyon sadece betik görevini başlatır,
-// tamamlanmasını beklemez, çıkış kodunu veya çıktısını alamaz.
+// Paket betiklerinin kök Kaynağı; her paketin betikleri kendi adını taşıyan alt dizindedir.
+pub const PAKET_BETIK_KOKU: &str = "sahne://system/package_scripts/";
+
+// Betik Kaynağı paketin kendi betik dizininde (PAKET_BETIK_KOKU/<paket>/) mi? Betik ID'leri paket meta verisinden
+// geldiği için aksi halde bir paket başka bir paketin betiğini kendi izinleriyle çalıştırabilirdi.
+pub fn paketin_betigi_mi(paket_adi: &str, betik_kaynagi_id: &str) -> bool {
+    let onek = format!("{}{}/", PAKET_BETIK_KOKU, paket_adi);
+    match betik_kaynagi_id.strip_prefix(onek.as_str()) {
+        Some(kalan) => kalan.split('/').all(|bilesen| !bilesen.is_empty() && bilesen != "." && bilesen != ".."),
+        None => false,
+    }
+}
+
+// Betiği yürütmek için kullanılan Sahne64 görev çağrıları (Varsayımsal, gerçek Sahne64 API'sında tanımlanmalıdır):
+// - task::spawn_sandboxed(code_handle, args, policy) -> Result<TaskId, SahneError>
+//   policy: postcard ile kodlanmış SandboxPolitikasi. Politika yalnızca başlatılan göreve (ve alt görevlerine) uygulanır;
+//   kod Kaynağı değiştirilmez, aynı betiği başka bir politikayla çalıştıran görevler birbirini etkilemez.
+//   Politika dışı acquire ve ağ çağrıları kaynak katmanında SahneError::PermissionDenied ile reddedilir.
+// - task::wait(tid) -> Result<i32, SahneError>: Görev bitene kadar bekler ve çıkış kodunu döner.
+// - task::sandbox_violations(tid) -> Result<Vec<u8>, SahneError>: Biten görev için reddedilen erişimler,
+//   postcard ile kodlanmış Vec<Ihlal>.
+
+// Verilen betik Kaynağını (executable code resource) politika altında yeni bir Sahne64 görevi olarak çalıştırır,
+// bitmesini bekler ve kaynak katmanının reddettiği erişimleri toplar.
+// Not: Görev çıktısını (stdout/stderr) yakalama mekanizması Sahne64 API'sında henüz tanımlanmamıştır.
 // betik_kaynagi_id: Çalıştırılacak betik veya yürütülebilir dosya Kaynağının ID'si (örn. "sahne://system/package_scripts/my_package/install.sh").
 // args: Betik görevine geçilecek argümanlar (byte dilimi olarak).
-// Dönüş değeri: Başarı (görevin başlatılması) veya PaketYoneticisiHatasi.
-pub fn betik_calistir(betik_kaynagi_id: &str, args: &[u8]) -> Result<(), PaketYoneticisiHatasi> { // betik_yolu -> betik_kaynagi_id: &str, args eklendi
+// politika: Betik görevinin uyacağı erişim politikası (srcsandbox::SandboxPolitikasi::betik_icin).
+// Dönüş değeri: Betik sıfır çıkış koduyla ve ihlalsiz bittiyse başarı. Reddedilen erişim olduysa (betik hatayı
+// yutsa bile) SandboxIhlali, sıfırdan farklı çıkış kodunda BetikCalistirmaHatasi; betik Kaynağı yoksa
+// PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound).
+pub fn betik_calistir(betik_kaynagi_id: &str, args: &[u8], politika: &SandboxPolitikasi) -> Result<(), PaketYoneticisiHatasi> { // betik_yolu -> betik_kaynagi_id: &str, args eklendi
     info!("Betik çalıştırılıyor (task başlatılıyor): {}", betik_kaynagi_id); // no_std log
+    let politika_baytlari = politika.kodla()?;
 
     // Betik Kaynağını acquire et.
     // Yürütülebilir kaynaklar genellikle MODE_READ izniyle acquire edilir.
     match resource::acquire(betik_kaynagi_id, resource::MODE_READ) { // Kaynak ID kullanılıyor
         Ok(script_handle) => {
-            // Yeni bir görev (task) olarak betiği başlat.
-            // task::spawn function signature: spawn(code_handle: Handle, args: &[u8]) -> Result<TaskId, SahneError>
-            match task::spawn(script_handle, args) { // args pass ediliyor
-                Ok(new_tid) => {
-                    info!("Betik görevi başlatıldı, TaskId: {:?}", new_tid); // no_std log
-                    // Betik görevi başlatıldıktan sonra Handle'ı serbest bırakabiliriz.
-                    // Betik kendi Kaynaklarına kendisi erişmelidir.
-                    let _ = resource::release(script_handle); // Handle'ı bırak
-
-                    // !!! Sahne64 API eksikliği: task::wait(new_tid) ve task çıktısını yakalama burada yapılamaz.
-                    // Betiğin başarıyla tamamlandığını veya hata verdiğini bilemeyiz.
-                    // Gerçek bir paket yöneticisi için bu kritik bir eksikliktir.
-
-                    println!("UYARI: Betik görevinin tamamlanması beklenmiyor ve çıktısı yakalanmıyor. Bu fonksiyon sadece görevi başlatır."); // no_std print
-
-                    Ok(()) // Görev başlatıldı olarak başarı dön
-                }
-                Err(e) => {
-                    // Görev başlatılamadı hatası.
-                    let _ = resource::release(script_handle); // Hata durumunda handle'ı temizle
-                    let hata_mesaji = format!( // format! alloc
-                        "Betik görevi başlatılamadı (Kaynak: {}): {:?}",
-                        betik_kaynagi_id, e
-                    );
-                    error!("{}", hata_mesaji); // no_std log
-                    Err(PaketYoneticisiHatasi::BetikCalistirmaHatasi(hata_mesaji)) // SahneError -> PaketYoneticisiHatasi
-                }
+            // Yeni bir görev (task) olarak betiği politikasıyla birlikte başlat; politika göreve başlatılırken bağlanır.
+            let baslatma = task::spawn_sandboxed(script_handle, args, &politika_baytlari);
+            // Görev başlatıldıktan sonra Handle'ı serbest bırakabiliriz. Betik kendi Kaynaklarına kendisi erişmelidir.
+            let _ = resource::release(script_handle);
+            let new_tid = baslatma.map_err(|e| {
+                let hata_mesaji = format!("Betik görevi başlatılamadı (Kaynak: {}): {:?}", betik_kaynagi_id, e);
+                error!("{}", hata_mesaji); // no_std log
+                PaketYoneticisiHatasi::BetikCalistirmaHatasi(hata_mesaji)
+            })?;
+            info!("Betik görevi başlatıldı, TaskId: {:?}", new_tid); // no_std log
+
+            let cikis_kodu = task::wait(new_tid).map_err(|e| {
+                PaketYoneticisiHatasi::BetikCalistirmaHatasi(format!("Betik görevi beklenemedi (Kaynak: {}): {:?}", betik_kaynagi_id, e))
+            })?;
+            let ihlal_baytlari = task::sandbox_violations(new_tid).map_err(|e| {
+                PaketYoneticisiHatasi::BetikCalistirmaHatasi(format!("Betik ihlalleri okunamadı (Kaynak: {}): {:?}", betik_kaynagi_id, e))
+            })?;
+            let ihlaller: Vec<Ihlal> = postcard::from_bytes(&ihlal_baytlari).map_err(PaketYoneticisiHatasi::DeserializationError)?;
+
+            // Reddedilen erişimler de host arka ucundakiyle aynı denetleyiciden geçip aynı hatayla raporlanır.
+            let mut denetleyici = PolitikaDenetleyicisi::yeni(betik_kaynagi_id, politika.clone());
+            for ihlal in ihlaller {
+                denetleyici.ihlali_kaydet(ihlal);
+            }
+            denetleyici.sonuclandir()?;
+
+            if cikis_kodu != 0 {
+                let hata_mesaji = format!("Betik {} çıkış koduyla bitti (Kaynak: {})", cikis_kodu, betik_kaynagi_id);
+                error!("{}", hata_mesaji); // no_std log
+                return Err(PaketYoneticisiHatasi::BetikCalistirmaHatasi(hata_mesaji));
             }
+            Ok(())
         }
+        Err(SahneError::ResourceNotFound) => Err(PaketYoneticisiHatasi::from(SahneError::ResourceNotFound)),
         Err(e) => {
-            // Betik Kaynağı acquire hatası (örn. bulunamadı, izin yok).
+            // Betik Kaynağı acquire hatası (örn. izin yok).
             let hata_mesaji = format!( // format! alloc
                 "Betik Kaynağı acquire hatası (Kaynak: {}): {:?}",
                 betik_kaynagi_id, e
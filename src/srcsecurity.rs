@@ -1,14 +1,20 @@
 #![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
 extern crate alloc; // String, Vec, format! için
 
-// no_std ve alloc uyumlu kripto ve hex crate'leri
-use sha2::{Sha256, Digest};
+// no_std ve alloc uyumlu hex crate'i
 use hex;
 use hex::FromHexError; // hex hata türü
 
+// Ed25519 ayrık imzalar ve anahtar halkası
+use crate::srcsigning::{self, AnahtarHalkasi, AyrikImza};
+// Yayıncı anahtarlarının kapsamları
+use crate::srctrust::{EsikKurali, TrustManager};
+// Yerel güvenlik bildirimi veritabanı
+use crate::srcadvisory::{BildiriVeritabani, Bulgu};
+// Betik ve yürütülebilirler için erişim politikası
+use crate::srcsandbox::{Sahne64ArkaUcu, SandboxArkaUcu, SandboxPolitikasi};
+
 // Sahne64 API modülleri
-use crate::resource; // Kaynak işlemleri (okuma)
-use crate::task; // Görev işlemleri (sandbox çalıştırma için)
 use crate::SahneError; // Sahne64 hata türü
 use crate::Handle; // Kaynak Handle'ları
 
@@ -23,6 +29,7 @@ use log::{info, error, warn, debug}; // Ek log seviyeleri eklendi
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned;
 
 // no_std uyumlu print makroları (örnek çıktılar için)
 use crate::print_macros::{println, eprintln};
@@ -41,8 +48,30 @@ pub enum SecurityError {
     // Geçersiz imza dosyası formatı
     InvalidSignatureFile(String), // İmza dosyasının içeriği beklenen formatta değil (örn. geçerli hex değil veya boş). String alloc gerektirir.
 
-    // İmza doğrulama başarısız oldu (özetler eşleşmiyor)
-    SignatureVerificationFailed,
+    // İmza doğrulama başarısız oldu (imza, anahtar halkasındaki anahtarla doğrulanamadı)
+    SignatureVerificationFailed { key_id: String, owner: String },
+
+    // İmzalayan anahtar iptal edilmiş (iptal zamanı, Unix saniyesi)
+    RevokedSigningKey { key_id: String, owner: String, revoked_at: u64 },
+
+    // İmzalayan anahtarın geçerlilik süresi dolmuş (son geçerlilik, Unix saniyesi)
+    ExpiredSigningKey { key_id: String, owner: String, expired_at: u64 },
+
+    // İmzalayan anahtar anahtar halkasında yok (anahtar kimliği)
+    UnknownSigningKey(String),
+
+    // İmzada veya anahtar halkasında desteklenmeyen algoritma adı
+    UnsupportedSignatureAlgorithm(String),
+
+    // Geçersiz özel veya genel anahtar
+    InvalidKey(String),
+
+    // İmzalayan anahtarın kapsamı bu paketi bu depodan imzalamaya izin vermiyor
+    KeyNotAuthorized { key_id: String, owner: String, package: String, repository: String },
+
+    // Pakete uygulanan bir eşik kuralı yeterli sayıda farklı anahtardan geçerli imza almadı
+    // (missing: imzası eksik anahtarların "ad (kimlik)" tanımları)
+    ThresholdNotMet { package: String, rule: String, required: u32, valid: u32, missing: Vec<String> },
 
     // Güvenlik açığı taraması sırasında oluşan hata (tarama motoru hatası vb.)
     VulnerabilityScanError(String), // Hata detayını string olarak tutmak alloc gerektirir.
@@ -61,7 +90,28 @@ impl core::fmt::Display for SecurityError {
             SecurityError::Sahne64ResourceError(e) => write!(f, "Sahne64 Kaynak hatası: {:?}", e),
             SecurityError::HexDecodeError(e) => write!(f, "Hex çözme hatası: {:?}", e),
             SecurityError::InvalidSignatureFile(s) => write!(f, "Geçersiz imza dosyası: {}", s),
-            SecurityError::SignatureVerificationFailed => write!(f, "İmza doğrulanamadı: Paket özeti imza özetiyle eşleşmiyor."),
+            SecurityError::SignatureVerificationFailed { key_id, owner } => {
+                write!(f, "İmza doğrulanamadı: İmza, imzalayan anahtarla eşleşmiyor ({} {}).", owner, key_id)
+            }
+            SecurityError::RevokedSigningKey { key_id, owner, revoked_at } => {
+                write!(f, "İmzalayan anahtar iptal edilmiş: {} {} (iptal: {})", owner, key_id, revoked_at)
+            }
+            SecurityError::ExpiredSigningKey { key_id, owner, expired_at } => {
+                write!(f, "İmzalayan anahtarın süresi dolmuş: {} {} (son geçerlilik: {})", owner, key_id, expired_at)
+            }
+            SecurityError::UnknownSigningKey(s) => write!(f, "İmzalayan anahtar anahtar halkasında yok: {}", s),
+            SecurityError::UnsupportedSignatureAlgorithm(s) => write!(f, "Desteklenmeyen imza algoritması: {}", s),
+            SecurityError::InvalidKey(s) => write!(f, "Geçersiz anahtar: {}", s),
+            SecurityError::KeyNotAuthorized { key_id, owner, package, repository } => {
+                write!(f, "İmzalayan anahtar {} {} '{}' deposundan '{}' paketini imzalamaya yetkili değil", owner, key_id, repository, package)
+            }
+            SecurityError::ThresholdNotMet { package, rule, required, valid, missing } => {
+                write!(f, "'{}' paketi '{}' eşik kuralını sağlamıyor: {}/{} geçerli imza", package, rule, valid, required)?;
+                if !missing.is_empty() {
+                    write!(f, " (eksik imzalayanlar: {})", missing.join(", "))?;
+                }
+                Ok(())
+            }
             SecurityError::VulnerabilityScanError(s) => write!(f, "Güvenlik açığı taraması başarısız oldu: {}", s),
             SecurityError::SandboxError(s) => write!(f, "Sandbox ortamında çalıştırma başarısız oldu: {}", s),
             SecurityError::OperationNotSupported(s) => write!(f, "İşlem desteklenmiyor: {}", s),
@@ -83,24 +133,69 @@ impl From<FromHexError> for SecurityError {
 }
 
 
+// Tek bir eşik kuralının değerlendirme sonucu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EsikSonucu {
+    pub kural: String,
+    pub gerekli: u32,
+    pub gecerli: Vec<String>, // Kurala sayılan geçerli imzalayan anahtar kimlikleri
+    pub eksik: Vec<String>,   // Kuraldaki, imzası bulunmayan anahtar kimlikleri
+}
+
+impl EsikSonucu {
+    pub fn saglandi_mi(&self) -> bool {
+        self.gecerli.len() as u64 >= self.gerekli as u64
+    }
+}
+
+// verify_threshold raporu: hangi imzalar sayıldı, hangileri neden reddedildi, kurallar nasıl sonuçlandı.
+#[derive(Debug, Default)]
+pub struct EsikRaporu {
+    pub gecerli_imzalayanlar: Vec<String>,           // Geçerli imza atan farklı anahtar kimlikleri (sıralı)
+    pub reddedilen_imzalar: Vec<(String, String)>,   // (anahtar kimliği, ret nedeni)
+    pub kurallar: Vec<EsikSonucu>,
+}
+
+// Eşik kurallarını geçerli imzalayan kümesine göre değerlendirir.
+// gecerli_imzalayanlar: Geçerli imza atan anahtar kimlikleri (sıralı, tekil).
+pub fn esik_kurallarini_degerlendir(kurallar: &[&EsikKurali], gecerli_imzalayanlar: &[String]) -> Vec<EsikSonucu> {
+    kurallar
+        .iter()
+        .map(|kural| {
+            let (gecerli, eksik): (Vec<String>, Vec<String>) = kural
+                .anahtarlar
+                .iter()
+                .cloned()
+                .partition(|kimlik| gecerli_imzalayanlar.binary_search(kimlik).is_ok());
+            EsikSonucu { kural: kural.ad.clone(), gerekli: kural.esik, gecerli, eksik }
+        })
+        .collect()
+}
+
 // Güvenlik yönetimi işlevlerini sağlar.
 // İmza doğrulama, güvenlik açığı taraması ve sandbox çalıştırma (Sahne64 API'sine bağlı).
 pub struct SecurityManager {
-    // Güvenlik yönetimi için gerekli veriler (şu an boş)
+    // İmzaları doğrulamak için güvenilen genel anahtarlar
+    keyring: AnahtarHalkasi,
 }
 
 impl SecurityManager {
     // Yeni bir SecurityManager örneği oluşturur.
-    pub fn new() -> Self {
-        SecurityManager {}
+    // keyring: İmza doğrulamada güvenilen genel anahtarlar (örn. AnahtarHalkasi::kaynaktan_yukle ile yüklenir).
+    pub fn new(keyring: AnahtarHalkasi) -> Self {
+        SecurityManager { keyring }
+    }
+
+    pub fn keyring(&self) -> &AnahtarHalkasi {
+        &self.keyring
     }
 
-    // Belirtilen Kaynakların (paket ve imza dosyası) imzasını doğrular.
-    // İmza dosyası, paketin SHA256 özetinin hex kodlanmış hali olmalıdır.
+    // Paketin ayrık Ed25519 imzasını (signature_resource_id, örn. "paket.zip.sig") anahtar halkasıyla doğrular.
     // package_resource_id: Paket arşiv dosyasının Kaynak ID'si.
-    // signature_resource_id: İmza dosyasının Kaynak ID'si.
+    // signature_resource_id: Ayrık imza dosyasının Kaynak ID'si (srcsigning::AyrikImza metin biçimi).
     // Dönüş değeri: İmza geçerliyse Ok(true), geçersizse Err(SecurityError::SignatureVerificationFailed),
-    // veya diğer hatalar durumunda ilgili SecurityError.
+    // imzalayan anahtar halkada yoksa Err(SecurityError::UnknownSigningKey), iptal edilmiş veya süresi dolmuşsa
+    // Err(SecurityError::RevokedSigningKey/ExpiredSigningKey), diğer hatalarda ilgili SecurityError.
     pub fn verify_signature(
         &self,
         package_resource_id: &str, // Path yerine &str Kaynak ID
@@ -108,184 +203,180 @@ impl SecurityManager {
     ) -> Result<bool, SecurityError> { // Result<bool, SecurityError> olmalı, PaketYoneticisiHatasi'na çağıran mapler
         debug!("İmza doğrulaması başlatılıyor. Paket: {}, İmza: {}", package_resource_id, signature_resource_id); // no_std log
 
-        // Paket ve imza Kaynaklarını oku (Vec<u8> olarak)
-        let package_data = self.read_resource_to_vec(package_resource_id)?; // Kendi helper'ımızı kullan (SecurityError döner)
-        let mut signature_data = self.read_resource_to_vec(signature_resource_id)?; // Kendi helper'ımızı kullan (SecurityError döner)
-
-        // İmza dosyasının içeriği sadece SHA256 özetinin hex stringi olmalı.
-        // Satır sonu gibi karakterleri temizleyelim.
-        if let Some(last) = signature_data.last() {
-            if *last == b'\n' || *last == b'\r' {
-                signature_data.pop(); // Sondaki satır sonunu temizle
-            }
-        }
-         // Eğer imza verisi boşsa veya çok kısaysa hata dön.
-        if signature_data.is_empty() {
-            error!("İmza dosyası boş: {}", signature_resource_id); // no_std log
-             return Err(SecurityError::InvalidSignatureFile(format!("İmza dosyası boş."))); // alloc
-        }
-
-
-        // Paketin SHA256 özetini hesaplama
-        let mut hasher = Sha256::new(); // Sha256::new() alloc gerektirir mi? Muhtemelen statik boyuttadır.
-        hasher.update(&package_data); // update &[] alır no_std
-        let package_digest = hasher.finalize(); // finalize GenericArray<u8, 32> no_std
-
-        trace!("Paket özeti (SHA256): {}", hex::encode(&package_digest)); // hex::encode alloc gerektirir, no_std.
-
-        // İmza dosyasındaki hex stringi SHA256 özetine çözme (byte vektörüne)
-        // hex::decode(&signature_data) -> Result<Vec<u8>, FromHexError>. signature_data &[u8] olmalı.
-        let signature_digest_vec = hex::decode(&signature_data).map_err(|e| { // decode &[] alır no_std, Vec döner alloc
-             eprintln!("İmza özeti hex çözme hatası (Kaynak: {}): {:?}", signature_resource_id, e); // no_std print
-             SecurityError::HexDecodeError(e) // FromHexError -> SecurityError
-        })?; // Hata durumunda ? ile yay
+        let imza = AyrikImza::kaynaktan_oku(signature_resource_id).map_err(|e| {
+            error!("İmza dosyası okunamadı ({}): {:?}", signature_resource_id, e); // no_std log
+            e
+        })?;
 
+        self.imzayi_dogrula(package_resource_id, &imza)
+    }
 
-        // Çözülen imza özeti 32 bayt (SHA256 boyutu) olmalıdır.
-        if signature_digest_vec.len() != 32 {
-             error!("İmza dosyası beklenmeyen boyutta hex çözüldü ({} bayt), SHA256 için 32 bayt bekleniyordu. Kaynak: {}", signature_digest_vec.len(), signature_resource_id); // no_std log
-             return Err(SecurityError::InvalidSignatureFile(format!("Beklenmeyen imza özeti boyutu: {} bayt.", signature_digest_vec.len()))); // alloc
+    // İmzayı doğrular ve imzalayan anahtarın kapsamının bu paketi bu depodan imzalamaya izin verdiğini denetler.
+    // package_name: Paketin adı (Paket.ad).
+    // repository: Paketin geldiği deponun adı (srcrepositoryrelease::DepoBildirimi.depo).
+    // trust: Anahtar kapsamlarını içeren güven deposu.
+    // Dönüş değeri: verify_signature ile aynı; kapsam dışındaysa Err(SecurityError::KeyNotAuthorized).
+    pub fn verify_package_signature(
+        &self,
+        package_resource_id: &str,
+        signature_resource_id: &str,
+        package_name: &str,
+        repository: &str,
+        trust: &TrustManager,
+    ) -> Result<bool, SecurityError> {
+        let imza = AyrikImza::kaynaktan_oku(signature_resource_id).map_err(|e| {
+            error!("İmza dosyası okunamadı ({}): {:?}", signature_resource_id, e); // no_std log
+            e
+        })?;
+        self.imzayi_dogrula(package_resource_id, &imza)?;
+
+        if !trust.is_key_allowed(&imza.anahtar_kimligi, package_name, repository) {
+            error!("Anahtar {} kapsam dışı: {} ({})", imza.anahtar_kimligi, package_name, repository); // no_std log
+            return Err(SecurityError::KeyNotAuthorized {
+                owner: self.anahtar_sahibi(&imza.anahtar_kimligi),
+                key_id: imza.anahtar_kimligi,
+                package: package_name.to_owned(),
+                repository: repository.to_owned(),
+            });
         }
-         // Vec<u8> -> &[u8; 32] dönüşümü güvenli olmayabilir, sadece dilim karşılaştırması yapalım.
-
+        Ok(true)
+    }
 
-        // Hesaplanan paket özetini (GenericArray) çözülen imza özeti vektörü ile karşılaştır.
-        // GenericArray<u8, 32> derefs to &[u8].
-        if package_digest.as_slice() == signature_digest_vec.as_slice() { // slice comparison no_std
-             info!("İmza başarıyla doğrulandı. Kaynak: {}", package_resource_id); // no_std log
-            Ok(true) // Eşleşirse true dön
-        } else {
-             // Özetler eşleşmedi, imza geçersiz.
-             error!("İmza doğrulama başarısız. Hesaplanan özet ile imza özeti eşleşmiyor. Paket: {}, İmza: {}", package_resource_id, signature_resource_id); // no_std log
-            Err(SecurityError::SignatureVerificationFailed) // Eşleşmezse hata dön
+    // Paketin tüm ayrık imzalarını toplar ve pakete uygulanan eşik kurallarını (TrustManager) denetler.
+    // Her kural için, kuralın anahtar listesindeki farklı anahtarlardan en az M geçerli imza gerekir; bir anahtarın
+    // birden çok imzası tek sayılır. Geçerli imza: anahtar halkasında bulunan, iptal edilmemiş, süresi dolmamış ve
+    // kapsamı bu paketi bu depodan imzalamaya izin veren bir anahtarla doğrulanan imza.
+    // Hiçbir kural uygulanmıyorsa tek bir geçerli imza yeterlidir.
+    // signature_resource_ids: İmza dosyalarının Kaynak ID'leri; her dosya birden çok imza bloğu içerebilir.
+    // Dönüş değeri: Kurallar sağlanıyorsa Ok(EsikRaporu); sağlanmayan ilk kural için Err(SecurityError::ThresholdNotMet),
+    // kural yokken geçerli imza bulunamazsa ilk reddedilen imzanın hatası.
+    pub fn verify_threshold(
+        &self,
+        package_resource_id: &str,
+        signature_resource_ids: &[&str],
+        package_name: &str,
+        repository: &str,
+        trust: &TrustManager,
+    ) -> Result<EsikRaporu, SecurityError> {
+        debug!("Eşik imza doğrulaması başlatılıyor. Paket: {} ({})", package_name, repository); // no_std log
+
+        let mut imzalar = Vec::new();
+        for signature_resource_id in signature_resource_ids {
+            imzalar.extend(AyrikImza::hepsini_kaynaktan_oku(signature_resource_id).map_err(|e| {
+                error!("İmza dosyası okunamadı ({}): {:?}", signature_resource_id, e); // no_std log
+                e
+            })?);
         }
-    }
 
-    // Helper fonksiyon: Sahne64 Kaynağından tüm içeriği Vec<u8> olarak oku.
-    // Daha önceki refaktoringlerden yeniden kullanıldı (srcrepository.rs).
-    // Note: Bu helper, utils gibi ortak bir modülde olmalıdır.
-    fn read_resource_to_vec(&self, resource_id: &str) -> Result<Vec<u8>, SecurityError> { // Result<Vec<u8>, SecurityError> olmalı
-        let handle = resource::acquire(resource_id, resource::MODE_READ)
-            .map_err(|e| {
-                eprintln!("Helper: Kaynak acquire hatası ({}): {:?}", resource_id, e); // no_std print
-                SecurityError::from(e) // SahneError -> SecurityError
-            })?;
-
-        let mut buffer = Vec::new(); // alloc gerektirir
-        let mut temp_buffer = [0u8; 512]; // Stack buffer
-
-        loop {
-            match resource::read(handle, &mut temp_buffer) {
-                Ok(0) => break,
-                Ok(bytes_read) => {
-                    buffer.extend_from_slice(&temp_buffer[..bytes_read]); // extend_from_slice alloc
+        let ozet = srcsigning::paket_ozeti(package_resource_id)?;
+        let simdi = srcsigning::simdiki_zaman()?;
+
+        let mut rapor = EsikRaporu::default();
+        let mut ilk_hata = None;
+        for imza in &imzalar {
+            let sonuc = self.keyring
+                .dogrula(srcsigning::PAKET_IMZA_BAGLAMI, &ozet, imza, simdi)
+                .map(|anahtar| anahtar.kimlik())
+                .and_then(|kimlik| {
+                    if trust.is_key_allowed(&kimlik, package_name, repository) {
+                        Ok(kimlik)
+                    } else {
+                        Err(SecurityError::KeyNotAuthorized {
+                            owner: self.anahtar_sahibi(&kimlik),
+                            key_id: kimlik,
+                            package: package_name.to_owned(),
+                            repository: repository.to_owned(),
+                        })
+                    }
+                });
+            match sonuc {
+                Ok(kimlik) => {
+                    if let Err(konum) = rapor.gecerli_imzalayanlar.binary_search(&kimlik) {
+                        rapor.gecerli_imzalayanlar.insert(konum, kimlik);
+                    }
                 }
                 Err(e) => {
-                    let _ = resource::release(handle);
-                    eprintln!("Helper: Kaynak okuma hatası ({}): {:?}", resource_id, e); // no_std print
-                    return Err(SecurityError::from(e)); // SahneError -> SecurityError
+                    warn!("İmza sayılmadı ({}): {}", imza.anahtar_kimligi, e); // no_std log
+                    rapor.reddedilen_imzalar.push((imza.anahtar_kimligi.clone(), e.to_string()));
+                    ilk_hata.get_or_insert(e);
                 }
             }
         }
 
-        let release_result = resource::release(handle);
-         if let Err(_e) = release_result {
-             eprintln!("Helper: Kaynak release hatası ({}): {:?}", resource_id, _e); // no_std print
-         }
+        let kurallar = trust.threshold_rules_for(package_name);
+        if kurallar.is_empty() {
+            if rapor.gecerli_imzalayanlar.is_empty() {
+                return Err(ilk_hata.unwrap_or_else(|| {
+                    SecurityError::InvalidSignatureFile(format!("'{}' için imza bulunamadı", package_name))
+                }));
+            }
+            info!("'{}' için {} geçerli imzalayan; eşik kuralı yok.", package_name, rapor.gecerli_imzalayanlar.len()); // no_std log
+            return Ok(rapor);
+        }
 
-        Ok(buffer) // Vec<u8> olarak döndür (alloc)
+        rapor.kurallar = esik_kurallarini_degerlendir(&kurallar, &rapor.gecerli_imzalayanlar);
+        if let Some(sonuc) = rapor.kurallar.iter().find(|sonuc| !sonuc.saglandi_mi()) {
+            error!("'{}' eşik kuralı sağlanmadı: {}/{}", sonuc.kural, sonuc.gecerli.len(), sonuc.gerekli); // no_std log
+            return Err(SecurityError::ThresholdNotMet {
+                package: package_name.to_owned(),
+                rule: sonuc.kural.clone(),
+                required: sonuc.gerekli,
+                valid: sonuc.gecerli.len() as u32,
+                missing: sonuc.eksik.iter().map(|kimlik| self.anahtar_tanimi(kimlik)).collect(),
+            });
+        }
+        info!("'{}' tüm eşik kurallarını sağlıyor ({} kural).", package_name, rapor.kurallar.len()); // no_std log
+        Ok(rapor)
     }
 
+    fn imzayi_dogrula(&self, package_resource_id: &str, imza: &AyrikImza) -> Result<bool, SecurityError> {
+        if srcsigning::verify_package(package_resource_id, imza, &self.keyring)? {
+            info!("İmza başarıyla doğrulandı. Kaynak: {}", package_resource_id); // no_std log
+            Ok(true)
+        } else {
+            error!("İmza doğrulama başarısız. Paket: {}, Anahtar: {}", package_resource_id, imza.anahtar_kimligi); // no_std log
+            Err(SecurityError::SignatureVerificationFailed {
+                key_id: imza.anahtar_kimligi.clone(),
+                owner: self.anahtar_sahibi(&imza.anahtar_kimligi),
+            })
+        }
+    }
 
-    // Belirtilen paketin Kaynağını güvenlik açıkları için tarar (Placeholder).
-    // package_resource_id: Taranacak paketin Kaynak ID'si.
-    // Dönüş değeri: Bulunan güvenlik açığı ID'lerinin listesi (Vec<String>) veya SecurityError.
-    // Not: Gerçek bir güvenlik açığı tarama motoru ve Sahne64 API desteği gerektirir.
-    pub fn scan_for_vulnerabilities(&self, package_resource_id: &str) -> Result<Vec<String>, SecurityError> { // Path yerine &str Kaynak ID
-        println!("Güvenlik açığı taraması başlatılıyor (implemente edilmedi): {}", package_resource_id); // no_std print
-
-        // --- Sahne64 API Eksikliği / Gerçek Tarama İhtiyacı ---
-        // Bu işlevsellik, ya bir harici güvenlik açığı tarama görevini başlatmayı ve
-        // çıktısını/sonuçlarını işlemeyi, ya da doğrudan Sahne64 çekirdeğinde bir
-        // tarama mekanizması olmasını gerektirir.
-        // Şu anda bu mekanizmalar Sahne64 API'sında tanımlanmamıştır.
-
-        // Şimdilik sadece bir placeholder implementasyonu sunulmaktadır.
-        // Gerçek tarama motoru, package_resource_id'yi okuyarak veya Kaynak üzerinde çalışarak
-        // güvenlik açıklarını bulmalıdır.
-
-        // Örnek olarak, tarama sırasında bir hata oluştuğunu simüle edelim.
-        let scan_engine_result = Err(SecurityError::VulnerabilityScanError("Simüle edilmiş tarama motoru hatası".to_string()));
+    fn anahtar_sahibi(&self, anahtar_kimligi: &str) -> String {
+        self.keyring.bul(anahtar_kimligi).map(|anahtar| anahtar.ad.clone()).unwrap_or_default()
+    }
 
-        // Veya örnek güvenlik açığı bulguları döndürelim.
-        let vulnerabilities = vec!["CVE-2023-1234".to_owned(), "CVE-2023-5678".to_owned()]; // alloc gerektirir
+    // Anahtarın "ad (kimlik)" tanımı; halkada yoksa yalnızca kimlik.
+    pub fn anahtar_tanimi(&self, anahtar_kimligi: &str) -> String {
+        self.keyring.bul(anahtar_kimligi).map(|anahtar| anahtar.tanim()).unwrap_or_else(|| anahtar_kimligi.to_owned())
+    }
 
-        // Tarama sonuçlarını veya hata durumunu döndürme
-        // Eğer güvenlik açığı bulunursa Vec<String> döner, bulunmazsa boş Vec.
-        // Tarama motoru hatası durumunda Err(SecurityError::VulnerabilityScanError) döner.
-        if vulnerabilities.is_empty() {
-             info!("Güvenlik açığı bulunamadı: {}", package_resource_id); // no_std log
-             Ok(Vec::new()) // Eğer güvenlik açığı bulunmazsa boş vektör döndür (alloc)
+    // Paketin bu sürümünü yerel güvenlik bildirimi veritabanına göre tarar (ağ gerekmez).
+    // advisories: srcadvisory::BildiriVeritabani::kaynaktan_yukle ile yüklenen veritabanı.
+    // Dönüş değeri: Bulunan açıklar, ciddiyete göre azalan sırada (açık yoksa boş).
+    pub fn scan_for_vulnerabilities(&self, advisories: &BildiriVeritabani, package_name: &str, version: &str) -> Vec<Bulgu> {
+        let bulgular = advisories.paketi_tara(package_name, version);
+        if bulgular.is_empty() {
+            info!("Güvenlik açığı bulunamadı: {} {}", package_name, version); // no_std log
         } else {
-             warn!("Paket için güvenlik açıkları bulundu ({} adet): {}", vulnerabilities.len(), package_resource_id); // no_std log
-             // Bulguları loglayabiliriz
-             for vul in &vulnerabilities {
-                 warn!("  - {}", vul); // no_std log
-             }
-            Ok(vulnerabilities) // Güvenlik açığı bulgularını vektör olarak döndür (alloc)
+            warn!("Paket için güvenlik açıkları bulundu ({} adet): {} {}", bulgular.len(), package_name, version); // no_std log
+            for bulgu in &bulgular {
+                warn!("  - {}", bulgu); // no_std log
+            }
         }
+        bulgular
     }
 
-    // Belirtilen yürütülebilir Kaynağı sandbox ortamında çalıştırır (Placeholder).
-    // executable_resource_id: Sandbox ortamında çalıştırılacak yürütülebilir Kaynağın ID'si.
-    // Dönüş değeri: Başarı (sandbox görevini başlatma) veya SecurityError.
-    // Not: Sahne64 API'sında sandbox ortamının detayları ve task::spawn'ın bu ortamı destekleyip desteklemediği
-    // henüz tanımlanmamıştır. Task::wait ve çıktı yakalama da eksiktir.
-    pub fn run_in_sandbox(&self, executable_resource_id: &str) -> Result<(), SecurityError> { // Path yerine &str Kaynak ID
-        println!("Sandbox ortamında çalıştırma başlatılıyor (implemente edilmedi): {}", executable_resource_id); // no_std print
-
-        // --- Sahne64 API Eksikliği / Sandbox Mekanizması İhtiyacı ---
-        // Sandbox ortamı, ya resource access control listeleriyle (ACL), ya
-        // da task::spawn fonksiyonuna özel parametreler geçirilerek Sahne64
-        // çekirdeği tarafından sağlanmalıdır.
-        // Şu anda bu mekanizmalar Sahne64 API'sında tanımlanmamıştır.
-        // Ayrıca, betik çalıştırma gibi, sandbox görevinin tamamlanmasını beklemek
-        // ve davranışını izlemek (örn. izin ihlalleri) API'da eksiktir.
-
-        // Şimdilik, sadece yürütülebilir Kaynağı normal bir görev olarak başlatmayı simüle edelim.
-        // task::spawn bir handle ve argümanlar alır. executable_resource_id'den handle almalıyız.
-        match resource::acquire(executable_resource_id, resource::MODE_READ) { // Yürütme izni MODE_READ olabilir
-            Ok(executable_handle) => {
-                // Varsayımsal olarak sandbox kısıtlamalarını task::spawn'a argüman olarak geçebiliriz.
-                // task::spawn(executable_handle, args: &[u8]) -> Result<TaskId, SahneError>
-                // Sandbox argümanları nasıl temsil edilir? Özel bir yapı? Byte dilimi içinde?
-                // Şu anki task::spawn sadece &[u8] alıyor.
-                // Gerçek API'da sandbox parametreleri için özel bir yol olmalıdır.
-
-                // Basitlik adına, sadece argümansız başlatalım ve sandbox kısıtlamalarının çekirdek tarafından
-                // Kaynak tipine veya handle'ın özelliklerine göre uygulandığını varsayalım (bu çok varsayımsal).
-                let task_args: &[u8] = b""; // Sandbox görevine argüman geçilebilir.
-
-                match task::spawn(executable_handle, task_args) {
-                    Ok(_new_tid) => {
-                        info!("Yürütülebilir kaynak sandbox (simüle edilmiş) ortamında başlatıldı: {}", executable_resource_id); // no_std log
-                        let _ = resource::release(executable_handle); // Handle'ı serbest bırak
-                        Ok(()) // Görev başlatıldı olarak başarı dön
-                    }
-                    Err(e) => {
-                        let _ = resource::release(executable_handle);
-                        let hata_mesaji = format!("Sandbox görevi başlatılamadı (Kaynak: {}): {:?}", executable_resource_id, e); // format! alloc
-                        error!("{}", hata_mesaji); // no_std log
-                        Err(SecurityError::SandboxError(hata_mesaji)) // SahneError -> SecurityError
-                    }
-                }
-            }
-            Err(e) => {
-                let hata_mesaji = format!("Sandbox çalıştırma Kaynağı acquire hatası ({}): {:?}", executable_resource_id, e); // format! alloc
-                error!("{}", hata_mesaji); // no_std log
-                Err(SecurityError::SandboxError(hata_mesaji)) // SahneError -> SecurityError
-            }
-        }
+    // Yürütülebilir Kaynağı verilen erişim politikasıyla Sahne64 sandbox'ında çalıştırır.
+    // policy: Görevin okuyabileceği/yazabileceği Kaynaklar ve ağ izni (srcsandbox::SandboxPolitikasi);
+    // politika kod Kaynağına bağlanır ve çekirdeğin kaynak katmanı politika dışı erişimleri reddedip günlüğe yazar.
+    // Dönüş değeri: Görev başlatıldıysa Ok(()), aksi halde SecurityError::SandboxError.
+    pub fn run_in_sandbox(&self, executable_resource_id: &str, policy: &SandboxPolitikasi) -> Result<(), SecurityError> {
+        Sahne64ArkaUcu.betik_calistir(executable_resource_id, b"", policy).map_err(|e| {
+            let hata_mesaji = format!("Sandbox görevi başlatılamadı (Kaynak: {}): {}", executable_resource_id, e); // format! alloc
+            error!("{}", hata_mesaji); // no_std log
+            SecurityError::SandboxError(hata_mesaji)
+        })
     }
 }
 
@@ -294,6 +385,38 @@ impl SecurityManager {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn kural(ad: &str, esik: u32, anahtarlar: &[&str]) -> EsikKurali {
+        EsikKurali {
+            ad: ad.to_owned(),
+            paket_desenleri: Vec::from([String::from("cekirdek*")]),
+            esik,
+            anahtarlar: anahtarlar.iter().map(|kimlik| (*kimlik).to_owned()).collect(),
+            aciklama: String::new(),
+            eklenme: 0,
+            ekleyen: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_esik_kurallarini_degerlendir() {
+        let iki_ucte = kural("cekirdek", 2, &["aa", "bb", "cc"]);
+        let tek = kural("derleme", 1, &["dd"]);
+        // Kurala ait olmayan "ee" imzası sayılmaz
+        let gecerli = Vec::from([String::from("aa"), String::from("cc"), String::from("ee")]);
+
+        let sonuclar = esik_kurallarini_degerlendir(&[&iki_ucte, &tek], &gecerli);
+        assert!(sonuclar[0].saglandi_mi());
+        assert_eq!(sonuclar[0].gecerli, ["aa", "cc"]);
+        assert_eq!(sonuclar[0].eksik, ["bb"]);
+        assert!(!sonuclar[1].saglandi_mi());
+        assert_eq!(sonuclar[1].eksik, ["dd"]);
+
+        let yalniz_bir = Vec::from([String::from("bb")]);
+        assert!(!esik_kurallarini_degerlendir(&[&iki_ucte], &yalniz_bir)[0].saglandi_mi());
+    }
+
     // std::path, std::io, std::fs, tempfile, sha2, hex kullandığı için no_std'de doğrudan çalışmaz.
     // Mock resource::acquire/read/release, task::spawn ve test dosyası oluşturma/okuma helper'ları gerektirir.
 }
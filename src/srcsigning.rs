@@ -3,13 +3,16 @@ extern crate alloc; // String, Vec, format! için
 
 // no_std ve alloc uyumlu kripto ve hex crate'leri
 use sha2::{Sha256, Digest};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey}; // Ed25519 (no_std, alloc özellikli)
 use hex;
 // hex::EncodeError için From implementasyonu gerekebilir.
 
 // Sahne64 API modülleri
 use crate::resource; // Kaynak işlemleri (okuma)
+use crate::task; // Anahtar geçerlilik denetimi için zaman bilgisi
 use crate::SahneError; // Sahne64 hata türü
 use crate::Handle; // Kaynak Handle'ları
+use crate::srcresource; // Atomik Kaynak yazma
 
 // Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
 use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
@@ -18,17 +21,450 @@ use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
 use crate::srcsecurity::SecurityError; // İmza/Hash/Hex hatalarını SecurityError ile handle edelim
 
 // log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
-use log::{info, error, debug};
+use log::{info, error, warn, debug};
 
 // String ve Vec from alloc
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned;
 
 // no_std uyumlu print makroları (örnek çıktılar için)
 use crate::print_macros::{println, eprintln};
 
 
+// Güvenilen genel anahtarların varsayılan Kaynağı
+pub const VARSAYILAN_ANAHTAR_HALKASI: &str = "sahne://config/trusted_keys.list";
+
+// Ayrık (detached) imza metninin ilk satırı; biçim değişirse sürüm artırılır.
+pub const IMZA_BICIMI_BASLIGI: &str = "sahne-imza-v1";
+
+// Paket arşivi imzalarının alan ayırıcısı. İmzalanan mesaj `bağlam || SHA-256(veri)` biçimindedir;
+// bağlam, bir tür veri için verilmiş imzanın başka bir tür veri için kabul edilmesini engeller.
+pub const PAKET_IMZA_BAGLAMI: &[u8] = b"sahne-paket-imzasi-v1\0";
+
+// Paket arşivinin ayrık imzaları depoda arşivin yanında "<dosya adı>.sig" olarak yayınlanır
+// (eşik kuralları için dosya birden çok imza bloğu içerebilir).
+pub const PAKET_IMZA_UZANTISI: &str = ".sig";
+
+// Anahtar kimliği, genel anahtarın SHA-256 özetinin ilk bu kadar baytıdır (hex olarak iki katı).
+const ANAHTAR_KIMLIGI_BAYT: usize = 8;
+
+// Desteklenen imza algoritmaları.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImzaAlgoritmasi {
+    Ed25519,
+}
+
+impl ImzaAlgoritmasi {
+    // İmza ve anahtar halkası metinlerinde kullanılan ad.
+    pub fn ad(&self) -> &'static str {
+        match self {
+            ImzaAlgoritmasi::Ed25519 => "ed25519",
+        }
+    }
+
+    pub fn addan(ad: &str) -> Result<Self, SecurityError> {
+        match ad.trim().to_lowercase().as_str() {
+            "ed25519" => Ok(ImzaAlgoritmasi::Ed25519),
+            _ => Err(SecurityError::UnsupportedSignatureAlgorithm(ad.to_owned())),
+        }
+    }
+}
+
+// Genel anahtarın kimliğini hesaplar (hex, 16 karakter).
+pub fn anahtar_kimligi(genel_anahtar: &[u8; 32]) -> String {
+    let ozet = Sha256::digest(genel_anahtar);
+    hex::encode(&ozet[..ANAHTAR_KIMLIGI_BAYT])
+}
+
+// Genel anahtarın tam parmak izini hesaplar (SHA-256, hex). Anahtar kimliği bunun ilk 16 karakteridir.
+pub fn parmak_izi(genel_anahtar: &[u8; 32]) -> String {
+    hex::encode(Sha256::digest(genel_anahtar))
+}
+
+// Şimdiki zamanı Unix saniyesi olarak döndürür (anahtar geçerlilik denetimi için).
+pub fn simdiki_zaman() -> Result<u64, SecurityError> {
+    task::current_time_us().map(|us| us / 1_000_000).map_err(SecurityError::from)
+}
+
+// Hex metni sabit uzunluklu bayt dizisine çözer.
+fn hex_diziye<const N: usize>(metin: &str, ne: &str) -> Result<[u8; N], SecurityError> {
+    let bayt = hex::decode(metin.trim())?;
+    bayt.as_slice().try_into().map_err(|_| {
+        SecurityError::InvalidKey(format!("{} {} bayt olmalı, {} bayt verildi", ne, N, bayt.len()))
+    })
+}
+
+// İmzalanan mesajı oluşturur: bağlam || SHA-256(veri özeti).
+fn imza_mesaji(baglam: &[u8], ozet: &[u8; 32]) -> Vec<u8> {
+    let mut mesaj = Vec::with_capacity(baglam.len() + ozet.len());
+    mesaj.extend_from_slice(baglam);
+    mesaj.extend_from_slice(ozet);
+    mesaj
+}
+
+
+// İmzalamada kullanılan özel anahtar. Yalnızca paketi yayınlayan tarafta bulunur.
+pub struct ImzalamaAnahtari {
+    anahtar: SigningKey,
+}
+
+impl ImzalamaAnahtari {
+    // 32 baytlık Ed25519 tohumundan anahtar oluşturur.
+    pub fn tohumdan(tohum: &[u8; 32]) -> Self {
+        ImzalamaAnahtari { anahtar: SigningKey::from_bytes(tohum) }
+    }
+
+    // Hex kodlu tohumdan anahtar oluşturur.
+    pub fn hex_tohumdan(metin: &str) -> Result<Self, SecurityError> {
+        Ok(ImzalamaAnahtari::tohumdan(&hex_diziye::<32>(metin, "Özel anahtar tohumu")?))
+    }
+
+    // Özel anahtarı Kaynaktan yükler (içerik: hex kodlu 32 baytlık tohum).
+    pub fn kaynaktan_yukle(resource_id: &str) -> Result<Self, SecurityError> {
+        let veri = read_resource_to_vec(resource_id)?;
+        let metin = core::str::from_utf8(&veri)
+            .map_err(|_| SecurityError::InvalidKey(format!("Özel anahtar UTF-8 değil: {}", resource_id)))?;
+        ImzalamaAnahtari::hex_tohumdan(metin)
+    }
+
+    // Bu anahtarla üretilen imzaları doğrulayacak genel anahtar.
+    pub fn genel_anahtar(&self, ad: &str) -> GenelAnahtar {
+        GenelAnahtar {
+            algoritma: ImzaAlgoritmasi::Ed25519,
+            anahtar: self.anahtar.verifying_key().to_bytes(),
+            ad: ad.to_owned(),
+            olusturma: 0,
+            son_gecerlilik: None,
+            iptal_zamani: None,
+        }
+    }
+
+    pub fn kimlik(&self) -> String {
+        anahtar_kimligi(&self.anahtar.verifying_key().to_bytes())
+    }
+
+    // Verilen özeti bağlamla birlikte imzalar.
+    pub fn ozet_imzala(&self, baglam: &[u8], ozet: &[u8; 32]) -> AyrikImza {
+        let imza = self.anahtar.sign(&imza_mesaji(baglam, ozet));
+        AyrikImza {
+            algoritma: ImzaAlgoritmasi::Ed25519,
+            anahtar_kimligi: self.kimlik(),
+            imza: imza.to_bytes().to_vec(),
+        }
+    }
+
+    // Bellekteki veriyi bağlamla birlikte imzalar.
+    pub fn veri_imzala(&self, baglam: &[u8], veri: &[u8]) -> AyrikImza {
+        self.ozet_imzala(baglam, &Sha256::digest(veri).into())
+    }
+}
+
+
+// Anahtar halkasındaki bir genel anahtar. Zamanlar Unix saniyesidir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenelAnahtar {
+    pub algoritma: ImzaAlgoritmasi,
+    pub anahtar: [u8; 32],
+    pub ad: String, // Anahtar sahibinin okunabilir adı (örn. "Sahne Paket Ekibi")
+    pub olusturma: u64, // Halkaya eklenme zamanı (bilinmiyorsa 0)
+    pub son_gecerlilik: Option<u64>, // Bu andan sonra anahtarla yapılan imzalar kabul edilmez
+    pub iptal_zamani: Option<u64>, // İptal edilen anahtarın hiçbir imzası kabul edilmez
+}
+
+impl GenelAnahtar {
+    pub fn kimlik(&self) -> String {
+        anahtar_kimligi(&self.anahtar)
+    }
+
+    pub fn parmak_izi(&self) -> String {
+        parmak_izi(&self.anahtar)
+    }
+
+    // Hata ve listeleme çıktıları için "ad (kimlik)".
+    pub fn tanim(&self) -> String {
+        format!("{} ({})", self.ad, self.kimlik())
+    }
+
+    // Anahtarın verilen anda imza doğrulamada kullanılabileceğini denetler.
+    // İmzanın ne zaman atıldığı bilinmediğinden iptal, iptal zamanından önceki imzaları da geçersiz kılar.
+    pub fn kullanilabilir_mi(&self, simdi: u64) -> Result<(), SecurityError> {
+        if let Some(iptal_zamani) = self.iptal_zamani {
+            return Err(SecurityError::RevokedSigningKey { key_id: self.kimlik(), owner: self.ad.clone(), revoked_at: iptal_zamani });
+        }
+        match self.son_gecerlilik {
+            Some(son_gecerlilik) if simdi >= son_gecerlilik => {
+                Err(SecurityError::ExpiredSigningKey { key_id: self.kimlik(), owner: self.ad.clone(), expired_at: son_gecerlilik })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // İmzanın bu anahtarla, verilen bağlam ve özet için üretildiğini doğrular.
+    // Katı (strict) doğrulama zayıf anahtarları ve şekil değiştirmiş (malleable) imzaları reddeder.
+    pub fn dogrula(&self, baglam: &[u8], ozet: &[u8; 32], imza: &AyrikImza) -> bool {
+        if imza.algoritma != self.algoritma {
+            return false;
+        }
+        let imza_bayt: [u8; 64] = match imza.imza.as_slice().try_into() {
+            Ok(bayt) => bayt,
+            Err(_) => return false,
+        };
+        match VerifyingKey::from_bytes(&self.anahtar) {
+            Ok(anahtar) => anahtar.verify_strict(&imza_mesaji(baglam, ozet), &Signature::from_bytes(&imza_bayt)).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+
+// Güvenilen genel anahtarlar. İmzalar yalnızca buradaki, süresi dolmamış ve iptal edilmemiş anahtarlarla doğrulanır.
+// Metin biçimi: her satırda `<algoritma> <genel anahtar hex> [olusturma=<t>] [bitis=<t>] [iptal=<t>] <ad>`;
+// '#' ile başlayan satırlar yorumdur. Alanları olmayan eski satırlar süresiz ve iptal edilmemiş kabul edilir.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnahtarHalkasi {
+    anahtarlar: Vec<GenelAnahtar>,
+}
+
+impl AnahtarHalkasi {
+    pub fn yeni() -> Self {
+        AnahtarHalkasi { anahtarlar: Vec::new() }
+    }
+
+    // Anahtarı ekler; aynı kimlikte anahtar varsa yerine koyar.
+    // İptal edilmiş bir anahtar yeniden eklenerek geri getirilemez; iptal kaydı korunur (önce silinmesi gerekir).
+    pub fn ekle(&mut self, mut anahtar: GenelAnahtar) {
+        let kimlik = anahtar.kimlik();
+        if let Some(mevcut) = self.bul(&kimlik) {
+            anahtar.iptal_zamani = mevcut.iptal_zamani.or(anahtar.iptal_zamani);
+        }
+        self.anahtarlar.retain(|mevcut| mevcut.kimlik() != kimlik);
+        self.anahtarlar.push(anahtar);
+    }
+
+    // Anahtarı iptal eder (halkada kalır, imzaları reddedilir). Dönüş değeri: anahtar bulundu mu?
+    // Daha önce iptal edilmişse ilk iptal zamanı korunur.
+    pub fn iptal_et(&mut self, kimlik: &str, zaman: u64) -> bool {
+        match self.anahtarlar.iter_mut().find(|anahtar| anahtar.kimlik() == kimlik || anahtar.parmak_izi() == kimlik) {
+            Some(anahtar) => {
+                anahtar.iptal_zamani.get_or_insert(zaman);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Kimliği veya parmak izi verilen anahtarı çıkarır. Dönüş değeri: anahtar bulundu mu?
+    pub fn cikar(&mut self, kimlik: &str) -> bool {
+        let onceki = self.anahtarlar.len();
+        self.anahtarlar.retain(|mevcut| mevcut.kimlik() != kimlik && mevcut.parmak_izi() != kimlik);
+        self.anahtarlar.len() != onceki
+    }
+
+    // Anahtarı kimliğiyle (16 hex) veya tam parmak iziyle bulur.
+    pub fn bul(&self, kimlik: &str) -> Option<&GenelAnahtar> {
+        self.anahtarlar.iter().find(|anahtar| anahtar.kimlik() == kimlik || anahtar.parmak_izi() == kimlik)
+    }
+
+    pub fn anahtarlar(&self) -> &[GenelAnahtar] {
+        &self.anahtarlar
+    }
+
+    pub fn ayristir(metin: &str) -> Result<Self, SecurityError> {
+        let mut halka = AnahtarHalkasi::yeni();
+        for (satir_no, satir) in metin.lines().enumerate() {
+            let satir = satir.trim();
+            if satir.is_empty() || satir.starts_with('#') {
+                continue;
+            }
+            let mut parcalar = satir.splitn(3, char::is_whitespace);
+            let algoritma = ImzaAlgoritmasi::addan(parcalar.next().unwrap_or(""))?;
+            let anahtar_hex = parcalar.next().ok_or_else(|| {
+                SecurityError::InvalidKey(format!("Anahtar halkası {}. satır: genel anahtar eksik", satir_no + 1))
+            })?;
+            let anahtar = hex_diziye::<32>(anahtar_hex, "Genel anahtar")?;
+            if VerifyingKey::from_bytes(&anahtar).is_err() {
+                return Err(SecurityError::InvalidKey(format!("Anahtar halkası {}. satır: geçersiz Ed25519 anahtarı", satir_no + 1)));
+            }
+
+            // Ad'dan önce gelen `alan=değer` çiftleri; ilk tanınmayan sözcükten itibaren satırın kalanı addır.
+            let mut kalan = parcalar.next().unwrap_or("").trim();
+            let mut genel = GenelAnahtar { algoritma, anahtar, ad: String::new(), olusturma: 0, son_gecerlilik: None, iptal_zamani: None };
+            loop {
+                let (sozcuk, sonrasi) = kalan.split_once(char::is_whitespace).unwrap_or((kalan, ""));
+                let (alan, deger) = match sozcuk.split_once('=') {
+                    Some((alan, deger)) if matches!(alan, "olusturma" | "bitis" | "iptal") => (alan, deger),
+                    _ => break,
+                };
+                let zaman = deger.parse::<u64>().map_err(|_| {
+                    SecurityError::InvalidKey(format!("Anahtar halkası {}. satır: geçersiz {} zamanı: {}", satir_no + 1, alan, deger))
+                })?;
+                match alan {
+                    "olusturma" => genel.olusturma = zaman,
+                    "bitis" => genel.son_gecerlilik = Some(zaman),
+                    _ => genel.iptal_zamani = Some(zaman),
+                }
+                kalan = sonrasi.trim_start();
+            }
+            genel.ad = kalan.trim().to_owned();
+            halka.ekle(genel);
+        }
+        Ok(halka)
+    }
+
+    pub fn metne_donustur(&self) -> String {
+        let mut metin = String::new();
+        for anahtar in &self.anahtarlar {
+            metin.push_str(&format!("{} {} olusturma={}", anahtar.algoritma.ad(), hex::encode(anahtar.anahtar), anahtar.olusturma));
+            if let Some(son_gecerlilik) = anahtar.son_gecerlilik {
+                metin.push_str(&format!(" bitis={}", son_gecerlilik));
+            }
+            if let Some(iptal_zamani) = anahtar.iptal_zamani {
+                metin.push_str(&format!(" iptal={}", iptal_zamani));
+            }
+            metin.push_str(&format!(" {}\n", anahtar.ad));
+        }
+        metin
+    }
+
+    // Anahtar halkasını Kaynaktan yükler. Kaynak yoksa boş halka döner (hiçbir imza doğrulanmaz).
+    pub fn kaynaktan_yukle(resource_id: &str) -> Result<Self, SecurityError> {
+        match read_resource_to_vec(resource_id) {
+            Ok(veri) => {
+                let metin = core::str::from_utf8(&veri)
+                    .map_err(|_| SecurityError::InvalidKey(format!("Anahtar halkası UTF-8 değil: {}", resource_id)))?;
+                AnahtarHalkasi::ayristir(metin)
+            }
+            Err(SecurityError::Sahne64ResourceError(SahneError::ResourceNotFound)) => {
+                warn!("Anahtar halkası bulunamadı ({}). Boş halka ile başlanıyor.", resource_id); // no_std log
+                Ok(AnahtarHalkasi::yeni())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Anahtar halkasını Kaynağa atomik olarak yazar (geçici Kaynak + taşıma); yarıda kalan yazma
+    // halkayı bozmaz, hedef ya eski ya da yeni halkanın tamamını taşır.
+    pub fn kaynaga_yaz(&self, resource_id: &str) -> Result<(), SecurityError> {
+        srcresource::atomik_yaz(resource_id, self.metne_donustur().as_bytes()).map_err(SecurityError::from)
+    }
+
+    // İmzayı bu halkadaki anahtarla doğrular.
+    // simdi: Anahtar geçerlilik denetimi için Unix zamanı (saniye).
+    // Dönüş değeri: İmzayı doğrulayan anahtar; anahtar halkada yoksa UnknownSigningKey, iptal edilmişse
+    // RevokedSigningKey, süresi dolmuşsa ExpiredSigningKey, imza geçersizse SignatureVerificationFailed
+    // (hepsi imzadaki anahtarı belirtir).
+    pub fn dogrula(&self, baglam: &[u8], ozet: &[u8; 32], imza: &AyrikImza, simdi: u64) -> Result<&GenelAnahtar, SecurityError> {
+        let anahtar = self.bul(&imza.anahtar_kimligi)
+            .ok_or_else(|| SecurityError::UnknownSigningKey(imza.anahtar_kimligi.clone()))?;
+        anahtar.kullanilabilir_mi(simdi)?;
+        if anahtar.dogrula(baglam, ozet, imza) {
+            Ok(anahtar)
+        } else {
+            Err(SecurityError::SignatureVerificationFailed { key_id: anahtar.kimlik(), owner: anahtar.ad.clone() })
+        }
+    }
+
+    // Bellekteki veri için imzayı doğrular.
+    pub fn veri_imzasini_dogrula(&self, baglam: &[u8], veri: &[u8], imza: &AyrikImza, simdi: u64) -> Result<&GenelAnahtar, SecurityError> {
+        self.dogrula(baglam, &Sha256::digest(veri).into(), imza, simdi)
+    }
+}
+
+
+// Ayrık imza: imzalanan dosyadan ayrı (örn. "paket.zip.sig") saklanır.
+// Metin biçimi:
+//   sahne-imza-v1
+//   algoritma: ed25519
+//   anahtar: <anahtar kimliği>
+//   imza: <hex imza>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AyrikImza {
+    pub algoritma: ImzaAlgoritmasi,
+    pub anahtar_kimligi: String,
+    pub imza: Vec<u8>,
+}
+
+impl AyrikImza {
+    pub fn kodla(&self) -> String {
+        format!(
+            "{}\nalgoritma: {}\nanahtar: {}\nimza: {}\n",
+            IMZA_BICIMI_BASLIGI, self.algoritma.ad(), self.anahtar_kimligi, hex::encode(&self.imza)
+        )
+    }
+
+    pub fn coz(metin: &str) -> Result<Self, SecurityError> {
+        let mut satirlar = metin.lines().map(str::trim).filter(|satir| !satir.is_empty());
+        if satirlar.next() != Some(IMZA_BICIMI_BASLIGI) {
+            return Err(SecurityError::InvalidSignatureFile(format!("'{}' başlığı bekleniyordu", IMZA_BICIMI_BASLIGI)));
+        }
+
+        let mut algoritma = None;
+        let mut anahtar_kimligi = None;
+        let mut imza = None;
+        for satir in satirlar {
+            let (alan, deger) = satir.split_once(':').ok_or_else(|| {
+                SecurityError::InvalidSignatureFile(format!("Geçersiz satır: {}", satir))
+            })?;
+            match alan.trim() {
+                "algoritma" => algoritma = Some(ImzaAlgoritmasi::addan(deger)?),
+                "anahtar" => anahtar_kimligi = Some(deger.trim().to_lowercase()),
+                "imza" => imza = Some(hex::decode(deger.trim())?),
+                // Bilinmeyen alanlar ileriye dönük uyumluluk için yok sayılır.
+                _ => {}
+            }
+        }
+
+        let eksik = |alan: &str| SecurityError::InvalidSignatureFile(format!("'{}' alanı eksik", alan));
+        let imza = imza.ok_or_else(|| eksik("imza"))?;
+        if imza.len() != 64 {
+            return Err(SecurityError::InvalidSignatureFile(format!("Ed25519 imzası 64 bayt olmalı, {} bayt verildi", imza.len())));
+        }
+        Ok(AyrikImza {
+            algoritma: algoritma.ok_or_else(|| eksik("algoritma"))?,
+            anahtar_kimligi: anahtar_kimligi.ok_or_else(|| eksik("anahtar"))?,
+            imza,
+        })
+    }
+
+    // Art arda yazılmış birden çok imzayı çözer (eşik imzalı paketler için her imzalayan bir blok ekler).
+    // Her blok başlık satırıyla başlar; tek imzalı dosya tek bloktur.
+    pub fn hepsini_coz(metin: &str) -> Result<Vec<Self>, SecurityError> {
+        let mut imzalar = Vec::new();
+        let mut blok = String::new();
+        for satir in metin.lines() {
+            if satir.trim() == IMZA_BICIMI_BASLIGI && !blok.trim().is_empty() {
+                imzalar.push(AyrikImza::coz(&blok)?);
+                blok.clear();
+            }
+            blok.push_str(satir);
+            blok.push('\n');
+        }
+        if !blok.trim().is_empty() {
+            imzalar.push(AyrikImza::coz(&blok)?);
+        }
+        Ok(imzalar)
+    }
+
+    // İmzayı Kaynaktan okur.
+    pub fn kaynaktan_oku(resource_id: &str) -> Result<Self, SecurityError> {
+        let veri = read_resource_to_vec(resource_id)?;
+        let metin = core::str::from_utf8(&veri)
+            .map_err(|_| SecurityError::InvalidSignatureFile(format!("İmza dosyası UTF-8 değil: {}", resource_id)))?;
+        AyrikImza::coz(metin)
+    }
+
+    // Kaynaktaki tüm imza bloklarını okur.
+    pub fn hepsini_kaynaktan_oku(resource_id: &str) -> Result<Vec<Self>, SecurityError> {
+        let veri = read_resource_to_vec(resource_id)?;
+        let metin = core::str::from_utf8(&veri)
+            .map_err(|_| SecurityError::InvalidSignatureFile(format!("İmza dosyası UTF-8 değil: {}", resource_id)))?;
+        AyrikImza::hepsini_coz(metin)
+    }
+}
+
+
 // Helper fonksiyon: Sahne64 Kaynağından tüm içeriği Vec<u8> olarak oku.
 // srcsecurity.rs veya utils modülünden yeniden kullanıldı.
 // Note: Bu helper, utils gibi ortak bir modülde olmalıdır.
@@ -60,62 +496,190 @@ fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, SecurityError> { /
     Ok(buffer) // Vec<u8> (alloc)
 }
 
+// Paket Kaynağının SHA-256 özetini akış halinde hesaplar (arşiv belleğe alınmaz).
+pub fn paket_ozeti(package_resource_id: &str) -> Result<[u8; 32], SecurityError> {
+    let handle = resource::acquire(package_resource_id, resource::MODE_READ)
+        .map_err(SecurityError::from)?;
 
-// Basit bir örnek için, imzaları dosya içeriklerinin SHA256 karmalarının hex kodlanmış hali olarak hesaplar.
-// package_resource_id: İmzalanacak paketin Kaynak ID'si.
-// Dönüş değeri: Hesaplanmış imza (hex string) veya SecurityError.
-// Not: Gerçek bir uygulamada, daha güvenli bir dijital imzalama yöntemi (asimetrik kriptografi) kullanılmalıdır.
-pub fn sign_package(package_resource_id: &str) -> Result<String, SecurityError> { // Path yerine &str Kaynak ID, Result<String, SecurityError> olmalı
-    debug!("Paket imzalanıyor: {}", package_resource_id); // no_std log
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 4096]; // Stack buffer
+    loop {
+        match resource::read(handle, &mut buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => hasher.update(&buffer[..bytes_read]),
+            Err(e) => {
+                let _ = resource::release(handle);
+                return Err(SecurityError::from(e));
+            }
+        }
+    }
+    let _ = resource::release(handle);
 
-    // Paket Kaynağının içeriğini oku (Vec<u8> olarak)
-    let package_data = read_resource_to_vec(package_resource_id)?; // Kendi helper'ımızı kullan (SecurityError döner)
+    Ok(hasher.finalize().into())
+}
 
-    // Dosya içeriğinin SHA256 karmasını hesapla.
-    let mut hasher = Sha256::new(); // Sha256::new() alloc gerektirir mi? Genellikle değil.
-    hasher.update(&package_data); // update &[] alır no_std
-    let digest = hasher.finalize(); // finalize GenericArray<u8, 32> no_std
 
-    trace!("Paket özeti (SHA256) hesaplandı."); // no_std log
+// Paketi özel anahtarla imzalar ve ayrık imzayı döndürür.
+// package_resource_id: İmzalanacak paketin Kaynak ID'si.
+// signing_key: Yayıncının özel anahtarı.
+// Dönüş değeri: Ayrık imza (`kodla()` ile "paket.zip.sig" dosyasına yazılır) veya SecurityError.
+pub fn sign_package(package_resource_id: &str, signing_key: &ImzalamaAnahtari) -> Result<AyrikImza, SecurityError> {
+    debug!("Paket imzalanıyor: {} (anahtar {})", package_resource_id, signing_key.kimlik()); // no_std log
 
-    // Karmayı hex string'e dönüştür.
-    // hex::encode(&digest) -> String. alloc gerektirir, no_std.
-    let signature = hex::encode(&digest); // hex::encode Result dönmüyor, hata fırlatabilir mi? Hayır, hep başarılı olmalı.
+    let ozet = paket_ozeti(package_resource_id)?;
+    let imza = signing_key.ozet_imzala(PAKET_IMZA_BAGLAMI, &ozet);
 
-    debug!("Paket imzası (hex SHA256) hesaplandı: {}", signature); // no_std log
-    Ok(signature) // İmza hex stringini döndür (alloc)
+    debug!("Paket imzası oluşturuldu: {}", package_resource_id); // no_std log
+    Ok(imza)
 }
 
-// Belirtilen paketin imzasını hesaplar ve beklenen imza (hex string) ile karşılaştırır.
+// Paketin ayrık imzasını anahtar halkasındaki genel anahtarla doğrular.
 // package_resource_id: Doğrulanacak paketin Kaynak ID'si.
-// expected_signature: Beklenen imza (hex string).
-// Dönüş değeri: İmza eşleşirse Ok(true), eşleşmezse Ok(false), veya hatalar durumunda SecurityError.
-// Not: İmza eşleşmezse SecurityError::SignatureVerificationFailed hatası döndürmek yerine Ok(false) dönmek
-// buradaki fonksiyonun sorumluluğuna daha uygun olabilir. İmza doğrulama başarısızlığını bir hata olarak
-// ele almak çağıran kodun (srcsecurity.rs) sorumluluğu olabilir.
-pub fn verify_package(package_resource_id: &str, expected_signature: &str) -> Result<bool, SecurityError> { // Path yerine &str Kaynak ID, Result<bool, SecurityError> olmalı
-    debug!("Paket imzası doğrulanıyor. Paket: {}, Beklenen imza (ilk 8 char): {}", package_resource_id, &expected_signature.chars().take(8).collect::<String>()); // no_std log, take(8).collect alloc
+// signature: Paketin ayrık imzası.
+// keyring: Güvenilen genel anahtarlar.
+// Dönüş değeri: İmza geçerliyse Ok(true), geçersizse Ok(false); imzalayan anahtar halkada yoksa
+// SecurityError::UnknownSigningKey, iptal edilmiş veya süresi dolmuşsa RevokedSigningKey/ExpiredSigningKey,
+// diğer hatalarda ilgili SecurityError.
+pub fn verify_package(package_resource_id: &str, signature: &AyrikImza, keyring: &AnahtarHalkasi) -> Result<bool, SecurityError> {
+    debug!("Paket imzası doğrulanıyor. Paket: {}, Anahtar: {}", package_resource_id, signature.anahtar_kimligi); // no_std log
 
-    // Paketin imzasını hesapla.
-    let calculated_signature = sign_package(package_resource_id)?; // sign_package çağrısı (SecurityError döner)
+    let ozet = paket_ozeti(package_resource_id)?;
+    match keyring.dogrula(PAKET_IMZA_BAGLAMI, &ozet, signature, simdiki_zaman()?) {
+        Ok(anahtar) => {
+            info!("İmza doğrulandı. Paket: {}, İmzalayan: {} ({})", package_resource_id, anahtar.ad, signature.anahtar_kimligi); // no_std log
+            Ok(true)
+        }
+        Err(SecurityError::SignatureVerificationFailed { key_id, owner }) => {
+            warn!("İmza doğrulama başarısız! Paket: {}, Anahtar: {} ({})", package_resource_id, owner, key_id); // no_std log
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
 
-    // Hesaplanan imza beklenen imza ile eşleşiyor mu kontrol et.
-    let is_valid = calculated_signature == expected_signature; // String == &str karşılaştırması no_std
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if is_valid {
-        info!("İmza doğrulandı. Paket: {}", package_resource_id); // no_std log
-    } else {
-        warn!("İmza doğrulama başarısız! Hesaplanan: {}, Beklenen: {}. Paket: {}", calculated_signature, expected_signature, package_resource_id); // no_std log
+    fn test_anahtari(bayt: u8) -> ImzalamaAnahtari {
+        ImzalamaAnahtari::tohumdan(&[bayt; 32])
     }
 
-    Ok(is_valid) // Eşleşirse true, eşleşmezse false dön
-}
+    #[test]
+    fn test_imzala_ve_dogrula() {
+        let anahtar = test_anahtari(7);
+        let mut halka = AnahtarHalkasi::yeni();
+        halka.ekle(anahtar.genel_anahtar("test"));
 
-// #[cfg(test)] bloğu std test runner'ı ve std bağımlılıkları gerektirir.
-// Testler için mock resource veya Sahne64 simülasyonu gereklidir.
+        let imza = anahtar.veri_imzala(PAKET_IMZA_BAGLAMI, b"paket icerigi");
+        assert_eq!(imza.anahtar_kimligi, anahtar.kimlik());
+        assert!(halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"paket icerigi", &imza, 0).is_ok());
 
-#[cfg(test)]
-mod tests {
-    // std::path, std::io, std::fs, tempfile, sha2, hex kullandığı için no_std'de doğrudan çalışmaz.
-    // Mock resource::acquire/read/release ve test dosyası oluşturma/okuma helper'ları gerektirir.
+        // Değiştirilmiş içerik veya farklı bağlam reddedilir; hata imzalayan anahtarı belirtir
+        match halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"paket icerigI", &imza, 0) {
+            Err(SecurityError::SignatureVerificationFailed { key_id, owner }) => {
+                assert_eq!(key_id, anahtar.kimlik());
+                assert_eq!(owner, "test");
+            }
+            sonuc => panic!("beklenmeyen sonuç: {:?}", sonuc),
+        }
+        assert!(halka.veri_imzasini_dogrula(b"baska-baglam\0", b"paket icerigi", &imza, 0).is_err());
+    }
+
+    #[test]
+    fn test_bilinmeyen_anahtar_reddedilir() {
+        let mut halka = AnahtarHalkasi::yeni();
+        halka.ekle(test_anahtari(1).genel_anahtar("güvenilen"));
+
+        let imza = test_anahtari(2).veri_imzala(PAKET_IMZA_BAGLAMI, b"veri");
+        assert!(matches!(
+            halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"veri", &imza, 0),
+            Err(SecurityError::UnknownSigningKey(_))
+        ));
+
+        // Kimliği taklit edilen imza da doğrulanmaz
+        let mut taklit = imza.clone();
+        taklit.anahtar_kimligi = test_anahtari(1).kimlik();
+        assert!(matches!(
+            halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"veri", &taklit, 0),
+            Err(SecurityError::SignatureVerificationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ayrik_imza_metni() {
+        let imza = test_anahtari(3).veri_imzala(PAKET_IMZA_BAGLAMI, b"x");
+        let metin = imza.kodla();
+        assert!(metin.starts_with("sahne-imza-v1\nalgoritma: ed25519\n"));
+        assert_eq!(AyrikImza::coz(&metin).unwrap(), imza);
+
+        assert!(AyrikImza::coz("baska-bicim\n").is_err());
+
+        // Art arda yazılmış imzalar ayrı ayrı çözülür
+        let ikinci = test_anahtari(4).veri_imzala(PAKET_IMZA_BAGLAMI, b"x");
+        let coklu = format!("{}\n{}", metin, ikinci.kodla());
+        assert_eq!(AyrikImza::hepsini_coz(&coklu).unwrap(), [imza.clone(), ikinci]);
+        assert_eq!(AyrikImza::hepsini_coz(&metin).unwrap().len(), 1);
+        assert!(AyrikImza::coz("sahne-imza-v1\nalgoritma: rsa\nanahtar: ab\nimza: 00\n").is_err());
+        assert!(AyrikImza::coz("sahne-imza-v1\nalgoritma: ed25519\nanahtar: ab\nimza: 00\n").is_err());
+    }
+
+    #[test]
+    fn test_anahtar_halkasi_metni() {
+        let mut halka = AnahtarHalkasi::yeni();
+        halka.ekle(test_anahtari(4).genel_anahtar("Sahne Paket Ekibi"));
+        halka.ekle(test_anahtari(5).genel_anahtar("Ayna"));
+
+        let metin = format!("# yorum\n\n{}", halka.metne_donustur());
+        let okunan = AnahtarHalkasi::ayristir(&metin).unwrap();
+        assert_eq!(okunan, halka);
+        assert_eq!(okunan.bul(&test_anahtari(4).kimlik()).unwrap().ad, "Sahne Paket Ekibi");
+        assert_eq!(test_anahtari(4).kimlik().len(), 16);
+
+        let mut halka = okunan;
+        assert!(halka.cikar(&test_anahtari(5).kimlik()));
+        assert!(!halka.cikar(&test_anahtari(5).kimlik()));
+        assert_eq!(halka.anahtarlar().len(), 1);
+
+        assert!(AnahtarHalkasi::ayristir("ed25519 zz ad\n").is_err());
+    }
+
+    #[test]
+    fn test_anahtar_suresi_ve_iptali() {
+        let anahtar = test_anahtari(6);
+        let mut genel = anahtar.genel_anahtar("Ayna Ekibi");
+        genel.olusturma = 100;
+        genel.son_gecerlilik = Some(200);
+        let mut halka = AnahtarHalkasi::yeni();
+        halka.ekle(genel);
+
+        // Alanlar metin biçiminde korunur; alansız eski satırlar da okunur
+        let okunan = AnahtarHalkasi::ayristir(&halka.metne_donustur()).unwrap();
+        assert_eq!(okunan, halka);
+        let genel_hex_kaynagi = test_anahtari(7).genel_anahtar("");
+        let eski = format!("ed25519 {} Eski Biçim Ekibi\n", hex::encode(genel_hex_kaynagi.anahtar));
+        let eski = AnahtarHalkasi::ayristir(&eski).unwrap();
+        assert_eq!(eski.anahtarlar()[0].ad, "Eski Biçim Ekibi");
+        assert_eq!(eski.anahtarlar()[0].son_gecerlilik, None);
+        assert!(AnahtarHalkasi::ayristir(&format!("ed25519 {} bitis=yarin ad\n", hex::encode(genel_hex_kaynagi.anahtar))).is_err());
+
+        let imza = anahtar.veri_imzala(PAKET_IMZA_BAGLAMI, b"veri");
+        assert!(halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"veri", &imza, 199).is_ok());
+        assert!(matches!(
+            halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"veri", &imza, 200),
+            Err(SecurityError::ExpiredSigningKey { expired_at: 200, .. })
+        ));
+
+        // İptal edilen anahtar süresi dolmamış olsa da reddedilir ve yeniden eklenerek geri getirilemez
+        assert!(halka.iptal_et(&anahtar.kimlik(), 150));
+        assert!(matches!(
+            halka.veri_imzasini_dogrula(PAKET_IMZA_BAGLAMI, b"veri", &imza, 160),
+            Err(SecurityError::RevokedSigningKey { revoked_at: 150, .. })
+        ));
+        halka.ekle(anahtar.genel_anahtar("Ayna Ekibi"));
+        assert_eq!(halka.bul(&anahtar.kimlik()).unwrap().iptal_zamani, Some(150));
+        assert!(halka.bul(&anahtar.genel_anahtar("").parmak_izi()).is_some());
+    }
+
+    // sign_package/verify_package testleri için mock resource::acquire/read/release gerekir.
 }
@@ -0,0 +1,346 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+// tar ve flate2 std::io'ya, bzip2 ve xz2 C kütüphanelerine (libbz2, liblzma) dayanır; Sahne64 no_std derlemesinde
+// bu arka uç yoktur ve srcarchive tar arşivlerini DesteklenmeyenArsivBicimi ile reddeder.
+#![cfg(feature = "std")]
+extern crate alloc; // String, Vec, Box, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::boxed::Box; // Çözücü zinciri için (dyn Read)
+use alloc::format; // format! makrosu için
+
+// `std` özelliğinde core2::io, std::io'yu yeniden dışa aktarır; tar ve çözücüler bu trait'lerle çalışır.
+use core2::io::Read;
+use tar::{Archive, EntryType};
+
+// Sahne64 API modülleri
+use crate::resource;
+
+// Ortak arşiv yardımcıları (Kaynak okuyucu, yol doğrulama, parça parça aktarım)
+use crate::srcarchive::{self, ArsivGirdisi, ArsivSinirlari, CikarilanGirdi, GirdiTuru, SahneResourceReader, SembolikBagIzleyici, SinirSayaci, AKTARIM_PARCA_BOYUTU};
+use crate::srcbuild; // Ayrılmış paket meta dizini
+// Hangi sıkıştırma algoritmalarının izinli olduğu
+use crate::srcfeatures::{CompressionAlgorithm, Feature, FeatureSet};
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// no_std uyumlu print makroları
+use crate::print_macros::{println, eprintln};
+
+
+// Sıkıştırılmış (veya sıkıştırılmamış) tar akışını çözen okuyucuyu oluşturur.
+// Algoritma FeatureSet'te etkin değilse arşiv açılmaz.
+fn cozucu_olustur(
+    okuyucu: SahneResourceReader,
+    sikistirma: Option<CompressionAlgorithm>,
+    ozellikler: &FeatureSet,
+) -> Result<Box<dyn Read>, PaketYoneticisiHatasi> {
+    let algoritma = match sikistirma {
+        None => return Ok(Box::new(okuyucu)),
+        Some(algoritma) => algoritma,
+    };
+
+    if !ozellikler.is_enabled(&Feature::Compression(algoritma)) {
+        return Err(PaketYoneticisiHatasi::DesteklenmeyenArsivBicimi(format!(
+            "Sıkıştırma algoritması etkin değil: {:?}", algoritma
+        )));
+    }
+
+    let cozucu: Box<dyn Read> = match algoritma {
+        CompressionAlgorithm::Gzip => Box::new(flate2::read::GzDecoder::new(okuyucu)),
+        CompressionAlgorithm::Bzip2 => Box::new(bzip2::read::BzDecoder::new(okuyucu)),
+        CompressionAlgorithm::Zstd => Box::new(ruzstd::StreamingDecoder::new(okuyucu).map_err(|e| {
+            PaketYoneticisiHatasi::DesteklenmeyenArsivBicimi(format!("Geçersiz zstd çerçevesi: {:?}", e))
+        })?),
+        CompressionAlgorithm::Xz => Box::new(xz2::read::XzDecoder::new(okuyucu)),
+        CompressionAlgorithm::Lz4 => Box::new(lz4_flex::frame::FrameDecoder::new(okuyucu)),
+        // Brotli akışlarında sihirli bayt yoktur; biçim algılaması bu algoritmayı hiç seçmez.
+        CompressionAlgorithm::Brotli => {
+            return Err(PaketYoneticisiHatasi::DesteklenmeyenArsivBicimi(String::from(
+                "Brotli sıkıştırılmış tar arşivleri algılanamaz ve desteklenmez"
+            )));
+        }
+    };
+    Ok(cozucu)
+}
+
+fn tar_hatasi(arsiv_resource_id: &str, e: core2::io::Error) -> PaketYoneticisiHatasi {
+    PaketYoneticisiHatasi::InstallationError(format!("Tar arşivi okunamadı ({}): {:?}", arsiv_resource_id, e))
+}
+
+// Tar arşivini açıp her girdi için verilen fonksiyonu çağırır. Arşiv akış olarak okunur (geri sarma yok).
+// Tar'da merkezi dizin olmadığından sınırlar her girdinin başlığıyla, girdi işlenmeden önce denetlenir;
+// sıkıştırma oranı girdi başına değil, açılan toplam boyutun arşiv boyutuna oranı olarak denetlenir.
+fn girdileri_gez<F>(
+    arsiv_resource_id: &str,
+    sikistirma: Option<CompressionAlgorithm>,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+    mut islem: F,
+) -> Result<(), PaketYoneticisiHatasi>
+where
+    F: FnMut(&mut tar::Entry<'_, Box<dyn Read>>) -> Result<(), PaketYoneticisiHatasi>,
+{
+    let arsiv_handle = resource::acquire(arsiv_resource_id, resource::MODE_READ)
+        .map_err(PaketYoneticisiHatasi::from)?;
+
+    let sonuc = (|| {
+        let okuyucu = SahneResourceReader::new(arsiv_handle).map_err(PaketYoneticisiHatasi::from)?;
+        let arsiv_boyutu = okuyucu.boyut();
+        let mut arsiv = Archive::new(cozucu_olustur(okuyucu, sikistirma, ozellikler)?);
+        let mut sayac = SinirSayaci::yeni(sinirlar);
+        for girdi in arsiv.entries().map_err(|e| tar_hatasi(arsiv_resource_id, e))? {
+            let mut girdi = girdi.map_err(|e| tar_hatasi(arsiv_resource_id, e))?;
+            let ham_ad = String::from_utf8_lossy(&girdi.path_bytes()).into_owned();
+            sayac.girdi_ekle(&ham_ad, girdi.size(), None)?;
+            sayac.toplam_orani_denetle(&ham_ad, arsiv_boyutu)?;
+            islem(&mut girdi)?;
+        }
+        Ok(())
+    })();
+
+    let _ = resource::release(arsiv_handle);
+    sonuc
+}
+
+// Girdi adını UTF-8 olarak alıp srcarchive ile aynı kurallarla normalleştirir.
+fn girdi_yolu(girdi: &tar::Entry<'_, Box<dyn Read>>) -> Result<String, PaketYoneticisiHatasi> {
+    let ham = girdi.path_bytes();
+    let ad = core::str::from_utf8(&ham).map_err(|_| {
+        PaketYoneticisiHatasi::PathTraversalHatasi(format!("Girdi adı UTF-8 değil: {:?}", ham))
+    })?;
+    srcarchive::girdi_yolunu_normallestir(ad)
+}
+
+// Tar başlığındaki girdi türünü GirdiTuru'na çevirir. Desteklenmeyen türler (aygıt, FIFO vb.) için None.
+fn girdi_turu(tur: EntryType) -> Option<GirdiTuru> {
+    match tur {
+        EntryType::Directory => Some(GirdiTuru::Dizin),
+        EntryType::Regular | EntryType::Continuous => Some(GirdiTuru::Dosya),
+        EntryType::Symlink => Some(GirdiTuru::SembolikBag),
+        EntryType::Link => Some(GirdiTuru::SabitBag),
+        _ => None,
+    }
+}
+
+// Tar arşivinin girdilerini ayrıntılarıyla listeler (dizin girdileri '/' ile biter).
+// Tar'da girdi başına sıkıştırılmış boyut ve CRC yoktur; desteklenmeyen girdi türleri listelenmez.
+pub fn tar_icerik_listele(
+    arsiv_resource_id: &str,
+    sikistirma: Option<CompressionAlgorithm>,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<Vec<ArsivGirdisi>, PaketYoneticisiHatasi> {
+    let mut icerikler = Vec::new();
+    girdileri_gez(arsiv_resource_id, sikistirma, ozellikler, sinirlar, |girdi| {
+        let mut ad = girdi_yolu(girdi)?;
+        let tur = match girdi_turu(girdi.header().entry_type()) {
+            Some(tur) => tur,
+            None => return Ok(()),
+        };
+        if tur == GirdiTuru::Dizin && !ad.ends_with('/') {
+            ad.push('/');
+        }
+        if ad.is_empty() {
+            return Ok(());
+        }
+        let (mod_, _, _) = baslik_oznitelikleri(girdi, &ad)?;
+        let degistirilme_zamani = girdi.header().mtime().map_err(|_| {
+            PaketYoneticisiHatasi::ParsingError(format!("Tar başlığında geçersiz mtime ({})", ad))
+        })?;
+        icerikler.push(ArsivGirdisi {
+            ad,
+            tur,
+            boyut: girdi.size(),
+            sikistirilmis_boyut: None,
+            mod_,
+            crc32: None,
+            degistirilme_zamani,
+        });
+        Ok(())
+    })?;
+    Ok(icerikler)
+}
+
+// Tar arşivindeki adı verilen tek dosya girdisinin okuyucusunu işleve verir.
+// Tar'da dizin olmadığından arşiv baştan okunur, ama yalnızca aranan girdinin içeriği açılır.
+fn tar_girdisiyle<F>(
+    arsiv_resource_id: &str,
+    sikistirma: Option<CompressionAlgorithm>,
+    girdi_adi: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+    mut islem: F,
+) -> Result<(), PaketYoneticisiHatasi>
+where
+    F: FnMut(&mut tar::Entry<'_, Box<dyn Read>>) -> Result<(), PaketYoneticisiHatasi>,
+{
+    let aranan = srcarchive::girdi_yolunu_normallestir(girdi_adi)?;
+    let mut bulundu = false;
+    girdileri_gez(arsiv_resource_id, sikistirma, ozellikler, sinirlar, |girdi| {
+        // Aynı ad birden çok kez geçebilir (tar'da sonraki girdi öncekinin yerine geçer); sonuncusu kullanılır.
+        if girdi_yolu(girdi)? != aranan {
+            return Ok(());
+        }
+        if girdi_turu(girdi.header().entry_type()) != Some(GirdiTuru::Dosya) {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!("Girdi bir dosya değil: {}", girdi_adi)));
+        }
+        bulundu = true;
+        islem(girdi)
+    })?;
+
+    if !bulundu {
+        return Err(PaketYoneticisiHatasi::InvalidParameter(format!("Arşivde girdi bulunamadı: {}", girdi_adi)));
+    }
+    Ok(())
+}
+
+// Tar arşivindeki tek bir dosya girdisini belleğe okur.
+pub fn tar_girdi_oku(
+    arsiv_resource_id: &str,
+    sikistirma: Option<CompressionAlgorithm>,
+    girdi_adi: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<Vec<u8>, PaketYoneticisiHatasi> {
+    let mut icerik = Vec::new();
+    tar_girdisiyle(arsiv_resource_id, sikistirma, girdi_adi, ozellikler, sinirlar, |girdi| {
+        icerik.clear();
+        girdi.read_to_end(&mut icerik).map(|_| ()).map_err(|e| tar_hatasi(arsiv_resource_id, e))
+    })?;
+    Ok(icerik)
+}
+
+// Tar arşivindeki tek bir dosya girdisini hedef Kaynağa çıkarır.
+pub fn tar_dosya_cikar(
+    arsiv_resource_id: &str,
+    sikistirma: Option<CompressionAlgorithm>,
+    girdi_adi: &str,
+    hedef_resource_id: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<(), PaketYoneticisiHatasi> {
+    let mut aktarim_tamponu = alloc::vec![0u8; AKTARIM_PARCA_BOYUTU];
+    tar_girdisiyle(arsiv_resource_id, sikistirma, girdi_adi, ozellikler, sinirlar, |girdi| {
+        let handle = resource::acquire(
+            hedef_resource_id,
+            resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+        ).map_err(PaketYoneticisiHatasi::from)?;
+        let sonuc = srcarchive::girdiyi_aktar(girdi, handle, hedef_resource_id, &mut aktarim_tamponu);
+        let _ = resource::release(handle);
+        if sonuc.is_err() {
+            let _ = resource::delete(hedef_resource_id); // Yarım kalan girdiyi bırakma
+        }
+        sonuc.map(|_| ())
+    })
+}
+
+// Tar başlığından girdinin modunu, sahibini ve grubunu okur.
+fn baslik_oznitelikleri(girdi: &tar::Entry<'_, Box<dyn Read>>, yol: &str) -> Result<(u32, u32, u32), PaketYoneticisiHatasi> {
+    let baslik = girdi.header();
+    let hata = |alan: &str| PaketYoneticisiHatasi::ParsingError(format!("Tar başlığında geçersiz {} ({})", alan, yol));
+    let mod_ = baslik.mode().map_err(|_| hata("mod"))? & srcarchive::IZIN_MASKESI;
+    let sahip = u32::try_from(baslik.uid().map_err(|_| hata("uid"))?).map_err(|_| hata("uid"))?;
+    let grup = u32::try_from(baslik.gid().map_err(|_| hata("gid"))?).map_err(|_| hata("gid"))?;
+    Ok((mod_, sahip, grup))
+}
+
+// Tar arşivini belirtilen Kaynak ID'si altına açar.
+// Girdiler sabit boyutlu tamponla parça parça yazılır; mod, sahip ve bağlar başlıktan uygulanır.
+// Bağ hedefleri çıkarma kökü içinde kalmalı, yollar ve hedefler önceki sembolik bağların içinden geçmemelidir.
+// Tar'da merkezi dizin olmadığından arşiv önce yazmadan baştan sona gezilir: girdi sayısı, boyut ve oran sınırları
+// ile girdi adları bu ön geçişte denetlenir; sınırı aşan bir arşivden hiçbir girdi çıkarılmaz.
+// Dönüş değeri: Çıkarılan girdiler, arşivdeki sırayla.
+pub fn tar_ac(
+    arsiv_resource_id: &str,
+    sikistirma: Option<CompressionAlgorithm>,
+    cikartma_base_resource_id: &str,
+    ozellikler: &FeatureSet,
+    sinirlar: &ArsivSinirlari,
+) -> Result<Vec<CikarilanGirdi>, PaketYoneticisiHatasi> {
+    let kok = cikartma_base_resource_id.trim_end_matches('/');
+    let mut aktarim_tamponu = alloc::vec![0u8; AKTARIM_PARCA_BOYUTU];
+    let mut cikarilanlar = Vec::new();
+    let mut baglar = SembolikBagIzleyici::yeni();
+
+    // Ön geçiş: yalnızca başlıklar okunur, sınırlar tüm arşiv için denetlenir.
+    girdileri_gez(arsiv_resource_id, sikistirma, ozellikler, sinirlar, |girdi| girdi_yolu(girdi).map(|_| ()))?;
+
+    girdileri_gez(arsiv_resource_id, sikistirma, ozellikler, sinirlar, |girdi| {
+        let yol = girdi_yolu(girdi)?;
+        if yol.is_empty() || yol.starts_with(srcbuild::META_DIZINI) {
+            return Ok(()); // "./" gibi köke denk gelen girdiler ve paket meta dizini
+        }
+        // Yolu daha önce çıkarılmış bir sembolik bağın içinden geçen girdi kökün dışına yazabilir.
+        baglar.girdiyi_denetle(&yol)?;
+        let hedef_id = format!("{}/{}", kok, yol.trim_end_matches('/'));
+        let (mod_, sahip, grup) = baslik_oznitelikleri(girdi, &yol)?;
+
+        let cikarilan = match girdi.header().entry_type() {
+            EntryType::Directory => {
+                let handle = resource::acquire(&hedef_id, resource::MODE_CREATE).map_err(PaketYoneticisiHatasi::from)?;
+                let _ = resource::release(handle);
+                let mut yol = yol;
+                if !yol.ends_with('/') {
+                    yol.push('/');
+                }
+                CikarilanGirdi { yol, tur: GirdiTuru::Dizin, mod_, sahip, grup, bag_hedefi: None }
+            }
+            EntryType::Regular | EntryType::Continuous => {
+                srcarchive::ebeveyni_olustur(&hedef_id)?;
+                let handle = resource::acquire(
+                    &hedef_id,
+                    resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+                ).map_err(PaketYoneticisiHatasi::from)?;
+                let sonuc = srcarchive::girdiyi_aktar(girdi, handle, &hedef_id, &mut aktarim_tamponu);
+                let _ = resource::release(handle);
+                if sonuc.is_err() {
+                    let _ = resource::delete(&hedef_id); // Yarım kalan dosyayı bırakma
+                }
+                sonuc?;
+                CikarilanGirdi { yol, tur: GirdiTuru::Dosya, mod_, sahip, grup, bag_hedefi: None }
+            }
+            EntryType::Symlink | EntryType::Link => {
+                let bag = girdi.link_name_bytes().ok_or_else(|| {
+                    PaketYoneticisiHatasi::PathTraversalHatasi(format!("Bağ girdisinin hedefi yok: {}", yol))
+                })?;
+                let bag = core::str::from_utf8(&bag).map_err(|_| {
+                    PaketYoneticisiHatasi::PathTraversalHatasi(format!("Bağ hedefi UTF-8 değil: {}", yol))
+                })?.to_string();
+                srcarchive::ebeveyni_olustur(&hedef_id)?;
+
+                if girdi.header().entry_type() == EntryType::Symlink {
+                    baglar.bagi_ekle(&yol, &bag)?;
+                    srcarchive::sembolik_bag_olustur(&hedef_id, &bag)?;
+                    // Sembolik bağın kendi modu ve sahibi anlamsızdır; uygulanmaz.
+                    cikarilanlar.push(CikarilanGirdi { yol, tur: GirdiTuru::SembolikBag, mod_: 0o777, sahip, grup, bag_hedefi: Some(bag) });
+                    return Ok(());
+                }
+
+                // Sabit bağ hedefleri arşiv köküne göre yazılır ve arşivde daha önce çıkarılmış olmalıdır.
+                let bag_yolu = srcarchive::girdi_yolunu_normallestir(&bag)?;
+                if !cikarilanlar.iter().any(|c: &CikarilanGirdi| c.yol == bag_yolu && c.tur == GirdiTuru::Dosya) {
+                    return Err(PaketYoneticisiHatasi::PathTraversalHatasi(format!(
+                        "Sabit bağ hedefi arşivde önceden çıkarılmış bir dosya değil: {} -> {}", yol, bag
+                    )));
+                }
+                srcarchive::sabit_bag_olustur(&format!("{}/{}", kok, bag_yolu), &hedef_id)?;
+                // Sabit bağ hedefle aynı Kaynağı paylaşır; mod ve sahip hedeften gelir.
+                cikarilanlar.push(CikarilanGirdi { yol, tur: GirdiTuru::SabitBag, mod_, sahip, grup, bag_hedefi: Some(bag_yolu) });
+                return Ok(());
+            }
+            diger => {
+                eprintln!("Desteklenmeyen tar girdi türü atlandı ({:?}): {}", diger, yol);
+                return Ok(());
+            }
+        };
+
+        srcarchive::oznitelikleri_uygula(&hedef_id, &cikarilan)?;
+        cikarilanlar.push(cikarilan);
+        Ok(())
+    })?;
+
+    println!("Tar arşivi çıkarıldı: {} -> {}", arsiv_resource_id, cikartma_base_resource_id);
+    Ok(cikarilanlar)
+}
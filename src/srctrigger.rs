@@ -0,0 +1,224 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, BTreeMap, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+use alloc::collections::BTreeMap; // Bekleyen tetikleyiciler (deterministik çalıştırma sırası)
+
+// Paket struct ve tetikleyici tanımı
+use crate::package::{Paket, TetikleyiciTanimi};
+// Kurulu paketlerin sağladığı tetikleyicileri yüklemek için
+use crate::srcinstalled::KuruluPaketVeritabani;
+// Betik çalıştırma (yaşam döngüsü betikleriyle aynı mekanizma ve aynı erişim politikası)
+use crate::srcscript;
+use crate::srcsandbox::SandboxPolitikasi;
+// Bileşen içi glob eşleştirme (tek, özyinelemesiz eşleştirici)
+use crate::srctrust;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, error, debug, warn};
+
+
+// Yol bileşenlerini desen bileşenleriyle karşılaştırır. Bileşen içi '*' ve '?' srctrust::desen_eslesir ile,
+// '**' (sıfır veya daha fazla bileşen) aynı tek geri dönüş noktalı yöntemle eşlenir (en kötü desen × yol adımı);
+// paket meta verisinden gelen desenler üstel zaman harcatamaz.
+fn bilesenler_eslesir(desen: &[&str], yol: &[&str]) -> bool {
+    let (mut d, mut y) = (0, 0);
+    let mut geri_donus: Option<(usize, usize)> = None; // Son '**' konumu ve eşlediği yerin sonu
+    while y < yol.len() {
+        if d < desen.len() && desen[d] == "**" {
+            geri_donus = Some((d, y));
+            d += 1;
+        } else if d < desen.len() && srctrust::desen_eslesir(desen[d], yol[y]) {
+            d += 1;
+            y += 1;
+        } else if let Some((yildiz, eslesen)) = geri_donus {
+            // '**' bir bileşen daha yutar
+            d = yildiz + 1;
+            y = eslesen + 1;
+            geri_donus = Some((yildiz, eslesen + 1));
+        } else {
+            return false;
+        }
+    }
+    desen[d..].iter().all(|parca| *parca == "**")
+}
+
+// Kurulum dizinine göre bir yolun tetikleyici desenine uyup uymadığını kontrol eder.
+// '*' ve '?' tek bir bileşen içinde kalır, '**' bileşen sınırlarını aşar.
+pub fn desen_eslesir(desen: &str, yol: &str) -> bool {
+    let desen_bilesenleri: Vec<&str> = desen.trim_start_matches('/').split('/').filter(|p| !p.is_empty()).collect();
+    let yol_bilesenleri: Vec<&str> = yol.trim_start_matches('/').split('/').filter(|p| !p.is_empty()).collect();
+    bilesenler_eslesir(&desen_bilesenleri, &yol_bilesenleri)
+}
+
+
+// Bir işlem boyunca değişen dosyaları toplar ve ilgili tetikleyicileri işlem sonunda bir kez çalıştırır.
+pub struct TetikleyiciKuyrugu {
+    // (sağlayan paket adı, tanım, betiğin erişim politikası). Aynı betik+desen çifti bir kez kaydedilir.
+    tanimlar: Vec<(String, TetikleyiciTanimi, SandboxPolitikasi)>,
+    // Tanım indeksi -> tetikleyen yollar ("paket:yol")
+    bekleyenler: BTreeMap<usize, Vec<String>>,
+}
+
+impl TetikleyiciKuyrugu {
+    pub fn yeni() -> Self {
+        TetikleyiciKuyrugu {
+            tanimlar: Vec::new(),
+            bekleyenler: BTreeMap::new(),
+        }
+    }
+
+    // Bir paketin sağladığı tetikleyicileri kaydeder.
+    // Betiği paketin kendi betik dizininde (PAKET_BETIK_KOKU/<paket>/) olmayan tanımlar kaydedilmez.
+    pub fn tanimlari_ekle(&mut self, paket: &Paket) {
+        for tanim in &paket.tetikleyiciler {
+            if self.tanimlar.iter().any(|(_, t, _)| t == tanim) {
+                continue;
+            }
+            if !srcscript::paketin_betigi_mi(&paket.ad, &tanim.betik) {
+                warn!("Tetikleyici yok sayıldı: {} başka bir paketin betiğini gösteriyor: {}", paket.ad, tanim.betik);
+                continue;
+            }
+            debug!("Tetikleyici kaydedildi: {} '{}' -> {}", paket.ad, tanim.desen, tanim.betik);
+            let politika = SandboxPolitikasi::betik_icin(&paket.betik_izinleri, &tanim.betik);
+            self.tanimlar.push((paket.ad.clone(), tanim.clone(), politika));
+        }
+    }
+
+    // Kurulu paketlerin sağladığı tetikleyicileri kaydeder.
+    pub fn kurulu_paketlerden_yukle(&mut self, veritabani: &KuruluPaketVeritabani) -> Result<(), PaketYoneticisiHatasi> {
+        for kayit in veritabani.tum_kayitlar()? {
+            self.tanimlari_ekle(&kayit.paket);
+        }
+        Ok(())
+    }
+
+    // Bir paketin dosyalarının değiştiğini bildirir; eşleşen tetikleyiciler bekleyenlere eklenir.
+    pub fn dosyalar_degisti(&mut self, paket_adi: &str, yollar: &[String]) {
+        for (indeks, (_, tanim, _)) in self.tanimlar.iter().enumerate() {
+            for yol in yollar {
+                if desen_eslesir(&tanim.desen, yol) {
+                    self.bekleyenler
+                        .entry(indeks)
+                        .or_default()
+                        .push(format!("{}:{}", paket_adi, yol));
+                }
+            }
+        }
+    }
+
+    // Bir paketin sağladığı tetikleyicilerin bekleyen çalışmalarını bırakır (örn. paket kurulamadığında).
+    pub fn saglayanin_bekleyenlerini_birak(&mut self, paket_adi: &str) {
+        let tanimlar = &self.tanimlar;
+        self.bekleyenler.retain(|indeks, _| tanimlar[*indeks].0 != paket_adi);
+    }
+
+    // Çalıştırılmayı bekleyen tetikleyici sayısı.
+    pub fn bekleyen_sayisi(&self) -> usize {
+        self.bekleyenler.len()
+    }
+
+    // Bekleyen her tetikleyiciyi bir kez çalıştırır. Betiğe tetikleyen yollar satır satır argüman olarak geçilir.
+    // Bir tetikleyicinin hatası diğerlerinin çalışmasını engellemez; hatalar sonunda tek bir hata olarak döner.
+    pub fn calistir(&mut self) -> Result<(), PaketYoneticisiHatasi> {
+        let bekleyenler = core::mem::take(&mut self.bekleyenler);
+        let mut basarisizlar = Vec::new();
+
+        for (indeks, yollar) in bekleyenler {
+            let (saglayan, tanim, politika) = &self.tanimlar[indeks];
+            info!("Tetikleyici çalıştırılıyor: {} ({}, {} yol)", tanim.betik, saglayan, yollar.len());
+
+            let argumanlar = yollar.join("\n");
+            if let Err(e) = srcscript::betik_calistir(&tanim.betik, argumanlar.as_bytes(), politika) {
+                error!("Tetikleyici başarısız ({}): {:?}", tanim.betik, e);
+                basarisizlar.push(tanim.betik.clone());
+            }
+        }
+
+        if basarisizlar.is_empty() {
+            Ok(())
+        } else {
+            Err(PaketYoneticisiHatasi::InstallationError(format!(
+                "Tetikleyiciler başarısız oldu: {}", basarisizlar.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desen_eslesir() {
+        assert!(desen_eslesir("share/fonts/**", "share/fonts/truetype/dejavu.ttf"));
+        assert!(desen_eslesir("share/fonts/**", "share/fonts/a.ttf"));
+        assert!(!desen_eslesir("share/fonts/**", "share/icons/a.png"));
+        assert!(desen_eslesir("lib/*.so*", "lib/libz.so.1"));
+        assert!(!desen_eslesir("lib/*.so*", "lib/sub/libz.so.1"));
+        assert!(desen_eslesir("share/man/man?/*", "share/man/man1/ls.1"));
+        assert!(desen_eslesir("/bin/*", "bin/ls"));
+        assert!(desen_eslesir("**/*.conf", "etc/app/a.conf"));
+        assert!(!desen_eslesir("etc/**/b/**/c", "etc/b/x/y"));
+
+        // Çok sayıda joker içeren desenler üstel geri izleme olmadan reddedilir.
+        let uzun_ad: String = core::iter::repeat('a').take(64).collect();
+        assert!(!desen_eslesir("*a*a*a*a*a*a*a*a*a*a*a*a*b", &uzun_ad));
+        let derin_yol: Vec<&str> = core::iter::repeat("x").take(64).collect();
+        assert!(!desen_eslesir("**/x/**/x/**/x/**/x/**/x/**/y", &derin_yol.join("/")));
+    }
+
+    #[test]
+    fn test_tetikleyici_islem_basina_bir_kez() {
+        let mut fontconfig = Paket::yeni("fontconfig".to_string(), "2.14".to_string(), Vec::new());
+        fontconfig.tetikleyiciler.push(TetikleyiciTanimi {
+            desen: "share/fonts/**".to_string(),
+            betik: "sahne://system/package_scripts/fontconfig/fc-cache".to_string(),
+        });
+
+        let mut kuyruk = TetikleyiciKuyrugu::yeni();
+        kuyruk.tanimlari_ekle(&fontconfig);
+        kuyruk.tanimlari_ekle(&fontconfig); // Tekrar kayıt çoğaltmamalı
+        kuyruk.dosyalar_degisti("font-a", &["share/fonts/a.ttf".to_string()]);
+        kuyruk.dosyalar_degisti("font-b", &["share/fonts/b.ttf".to_string(), "bin/x".to_string()]);
+
+        assert_eq!(kuyruk.bekleyen_sayisi(), 1);
+        assert_eq!(kuyruk.bekleyenler[&0], ["font-a:share/fonts/a.ttf", "font-b:share/fonts/b.ttf"]);
+
+        // Sağlayan paket kurulamadıysa tetikleyicisi çalışmaz
+        kuyruk.saglayanin_bekleyenlerini_birak("font-a");
+        assert_eq!(kuyruk.bekleyen_sayisi(), 1);
+        kuyruk.saglayanin_bekleyenlerini_birak("fontconfig");
+        assert_eq!(kuyruk.bekleyen_sayisi(), 0);
+    }
+
+    #[test]
+    fn test_baska_paketin_betigi_kaydedilmez() {
+        let mut kotu = Paket::yeni("kotu".to_string(), "1.0".to_string(), Vec::new());
+        for betik in [
+            "sahne://system/package_scripts/fontconfig/fc-cache",
+            "sahne://system/package_scripts/kotu/../fontconfig/fc-cache",
+            "sahne://system/package_scripts/kotu-ek/betik",
+            "sahne://bin/sh",
+        ] {
+            kotu.tetikleyiciler.push(TetikleyiciTanimi { desen: "**".to_string(), betik: betik.to_string() });
+        }
+        kotu.tetikleyiciler.push(TetikleyiciTanimi {
+            desen: "**".to_string(),
+            betik: "sahne://system/package_scripts/kotu/guncelle".to_string(),
+        });
+
+        let mut kuyruk = TetikleyiciKuyrugu::yeni();
+        kuyruk.tanimlari_ekle(&kotu);
+        assert_eq!(kuyruk.tanimlar.len(), 1);
+        assert_eq!(kuyruk.tanimlar[0].1.betik, "sahne://system/package_scripts/kotu/guncelle");
+    }
+
+    // calistir() testleri mock task::spawn gerektirir.
+}
@@ -1,16 +1,21 @@
 #![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
-extern crate alloc; // HashSet, String, Vec, format! için
+extern crate alloc; // String, Vec, format! için
 
-use alloc::collections::HashSet; // std::collections::HashSet yerine
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::format; // format! makrosu için
 use alloc::borrow::ToOwned; // to_owned() için
 
+// serde ve no_std uyumlu serileştirme/deserileştirme kütüphanesi
+use serde::{Deserialize, Serialize};
+use postcard; // no_std uyumlu binary serileştirme
+
 // Sahne64 API modülleri
+use crate::task; // Kayıt zamanları için
 use crate::resource; // Kaynak işlemleri (acquire, read, write, release)
 use crate::SahneError; // Sahne64 hata türü
 use crate::Handle; // Kaynak Handle'ları
+use crate::srcresource; // Atomik Kaynak yazma
 
 // Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
 use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
@@ -34,8 +39,8 @@ pub enum TrustError {
     // Parsing Hatası (örn. güvenilenler dosyasından okuma sırasında)
     ParsingError(String), // String alloc gerektirir
 
-    // Diğer güven yönetimi hataları
-    // ...
+    // Güven deposu yüklenemediği için değişiklik reddedildi (boş depo eskisinin üzerine yazılmasın diye)
+    StoreUnavailable(String),
 }
 
 // core::fmt::Display implementasyonu
@@ -44,6 +49,7 @@ impl core::fmt::Display for TrustError {
         match self {
             TrustError::Sahne64ResourceError(e) => write!(f, "Sahne64 Kaynak hatası: {:?}", e),
             TrustError::ParsingError(s) => write!(f, "Ayrıştırma hatası: {}", s),
+            TrustError::StoreUnavailable(s) => write!(f, "Güven deposu yüklenemedi, değişiklik reddedildi: {}", s),
         }
     }
 }
@@ -92,20 +98,19 @@ fn read_resource_to_vec(resource_id: &str) -> Result<Vec<u8>, TrustError> { // R
     Ok(buffer) // Vec<u8> (alloc)
 }
 
-// Helper fonksiyon: String içeriğini Sahne64 Kaynağına yazar (truncate ederek).
+// Helper fonksiyon: Veriyi Sahne64 Kaynağına yazar.
 // resource_id: Yazılacak Kaynağın ID'si.
-// content: Yazılacak string içerik.
-fn write_string_to_resource(resource_id: &str, content: &str) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
+// append: true ise sonuna ekler, false ise truncate ederek yazar.
+fn write_bytes_to_resource(resource_id: &str, buffer: &[u8], append: bool) -> Result<(), TrustError> {
+     let mode = if append { resource::MODE_APPEND } else { resource::MODE_TRUNCATE };
      let handle = resource::acquire(
          resource_id,
-         resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_TRUNCATE
+         resource::MODE_WRITE | resource::MODE_CREATE | mode
      ).map_err(|e| {
           error!("Helper: Kaynak acquire hatası ({}): {:?}", resource_id, e); // no_std log
           TrustError::from(e) // SahneError -> TrustError
      })?;
 
-     let buffer = content.as_bytes(); // &str -> &[u8]
-
      let mut written = 0;
      while written < buffer.len() {
           match resource::write(handle, &buffer[written..]) {
@@ -134,294 +139,568 @@ fn write_string_to_resource(resource_id: &str, content: &str) -> Result<(), Trus
     Ok(()) // Başarı
 }
 
+// Şimdiki zaman (Unix saniyesi).
+fn simdiki_zaman() -> Result<u64, TrustError> {
+    task::current_time_us().map(|us| us / 1_000_000).map_err(TrustError::from)
+}
+
+
+// Güven deposunun varsayılan Kaynakları
+pub const VARSAYILAN_GUVEN_DEPOSU: &str = "sahne://config/trust_store.bin";
+pub const VARSAYILAN_DENETIM_KAYDI: &str = "sahne://config/trust_audit.log";
+
+// Yapılandırılmış depodan önceki satır tabanlı listeler; ilk yüklemede depoya aktarılır ve yerinde bırakılır.
+pub const VARSAYILAN_YAYINCI_LISTESI: &str = "sahne://config/trusted_publishers.list";
+pub const VARSAYILAN_PAKET_LISTESI: &str = "sahne://config/trusted_packages.list";
+pub const VARSAYILAN_ANAHTAR_KAPSAMLARI: &str = "sahne://config/key_scopes.list";
+
+// Güven deposu biçim sürümü; 1. sürüm (eşik kuralları yok) yüklenirken yükseltilir, diğerleri reddedilir
+const GUVEN_DEPOSU_BICIM_SURUMU: u32 = 2;
+
+// Güvenilen bir yayıncı veya paket.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GuvenKaydi {
+    pub ad: String,
+    pub aciklama: String,
+    pub eklenme: u64, // Unix saniyesi
+    pub ekleyen: String,
+}
+
+// Bir yayıncı anahtarının imzalayabileceği paketler.
+// Anahtar yalnızca `depolar` desenlerinden birine uyan depodan gelen ve adı `paket_desenleri`nden birine uyan
+// paketleri imzalayabilir (örn. üretici anahtarı: depo "vendor", paket "vendor-*").
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AnahtarKapsami {
+    pub anahtar_kimligi: String,
+    pub depolar: Vec<String>, // Depo adı desenleri (srcrepositoryrelease::DepoBildirimi.depo ile eşleşir)
+    pub paket_desenleri: Vec<String>, // Paket adı desenleri
+    pub aciklama: String,
+    pub eklenme: u64, // Unix saniyesi
+    pub ekleyen: String,
+}
+
+impl AnahtarKapsami {
+    pub fn izin_veriyor_mu(&self, paket_adi: &str, depo: &str) -> bool {
+        self.depoya_izin_veriyor_mu(depo)
+            && self.paket_desenleri.iter().any(|desen| desen_eslesir(desen, paket_adi))
+    }
+
+    // Anahtarın bu deponun bildirimini imzalayıp imzalayamayacağı (paket desenlerinden bağımsız).
+    pub fn depoya_izin_veriyor_mu(&self, depo: &str) -> bool {
+        self.depolar.iter().any(|desen| desen_eslesir(desen, depo))
+    }
+
+    // Eski satır biçimini ayrıştırır: `<anahtar kimliği> depo=<desen,...> paket=<desen,...>`.
+    fn ayristir(satir: &str) -> Result<Self, TrustError> {
+        let mut parcalar = satir.split_whitespace();
+        let anahtar_kimligi = parcalar.next().unwrap_or("").to_lowercase();
+        let mut depolar = Vec::new();
+        let mut paket_desenleri = Vec::new();
+        for parca in parcalar {
+            let liste = |deger: &str| -> Vec<String> {
+                deger.split(',').map(str::trim).filter(|desen| !desen.is_empty()).map(str::to_owned).collect()
+            };
+            match parca.split_once('=') {
+                Some(("depo", deger)) => depolar.extend(liste(deger)),
+                Some(("paket", deger)) => paket_desenleri.extend(liste(deger)),
+                _ => return Err(TrustError::ParsingError(format!("Geçersiz anahtar kapsamı alanı: {}", parca))),
+            }
+        }
+        if depolar.is_empty() || paket_desenleri.is_empty() {
+            return Err(TrustError::ParsingError(format!("Anahtar kapsamında depo ve paket desenleri gerekli: {}", satir)));
+        }
+        Ok(AnahtarKapsami { anahtar_kimligi, depolar, paket_desenleri, aciklama: String::new(), eklenme: 0, ekleyen: String::new() })
+    }
+}
+
+// Eşik imza kuralı: adı desenlerden birine uyan paket, `anahtarlar` listesindeki (N) farklı anahtarlardan en az
+// `esik` (M) tanesince geçerli olarak imzalanmış olmalıdır (örn. çekirdek ve önyükleyici paketleri için 2/3).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EsikKurali {
+    pub ad: String, // Kuralın adı (örn. "cekirdek")
+    pub paket_desenleri: Vec<String>,
+    pub esik: u32,
+    pub anahtarlar: Vec<String>, // İmzası sayılan anahtar kimlikleri (sıralı, tekil)
+    pub aciklama: String,
+    pub eklenme: u64, // Unix saniyesi
+    pub ekleyen: String,
+}
+
+impl EsikKurali {
+    pub fn uygulanir_mi(&self, paket_adi: &str) -> bool {
+        self.paket_desenleri.iter().any(|desen| desen_eslesir(desen, paket_adi))
+    }
+}
+
+// Basit glob eşleştirme: '*' herhangi bir dizi karakterle, '?' tek karakterle eşleşir.
+pub fn desen_eslesir(desen: &str, ad: &str) -> bool {
+    let desen: Vec<char> = desen.chars().collect();
+    let ad: Vec<char> = ad.chars().collect();
+    let (mut d, mut a) = (0, 0);
+    let mut geri_donus: Option<(usize, usize)> = None; // Son '*' konumu ve eşlediği yerin sonu
+    while a < ad.len() {
+        if d < desen.len() && (desen[d] == '?' || desen[d] == ad[a]) {
+            d += 1;
+            a += 1;
+        } else if d < desen.len() && desen[d] == '*' {
+            geri_donus = Some((d, a));
+            d += 1;
+        } else if let Some((yildiz, eslesen)) = geri_donus {
+            // '*' bir karakter daha yutar
+            d = yildiz + 1;
+            a = eslesen + 1;
+            geri_donus = Some((yildiz, eslesen + 1));
+        } else {
+            return false;
+        }
+    }
+    desen[d..].iter().all(|&karakter| karakter == '*')
+}
+
+// Diskteki güven deposu. Listeler ada (kapsamlar anahtar kimliğine) göre sıralı tutulur,
+// böylece aynı içerik her zaman aynı baytlarla yazılır.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+struct GuvenDeposu {
+    bicim_surumu: u32,
+    yayincilar: Vec<GuvenKaydi>,
+    paketler: Vec<GuvenKaydi>,
+    anahtar_kapsamlari: Vec<AnahtarKapsami>,
+    esik_kurallari: Vec<EsikKurali>, // Ada göre sıralı
+}
+
+// 1. sürüm güven deposu (eşik kuralları öncesi); yalnızca yükseltme için okunur.
+#[derive(Deserialize)]
+struct GuvenDeposuV1 {
+    bicim_surumu: u32,
+    yayincilar: Vec<GuvenKaydi>,
+    paketler: Vec<GuvenKaydi>,
+    anahtar_kapsamlari: Vec<AnahtarKapsami>,
+}
+
+impl GuvenDeposu {
+    // Depo baytlarını çözer; 1. sürümü boş eşik kurallarıyla yükseltir.
+    fn coz(veri: &[u8]) -> Result<Self, TrustError> {
+        let cozme_hatasi = |e: postcard::Error| TrustError::ParsingError(format!("Güven deposu çözülemedi: {:?}", e));
+        let (bicim_surumu, _) = postcard::take_from_bytes::<u32>(veri).map_err(cozme_hatasi)?;
+        match bicim_surumu {
+            1 => {
+                let eski: GuvenDeposuV1 = postcard::from_bytes(veri).map_err(cozme_hatasi)?;
+                info!("Güven deposu 1. sürümden {}. sürüme yükseltiliyor.", GUVEN_DEPOSU_BICIM_SURUMU); // no_std log
+                Ok(GuvenDeposu {
+                    bicim_surumu: GUVEN_DEPOSU_BICIM_SURUMU,
+                    yayincilar: eski.yayincilar,
+                    paketler: eski.paketler,
+                    anahtar_kapsamlari: eski.anahtar_kapsamlari,
+                    esik_kurallari: Vec::new(),
+                })
+            }
+            GUVEN_DEPOSU_BICIM_SURUMU => postcard::from_bytes(veri).map_err(cozme_hatasi),
+            surum => Err(TrustError::ParsingError(format!("Desteklenmeyen güven deposu biçim sürümü: {}", surum))),
+        }
+    }
+}
+
+// Sıralı listeye ekler; aynı adda kayıt varsa false döner (liste değişmez).
+fn sirali_ekle(liste: &mut Vec<GuvenKaydi>, kayit: GuvenKaydi) -> bool {
+    match liste.binary_search_by(|mevcut| mevcut.ad.as_str().cmp(&kayit.ad)) {
+        Ok(_) => false,
+        Err(konum) => {
+            liste.insert(konum, kayit);
+            true
+        }
+    }
+}
+
+fn sirali_cikar(liste: &mut Vec<GuvenKaydi>, ad: &str) -> Option<GuvenKaydi> {
+    liste.binary_search_by(|mevcut| mevcut.ad.as_str().cmp(ad)).ok().map(|konum| liste.remove(konum))
+}
+
+// Denetim kaydındaki bir değişiklik.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenetimIslemi {
+    Ekle,
+    Kaldir,
+    Aktar, // Eski satır tabanlı listelerden aktarım
+}
+
+impl DenetimIslemi {
+    fn ad(&self) -> &'static str {
+        match self {
+            DenetimIslemi::Ekle => "ekle",
+            DenetimIslemi::Kaldir => "kaldir",
+            DenetimIslemi::Aktar => "aktar",
+        }
+    }
+}
 
-// Güvenilen yayıncıları ve paketleri yönetir.
-// Güvenilen listeleri Sahne64 Kaynaklarında saklar.
+// Denetim kaydı satırı: `<zaman>\t<işlem>\t<tür>\t<ad>\t<yapan>\t<açıklama>`.
+// Alanlardaki sekme ve satır sonları boşluğa çevrilir; kayıt satır satır ayrıştırılabilir kalır.
+// '#' ile başlayan satırlar nottur (örn. bir önceki değişikliğin depoya yazılamadığı).
+fn denetim_satiri(zaman: u64, islem: DenetimIslemi, tur: &str, ad: &str, yapan: &str, aciklama: &str) -> String {
+    let temizle = |alan: &str| -> String { alan.chars().map(|c| if c.is_control() { ' ' } else { c }).collect() };
+    format!("{}\t{}\t{}\t{}\t{}\t{}\n", zaman, islem.ad(), tur, temizle(ad), temizle(yapan), temizle(aciklama))
+}
+
+
+// Güvenilen yayıncıları, paketleri ve yayıncı anahtarlarının kapsamlarını yönetir.
+// Tüm güven verisi tek bir yapılandırılmış Kaynakta tutulur ve atomik olarak (geçici Kaynak + taşıma) yazılır;
+// her ekleme ve kaldırma ayrıca yalnızca sonuna eklenen bir denetim kaydına yazılır.
 pub struct TrustManager {
-    trusted_publishers: HashSet<String>, // alloc
-    trusted_packages: HashSet<String>, // alloc
-    // Güvenilen listelerin Kaynak ID'leri
-    publishers_resource_id: String, // alloc
-    packages_resource_id: String, // alloc
+    depo: GuvenDeposu,
+    // Depo okunamadıysa (bozuk veya erişilemez) değişiklikler reddedilir; aksi halde boş depo eskisinin üzerine yazılırdı.
+    yuklenemedi: bool,
+    // Kaynak deposu bulunamadı (ilk kullanım); eski listeler aktarılabilir
+    depo_yok: bool,
+    store_resource_id: String,
+    audit_resource_id: String,
 }
 
 impl TrustManager {
-    // Yeni bir TrustManager örneği oluşturur ve güvenilen verileri yükler.
-    // publishers_resource_id: Güvenilen yayıncı listesi Kaynağının ID'si.
-    // packages_resource_id: Güvenilen paket listesi Kaynağının ID'si.
-    pub fn new(publishers_resource_id: &str, packages_resource_id: &str) -> Self { // &str Kaynak ID'leri
+    // Yeni bir TrustManager örneği oluşturur ve güven deposunu yükler.
+    // store_resource_id: Yapılandırılmış güven deposu Kaynağının ID'si.
+    // audit_resource_id: Denetim kaydı Kaynağının ID'si (yalnızca sonuna eklenir).
+    pub fn new(store_resource_id: &str, audit_resource_id: &str) -> Self { // &str Kaynak ID'leri
         let mut manager = TrustManager {
-            trusted_publishers: HashSet::new(), // alloc
-            trusted_packages: HashSet::new(), // alloc
-            publishers_resource_id: publishers_resource_id.to_owned(), // to_owned alloc
-            packages_resource_id: packages_resource_id.to_owned(), // to_owned alloc
+            depo: GuvenDeposu { bicim_surumu: GUVEN_DEPOSU_BICIM_SURUMU, ..GuvenDeposu::default() },
+            yuklenemedi: false,
+            depo_yok: false,
+            store_resource_id: store_resource_id.to_owned(), // to_owned alloc
+            audit_resource_id: audit_resource_id.to_owned(),
         };
         // Yükleme hatasını burada logluyoruz, çünkü constructor Result döndürmez.
         if let Err(e) = manager.load_trusted_data() {
-             error!("Güvenilen veri yüklenirken hata oluştu: {:?}", e); // no_std log
-             // Hata durumunda boş bir yönetici ile devam et.
+             error!("Güven deposu yüklenirken hata oluştu, değişiklikler reddedilecek: {:?}", e); // no_std log
+             manager.yuklenemedi = true;
         }
         manager
     }
 
-    // Güvenilen yayıncı ve paket listelerini Sahne64 Kaynaklarından yükler.
-    fn load_trusted_data(&mut self) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
-        debug!("Güvenilen veri yükleniyor."); // no_std log
-        self.load_trusted_publishers()?; // TrustError yayar
-        self.load_trusted_packages()?; // TrustError yayar
-        info!("Güvenilen veri yükleme tamamlandı."); // no_std log
-        Ok(()) // Başarı
+    // Varsayılan Kaynaklardaki güven deposunu yükler; depo henüz yoksa eski satır tabanlı listeleri aktarır.
+    // Aktarım başarısız olursa depo yüklenemedi sayılır: eski listelerdeki kayıtlar olmadan boş depo üzerine
+    // yazılmasın diye değişiklikler reddedilir.
+    pub fn varsayilan() -> Self {
+        let mut manager = TrustManager::new(VARSAYILAN_GUVEN_DEPOSU, VARSAYILAN_DENETIM_KAYDI);
+        if manager.depo_yok {
+            if let Err(e) = manager.import_legacy_lists(VARSAYILAN_YAYINCI_LISTESI, VARSAYILAN_PAKET_LISTESI, VARSAYILAN_ANAHTAR_KAPSAMLARI) {
+                error!("Eski güven listeleri aktarılamadı, değişiklikler reddedilecek: {:?}", e); // no_std log
+                manager.yuklenemedi = true;
+            }
+        }
+        manager
     }
 
-    // Güvenilen yayıncı listesini Kaynaktan yükler.
-    fn load_trusted_publishers(&mut self) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
-        debug!("Güvenilen yayıncılar yükleniyor: {}", self.publishers_resource_id); // no_std log
-        self.trusted_publishers.clear(); // Önceki veriyi temizle (alloc)
-
-        match read_resource_to_vec(&self.publishers_resource_id) { // Helper fonksiyonu kullan
+    // Güven deposunu Kaynaktan yükler. Kaynak yoksa boş depo ile başlanır.
+    fn load_trusted_data(&mut self) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
+        debug!("Güven deposu yükleniyor: {}", self.store_resource_id); // no_std log
+        match read_resource_to_vec(&self.store_resource_id) {
             Ok(buffer) => {
-                 // Byte içeriğini UTF-8 stringe çevir.
-                 let content = core::str::from_utf8(&buffer) // from_utf8 Result<_, Utf8Error> no_std
-                    .map_err(|e| TrustError::from(e))?; // Utf8Error -> TrustError
-
-                // Satırları işle ve HashSet'e ekle.
-                for line in content.lines() { // lines() Iter over &str no_std
-                    let publisher = line.trim(); // trim() &str metodu no_std
-                    if !publisher.is_empty() { // is_empty() &str metodu no_std
-                        self.trusted_publishers.insert(publisher.to_owned()); // insert, to_owned alloc
-                    }
-                }
-                 debug!("{} güvenilen yayıncı yüklendi.", self.trusted_publishers.len()); // no_std log
-                 Ok(()) // Başarı
+                let depo = GuvenDeposu::coz(&buffer)?;
+                info!("Güven deposu yüklendi: {} yayıncı, {} paket, {} anahtar kapsamı, {} eşik kuralı.",
+                      depo.yayincilar.len(), depo.paketler.len(), depo.anahtar_kapsamlari.len(), depo.esik_kurallari.len()); // no_std log
+                self.depo = depo;
+                Ok(())
             }
             Err(TrustError::Sahne64ResourceError(SahneError::ResourceNotFound)) => {
-                // Kaynak bulunamadıysa hata değil, boş liste ile başla.
-                warn!("Güvenilen yayıncılar Kaynağı bulunamadı ({}). Boş liste ile başlanıyor.", self.publishers_resource_id); // no_std log
-                Ok(()) // Başarı (boş HashSet ile)
-            }
-            Err(e) => {
-                // Diğer okuma hatalarını yay.
-                 error!("Güvenilen yayıncılar yüklenirken hata oluştu ({}): {:?}", self.publishers_resource_id, e); // no_std log
-                Err(e) // TrustError yayılır
+                // Kaynak bulunamadıysa hata değil, boş depo ile başla.
+                warn!("Güven deposu bulunamadı ({}). Boş depo ile başlanıyor.", self.store_resource_id); // no_std log
+                self.depo_yok = true;
+                Ok(())
             }
+            Err(e) => Err(e),
         }
     }
 
-    // Güvenilen paket listesini Kaynaktan yükler.
-    fn load_trusted_packages(&mut self) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
-        debug!("Güvenilen paketler yükleniyor: {}", self.packages_resource_id); // no_std log
-        self.trusted_packages.clear(); // Önceki veriyi temizle (alloc)
-
-        match read_resource_to_vec(&self.packages_resource_id) { // Helper fonksiyonu kullan
-            Ok(buffer) => {
-                 // Byte içeriğini UTF-8 stringe çevir.
-                 let content = core::str::from_utf8(&buffer)
-                     .map_err(|e| TrustError::from(e))?; // Utf8Error -> TrustError
-
-                // Satırları işle ve HashSet'e ekle.
-                for line in content.lines() {
-                    let package = line.trim();
-                    if !package.is_empty() {
-                        self.trusted_packages.insert(package.to_owned()); // alloc
-                    }
-                }
-                 debug!("{} güvenilen paket yüklendi.", self.trusted_packages.len()); // no_std log
-                 Ok(()) // Başarı
+    // Eski satır tabanlı listeleri (yayıncılar, paketler, anahtar kapsamları) depoya aktarır.
+    // Bulunmayan listeler atlanır; eski Kaynaklar silinmez. Dönüş değeri: aktarılan kayıt sayısı.
+    pub fn import_legacy_lists(&mut self, publishers_resource_id: &str, packages_resource_id: &str, scopes_resource_id: &str) -> Result<usize, PaketYoneticisiHatasi> {
+        let satirlar = |resource_id: &str| -> Result<Vec<String>, TrustError> {
+            match read_resource_to_vec(resource_id) {
+                Ok(buffer) => Ok(core::str::from_utf8(&buffer)?
+                    .lines()
+                    .map(str::trim)
+                    .filter(|satir| !satir.is_empty() && !satir.starts_with('#'))
+                    .map(str::to_owned)
+                    .collect()),
+                Err(TrustError::Sahne64ResourceError(SahneError::ResourceNotFound)) => Ok(Vec::new()),
+                Err(e) => Err(e),
             }
-            Err(TrustError::Sahne64ResourceError(SahneError::ResourceNotFound)) => {
-                // Kaynak bulunamadıysa hata değil, boş liste ile başla.
-                warn!("Güvenilen paketler Kaynağı bulunamadı ({}). Boş liste ile başlanıyor.", self.packages_resource_id); // no_std log
-                Ok(()) // Başarı (boş HashSet ile)
+        };
+        let yayincilar = satirlar(publishers_resource_id)?;
+        let paketler = satirlar(packages_resource_id)?;
+        let kapsamlar = satirlar(scopes_resource_id)?
+            .iter()
+            .map(|satir| AnahtarKapsami::ayristir(satir))
+            .collect::<Result<Vec<_>, _>>()?;
+        if yayincilar.is_empty() && paketler.is_empty() && kapsamlar.is_empty() {
+            return Ok(0);
+        }
+
+        let simdi = simdiki_zaman()?;
+        let kayit = |ad: &str| GuvenKaydi {
+            ad: ad.to_owned(),
+            aciklama: String::from("eski listeden aktarıldı"),
+            eklenme: simdi,
+            ekleyen: String::from("aktarım"),
+        };
+        let mut yeni = self.depo.clone();
+        let mut denetim = String::new();
+        for yayinci in &yayincilar {
+            if sirali_ekle(&mut yeni.yayincilar, kayit(yayinci)) {
+                denetim.push_str(&denetim_satiri(simdi, DenetimIslemi::Aktar, "yayinci", yayinci, "aktarım", publishers_resource_id));
             }
-            Err(e) => {
-                // Diğer okuma hatalarını yay.
-                 error!("Güvenilen paketler yüklenirken hata oluştu ({}): {:?}", self.packages_resource_id, e); // no_std log
-                Err(e) // TrustError yayılır
+        }
+        for paket in &paketler {
+            if sirali_ekle(&mut yeni.paketler, kayit(paket)) {
+                denetim.push_str(&denetim_satiri(simdi, DenetimIslemi::Aktar, "paket", paket, "aktarım", packages_resource_id));
             }
         }
+        for mut kapsam in kapsamlar {
+            kapsam.aciklama = String::from("eski listeden aktarıldı");
+            kapsam.eklenme = simdi;
+            kapsam.ekleyen = String::from("aktarım");
+            denetim.push_str(&denetim_satiri(simdi, DenetimIslemi::Aktar, "kapsam", &kapsam.anahtar_kimligi, "aktarım", scopes_resource_id));
+            yeni.anahtar_kapsamlari.retain(|mevcut| mevcut.anahtar_kimligi != kapsam.anahtar_kimligi);
+            yeni.anahtar_kapsamlari.push(kapsam);
+        }
+        yeni.anahtar_kapsamlari.sort_by(|a, b| a.anahtar_kimligi.cmp(&b.anahtar_kimligi));
+
+        let aktarilan = denetim.lines().count();
+        self.commit(yeni, &denetim)?;
+        self.depo_yok = false;
+        info!("{} kayıt eski güven listelerinden aktarıldı.", aktarilan); // no_std log
+        Ok(aktarilan)
     }
 
+    // Değişikliği kalıcı hale getirir: önce denetim kaydına eklenir, sonra depo atomik olarak yazılır.
+    // Depo yazılamazsa bellekteki durum değişmez ve denetim kaydına başarısızlık satırı eklenir.
+    fn commit(&mut self, yeni: GuvenDeposu, denetim: &str) -> Result<(), PaketYoneticisiHatasi> {
+        if self.yuklenemedi {
+            return Err(PaketYoneticisiHatasi::from(TrustError::StoreUnavailable(self.store_resource_id.clone())));
+        }
+        write_bytes_to_resource(&self.audit_resource_id, denetim.as_bytes(), true)
+            .map_err(PaketYoneticisiHatasi::from)?;
+
+        let veri = postcard::to_allocvec(&yeni).map_err(PaketYoneticisiHatasi::SerializationError)?;
+        if let Err(e) = srcresource::atomik_yaz(&self.store_resource_id, &veri).map_err(TrustError::from) {
+            error!("Güven deposu yazılamadı ({}): {:?}", self.store_resource_id, e); // no_std log
+            let not = format!("# {} önceki değişiklik uygulanamadı: {}\n", simdiki_zaman().unwrap_or(0), e);
+            let _ = write_bytes_to_resource(&self.audit_resource_id, not.as_bytes(), true);
+            return Err(PaketYoneticisiHatasi::from(e));
+        }
+        self.depo = yeni;
+        Ok(())
+    }
 
-    // Güvenilen yayıncı listesine bir yayıncı ekler ve Kaynağa kaydeder.
-    // publisher_name: Eklenecek yayıncının adı.
-    // Dönüş değeri: Ekleme başarılı olursa Ok(()), zaten varsa Ok(()), hata olursa PaketYoneticisiHatasi.
-    pub fn add_trusted_publisher(&mut self, publisher_name: &str) -> Result<(), PaketYoneticisiHatasi> { // Result<(), PaketYoneticisiHatasi> olmalı
-        // HashSet'e ekle. Eğer zaten varsa false döner.
-        if self.trusted_publishers.insert(publisher_name.to_owned()) { // alloc, insert bool döner
-             // Yeni eklendiyse Kaynağa kaydet.
-             debug!("Yeni güvenilen yayıncı eklendi, Kaynağa kaydediliyor: {}", publisher_name); // no_std log
-             // append yerine Kaynağa yazma helper'ı kullanalım ve append moda ayarlayalım.
-             let line_to_append = format!("{}\n", publisher_name); // format! alloc
-             match self.append_string_to_resource(&self.publishers_resource_id, &line_to_append) { // append_string_to_resource TrustError dönsün
-                 Ok(_) => {
-                      info!("Güvenilen yayıncı Kaynağa kaydedildi: {}", publisher_name); // no_std log
-                     Ok(()) // Başarı
-                 }
-                 Err(e) => {
-                      error!("Güvenilen yayıncı Kaynağa kaydedilirken hata oluştu ({}): {:?}", publisher_name, e); // no_std log
-                      // HashSet'ten geri çıkarmayı düşünebiliriz, ama hata durumunda tutarlılık zor.
-                     Err(PaketYoneticisiHatasi::from(e)) // TrustError -> PaketYoneticisiHatasi
-                 }
-             }
-        } else {
-             // Zaten varsa bir şey yapmaya gerek yok, başarı dön.
-             debug!("Güvenilen yayıncı zaten listede: {}", publisher_name); // no_std log
-            Ok(())
+    // Kayıt listesine (yayıncılar veya paketler) ekleme; zaten varsa değişiklik yapılmaz.
+    fn add_entry(&mut self, tur: &str, ad: &str, aciklama: &str, ekleyen: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let simdi = simdiki_zaman().map_err(PaketYoneticisiHatasi::from)?;
+        let mut yeni = self.depo.clone();
+        let liste = if tur == "yayinci" { &mut yeni.yayincilar } else { &mut yeni.paketler };
+        let kayit = GuvenKaydi { ad: ad.to_owned(), aciklama: aciklama.to_owned(), eklenme: simdi, ekleyen: ekleyen.to_owned() };
+        if !sirali_ekle(liste, kayit) {
+            debug!("Güven deposunda zaten var ({}): {}", tur, ad); // no_std log
+            return Ok(());
         }
+        self.commit(yeni, &denetim_satiri(simdi, DenetimIslemi::Ekle, tur, ad, ekleyen, aciklama))?;
+        info!("Güven deposuna eklendi ({}): {}", tur, ad); // no_std log
+        Ok(())
     }
 
-    // Helper fonksiyon: String içeriğini Sahne64 Kaynağına append eder.
-    // resource_id: Yazılacak Kaynağın ID'si.
-    // content: Yazılacak string içerik.
-    fn append_string_to_resource(&self, resource_id: &str, content: &str) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
-         let handle = resource::acquire(
-             resource_id,
-             resource::MODE_WRITE | resource::MODE_CREATE | resource::MODE_APPEND // MODE_APPEND
-         ).map_err(|e| {
-              error!("Helper: Kaynak acquire hatası ({}): {:?}", resource_id, e); // no_std log
-              TrustError::from(e) // SahneError -> TrustError
-         })?;
-
-         let buffer = content.as_bytes(); // &str -> &[u8]
-
-         let mut written = 0;
-         while written < buffer.len() {
-              match resource::write(handle, &buffer[written..]) {
-                   Ok(bytes_written) => {
-                       if bytes_written == 0 {
-                           // Hiçbir şey yazılamadı
-                            let _ = resource::release(handle);
-                             error!("Helper: Kaynak yazma hatası ({}): Kaynak yazmayı durdurdu.", resource_id); // no_std log
-                            return Err(TrustError::Sahne64ResourceError(SahneError::InvalidOperation)); // Veya daha uygun bir hata
-                       }
-                        written += bytes_written;
-                   }
-                   Err(e) => {
-                        let _ = resource::release(handle);
-                         error!("Helper: Kaynak yazma hatası ({}): {:?}", resource_id, e); // no_std log
-                        return Err(TrustError::from(e)); // SahneError -> TrustError
-                   }
-              }
-         }
-
-         let release_result = resource::release(handle);
-         if let Err(_e) = release_result {
-              error!("Helper: Kaynak release hatası ({}): {:?}", resource_id, _e); // no_std log
-         }
-
-        Ok(()) // Başarı
-    }
-
-
-    // Güvenilen yayıncı listesinden bir yayıncıyı kaldırır ve Kaynağı günceller.
-    // publisher_name: Kaldırılacak yayıncının adı.
-    // Dönüş değeri: Kaldırma başarılı olursa true, yoksa false. Hata durumunda PaketYoneticisiHatasi.
-    // Not: Bu implementasyon, tüm listeyi yeniden yazarak güncellemeyi yapar.
-    pub fn remove_trusted_publisher(&mut self, publisher_name: &str) -> Result<bool, PaketYoneticisiHatasi> { // Result<bool, PaketYoneticisiHatasi> olmalı
-         // HashSet'ten kaldır. Eğer bulunduysa ve kaldırıldıysa true döner.
-         let removed = self.trusted_publishers.remove(publisher_name); // remove &str alır no_std
-
-         if removed {
-              // HashSet'ten kaldırıldıysa, Kaynağı yeniden yazarak güncelle.
-              debug!("Güvenilen yayıncı listeden kaldırıldı, Kaynak güncelleniyor: {}", publisher_name); // no_std log
-              if let Err(e) = self.persist_trusted_publishers() { // TrustError dönsün
-                   error!("Güvenilen yayıncılar Kaynağı güncellenirken hata oluştu ({}): {:?}", self.publishers_resource_id, e); // no_std log
-                   // Hata durumunda ne yapılmalı? HashSet güncellendi ama dosya güncellenmedi. Tutarlılık sorunu.
-                   // Geri alma mekanizması (örn. orijinal dosyayı geri yükleme) burada düşünülebilir.
-                  return Err(PaketYoneticisiHatasi::from(e)); // TrustError -> PaketYoneticisiHatasi
-              }
-             info!("Güvenilen yayıncı Kaynaktan kaldırıldı: {}", publisher_name); // no_std log
-         } else {
-             debug!("Güvenilen yayıncı listede bulunamadı: {}", publisher_name); // no_std log
-         }
-
-         Ok(removed) // Kaldırılıp kaldırılmadığını döndür.
-    }
-
-    // Tüm güvenilen yayıncıları Kaynağa yeniden yazar (truncate ve yaz).
-    // Bu basit ama potansiyel olarak büyük listeler için verimsiz bir yöntemdir.
-    fn persist_trusted_publishers(&self) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
-         debug!("Tüm güvenilen yayıncılar Kaynağa yeniden yazılıyor: {}", self.publishers_resource_id); // no_std log
-         let mut content_to_write = String::new(); // alloc
-         for publisher in &self.trusted_publishers {
-              content_to_write.push_str(&format!("{}\n", publisher)); // push_str, format! alloc
-         }
-         write_string_to_resource(&self.publishers_resource_id, &content_to_write) // Helper fonksiyonu kullan
+    fn remove_entry(&mut self, tur: &str, ad: &str, kaldiran: &str) -> Result<bool, PaketYoneticisiHatasi> {
+        let mut yeni = self.depo.clone();
+        let liste = if tur == "yayinci" { &mut yeni.yayincilar } else { &mut yeni.paketler };
+        let kaldirilan = match sirali_cikar(liste, ad) {
+            Some(kayit) => kayit,
+            None => {
+                debug!("Güven deposunda bulunamadı ({}): {}", tur, ad); // no_std log
+                return Ok(false);
+            }
+        };
+        let simdi = simdiki_zaman().map_err(PaketYoneticisiHatasi::from)?;
+        let aciklama = format!("{} tarafından {} tarihinde eklenmişti", kaldirilan.ekleyen, kaldirilan.eklenme);
+        self.commit(yeni, &denetim_satiri(simdi, DenetimIslemi::Kaldir, tur, ad, kaldiran, &aciklama))?;
+        info!("Güven deposundan kaldırıldı ({}): {}", tur, ad); // no_std log
+        Ok(true)
+    }
+
+    // Güvenilen yayıncı listesine bir yayıncı ekler.
+    // aciklama: Kaydın neden eklendiği. ekleyen: Değişikliği yapan (denetim kaydına yazılır).
+    // Dönüş değeri: Ekleme başarılı olursa veya zaten varsa Ok(()), hata olursa PaketYoneticisiHatasi.
+    pub fn add_trusted_publisher(&mut self, publisher_name: &str, aciklama: &str, ekleyen: &str) -> Result<(), PaketYoneticisiHatasi> {
+        self.add_entry("yayinci", publisher_name, aciklama, ekleyen)
+    }
+
+    // Güvenilen yayıncı listesinden bir yayıncıyı kaldırır.
+    // Dönüş değeri: Kaldırıldıysa true, listede yoksa false.
+    pub fn remove_trusted_publisher(&mut self, publisher_name: &str, kaldiran: &str) -> Result<bool, PaketYoneticisiHatasi> {
+        self.remove_entry("yayinci", publisher_name, kaldiran)
     }
 
     // Belirtilen yayıncının güvenilir olup olmadığını kontrol eder.
-    // publisher_name: Kontrol edilecek yayıncının adı.
-    // Dönüş değeri: Güvenilir ise true, değilse false.
     pub fn is_trusted_publisher(&self, publisher_name: &str) -> bool {
-        self.trusted_publishers.contains(publisher_name) // contains &str alır no_std
-    }
-
-    // Güvenilen paket listesine bir paket ekler ve Kaynağa kaydeder.
-    // package_name: Eklenecek paketin adı.
-    // Dönüş değeri: Ekleme başarılı olursa Ok(()), zaten varsa Ok(()), hata olursa PaketYoneticisiHatasi.
-    pub fn add_trusted_package(&mut self, package_name: &str) -> Result<(), PaketYoneticisiHatasi> { // Result<(), PaketYoneticisiHatasi> olmalı
-        // HashSet'e ekle. Eğer zaten varsa false döner.
-        if self.trusted_packages.insert(package_name.to_owned()) { // alloc, insert bool döner
-             // Yeni eklendiyse Kaynağa kaydet.
-             debug!("Yeni güvenilen paket eklendi, Kaynağa kaydediliyor: {}", package_name); // no_std log
-             let line_to_append = format!("{}\n", package_name); // format! alloc
-             match self.append_string_to_resource(&self.packages_resource_id, &line_to_append) { // append_string_to_resource TrustError dönsün
-                 Ok(_) => {
-                      info!("Güvenilen paket Kaynağa kaydedildi: {}", package_name); // no_std log
-                     Ok(()) // Başarı
-                 }
-                 Err(e) => {
-                      error!("Güvenilen paket Kaynağa kaydedilirken hata oluştu ({}): {:?}", package_name, e); // no_std log
-                     Err(PaketYoneticisiHatasi::from(e)) // TrustError -> PaketYoneticisiHatasi
-                 }
-             }
-        } else {
-             // Zaten varsa bir şey yapmaya gerek yok, başarı dön.
-             debug!("Güvenilen paket zaten listede: {}", package_name); // no_std log
-            Ok(())
-        }
+        self.depo.yayincilar.binary_search_by(|kayit| kayit.ad.as_str().cmp(publisher_name)).is_ok()
     }
 
-    // Güvenilen paket listesinden bir paketi kaldırır ve Kaynağı günceller.
-    // package_name: Kaldırılacak paketin adı.
-    // Dönüş değeri: Kaldırma başarılı olursa true, yoksa false. Hata durumunda PaketYoneticisiHatasi.
-    // Not: Bu implementasyon, tüm listeyi yeniden yazarak güncellemeyi yapar.
-    pub fn remove_trusted_package(&mut self, package_name: &str) -> Result<bool, PaketYoneticisiHatasi> { // Result<bool, PaketYoneticisiHatasi> olmalı
-        // HashSet'ten kaldır. Eğer bulunduysa ve kaldırıldıysa true döner.
-         let removed = self.trusted_packages.remove(package_name); // remove &str alır no_std
-
-         if removed {
-              // HashSet'ten kaldırıldıysa, Kaynağı yeniden yazarak güncelle.
-              debug!("Güvenilen paket listeden kaldırıldı, Kaynak güncelleniyor: {}", package_name); // no_std log
-              if let Err(e) = self.persist_trusted_packages() { // TrustError dönsün
-                   error!("Güvenilen paketler Kaynağı güncellenirken hata oluştu ({}): {:?}", self.packages_resource_id, e); // no_std log
-                   // Hata durumunda ne yapılmalı? Tutarlılık sorunu.
-                  return Err(PaketYoneticisiHatasi::from(e)); // TrustError -> PaketYoneticisiHatasi
-              }
-             info!("Güvenilen paket Kaynaktan kaldırıldı: {}", package_name); // no_std log
-         } else {
-             debug!("Güvenilen paket listede bulunamadı: {}", package_name); // no_std log
-         }
-
-         Ok(removed) // Kaldırılıp kaldırılmadığını döndür.
-    }
-
-    // Tüm güvenilen paketleri Kaynağa yeniden yazar (truncate ve yaz).
-    fn persist_trusted_packages(&self) -> Result<(), TrustError> { // Result<(), TrustError> olmalı
-        debug!("Tüm güvenilen paketler Kaynağa yeniden yazılıyor: {}", self.packages_resource_id); // no_std log
-        let mut content_to_write = String::new(); // alloc
-        for package in &self.trusted_packages {
-            content_to_write.push_str(&format!("{}\n", package)); // push_str, format! alloc
-        }
-        write_string_to_resource(&self.packages_resource_id, &content_to_write) // Helper fonksiyonu kullan
+    pub fn trusted_publishers(&self) -> &[GuvenKaydi] {
+        &self.depo.yayincilar
     }
 
+    // Güvenilen paket listesine bir paket ekler.
+    pub fn add_trusted_package(&mut self, package_name: &str, aciklama: &str, ekleyen: &str) -> Result<(), PaketYoneticisiHatasi> {
+        self.add_entry("paket", package_name, aciklama, ekleyen)
+    }
+
+    // Güvenilen paket listesinden bir paketi kaldırır.
+    pub fn remove_trusted_package(&mut self, package_name: &str, kaldiran: &str) -> Result<bool, PaketYoneticisiHatasi> {
+        self.remove_entry("paket", package_name, kaldiran)
+    }
 
     // Belirtilen paketin güvenilir olup olmadığını kontrol eder.
-    // package_name: Kontrol edilecek paketin adı.
-    // Dönüş değeri: Güvenilir ise true, değilse false.
     pub fn is_trusted_package(&self, package_name: &str) -> bool {
-        self.trusted_packages.contains(package_name) // contains &str alır no_std
+        self.depo.paketler.binary_search_by(|kayit| kayit.ad.as_str().cmp(package_name)).is_ok()
+    }
+
+    pub fn trusted_packages(&self) -> &[GuvenKaydi] {
+        &self.depo.paketler
+    }
+
+    // Anahtarın kapsamını belirler (varsa öncekinin yerine geçer).
+    // anahtar_kimligi: srcsigning::GenelAnahtar::kimlik() değeri.
+    // depolar / paket_desenleri: '*' ve '?' içerebilen desenler; ikisi de boş olamaz.
+    pub fn set_key_scope(
+        &mut self,
+        anahtar_kimligi: &str,
+        depolar: &[&str],
+        paket_desenleri: &[&str],
+        aciklama: &str,
+        ekleyen: &str,
+    ) -> Result<(), PaketYoneticisiHatasi> {
+        if depolar.is_empty() || paket_desenleri.is_empty() {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(String::from("Anahtar kapsamında depo ve paket desenleri gerekli")));
+        }
+        let simdi = simdiki_zaman().map_err(PaketYoneticisiHatasi::from)?;
+        let kapsam = AnahtarKapsami {
+            anahtar_kimligi: anahtar_kimligi.to_lowercase(),
+            depolar: depolar.iter().map(|desen| (*desen).to_owned()).collect(),
+            paket_desenleri: paket_desenleri.iter().map(|desen| (*desen).to_owned()).collect(),
+            aciklama: aciklama.to_owned(),
+            eklenme: simdi,
+            ekleyen: ekleyen.to_owned(),
+        };
+        let denetim = denetim_satiri(
+            simdi, DenetimIslemi::Ekle, "kapsam", &kapsam.anahtar_kimligi, ekleyen,
+            &format!("depo={} paket={} {}", kapsam.depolar.join(","), kapsam.paket_desenleri.join(","), aciklama),
+        );
+
+        let mut yeni = self.depo.clone();
+        yeni.anahtar_kapsamlari.retain(|mevcut| mevcut.anahtar_kimligi != kapsam.anahtar_kimligi);
+        yeni.anahtar_kapsamlari.push(kapsam);
+        yeni.anahtar_kapsamlari.sort_by(|a, b| a.anahtar_kimligi.cmp(&b.anahtar_kimligi));
+        self.commit(yeni, &denetim)?;
+        info!("Anahtar kapsamı kaydedildi: {}", anahtar_kimligi); // no_std log
+        Ok(())
+    }
+
+    // Anahtarın kapsamını kaldırır (anahtar artık hiçbir paketi veya depo bildirimini imzalayamaz). Dönüş değeri: kapsam var mıydı?
+    pub fn remove_key_scope(&mut self, anahtar_kimligi: &str, kaldiran: &str) -> Result<bool, PaketYoneticisiHatasi> {
+        let mut yeni = self.depo.clone();
+        yeni.anahtar_kapsamlari.retain(|mevcut| mevcut.anahtar_kimligi != anahtar_kimligi);
+        if yeni.anahtar_kapsamlari.len() == self.depo.anahtar_kapsamlari.len() {
+            return Ok(false);
+        }
+        let simdi = simdiki_zaman().map_err(PaketYoneticisiHatasi::from)?;
+        self.commit(yeni, &denetim_satiri(simdi, DenetimIslemi::Kaldir, "kapsam", anahtar_kimligi, kaldiran, ""))?;
+        info!("Anahtar kapsamı kaldırıldı: {}", anahtar_kimligi); // no_std log
+        Ok(true)
+    }
+
+    pub fn key_scope(&self, anahtar_kimligi: &str) -> Option<&AnahtarKapsami> {
+        self.depo.anahtar_kapsamlari.iter().find(|kapsam| kapsam.anahtar_kimligi == anahtar_kimligi)
+    }
+
+    pub fn key_scopes(&self) -> &[AnahtarKapsami] {
+        &self.depo.anahtar_kapsamlari
+    }
+
+    // Anahtarın verilen depodan gelen paketi imzalamaya yetkili olup olmadığını kontrol eder.
+    // Kapsamı olmayan anahtar hiçbir paketi imzalayamaz; anahtar halkasına eklenen her anahtara kapsam verilmelidir.
+    pub fn is_key_allowed(&self, anahtar_kimligi: &str, package_name: &str, repository: &str) -> bool {
+        self.key_scope(anahtar_kimligi).map_or(false, |kapsam| kapsam.izin_veriyor_mu(package_name, repository))
+    }
+
+    // Eşik imza kuralını ekler veya aynı adlı kuralın yerine koyar.
+    // anahtarlar: İmzası sayılan anahtar kimlikleri (N); esik (M) 1 ile N arasında olmalıdır.
+    pub fn set_threshold_rule(
+        &mut self,
+        ad: &str,
+        paket_desenleri: &[&str],
+        esik: u32,
+        anahtarlar: &[&str],
+        aciklama: &str,
+        ekleyen: &str,
+    ) -> Result<(), PaketYoneticisiHatasi> {
+        let mut anahtarlar: Vec<String> = anahtarlar.iter().map(|kimlik| kimlik.to_lowercase()).collect();
+        anahtarlar.sort();
+        anahtarlar.dedup();
+        if paket_desenleri.is_empty() || esik == 0 || esik as usize > anahtarlar.len() {
+            return Err(PaketYoneticisiHatasi::InvalidParameter(format!(
+                "Eşik kuralı '{}': en az bir paket deseni ve 1 ile {} arasında eşik gerekli (verilen {})", ad, anahtarlar.len(), esik
+            )));
+        }
+        let simdi = simdiki_zaman().map_err(PaketYoneticisiHatasi::from)?;
+        let kural = EsikKurali {
+            ad: ad.to_owned(),
+            paket_desenleri: paket_desenleri.iter().map(|desen| (*desen).to_owned()).collect(),
+            esik,
+            anahtarlar,
+            aciklama: aciklama.to_owned(),
+            eklenme: simdi,
+            ekleyen: ekleyen.to_owned(),
+        };
+        let denetim = denetim_satiri(
+            simdi, DenetimIslemi::Ekle, "esik", ad, ekleyen,
+            &format!("paket={} {}/{} anahtar={} {}", kural.paket_desenleri.join(","), kural.esik, kural.anahtarlar.len(), kural.anahtarlar.join(","), aciklama),
+        );
+
+        let mut yeni = self.depo.clone();
+        yeni.esik_kurallari.retain(|mevcut| mevcut.ad != kural.ad);
+        yeni.esik_kurallari.push(kural);
+        yeni.esik_kurallari.sort_by(|a, b| a.ad.cmp(&b.ad));
+        self.commit(yeni, &denetim)?;
+        info!("Eşik kuralı kaydedildi: {}", ad); // no_std log
+        Ok(())
+    }
+
+    // Eşik kuralını kaldırır. Dönüş değeri: kural var mıydı?
+    pub fn remove_threshold_rule(&mut self, ad: &str, kaldiran: &str) -> Result<bool, PaketYoneticisiHatasi> {
+        let mut yeni = self.depo.clone();
+        yeni.esik_kurallari.retain(|mevcut| mevcut.ad != ad);
+        if yeni.esik_kurallari.len() == self.depo.esik_kurallari.len() {
+            return Ok(false);
+        }
+        let simdi = simdiki_zaman().map_err(PaketYoneticisiHatasi::from)?;
+        self.commit(yeni, &denetim_satiri(simdi, DenetimIslemi::Kaldir, "esik", ad, kaldiran, ""))?;
+        info!("Eşik kuralı kaldırıldı: {}", ad); // no_std log
+        Ok(true)
+    }
+
+    pub fn threshold_rules(&self) -> &[EsikKurali] {
+        &self.depo.esik_kurallari
+    }
+
+    // Pakete uygulanan tüm eşik kuralları; paket hepsini ayrı ayrı sağlamalıdır.
+    pub fn threshold_rules_for(&self, package_name: &str) -> Vec<&EsikKurali> {
+        self.depo.esik_kurallari.iter().filter(|kural| kural.uygulanir_mi(package_name)).collect()
+    }
+
+    // Denetim kaydının tamamını döndürür (kayıt yoksa boş).
+    pub fn audit_log(&self) -> Result<String, PaketYoneticisiHatasi> {
+        match read_resource_to_vec(&self.audit_resource_id) {
+            Ok(buffer) => String::from_utf8(buffer)
+                .map_err(|_| PaketYoneticisiHatasi::ParsingError(format!("Denetim kaydı UTF-8 değil: {}", self.audit_resource_id))),
+            Err(TrustError::Sahne64ResourceError(SahneError::ResourceNotFound)) => Ok(String::new()),
+            Err(e) => Err(PaketYoneticisiHatasi::from(e)),
+        }
     }
 }
 
@@ -430,8 +709,85 @@ impl TrustManager {
 
 #[cfg(test)]
 mod tests {
-    // std::collections, std::io, std::path, std::fs, tempfile kullandığı için no_std'de doğrudan çalışmaz.
-    // Mock resource::acquire/read/write/release ve test dosyası oluşturma/okuma helper'ları gerektirir.
+    use super::*;
+
+    #[test]
+    fn test_desen_eslesir() {
+        assert!(desen_eslesir("vendor-*", "vendor-araclar"));
+        assert!(desen_eslesir("vendor-*", "vendor-"));
+        assert!(!desen_eslesir("vendor-*", "coreutils"));
+        assert!(desen_eslesir("*", "coreutils"));
+        assert!(desen_eslesir("lib*-dev", "libssl-dev"));
+        assert!(!desen_eslesir("lib*-dev", "libssl-devel"));
+        assert!(desen_eslesir("a?c", "abc"));
+        assert!(!desen_eslesir("coreutils", "coreutils2"));
+    }
+
+    #[test]
+    fn test_anahtar_kapsami() {
+        let kapsam = AnahtarKapsami::ayristir("ABCD depo=vendor paket=vendor-*,vendorctl").unwrap();
+        assert_eq!(kapsam.anahtar_kimligi, "abcd");
+        assert!(kapsam.izin_veriyor_mu("vendor-araclar", "vendor"));
+        assert!(kapsam.izin_veriyor_mu("vendorctl", "vendor"));
+        // Başka paket veya başka depodan gelen aynı paket reddedilir
+        assert!(!kapsam.izin_veriyor_mu("coreutils", "vendor"));
+        assert!(!kapsam.izin_veriyor_mu("vendor-araclar", "sahne-ana"));
+        assert!(kapsam.depoya_izin_veriyor_mu("vendor"));
+        assert!(!kapsam.depoya_izin_veriyor_mu("sahne-ana"));
+
+        assert!(AnahtarKapsami::ayristir("abcd depo=vendor").is_err());
+        assert!(AnahtarKapsami::ayristir("abcd depo=vendor paket=x sahip=y").is_err());
+    }
+
+    #[test]
+    fn test_sirali_liste_ve_deterministik_bicim() {
+        let kayit = |ad: &str| GuvenKaydi { ad: ad.to_owned(), aciklama: String::new(), eklenme: 1, ekleyen: String::from("test") };
+        let mut a = GuvenDeposu::default();
+        let mut b = GuvenDeposu::default();
+        for ad in ["zeta", "alfa", "orta"] {
+            assert!(sirali_ekle(&mut a.yayincilar, kayit(ad)));
+        }
+        for ad in ["orta", "zeta", "alfa"] {
+            assert!(sirali_ekle(&mut b.yayincilar, kayit(ad)));
+        }
+        assert!(!sirali_ekle(&mut a.yayincilar, kayit("alfa")));
+        // Ekleme sırası farklı olsa da aynı baytlar yazılır
+        assert_eq!(postcard::to_allocvec(&a).unwrap(), postcard::to_allocvec(&b).unwrap());
+        assert_eq!(a.yayincilar[0].ad, "alfa");
+
+        assert_eq!(sirali_cikar(&mut a.yayincilar, "orta").unwrap().ad, "orta");
+        assert!(sirali_cikar(&mut a.yayincilar, "orta").is_none());
+    }
+
+    #[test]
+    fn test_denetim_satiri() {
+        let satir = denetim_satiri(10, DenetimIslemi::Ekle, "yayinci", "Sahne\tEkibi", "yönetici", "satır\nsonu");
+        assert_eq!(satir, "10\tekle\tyayinci\tSahne Ekibi\työnetici\tsatır sonu\n");
+    }
+
+    #[test]
+    fn test_guven_deposu_v1_yukseltme() {
+        #[derive(Serialize)]
+        struct V1<'a> {
+            bicim_surumu: u32,
+            yayincilar: &'a [GuvenKaydi],
+            paketler: &'a [GuvenKaydi],
+            anahtar_kapsamlari: &'a [AnahtarKapsami],
+        }
+        let yayinci = GuvenKaydi { ad: String::from("sahne"), aciklama: String::new(), eklenme: 1, ekleyen: String::from("test") };
+        let eski = postcard::to_allocvec(&V1 { bicim_surumu: 1, yayincilar: &[yayinci.clone()], paketler: &[], anahtar_kapsamlari: &[] }).unwrap();
+
+        let depo = GuvenDeposu::coz(&eski).unwrap();
+        assert_eq!(depo.bicim_surumu, GUVEN_DEPOSU_BICIM_SURUMU);
+        assert_eq!(depo.yayincilar, [yayinci]);
+        assert!(depo.esik_kurallari.is_empty());
+        assert_eq!(GuvenDeposu::coz(&postcard::to_allocvec(&depo).unwrap()).unwrap(), depo);
+
+        let gelecek = postcard::to_allocvec(&(99u32,)).unwrap();
+        assert!(GuvenDeposu::coz(&gelecek).is_err());
+    }
+
+    // TrustManager yükleme/kaydetme testleri için mock resource::acquire/read/write/control/release gerekir.
 }
 
 // --- TrustError enum tanımı ---
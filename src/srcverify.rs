@@ -0,0 +1,565 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+use alloc::collections::BTreeSet; // Beklenmeyen dosya tespiti için (deterministik sıra)
+
+use serde::Serialize; // Makine tarafından okunabilir rapor için
+use serde_json; // JSON çıktısı (alloc özellikli no_std backend varsayımıyla)
+
+// Kurulu paket kayıtları
+use crate::srcinstalled::{self, KuruluDosya, KuruluPaketKaydi, KuruluPaketVeritabani};
+// Tek dosya çıkarma, bağ oluşturma ve öznitelik uygulama (onarım için)
+use crate::srcarchive::{self, ArsivSinirlari, CikarilanGirdi, GirdiTuru};
+// Onarımda izin verilen sıkıştırma algoritmaları
+use crate::srcfeatures::FeatureSet;
+// Onarımdan önce önbellekteki arşivin doğrulanması
+use crate::srcchecksum;
+
+// Sahne64 API modülleri
+use crate::resource;
+use crate::SahneError;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+// log kütüphanesini içe aktar (no_std uyumlu backend varsayımıyla)
+use log::{info, warn, error, debug};
+
+
+// Bir dosyada tespit edilen sorun türü.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SorunTuru {
+    Eksik, // Kayıtta var, diskte yok
+    IcerikDegisti, // SHA-256 özeti farklı
+    BoyutDegisti, // Boyut farklı
+    ModDegisti, // Erişim izinleri farklı
+    SahipDegisti, // Sahip (uid) veya grup (gid) farklı
+    BagDegisti, // Sembolik bağ hedefi veya girdi türü farklı
+    Beklenmeyen, // Diskte var, kayıtta yok
+}
+
+impl SorunTuru {
+    // `rpm -V` benzeri tek karakterlik kısa kod.
+    fn kod(&self) -> char {
+        match self {
+            SorunTuru::Eksik => '!',
+            SorunTuru::IcerikDegisti => '5',
+            SorunTuru::BoyutDegisti => 'S',
+            SorunTuru::ModDegisti => 'M',
+            SorunTuru::SahipDegisti => 'U',
+            SorunTuru::BagDegisti => 'L',
+            SorunTuru::Beklenmeyen => '?',
+        }
+    }
+
+    // Onarım (arşivden yeniden çıkarma) ile düzeltilebilir mi?
+    fn onarilabilir(&self) -> bool {
+        !matches!(self, SorunTuru::Beklenmeyen)
+    }
+}
+
+// Bir dosya için doğrulama bulgusu.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DosyaBulgusu {
+    pub paket: String,
+    pub yol: String,
+    pub sorunlar: Vec<SorunTuru>,
+}
+
+// Doğrulama raporu (bir veya daha fazla paket için).
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct DogrulamaRaporu {
+    pub dogrulanan_paket_sayisi: usize,
+    pub dogrulanan_dosya_sayisi: usize,
+    pub bulgular: Vec<DosyaBulgusu>,
+}
+
+impl DogrulamaRaporu {
+    // Hiç bulgu yoksa true.
+    pub fn temiz(&self) -> bool {
+        self.bulgular.is_empty()
+    }
+
+    // Rapor metnini `rpm -V` benzeri biçimde üretir: "<kodlar> <paket> <yol>".
+    // Kod sütunu sabit genişliktedir, sorun olmayan kodlar '.' ile gösterilir.
+    pub fn metin(&self) -> String {
+        const SIRA: [SorunTuru; 7] = [
+            SorunTuru::Eksik,
+            SorunTuru::IcerikDegisti,
+            SorunTuru::BoyutDegisti,
+            SorunTuru::ModDegisti,
+            SorunTuru::SahipDegisti,
+            SorunTuru::BagDegisti,
+            SorunTuru::Beklenmeyen,
+        ];
+
+        let mut cikti = String::new();
+        for bulgu in &self.bulgular {
+            for tur in SIRA.iter() {
+                cikti.push(if bulgu.sorunlar.contains(tur) { tur.kod() } else { '.' });
+            }
+            cikti.push_str(&format!(" {} {}\n", bulgu.paket, bulgu.yol));
+        }
+        cikti.push_str(&format!(
+            "{} paket, {} dosya doğrulandı, {} sorunlu dosya.\n",
+            self.dogrulanan_paket_sayisi, self.dogrulanan_dosya_sayisi, self.bulgular.len()
+        ));
+        cikti
+    }
+
+    // Raporu JSON olarak üretir (denetim araçları için).
+    pub fn json(&self) -> Result<String, PaketYoneticisiHatasi> {
+        serde_json::to_string(self).map_err(|e| {
+            PaketYoneticisiHatasi::UnknownError(format!("Doğrulama raporu JSON'a çevrilemedi: {:?}", e))
+        })
+    }
+}
+
+
+// Kurulu paketleri kayıtlı tür, özet, boyut, mod, sahip ve bağ hedeflerine göre doğrular.
+pub struct Dogrulayici {
+    veritabani: KuruluPaketVeritabani,
+    kurulum_base_resource_id: String, // Kurulu paketlerin temel Kaynak ID'si (örn. "sahne://installed_packages")
+    onbellek_base_resource_id: String, // İndirilen arşivlerin önbelleği (onarım için)
+}
+
+impl Dogrulayici {
+    pub fn yeni(
+        veritabani: KuruluPaketVeritabani,
+        kurulum_base_resource_id: String,
+        onbellek_base_resource_id: String,
+    ) -> Self {
+        Dogrulayici {
+            veritabani,
+            kurulum_base_resource_id,
+            onbellek_base_resource_id,
+        }
+    }
+
+    fn kurulum_dizini(&self, paket_adi: &str) -> String {
+        format!("{}/{}/", self.kurulum_base_resource_id.trim_end_matches('/'), paket_adi)
+    }
+
+    // Belirtilen paketi veya (None ise) tüm kurulu paketleri doğrular.
+    pub fn dogrula(&self, paket_adi: Option<&str>) -> Result<DogrulamaRaporu, PaketYoneticisiHatasi> {
+        let kayitlar = match paket_adi {
+            Some(ad) => match self.veritabani.kaydi_yukle(ad)? {
+                Some(kayit) => alloc::vec![kayit],
+                None => return Err(PaketYoneticisiHatasi::PaketBulunamadi(ad.to_owned())),
+            },
+            None => self.veritabani.tum_kayitlar()?,
+        };
+
+        let mut rapor = DogrulamaRaporu::default();
+        for kayit in &kayitlar {
+            self.paketi_dogrula(kayit, &mut rapor)?;
+        }
+
+        info!("Doğrulama tamamlandı: {} paket, {} sorunlu dosya.", rapor.dogrulanan_paket_sayisi, rapor.bulgular.len());
+        Ok(rapor)
+    }
+
+    fn paketi_dogrula(&self, kayit: &KuruluPaketKaydi, rapor: &mut DogrulamaRaporu) -> Result<(), PaketYoneticisiHatasi> {
+        let kurulum_dizini = self.kurulum_dizini(&kayit.paket.ad);
+        debug!("Paket doğrulanıyor: {} ({})", kayit.paket.ad, kurulum_dizini);
+
+        for dosya in &kayit.dosyalar {
+            let dosya_id = srcinstalled::kurulu_dosya_id(&kurulum_dizini, &dosya.yol);
+            let bilgi = match srcinstalled::kaynak_bilgisi_al(&dosya_id) {
+                Ok(bilgi) => Some(bilgi),
+                Err(PaketYoneticisiHatasi::SahneApiError(SahneError::ResourceNotFound)) => None,
+                Err(e) => return Err(e),
+            };
+            let sorunlar = girdiyi_karsilastir(
+                dosya,
+                bilgi.as_ref(),
+                || srcinstalled::bag_hedefi_oku(&dosya_id),
+                || srcinstalled::kaynak_sha256(&dosya_id),
+            )?;
+
+            rapor.dogrulanan_dosya_sayisi += 1;
+            if !sorunlar.is_empty() {
+                rapor.bulgular.push(DosyaBulgusu {
+                    paket: kayit.paket.ad.clone(),
+                    yol: dosya.yol.clone(),
+                    sorunlar,
+                });
+            }
+        }
+
+        // Kurulum dizininde olup kayıtta olmayan dosyalar.
+        // Varsayım: resource::list(dizin) dizin altındaki tüm Kaynakların göreli yollarını (recursive) döner.
+        match resource::list(&kurulum_dizini) {
+            Ok(diskteki_yollar) => {
+                for yol in beklenmeyen_yollar(&kayit.dosyalar, diskteki_yollar) {
+                    rapor.bulgular.push(DosyaBulgusu {
+                        paket: kayit.paket.ad.clone(),
+                        yol,
+                        sorunlar: alloc::vec![SorunTuru::Beklenmeyen],
+                    });
+                }
+            }
+            Err(SahneError::NotSupported) => {
+                warn!("Kaynak listeleme desteklenmiyor, beklenmeyen dosya kontrolü atlandı: {}", kurulum_dizini);
+            }
+            Err(e) => return Err(PaketYoneticisiHatasi::from(e)),
+        }
+
+        rapor.dogrulanan_paket_sayisi += 1;
+        Ok(())
+    }
+
+    // Rapordaki onarılabilir dosyaları önbellekteki arşivden yeniden çıkarır.
+    // Sadece hasarlı dosyalar yazılır; paketin geri kalanına dokunulmaz.
+    // Sembolik bağlar, sabit bağlar ve dizinler kayıttan yeniden oluşturulur; mod ve sahip kayıttaki değerlere döndürülür.
+    // Sabit bağlar hedef dosyaya bağlandığından dosyalardan sonra onarılır.
+    // Dönüş değeri: Onarılan dosya sayısı.
+    pub fn onar(&self, rapor: &DogrulamaRaporu) -> Result<usize, PaketYoneticisiHatasi> {
+        let onarilacaklar = onarilacaklari_sirala(rapor, |ad| self.veritabani.kaydi_yukle(ad))?;
+
+        let mut onarilan = 0;
+        let mut dogrulanan_arsivler = BTreeSet::new();
+        for (bulgu, kayit, dosya_sirasi) in &onarilacaklar {
+            let dosya_adi = kayit.paket.dosya_adi.as_deref().ok_or_else(|| {
+                PaketYoneticisiHatasi::InvalidParameter(format!("Paket '{}' için dosya adı belirtilmemiş.", kayit.paket.ad))
+            })?;
+            let arsiv_id = format!("{}/{}", self.onbellek_base_resource_id.trim_end_matches('/'), dosya_adi);
+            // Önbellekteki arşiv kurulumdan sonra değişmiş olabilir; kayıttaki paket özetiyle doğrulanmadan açılmaz.
+            if !dogrulanan_arsivler.contains(&arsiv_id) {
+                arsivi_dogrula(&arsiv_id, kayit)?;
+                dogrulanan_arsivler.insert(arsiv_id.clone());
+            }
+
+            match self.girdiyi_onar(kayit, &kayit.dosyalar[*dosya_sirasi], &arsiv_id, &self.kurulum_dizini(&bulgu.paket)) {
+                Ok(()) => {
+                    info!("Dosya onarıldı: {} {}", bulgu.paket, bulgu.yol);
+                    onarilan += 1;
+                }
+                Err(e) => {
+                    error!("Dosya onarılamadı ({} {}): {:?}", bulgu.paket, bulgu.yol, e);
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(onarilan)
+    }
+
+    // Tek bir girdiyi türüne göre yeniden oluşturur ve kayıttaki öznitelikleri uygular.
+    // kayit: Girdinin ait olduğu paketin kaydı (sabit bağların hedefleri için).
+    // kurulum_dizini: Paketin kurulum dizini Kaynak ID'si.
+    fn girdiyi_onar(&self, kayit: &KuruluPaketKaydi, dosya: &KuruluDosya, arsiv_id: &str, kurulum_dizini: &str) -> Result<(), PaketYoneticisiHatasi> {
+        let hedef_id = srcinstalled::kurulu_dosya_id(kurulum_dizini, dosya.yol.trim_end_matches('/'));
+        let hedef_id = hedef_id.as_str();
+        match dosya.tur {
+            GirdiTuru::SembolikBag => {
+                let bag_hedefi = dosya.bag_hedefi.as_deref().ok_or_else(|| {
+                    PaketYoneticisiHatasi::InvalidParameter(format!("Sembolik bağ kaydında hedef yok: {}", dosya.yol))
+                })?;
+                return srcarchive::sembolik_bag_olustur(hedef_id, bag_hedefi);
+            }
+            GirdiTuru::Dizin => {
+                let handle = resource::acquire(hedef_id, resource::MODE_CREATE).map_err(PaketYoneticisiHatasi::from)?;
+                let _ = resource::release(handle);
+            }
+            GirdiTuru::SabitBag => {
+                // Sabit bağ hedef dosyayla aynı Kaynağı paylaşmalıdır; içerik ayrı bir kopya olarak çıkarılmaz,
+                // bağ hedeften yeniden oluşturulur. Mod ve sahip hedeften gelir.
+                let bag_hedefi = dosya.bag_hedefi.as_deref().ok_or_else(|| {
+                    PaketYoneticisiHatasi::InvalidParameter(format!("Sabit bağ kaydında hedef yok: {}", dosya.yol))
+                })?;
+                return srcarchive::sabit_bag_olustur(&srcinstalled::kurulu_dosya_id(kurulum_dizini, bag_hedefi), hedef_id);
+            }
+            GirdiTuru::Dosya => {
+                // Var olan bir sembolik bağın üzerine yazmak hedefini değiştirir; önce kaldırılır.
+                match resource::delete(hedef_id) {
+                    Ok(_) | Err(SahneError::ResourceNotFound) => {}
+                    Err(e) => return Err(PaketYoneticisiHatasi::from(e)),
+                }
+                // Yalnızca bu girdi açılır; arşiv biçimi (zip/tar) içerikten algılanır.
+                srcarchive::arsiv_dosya_cikar(
+                    arsiv_id,
+                    &dosya.yol,
+                    hedef_id,
+                    &FeatureSet::with_default_compression(),
+                    &ArsivSinirlari::default(),
+                )?;
+                // Çıkarılan içerik kayıttaki özetle eşleşmiyorsa dosya onarılmış sayılmaz.
+                let hesaplanan = srcinstalled::kaynak_sha256(hedef_id)?;
+                if hesaplanan != dosya.sha256 {
+                    return Err(PaketYoneticisiHatasi::OzetUyusmazligi {
+                        kaynak: hedef_id.to_owned(),
+                        algoritma: String::from("sha256"),
+                        beklenen: dosya.sha256.clone(),
+                        hesaplanan,
+                    });
+                }
+            }
+        }
+
+        srcarchive::oznitelikleri_uygula(hedef_id, &CikarilanGirdi {
+            yol: dosya.yol.clone(),
+            tur: dosya.tur,
+            mod_: dosya.mod_,
+            sahip: dosya.sahip,
+            grup: dosya.grup,
+            bag_hedefi: dosya.bag_hedefi.clone(),
+        })?;
+
+        // Dosya yeni bir Kaynak olarak çıkarıldığından ona verilmiş sabit bağlar eski Kaynağı gösterir; yeniden bağlanır.
+        if dosya.tur == GirdiTuru::Dosya {
+            for bag in kayit.dosyalar.iter().filter(|d| d.tur == GirdiTuru::SabitBag && d.bag_hedefi.as_deref() == Some(dosya.yol.as_str())) {
+                srcarchive::sabit_bag_olustur(hedef_id, &srcinstalled::kurulu_dosya_id(kurulum_dizini, &bag.yol))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Tek bir kayıtlı girdiyi diskteki durumuyla karşılaştırır.
+// bilgi: Diskteki girdinin bilgisi; None ise girdi diskte yok.
+// bag_hedefi_oku / icerik_ozeti: Yalnızca gerektiğinde çağrılır (sembolik bağ hedefi, SHA-256 hex).
+// Sembolik bağlarda içerik ve mod yerine bağ hedefi karşılaştırılır (bağ izlenmez).
+fn girdiyi_karsilastir<B, O>(
+    dosya: &KuruluDosya,
+    bilgi: Option<&srcinstalled::KaynakBilgisi>,
+    bag_hedefi_oku: B,
+    icerik_ozeti: O,
+) -> Result<Vec<SorunTuru>, PaketYoneticisiHatasi>
+where
+    B: FnOnce() -> Result<String, PaketYoneticisiHatasi>,
+    O: FnOnce() -> Result<String, PaketYoneticisiHatasi>,
+{
+    let mut sorunlar = Vec::new();
+    let bilgi = match bilgi {
+        Some(bilgi) => bilgi,
+        None => {
+            sorunlar.push(SorunTuru::Eksik);
+            return Ok(sorunlar);
+        }
+    };
+
+    // Sabit bağlar diskte sıradan dosya olarak görünür.
+    let beklenen_tur = if dosya.tur == GirdiTuru::SabitBag { GirdiTuru::Dosya } else { dosya.tur };
+    if bilgi.tur != beklenen_tur {
+        sorunlar.push(SorunTuru::BagDegisti);
+        return Ok(sorunlar); // Tür farklıyken diğer alanların karşılaştırması anlamsız
+    }
+
+    if dosya.tur == GirdiTuru::SembolikBag {
+        if Some(bag_hedefi_oku()?) != dosya.bag_hedefi {
+            sorunlar.push(SorunTuru::BagDegisti);
+        }
+        return Ok(sorunlar);
+    }
+
+    if bilgi.mod_ != dosya.mod_ {
+        sorunlar.push(SorunTuru::ModDegisti);
+    }
+    if bilgi.sahip != dosya.sahip || bilgi.grup != dosya.grup {
+        sorunlar.push(SorunTuru::SahipDegisti);
+    }
+    if dosya.tur == GirdiTuru::Dizin {
+        return Ok(sorunlar);
+    }
+
+    if bilgi.boyut != dosya.boyut {
+        sorunlar.push(SorunTuru::BoyutDegisti);
+    }
+    // Boyut farklıysa özet de farklıdır, ama raporda ikisini de göstermek için yine hesaplıyoruz.
+    if icerik_ozeti()? != dosya.sha256 {
+        sorunlar.push(SorunTuru::IcerikDegisti);
+    }
+    Ok(sorunlar)
+}
+
+// Kurulum dizininde olup kayıtta olmayan yollar (dizinler hariç), listeleme sırasıyla.
+fn beklenmeyen_yollar(dosyalar: &[KuruluDosya], diskteki_yollar: Vec<String>) -> Vec<String> {
+    let kayitli: BTreeSet<&str> = dosyalar.iter().map(|d| d.yol.as_str()).collect();
+    diskteki_yollar
+        .into_iter()
+        .filter(|yol| !yol.ends_with('/') && !kayitli.contains(yol.as_str()))
+        .collect()
+}
+
+// Rapordaki onarılabilir bulguları paket kayıtlarıyla eşler ve onarım sırasına dizer.
+// Sabit bağlar hedef dosyaya bağlandığından dosyalardan sonra gelir; diğer bulgular rapor sırasını korur.
+// kaydi_yukle: Paket adına göre kurulu paket kaydını döndürür.
+// Dönüş değeri: (bulgu, paket kaydı, kayıttaki dosya sırası) üçlüleri.
+fn onarilacaklari_sirala<'a, F>(
+    rapor: &'a DogrulamaRaporu,
+    mut kaydi_yukle: F,
+) -> Result<Vec<(&'a DosyaBulgusu, KuruluPaketKaydi, usize)>, PaketYoneticisiHatasi>
+where
+    F: FnMut(&str) -> Result<Option<KuruluPaketKaydi>, PaketYoneticisiHatasi>,
+{
+    let mut onarilacaklar = Vec::new();
+    for bulgu in &rapor.bulgular {
+        if !bulgu.sorunlar.iter().any(|s| s.onarilabilir()) {
+            continue;
+        }
+
+        let kayit = kaydi_yukle(&bulgu.paket)?
+            .ok_or_else(|| PaketYoneticisiHatasi::PaketBulunamadi(bulgu.paket.clone()))?;
+        let dosya_sirasi = kayit.dosyalar.iter().position(|d| d.yol == bulgu.yol).ok_or_else(|| {
+            PaketYoneticisiHatasi::InvalidParameter(format!("'{}' paket kaydında yok: {}", bulgu.paket, bulgu.yol))
+        })?;
+        onarilacaklar.push((bulgu, kayit, dosya_sirasi));
+    }
+    // sort_by_key kararlıdır.
+    onarilacaklar.sort_by_key(|(_, kayit, sira)| kayit.dosyalar[*sira].tur == GirdiTuru::SabitBag);
+    Ok(onarilacaklar)
+}
+
+// Önbellekteki arşivi paket kaydındaki en güçlü özete göre doğrular.
+// Onarım kurulumdan daha gevşek olmamalıdır; yalnızca MD5 içeren kayıtlar reddedilir.
+fn arsivi_dogrula(arsiv_id: &str, kayit: &KuruluPaketKaydi) -> Result<(), PaketYoneticisiHatasi> {
+    let (algoritma, beklenen_hex) = srcchecksum::dogrulama_ozetini_sec(&kayit.paket.checksums, false)?;
+    let hesaplanan = srcchecksum::ozet_hesapla(arsiv_id, algoritma)?;
+    srcchecksum::ozeti_karsilastir(arsiv_id, algoritma, &hesaplanan, beklenen_hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metin_raporu() {
+        let rapor = DogrulamaRaporu {
+            dogrulanan_paket_sayisi: 1,
+            dogrulanan_dosya_sayisi: 3,
+            bulgular: alloc::vec![
+                DosyaBulgusu { paket: "ls".to_string(), yol: "bin/ls".to_string(), sorunlar: alloc::vec![SorunTuru::IcerikDegisti, SorunTuru::ModDegisti] },
+                DosyaBulgusu { paket: "ls".to_string(), yol: "bin/extra".to_string(), sorunlar: alloc::vec![SorunTuru::Beklenmeyen] },
+            ],
+        };
+
+        let metin = rapor.metin();
+        assert!(metin.contains(".5.M... ls bin/ls\n"));
+        assert!(metin.contains("......? ls bin/extra\n"));
+        assert!(!rapor.temiz());
+    }
+
+    #[test]
+    fn test_bag_ve_sahip_kodlari() {
+        let rapor = DogrulamaRaporu {
+            dogrulanan_paket_sayisi: 1,
+            dogrulanan_dosya_sayisi: 2,
+            bulgular: alloc::vec![
+                DosyaBulgusu { paket: "ls".to_string(), yol: "bin/dir".to_string(), sorunlar: alloc::vec![SorunTuru::BagDegisti] },
+                DosyaBulgusu { paket: "ls".to_string(), yol: "bin/ls".to_string(), sorunlar: alloc::vec![SorunTuru::SahipDegisti] },
+            ],
+        };
+
+        let metin = rapor.metin();
+        assert!(metin.contains(".....L. ls bin/dir\n"));
+        assert!(metin.contains("....U.. ls bin/ls\n"));
+        assert!(SorunTuru::BagDegisti.onarilabilir());
+    }
+
+    fn dosya(yol: &str, tur: GirdiTuru, bag_hedefi: Option<&str>) -> KuruluDosya {
+        KuruluDosya {
+            yol: yol.to_string(), tur, boyut: 4, mod_: 0o755, sahip: 0, grup: 0,
+            sha256: if tur == GirdiTuru::Dosya || tur == GirdiTuru::SabitBag { "ab12".to_string() } else { String::new() },
+            bag_hedefi: bag_hedefi.map(str::to_string),
+        }
+    }
+
+    fn bilgi(dosya: &KuruluDosya) -> srcinstalled::KaynakBilgisi {
+        srcinstalled::KaynakBilgisi {
+            tur: if dosya.tur == GirdiTuru::SabitBag { GirdiTuru::Dosya } else { dosya.tur },
+            boyut: dosya.boyut, mod_: dosya.mod_, sahip: dosya.sahip, grup: dosya.grup,
+        }
+    }
+
+    fn karsilastir(dosya: &KuruluDosya, bilgi: Option<&srcinstalled::KaynakBilgisi>, bag: &str, ozet: &str) -> Vec<SorunTuru> {
+        girdiyi_karsilastir(dosya, bilgi, || Ok(bag.to_string()), || Ok(ozet.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_girdi_karsilastirma() {
+        let ls = dosya("bin/ls", GirdiTuru::Dosya, None);
+        assert!(karsilastir(&ls, Some(&bilgi(&ls)), "", "ab12").is_empty());
+        assert_eq!(karsilastir(&ls, None, "", "ab12"), alloc::vec![SorunTuru::Eksik]);
+        assert_eq!(karsilastir(&ls, Some(&bilgi(&ls)), "", "cd34"), alloc::vec![SorunTuru::IcerikDegisti]);
+
+        let mut degisik = bilgi(&ls);
+        degisik.boyut = 5;
+        degisik.mod_ = 0o777;
+        degisik.grup = 100;
+        assert_eq!(karsilastir(&ls, Some(&degisik), "", "cd34"), alloc::vec![
+            SorunTuru::ModDegisti, SorunTuru::SahipDegisti, SorunTuru::BoyutDegisti, SorunTuru::IcerikDegisti,
+        ]);
+
+        // Tür farklıysa yalnızca BagDegisti raporlanır.
+        let mut dizin_oldu = bilgi(&ls);
+        dizin_oldu.tur = GirdiTuru::Dizin;
+        assert_eq!(karsilastir(&ls, Some(&dizin_oldu), "", "ab12"), alloc::vec![SorunTuru::BagDegisti]);
+    }
+
+    #[test]
+    fn test_bag_ve_dizin_karsilastirma() {
+        // Sembolik bağda içerik okunmaz, hedef karşılaştırılır.
+        let bag = dosya("bin/sh", GirdiTuru::SembolikBag, Some("bash"));
+        let ozet_okunmaz = || -> Result<String, PaketYoneticisiHatasi> { panic!("sembolik bağın içeriği okunmamalı") };
+        assert!(girdiyi_karsilastir(&bag, Some(&bilgi(&bag)), || Ok("bash".to_string()), ozet_okunmaz).unwrap().is_empty());
+        assert_eq!(karsilastir(&bag, Some(&bilgi(&bag)), "dash", ""), alloc::vec![SorunTuru::BagDegisti]);
+
+        // Sabit bağ diskte dosya olarak görünür.
+        let sabit = dosya("bin/ls2", GirdiTuru::SabitBag, Some("bin/ls"));
+        assert!(karsilastir(&sabit, Some(&bilgi(&sabit)), "", "ab12").is_empty());
+
+        // Dizinde boyut ve içerik karşılaştırılmaz.
+        let dizin = dosya("bin/", GirdiTuru::Dizin, None);
+        let mut dizin_bilgisi = bilgi(&dizin);
+        dizin_bilgisi.boyut = 4096;
+        assert!(karsilastir(&dizin, Some(&dizin_bilgisi), "", "x").is_empty());
+        dizin_bilgisi.sahip = 1000;
+        assert_eq!(karsilastir(&dizin, Some(&dizin_bilgisi), "", "x"), alloc::vec![SorunTuru::SahipDegisti]);
+    }
+
+    #[test]
+    fn test_beklenmeyen_yollar() {
+        let dosyalar = alloc::vec![dosya("bin/", GirdiTuru::Dizin, None), dosya("bin/ls", GirdiTuru::Dosya, None)];
+        let diskteki = alloc::vec!["bin/".to_string(), "bin/ls".to_string(), "bin/extra".to_string(), "tmp/".to_string()];
+        assert_eq!(beklenmeyen_yollar(&dosyalar, diskteki), alloc::vec!["bin/extra".to_string()]);
+    }
+
+    #[test]
+    fn test_onarim_sirasi() {
+        let kayit = KuruluPaketKaydi {
+            paket: Default::default(),
+            dosyalar: alloc::vec![
+                dosya("bin/ls", GirdiTuru::Dosya, None),
+                dosya("bin/dir", GirdiTuru::SabitBag, Some("bin/ls")),
+                dosya("bin/sh", GirdiTuru::SembolikBag, Some("bash")),
+            ],
+        };
+        let bulgu = |yol: &str, sorun: SorunTuru| DosyaBulgusu { paket: "ls".to_string(), yol: yol.to_string(), sorunlar: alloc::vec![sorun] };
+        let rapor = DogrulamaRaporu {
+            dogrulanan_paket_sayisi: 1,
+            dogrulanan_dosya_sayisi: 3,
+            bulgular: alloc::vec![
+                bulgu("bin/dir", SorunTuru::Eksik),
+                bulgu("bin/extra", SorunTuru::Beklenmeyen),
+                bulgu("bin/ls", SorunTuru::IcerikDegisti),
+                bulgu("bin/sh", SorunTuru::BagDegisti),
+            ],
+        };
+
+        let sira = onarilacaklari_sirala(&rapor, |_| Ok(Some(kayit.clone()))).unwrap();
+        let yollar: Vec<&str> = sira.iter().map(|(bulgu, _, _)| bulgu.yol.as_str()).collect();
+        assert_eq!(yollar, alloc::vec!["bin/ls", "bin/sh", "bin/dir"]);
+        assert_eq!(sira[2].2, 1);
+
+        // Kaydı olmayan paket onarılamaz.
+        assert!(matches!(onarilacaklari_sirala(&rapor, |_| Ok(None)), Err(PaketYoneticisiHatasi::PaketBulunamadi(_))));
+    }
+
+    // Kaynak tabanlı doğrulama/onarım testleri mock resource gerektirir.
+}
@@ -0,0 +1,255 @@
+#![no_std] // Standart kütüphaneye ihtiyaç duymuyoruz (alloc kullanacağız)
+extern crate alloc; // String, Vec, format! için
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format; // format! makrosu için
+use alloc::borrow::ToOwned; // &str -> String için
+
+use core::cmp::Ordering;
+
+// Özel hata enum'ımızı içe aktar (no_std uyumlu hali)
+use crate::paket_yoneticisi_hatasi::PaketYoneticisiHatasi;
+
+
+// İki sürüm dizesini karşılaştırır ("1.10" > "1.9", "2.0" == "2.0.0").
+// Sürüm '.', '-', '_' ve '+' ile bileşenlere ayrılır; sayısal bileşenler sayı olarak,
+// diğerleri sözlük sırasıyla karşılaştırılır. Sayısal bileşen, metin bileşeninden büyük kabul edilir.
+pub fn surum_karsilastir(a: &str, b: &str) -> Ordering {
+    let ayir = |s: &str| -> Vec<String> {
+        s.split(|c| c == '.' || c == '-' || c == '_' || c == '+')
+            .filter(|p| !p.is_empty())
+            .map(|p| p.to_owned())
+            .collect()
+    };
+    let a_bilesenleri = ayir(a);
+    let b_bilesenleri = ayir(b);
+
+    let uzunluk = a_bilesenleri.len().max(b_bilesenleri.len());
+    for i in 0..uzunluk {
+        // Eksik bileşenler "0" sayılır, böylece "2.0" ile "2.0.0" eşittir.
+        let x = a_bilesenleri.get(i).map(|s| s.as_str()).unwrap_or("0");
+        let y = b_bilesenleri.get(i).map(|s| s.as_str()).unwrap_or("0");
+
+        let sonuc = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(xn), Ok(yn)) => xn.cmp(&yn),
+            (Ok(_), Err(_)) => Ordering::Greater,
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if sonuc != Ordering::Equal {
+            return sonuc;
+        }
+    }
+    Ordering::Equal
+}
+
+
+// Bağımlılık kısıtındaki karşılaştırma işleci.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Islec {
+    Esit, // "="
+    BuyukEsit, // ">="
+    KucukEsit, // "<="
+    Buyuk, // ">"
+    Kucuk, // "<"
+}
+
+impl Islec {
+    fn saglar(&self, sira: Ordering) -> bool {
+        match self {
+            Islec::Esit => sira == Ordering::Equal,
+            Islec::BuyukEsit => sira != Ordering::Less,
+            Islec::KucukEsit => sira != Ordering::Greater,
+            Islec::Buyuk => sira == Ordering::Greater,
+            Islec::Kucuk => sira == Ordering::Less,
+        }
+    }
+
+    fn simge(&self) -> &'static str {
+        match self {
+            Islec::Esit => "=",
+            Islec::BuyukEsit => ">=",
+            Islec::KucukEsit => "<=",
+            Islec::Buyuk => ">",
+            Islec::Kucuk => "<",
+        }
+    }
+
+    // Metnin başındaki işleci ayırır; işleç yoksa (veya yalnızca "=" varsa) Esit döner.
+    // İki karakterli işleçler önce denenir. Dönen sürüm kırpılmıştır.
+    fn ayir(metin: &str) -> (Islec, &str) {
+        let (islec, surum) = if let Some(s) = metin.strip_prefix(">=") {
+            (Islec::BuyukEsit, s)
+        } else if let Some(s) = metin.strip_prefix("<=") {
+            (Islec::KucukEsit, s)
+        } else if let Some(s) = metin.strip_prefix('>') {
+            (Islec::Buyuk, s)
+        } else if let Some(s) = metin.strip_prefix('<') {
+            (Islec::Kucuk, s)
+        } else {
+            (Islec::Esit, metin.trim_start_matches('='))
+        };
+        (islec, surum.trim())
+    }
+}
+
+// `Paket::bagimliliklar` içindeki bir girdi: "zlib", "zlib>=1.2.11", "libc = 2.31" gibi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BagimlilikKisiti {
+    pub ad: String,
+    pub kisit: Option<(Islec, String)>, // None: herhangi bir sürüm
+}
+
+impl BagimlilikKisiti {
+    // Bağımlılık girdisini ayrıştırır.
+    pub fn ayristir(girdi: &str) -> Result<Self, PaketYoneticisiHatasi> {
+        let girdi = girdi.trim();
+        let islec_baslangici = girdi.find(|c| c == '<' || c == '>' || c == '=');
+
+        let (ad, kisit) = match islec_baslangici {
+            None => (girdi, None),
+            Some(idx) => {
+                let (ad, geri_kalan) = girdi.split_at(idx);
+                let (islec, surum) = Islec::ayir(geri_kalan);
+                if surum.is_empty() {
+                    return Err(PaketYoneticisiHatasi::ParsingError(format!(
+                        "Bağımlılık kısıtında sürüm eksik: '{}'", girdi
+                    )));
+                }
+                (ad.trim(), Some((islec, surum.to_owned())))
+            }
+        };
+
+        if ad.is_empty() {
+            return Err(PaketYoneticisiHatasi::ParsingError(format!(
+                "Bağımlılık kısıtında paket adı eksik: '{}'", girdi
+            )));
+        }
+
+        Ok(BagimlilikKisiti { ad: ad.to_owned(), kisit })
+    }
+
+    // Verilen sürüm bu kısıtı sağlıyor mu?
+    pub fn saglanir_mi(&self, surum: &str) -> bool {
+        match &self.kisit {
+            None => true,
+            Some((islec, istenen)) => islec.saglar(surum_karsilastir(surum, istenen)),
+        }
+    }
+}
+
+impl core::fmt::Display for BagimlilikKisiti {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kisit {
+            None => write!(f, "{}", self.ad),
+            Some((islec, surum)) => write!(f, "{}{}{}", self.ad, islec.simge(), surum),
+        }
+    }
+}
+
+// Sürüm aralığı: virgülle ayrılmış kısıtların kesişimi (örn. ">=1.2.0, <1.2.12", "=2.4.1").
+// "*" tüm sürümleri kapsar. Güvenlik bildirimlerinde (srcadvisory.rs) etkilenen sürümleri tanımlar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurumAraligi {
+    pub kisitlar: Vec<(Islec, String)>, // Boş: tüm sürümler
+}
+
+impl SurumAraligi {
+    pub fn ayristir(girdi: &str) -> Result<Self, PaketYoneticisiHatasi> {
+        let girdi = girdi.trim();
+        if girdi == "*" {
+            return Ok(SurumAraligi { kisitlar: Vec::new() });
+        }
+        let mut kisitlar = Vec::new();
+        for parca in girdi.split(',') {
+            let (islec, surum) = Islec::ayir(parca.trim());
+            if surum.is_empty() || surum.contains(|c: char| c == '<' || c == '>' || c == '=' || c.is_whitespace()) {
+                return Err(PaketYoneticisiHatasi::ParsingError(format!(
+                    "Geçersiz sürüm aralığı: '{}'", girdi
+                )));
+            }
+            kisitlar.push((islec, surum.to_owned()));
+        }
+        Ok(SurumAraligi { kisitlar })
+    }
+
+    // Sürüm aralıktaki tüm kısıtları sağlıyor mu?
+    pub fn icerir(&self, surum: &str) -> bool {
+        self.kisitlar.iter().all(|(islec, sinir)| islec.saglar(surum_karsilastir(surum, sinir)))
+    }
+}
+
+impl core::fmt::Display for SurumAraligi {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.kisitlar.is_empty() {
+            return write!(f, "*");
+        }
+        for (i, (islec, surum)) in self.kisitlar.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}{}", islec.simge(), surum)?;
+        }
+        Ok(())
+    }
+}
+
+// Mevcut sürümden eski sürümler arasından en yenisini seçer (sürüm düşürme için varsayılan hedef).
+pub fn onceki_surum_sec<'a>(surumler: &'a [String], mevcut: &str) -> Option<&'a String> {
+    surumler
+        .iter()
+        .filter(|s| surum_karsilastir(s, mevcut) == Ordering::Less)
+        .max_by(|a, b| surum_karsilastir(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surum_karsilastir() {
+        assert_eq!(surum_karsilastir("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(surum_karsilastir("2.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(surum_karsilastir("1.2.3", "1.2.4"), Ordering::Less);
+        assert_eq!(surum_karsilastir("1.0-rc1", "1.0"), Ordering::Less); // "rc1" metin, eksik bileşen "0" sayısal
+    }
+
+    #[test]
+    fn test_kisit_ayristir_ve_sagla() {
+        let kisit = BagimlilikKisiti::ayristir("zlib >= 1.2.11").unwrap();
+        assert_eq!(kisit.ad, "zlib");
+        assert!(kisit.saglanir_mi("1.3"));
+        assert!(!kisit.saglanir_mi("1.2.10"));
+        assert_eq!(kisit.to_string(), "zlib>=1.2.11");
+
+        let serbest = BagimlilikKisiti::ayristir("libc").unwrap();
+        assert!(serbest.kisit.is_none());
+        assert!(serbest.saglanir_mi("0.1"));
+
+        assert!(BagimlilikKisiti::ayristir("zlib>=").is_err());
+        assert!(BagimlilikKisiti::ayristir(">=1.0").is_err());
+    }
+
+    #[test]
+    fn test_surum_araligi() {
+        let aralik = SurumAraligi::ayristir(">=1.2.0, <1.2.12").unwrap();
+        assert!(aralik.icerir("1.2.0"));
+        assert!(aralik.icerir("1.2.11"));
+        assert!(!aralik.icerir("1.2.12"));
+        assert!(!aralik.icerir("1.1.9"));
+        assert_eq!(aralik.to_string(), ">=1.2.0, <1.2.12");
+
+        assert!(SurumAraligi::ayristir("=2.4.1").unwrap().icerir("2.4.1.0"));
+        assert!(SurumAraligi::ayristir("*").unwrap().icerir("0.1"));
+        assert!(SurumAraligi::ayristir(">=1.0,").is_err());
+        assert!(SurumAraligi::ayristir(">=1.0 <2.0").is_err());
+    }
+
+    #[test]
+    fn test_onceki_surum_sec() {
+        let surumler = ["1.0".to_string(), "1.2".to_string(), "1.10".to_string(), "2.0".to_string()];
+        assert_eq!(onceki_surum_sec(&surumler, "2.0").map(|s| s.as_str()), Some("1.10"));
+        assert_eq!(onceki_surum_sec(&surumler, "1.0"), None);
+    }
+}